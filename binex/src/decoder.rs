@@ -85,10 +85,9 @@ impl<'a, R: Read> Decoder<'a, R> {
     ///                 // and user should react accordingly,
     ///                 break;
     ///             },
-    ///             Error::ReversedStream => {
+    ///             Error::EnhancedCrc => {
     ///                 // this library is currently limited:
-    ///                 //  - reversed streams are not supported yet
-    ///                 //  - little endian streams are not supported yet
+    ///                 //  - enhanced CRC is not supported yet
     ///             },
     ///             _ => {},
     ///         },
@@ -138,10 +137,9 @@ impl<'a, R: Read> Decoder<'a, R> {
     ///                 // and user should react accordingly,
     ///                 break;
     ///             },
-    ///             Error::ReversedStream => {
+    ///             Error::EnhancedCrc => {
     ///                 // this library is currently limited:
-    ///                 //  - reversed streams are not supported yet
-    ///                 //  - little endian streams are not supported yet
+    ///                 //  - enhanced CRC is not supported yet
     ///             },
     ///             _ => {},
     ///         },
@@ -15,9 +15,10 @@ pub mod prelude {
         decoder::Decoder,
         message::{
             EphemerisFrame, GALEphemeris, GLOEphemeris, GPSEphemeris, GPSRaw, GeoStringFrame,
-            Message, Meta, MonumentGeoMetadata, MonumentGeoRecord, PositionEcef3d, PositionGeo3d,
-            Record, SBASEphemeris, Solutions, SolutionsFrame, TemporalSolution, Velocity3d,
-            VelocityNED3d,
+            Message, Meta, MonumentGeoMetadata, MonumentGeoRecord, NetRSL1, NetRSL2,
+            NetRSObservation, NetRSPhase, NetRSSatellite, ObservationFrame, PositionEcef3d,
+            PositionGeo3d, Record, SBASEphemeris, Solutions, SolutionsFrame, TemporalSolution,
+            Velocity3d, VelocityNED3d,
         },
         stream::{ClosedSourceElement, Provider, StreamElement},
         ClosedSourceMeta, Error,
@@ -57,7 +58,8 @@ pub enum Error {
     /// Missing SYNC byte
     NoSyncByte,
     // InvalidStartofStream,
-    /// Library limitation: reversed streams are not supported
+    /// Reverse-readable message is not correctly terminated:
+    /// invalid reversed length or terminating SYNC byte
     ReversedStream,
     /// Library limitation: enhanced CRC is not supported yet
     EnhancedCrc,
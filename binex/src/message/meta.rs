@@ -28,6 +28,15 @@ impl Meta {
     const REV_SYNC_LE_ENHANCED_CRC: u8 = 0xd8;
     // Reversed stream +BE +enhanced
     const REV_SYNC_BE_ENHANCED_CRC: u8 = 0xf8;
+
+    // Reversed stream termination +LE +standard
+    const REV_TERM_LE_STANDARD_CRC: u8 = 0xb4;
+    // Reversed stream termination +BE +standard
+    const REV_TERM_BE_STANDARD_CRC: u8 = 0xb0;
+    // Reversed stream termination +LE +enhanced
+    const REV_TERM_LE_ENHANCED_CRC: u8 = 0xe4;
+    // Reversed stream termination +BE +enhanced
+    const REV_TERM_BE_ENHANCED_CRC: u8 = 0xe0;
 }
 
 impl Meta {
@@ -57,6 +66,21 @@ impl Meta {
             Self::FWD_SYNC_LE_STANDARD_CRC
         }
     }
+    /// Generates the terminating Sync byte of reverse-readable messages.
+    /// Only meaningful for reversed [Meta].
+    pub(crate) fn terminating_sync_byte(&self) -> u8 {
+        if self.big_endian {
+            if self.enhanced_crc {
+                Self::REV_TERM_BE_ENHANCED_CRC
+            } else {
+                Self::REV_TERM_BE_STANDARD_CRC
+            }
+        } else if self.enhanced_crc {
+            Self::REV_TERM_LE_ENHANCED_CRC
+        } else {
+            Self::REV_TERM_LE_STANDARD_CRC
+        }
+    }
     /// Locate SYNC byte in provided buffer
     /// ## Returns
     /// - [Meta]
@@ -6,7 +6,8 @@ mod time; // Epoch encoding/decoding // checksum calc.
 
 pub use record::{
     EphemerisFrame, GALEphemeris, GLOEphemeris, GPSEphemeris, GPSRaw, GeoStringFrame,
-    MonumentGeoMetadata, MonumentGeoRecord, PositionEcef3d, PositionGeo3d, Record, SBASEphemeris,
+    MonumentGeoMetadata, MonumentGeoRecord, NetRSL1, NetRSL2, NetRSObservation, NetRSPhase,
+    NetRSSatellite, ObservationFrame, PositionEcef3d, PositionGeo3d, Record, SBASEphemeris,
     Solutions, SolutionsFrame, TemporalSolution, Velocity3d, VelocityNED3d,
 };
 
@@ -56,6 +57,12 @@ impl Message {
         let ck = Checksum::from_len(mlen_1_4 + mlen + mid_1_4, self.meta.enhanced_crc);
         total += ck.len();
 
+        if self.meta.reversed {
+            // reversed length + terminating SYNC
+            let rlen = mid_1_4 + mlen_1_4 + mlen + ck.len();
+            total += Self::bnxi_encoding_size(rlen as u32) + 1;
+        }
+
         total
     }
 
@@ -82,10 +89,6 @@ impl Message {
         /////////////////////////////////////
         // TODO: current library limitations
         /////////////////////////////////////
-        if reversed {
-            // Reversed streams: not understood
-            return Err(Error::ReversedStream);
-        }
         if enhanced_crc {
             // Enhanced CRC scheme not implemented
             return Err(Error::EnhancedCrc);
@@ -137,6 +140,17 @@ impl Message {
                 let solutions = Solutions::decode(mlen, big_endian, &buf[ptr..])?;
                 Record::new_solutions(solutions)
             },
+            MessageID::ObservationPrototype => {
+                let fr = match ObservationFrame::decode(mlen, big_endian, &buf[ptr..]) {
+                    Ok(fr) => fr,
+                    Err(Error::NonSupportedSubRecord) => {
+                        // receiver specific layout we do not support
+                        return Err(Error::NonSupportedMesssage(mlen));
+                    },
+                    Err(e) => return Err(e),
+                };
+                Record::new_observation(fr)
+            },
             MessageID::Unknown => {
                 return Err(Error::UnknownMessage);
             },
@@ -173,10 +187,32 @@ impl Message {
         let expected = checksum.calc(&buf[sync_off + 1..], mlen + mid_1_4 + mlen_1_4);
 
         if expected != ck {
-            Err(Error::CorrupctBadCRC)
-        } else {
-            Ok(Self { meta, record })
+            return Err(Error::CorrupctBadCRC);
+        }
+
+        // 6. reverse-readable messages are terminated by
+        //    the reversed forward length, and a terminating SYNC byte
+        if reversed {
+            let rlen = mid_1_4 + mlen_1_4 + mlen + ck_len;
+            let rlen_1_4 = Self::bnxi_encoding_size(rlen as u32);
+            let rptr = ptr + mlen + ck_len;
+
+            if rptr + rlen_1_4 + 1 > buf_len {
+                return Err(Error::IncompleteMessage(mlen));
+            }
+
+            let mut rbuf = [0; 4];
+            rbuf[..rlen_1_4].copy_from_slice(&buf[rptr..rptr + rlen_1_4]);
+            rbuf[..rlen_1_4].reverse();
+
+            let (decoded, _) = Self::decode_bnxi(&rbuf[..rlen_1_4], big_endian);
+
+            if decoded as usize != rlen || buf[rptr + rlen_1_4] != meta.terminating_sync_byte() {
+                return Err(Error::ReversedStream);
+            }
         }
+
+        Ok(Self { meta, record })
     }
 
     /// Tries to encode [Message] into provided buffer.
@@ -194,7 +230,6 @@ impl Message {
 
         // grab meta definitions
         let big_endian = self.meta.big_endian;
-        // let reversed = self.meta.reversed;
         let enhanced_crc = self.meta.enhanced_crc;
 
         // Encode SYNC byte
@@ -222,6 +257,9 @@ impl Message {
             Record::Solutions(fr) => {
                 ptr += fr.encode(big_endian, &mut buf[ptr..])?;
             },
+            Record::Observation(fr) => {
+                ptr += fr.encode(big_endian, &mut buf[ptr..])?;
+            },
         }
 
         // encode CRC
@@ -261,7 +299,21 @@ impl Message {
             }
         }
 
-        Ok(ptr + ck_len)
+        ptr += ck_len;
+
+        // reverse-readable: reversed length + terminating SYNC
+        if self.meta.reversed {
+            let rlen = mid_1_4 + mlen_1_4 + mlen + ck_len;
+            let mut rbuf = [0; 4];
+            let rlen_1_4 = Self::encode_bnxi(rlen as u32, big_endian, &mut rbuf)?;
+            rbuf[..rlen_1_4].reverse();
+            buf[ptr..ptr + rlen_1_4].copy_from_slice(&rbuf[..rlen_1_4]);
+            ptr += rlen_1_4;
+            buf[ptr] = self.meta.terminating_sync_byte();
+            ptr += 1;
+        }
+
+        Ok(ptr)
     }
 
     /// Number of bytes to encode U32 using the 1-4 BNXI algorithm.
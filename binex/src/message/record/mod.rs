@@ -4,6 +4,7 @@ use crate::message::MessageID;
 
 mod ephemeris; // ephemeris frames
 mod monument; // geodetic marker // ephemeris frames
+mod observation; // observation frames
 mod solutions; // solutions frames
 
 pub use ephemeris::{
@@ -12,6 +13,10 @@ pub use ephemeris::{
 
 pub use monument::{GeoStringFrame, MonumentGeoMetadata, MonumentGeoRecord};
 
+pub use observation::{
+    NetRSL1, NetRSL2, NetRSObservation, NetRSPhase, NetRSSatellite, ObservationFrame,
+};
+
 pub use solutions::{
     PositionEcef3d, PositionGeo3d, Solutions, SolutionsFrame, TemporalSolution, Velocity3d,
     VelocityNED3d,
@@ -26,6 +31,8 @@ pub enum Record {
     EphemerisFrame(EphemerisFrame),
     /// Solutions frame
     Solutions(Solutions),
+    /// Observation frame
+    Observation(ObservationFrame),
 }
 
 impl From<MonumentGeoRecord> for Record {
@@ -40,6 +47,12 @@ impl From<EphemerisFrame> for Record {
     }
 }
 
+impl From<ObservationFrame> for Record {
+    fn from(fr: ObservationFrame) -> Self {
+        Self::Observation(fr)
+    }
+}
+
 impl Default for Record {
     fn default() -> Self {
         Self::MonumentGeo(Default::default())
@@ -59,6 +72,10 @@ impl Record {
     pub fn new_solutions(sol: Solutions) -> Self {
         Self::Solutions(sol)
     }
+    /// Builds new [ObservationFrame]
+    pub fn new_observation(fr: ObservationFrame) -> Self {
+        Self::Observation(fr)
+    }
     /// [MonumentGeoRecord] unwrapping attempt
     pub fn as_monument_geo(&self) -> Option<&MonumentGeoRecord> {
         match self {
@@ -80,12 +97,20 @@ impl Record {
             _ => None,
         }
     }
+    /// [ObservationFrame] unwrapping attempt
+    pub fn as_observation(&self) -> Option<&ObservationFrame> {
+        match self {
+            Self::Observation(fr) => Some(fr),
+            _ => None,
+        }
+    }
     /// Returns [MessageID] to associate to [Self] in stream header.
     pub(crate) fn to_message_id(&self) -> MessageID {
         match self {
             Self::EphemerisFrame(_) => MessageID::Ephemeris,
             Self::MonumentGeo(_) => MessageID::SiteMonumentMarker,
             Self::Solutions(_) => MessageID::ProcessedSolutions,
+            Self::Observation(_) => MessageID::ObservationPrototype,
         }
     }
 
@@ -95,6 +120,7 @@ impl Record {
             Self::Solutions(sol) => sol.encoding_size(),
             Self::EphemerisFrame(fr) => fr.encoding_size(),
             Self::MonumentGeo(geo) => geo.encoding_size(),
+            Self::Observation(fr) => fr.encoding_size(),
         }
     }
 }
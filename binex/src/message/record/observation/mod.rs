@@ -0,0 +1,83 @@
+//! Observation prototype (0x7f) frames
+use crate::{message::Message, Error};
+
+mod netrs;
+
+pub use netrs::{NetRSL1, NetRSL2, NetRSObservation, NetRSPhase, NetRSSatellite};
+
+/// [ObservationFrame] describes GNSS observations at a single Epoch.
+/// The 0x7f record is a prototype: each subrecord is receiver specific.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObservationFrame {
+    /// Trimble NetRS GPS observations (subrecord 0x03)
+    NetRS(NetRSObservation),
+}
+
+impl ObservationFrame {
+    /// Trimble NetRS subrecord
+    const NETRS_ID: u32 = 0x03;
+
+    /// Returns subrecord ID
+    fn subrecord_id(&self) -> u32 {
+        match self {
+            Self::NetRS(_) => Self::NETRS_ID,
+        }
+    }
+
+    /// Creates new [NetRSObservation] frame
+    pub fn new_netrs(obs: NetRSObservation) -> Self {
+        Self::NetRS(obs)
+    }
+
+    /// [NetRSObservation] unwrapping attempt
+    pub fn as_netrs(&self) -> Option<&NetRSObservation> {
+        match self {
+            Self::NetRS(obs) => Some(obs),
+        }
+    }
+
+    /// [ObservationFrame] decoding attempt from buffered content.
+    /// ## Inputs
+    ///    - mlen: message length in bytes
+    ///    - big_endian: endianness
+    ///    - buf: buffered content
+    pub(crate) fn decode(mlen: usize, big_endian: bool, buf: &[u8]) -> Result<Self, Error> {
+        if mlen == 0 || buf.len() < mlen {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        let (subrecord, size) = Message::decode_bnxi(&buf[..mlen], big_endian);
+
+        match subrecord {
+            Self::NETRS_ID => {
+                let obs = NetRSObservation::decode(big_endian, &buf[size..mlen])?;
+                Ok(Self::NetRS(obs))
+            },
+            _ => Err(Error::NonSupportedSubRecord),
+        }
+    }
+
+    /// Encodes [ObservationFrame] into buffer, returns encoded size (total bytes).
+    /// [ObservationFrame] must fit in preallocated buffer.
+    pub(crate) fn encode(&self, big_endian: bool, buf: &mut [u8]) -> Result<usize, Error> {
+        if buf.len() < self.encoding_size() {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        let offset = Message::encode_bnxi(self.subrecord_id(), big_endian, buf)?;
+
+        let size = match self {
+            Self::NetRS(obs) => obs.encode(big_endian, &mut buf[offset..])?,
+        };
+
+        Ok(offset + size)
+    }
+
+    /// Returns total length (bytewise) required to fully encode [Self].
+    pub(crate) fn encoding_size(&self) -> usize {
+        let id_1_4 = Message::bnxi_encoding_size(self.subrecord_id());
+        match self {
+            Self::NetRS(obs) => id_1_4 + obs.encoding_size(),
+        }
+    }
+}
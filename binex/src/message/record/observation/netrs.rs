@@ -0,0 +1,468 @@
+//! Trimble NetRS GPS observations (0x7f-03)
+use crate::{
+    message::time::{decode_gpst_epoch, encode_epoch, TimeResolution},
+    Error,
+};
+
+use hifitime::{Epoch, TimeScale};
+
+/// Carrier phase offsets are expressed in 0.02 mm units
+const PHASE_OFFSET_SCALING: f64 = 2.0E-5;
+
+/// This phase word means "phase not available"
+const NO_PHASE: u32 = 0x800000;
+
+/// Reads `size` bytes as an unsigned integer, with desired endianness
+fn decode_uint(big_endian: bool, size: usize, buf: &[u8]) -> u64 {
+    let mut val = 0_u64;
+    for i in 0..size {
+        let byte = if big_endian {
+            buf[i]
+        } else {
+            buf[size - 1 - i]
+        };
+        val = (val << 8) | byte as u64;
+    }
+    val
+}
+
+/// Writes `size` bytes of an unsigned integer, with desired endianness
+fn encode_uint(val: u64, big_endian: bool, size: usize, buf: &mut [u8]) {
+    for i in 0..size {
+        let byte = (val >> (8 * (size - 1 - i))) as u8;
+        if big_endian {
+            buf[i] = byte;
+        } else {
+            buf[size - 1 - i] = byte;
+        }
+    }
+}
+
+/// [NetRSPhase] carrier phase measurement, expressed as
+/// an offset to the C/A pseudo range.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct NetRSPhase {
+    /// Receiver specific flag (bit 23), its meaning is not disclosed.
+    pub flag: bool,
+    /// Offset to the C/A pseudo range, in 0.02 mm units
+    pub offset: i32,
+}
+
+impl NetRSPhase {
+    /// Decodes the 24 bit phase word
+    fn decode(word: u32) -> Option<Self> {
+        if word == NO_PHASE {
+            return None;
+        }
+        // sign extension of the 23 bit offset
+        let offset = ((word << 9) as i32) >> 9;
+        Some(Self {
+            flag: word & 0x800000 > 0,
+            offset,
+        })
+    }
+
+    /// Encodes the 24 bit phase word
+    fn encode(phase: Option<&Self>) -> u32 {
+        match phase {
+            Some(phase) => {
+                let mut word = (phase.offset as u32) & 0x7fffff;
+                if phase.flag {
+                    word |= 0x800000;
+                }
+                word
+            },
+            None => NO_PHASE,
+        }
+    }
+}
+
+/// L1 C/A tracking block
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct NetRSL1 {
+    /// Tracking status byte, "as is"
+    pub status: u8,
+    /// Carrier to noise density ratio, in 0.1 dB-Hz units
+    pub cn0: u16,
+    /// C/A pseudo range, in millimeters
+    pub pseudo_range_mm: u64,
+    /// Carrier phase, if tracked
+    pub phase: Option<NetRSPhase>,
+}
+
+impl NetRSL1 {
+    const SIZE: usize = 10;
+
+    fn decode(big_endian: bool, buf: &[u8]) -> Self {
+        let status = buf[0];
+        let word = decode_uint(big_endian, 5, &buf[2..]);
+        let cn0 = (buf[1] as u16) * 4 + ((word >> 38) & 0x03) as u16;
+        let pseudo_range_mm = word & 0x3f_ffff_ffff;
+        let phase = NetRSPhase::decode(decode_uint(big_endian, 3, &buf[7..]) as u32);
+        Self {
+            status,
+            cn0,
+            pseudo_range_mm,
+            phase,
+        }
+    }
+
+    fn encode(&self, big_endian: bool, buf: &mut [u8]) {
+        buf[0] = self.status;
+        buf[1] = (self.cn0 / 4) as u8;
+        let word = (((self.cn0 % 4) as u64) << 38) | (self.pseudo_range_mm & 0x3f_ffff_ffff);
+        encode_uint(word, big_endian, 5, &mut buf[2..]);
+        let word = NetRSPhase::encode(self.phase.as_ref());
+        encode_uint(word as u64, big_endian, 3, &mut buf[7..]);
+    }
+
+    /// Returns C/A pseudo range in meters
+    pub fn pseudo_range_m(&self) -> f64 {
+        self.pseudo_range_mm as f64 * 1.0E-3
+    }
+
+    /// Returns L1 carrier phase range in meters, if tracked
+    pub fn phase_range_m(&self) -> Option<f64> {
+        let phase = self.phase?;
+        Some(self.pseudo_range_m() + phase.offset as f64 * PHASE_OFFSET_SCALING)
+    }
+
+    /// Returns carrier to noise density ratio in dB-Hz
+    pub fn cn0_dbhz(&self) -> f64 {
+        self.cn0 as f64 / 10.0
+    }
+}
+
+/// L2 tracking block
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct NetRSL2 {
+    /// Tracking status (6 bits), "as is"
+    pub status: u8,
+    /// Carrier to noise density ratio, in 0.1 dB-Hz units
+    pub cn0: u16,
+    /// L2 pseudo range, as a difference to the C/A pseudo range, in millimeters
+    pub range_delta_mm: i16,
+    /// Carrier phase, if tracked
+    pub phase: Option<NetRSPhase>,
+}
+
+impl NetRSL2 {
+    const SIZE: usize = 7;
+
+    /// Semi-codeless P(Y) tracking (as opposed to L2C tracking)
+    const P_CODE_MASK: u8 = 0x10;
+
+    fn decode(big_endian: bool, buf: &[u8]) -> Self {
+        let cn0 = (buf[0] as u16) * 4 + (buf[1] >> 6) as u16;
+        let status = buf[1] & 0x3f;
+        let range_delta_mm = decode_uint(big_endian, 2, &buf[2..]) as u16 as i16;
+        let phase = NetRSPhase::decode(decode_uint(big_endian, 3, &buf[4..]) as u32);
+        Self {
+            status,
+            cn0,
+            range_delta_mm,
+            phase,
+        }
+    }
+
+    fn encode(&self, big_endian: bool, buf: &mut [u8]) {
+        buf[0] = (self.cn0 / 4) as u8;
+        buf[1] = (((self.cn0 % 4) as u8) << 6) | (self.status & 0x3f);
+        encode_uint(
+            self.range_delta_mm as u16 as u64,
+            big_endian,
+            2,
+            &mut buf[2..],
+        );
+        let word = NetRSPhase::encode(self.phase.as_ref());
+        encode_uint(word as u64, big_endian, 3, &mut buf[4..]);
+    }
+
+    /// Returns true if this signal was tracked as L2 P(Y) (semi-codeless),
+    /// false when L2C was tracked.
+    pub fn is_p_code(&self) -> bool {
+        self.status & Self::P_CODE_MASK > 0
+    }
+
+    /// Returns L2 pseudo range in meters
+    pub fn pseudo_range_m(&self, l1: &NetRSL1) -> f64 {
+        l1.pseudo_range_m() + self.range_delta_mm as f64 * 1.0E-3
+    }
+
+    /// Returns L2 carrier phase range in meters, if tracked
+    pub fn phase_range_m(&self, l1: &NetRSL1) -> Option<f64> {
+        let phase = self.phase?;
+        Some(
+            l1.pseudo_range_m() - self.range_delta_mm as f64 * 1.0E-3
+                + phase.offset as f64 * PHASE_OFFSET_SCALING,
+        )
+    }
+
+    /// Returns carrier to noise density ratio in dB-Hz
+    pub fn cn0_dbhz(&self) -> f64 {
+        self.cn0 as f64 / 10.0
+    }
+}
+
+/// Observations of a single GPS satellite
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct NetRSSatellite {
+    /// PRN (encoded as PRN-1, like other BINEX frames)
+    pub prn: u8,
+    /// L1 C/A block
+    pub l1: Option<NetRSL1>,
+    /// L2 block. Its ranges are expressed with respect to L1.
+    pub l2: Option<NetRSL2>,
+}
+
+impl NetRSSatellite {
+    const L1_MASK: u8 = 0x02;
+    const L2_MASK: u8 = 0x04;
+
+    fn content_mask(&self) -> u8 {
+        let mut mask = 0;
+        if self.l1.is_some() {
+            mask |= Self::L1_MASK;
+        }
+        if self.l2.is_some() {
+            mask |= Self::L2_MASK;
+        }
+        mask
+    }
+
+    fn content_size(&self) -> usize {
+        let mut size = 0;
+        if self.l1.is_some() {
+            size += NetRSL1::SIZE;
+        }
+        if self.l2.is_some() {
+            size += NetRSL2::SIZE;
+        }
+        size
+    }
+}
+
+/// [NetRSObservation] describes the GPS observations produced
+/// by a Trimble NetRS receiver at a single [Epoch].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetRSObservation {
+    /// Sampling [Epoch], in GPST
+    pub epoch: Epoch,
+    /// Receiver specific bytes, when present. Their meaning is not disclosed.
+    pub reserved: Option<[u8; 3]>,
+    /// True when each satellite describes its own content (mask byte).
+    /// Otherwise, the content of the first satellite applies to all of them.
+    pub per_sv_content: bool,
+    /// Observations, per satellite
+    pub satellites: Vec<NetRSSatellite>,
+}
+
+impl NetRSObservation {
+    /// Satellites count (minus one) mask
+    const NUM_SV_MASK: u8 = 0x3f;
+
+    /// Receiver specific bytes are present
+    const RESERVED_MASK: u8 = 0x40;
+
+    /// Not supported (undisclosed) extension
+    const EXTENDED_MASK: u8 = 0x80;
+
+    /// Per satellite content description
+    const PER_SV_MASK: u8 = 0x01;
+
+    /// 4 byte minutes + 2 byte ms + 1 byte flags
+    const MIN_SIZE: usize = 7;
+
+    /// Creates new [NetRSObservation] for this [Epoch].
+    pub fn new(epoch: Epoch) -> Self {
+        Self {
+            epoch,
+            ..Default::default()
+        }
+    }
+
+    /// Adds one [NetRSSatellite] to this [NetRSObservation].
+    pub fn with_satellite(&self, sat: NetRSSatellite) -> Self {
+        let mut s = self.clone();
+        if let Some(first) = s.satellites.first() {
+            if first.content_mask() != sat.content_mask() {
+                s.per_sv_content = true;
+            }
+        }
+        s.satellites.push(sat);
+        s
+    }
+
+    /// Decodes [NetRSObservation] from this buffer, which
+    /// must contain the message, exactly.
+    pub(crate) fn decode(big_endian: bool, buf: &[u8]) -> Result<Self, Error> {
+        let mlen = buf.len();
+        if mlen < Self::MIN_SIZE {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        let epoch = decode_gpst_epoch(big_endian, TimeResolution::MilliSecond, buf)?;
+
+        let flags = buf[6];
+        if flags & Self::EXTENDED_MASK > 0 {
+            return Err(Error::NonSupportedSubRecord);
+        }
+
+        let num_sv = (flags & Self::NUM_SV_MASK) as usize + 1;
+        let mut ptr = Self::MIN_SIZE;
+
+        let reserved = if flags & Self::RESERVED_MASK > 0 {
+            if mlen < ptr + 3 {
+                return Err(Error::NotEnoughBytes);
+            }
+            ptr += 3;
+            Some([buf[ptr - 3], buf[ptr - 2], buf[ptr - 1]])
+        } else {
+            None
+        };
+
+        // PRNs + first content mask
+        if mlen < ptr + num_sv + 1 {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        let prns = &buf[ptr..ptr + num_sv];
+        ptr += num_sv;
+
+        let mut mask = buf[ptr];
+        ptr += 1;
+
+        let per_sv_content = mask & Self::PER_SV_MASK > 0;
+        let mut satellites = Vec::with_capacity(num_sv);
+
+        for (i, prn) in prns.iter().enumerate() {
+            if i > 0 && per_sv_content {
+                if mlen < ptr + 1 {
+                    return Err(Error::NotEnoughBytes);
+                }
+                mask = buf[ptr];
+                ptr += 1;
+            }
+
+            let mut sat = NetRSSatellite {
+                prn: *prn,
+                l1: None,
+                l2: None,
+            };
+
+            if mask & NetRSSatellite::L1_MASK > 0 {
+                if mlen < ptr + NetRSL1::SIZE {
+                    return Err(Error::NotEnoughBytes);
+                }
+                sat.l1 = Some(NetRSL1::decode(big_endian, &buf[ptr..]));
+                ptr += NetRSL1::SIZE;
+            }
+
+            if mask & NetRSSatellite::L2_MASK > 0 {
+                if mlen < ptr + NetRSL2::SIZE {
+                    return Err(Error::NotEnoughBytes);
+                }
+                sat.l2 = Some(NetRSL2::decode(big_endian, &buf[ptr..]));
+                ptr += NetRSL2::SIZE;
+            }
+
+            satellites.push(sat);
+        }
+
+        if ptr != mlen {
+            // layout we do not understand
+            return Err(Error::NonSupportedSubRecord);
+        }
+
+        Ok(Self {
+            epoch,
+            reserved,
+            per_sv_content,
+            satellites,
+        })
+    }
+
+    /// Encodes [NetRSObservation] into buffer, returns encoded size.
+    pub(crate) fn encode(&self, big_endian: bool, buf: &mut [u8]) -> Result<usize, Error> {
+        let size = self.encoding_size();
+        if buf.len() < size {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        let num_sv = self.satellites.len();
+        if num_sv == 0 || num_sv > Self::NUM_SV_MASK as usize + 1 {
+            return Err(Error::NonSupportedSubRecord);
+        }
+
+        if !self.per_sv_content {
+            // first description applies to all satellites
+            let mask = self.satellites[0].content_mask();
+            if self.satellites.iter().any(|sat| sat.content_mask() != mask) {
+                return Err(Error::NonSupportedSubRecord);
+            }
+        }
+
+        let t = self.epoch.to_time_scale(TimeScale::GPST);
+        let mut ptr = encode_epoch(t, TimeResolution::MilliSecond, big_endian, buf)?;
+
+        let mut flags = (num_sv - 1) as u8;
+        if let Some(reserved) = self.reserved {
+            flags |= Self::RESERVED_MASK;
+            buf[ptr + 1..ptr + 4].copy_from_slice(&reserved);
+        }
+        buf[ptr] = flags;
+        ptr += 1;
+
+        if self.reserved.is_some() {
+            ptr += 3;
+        }
+
+        for sat in self.satellites.iter() {
+            buf[ptr] = sat.prn;
+            ptr += 1;
+        }
+
+        for (i, sat) in self.satellites.iter().enumerate() {
+            if i == 0 || self.per_sv_content {
+                let mut mask = sat.content_mask();
+                if self.per_sv_content {
+                    mask |= Self::PER_SV_MASK;
+                }
+                buf[ptr] = mask;
+                ptr += 1;
+            }
+            if let Some(l1) = &sat.l1 {
+                l1.encode(big_endian, &mut buf[ptr..]);
+                ptr += NetRSL1::SIZE;
+            }
+            if let Some(l2) = &sat.l2 {
+                l2.encode(big_endian, &mut buf[ptr..]);
+                ptr += NetRSL2::SIZE;
+            }
+        }
+
+        Ok(ptr)
+    }
+
+    /// Returns total length (bytewise) required to fully encode [Self].
+    pub(crate) fn encoding_size(&self) -> usize {
+        let mut size = Self::MIN_SIZE;
+        if self.reserved.is_some() {
+            size += 3;
+        }
+
+        size += self.satellites.len(); // PRNs
+
+        if self.per_sv_content {
+            size += self.satellites.len();
+        } else {
+            size += 1;
+        }
+
+        for sat in self.satellites.iter() {
+            size += sat.content_size();
+        }
+
+        size
+    }
+}
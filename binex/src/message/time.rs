@@ -67,7 +67,7 @@ pub fn encode_epoch(
     }
 
     let dt_s = t.duration.to_seconds();
-    let total_mins = (dt_s / 60.0).round() as u32;
+    let total_mins = (dt_s / 60.0).floor() as u32;
     let bytes = total_mins.to_be_bytes();
 
     if big_endian {
@@ -84,7 +84,7 @@ pub fn encode_epoch(
 
     match time_res {
         TimeResolution::MilliSecond => {
-            let total_msec = (dt_s - (total_mins as f64) * 60.0) * 1.0E3;
+            let total_msec = ((dt_s - (total_mins as f64) * 60.0) * 1.0E3).round();
             let bytes = ((total_msec as u16).min(59999)).to_be_bytes();
 
             if big_endian {
@@ -276,5 +276,14 @@ mod test {
 
         let parsed = decode_gpst_epoch(true, TimeResolution::MilliSecond, &buf).unwrap();
         assert_eq!(parsed, t);
+
+        // second half of the minute
+        let mut buf = [0, 0, 0, 0, 0, 0];
+        let t = Epoch::from_gpst_seconds(105.0);
+        encode_epoch(t, TimeResolution::MilliSecond, true, &mut buf).unwrap();
+        assert_eq!(buf, [0, 0, 0, 1, 0xaf, 0xc8]);
+
+        let parsed = decode_gpst_epoch(true, TimeResolution::MilliSecond, &buf).unwrap();
+        assert_eq!(parsed, t);
     }
 }
@@ -1,5 +1,6 @@
-use binex::prelude::{Decoder, Error, StreamElement};
+use binex::prelude::{Decoder, Epoch, Error, Message, StreamElement};
 use std::fs::File;
+use std::str::FromStr;
 
 #[test]
 fn mfle20190130() {
@@ -29,6 +30,68 @@ fn mfle20190130() {
     assert!(found > 0, "not a single msg decoded");
 }
 
+#[test]
+fn mfle20190130_netrs_observations() {
+    let mut found = 0;
+    let fd = File::open("../test_resources/BIN/mfle20190130.bnx").unwrap();
+
+    let decoder = Decoder::new(fd);
+
+    for element in decoder {
+        let msg = match element {
+            Ok(StreamElement::OpenSource(msg)) => msg,
+            Ok(StreamElement::ClosedSource(_)) => continue,
+            Err(Error::IoError) => panic!("i/o error"),
+            Err(_) => continue,
+        };
+
+        let netrs = match msg.record.as_observation() {
+            Some(fr) => fr.as_netrs().unwrap(),
+            None => continue,
+        };
+
+        // mirror op
+        let mut buf = [0; 1024];
+        let size = msg.encode(&mut buf, 1024).unwrap();
+        assert_eq!(size, msg.encoding_size());
+        assert_eq!(Message::decode(&buf[..size]).unwrap(), msg);
+
+        if found == 0 {
+            assert_eq!(
+                netrs.epoch,
+                Epoch::from_str("2019-01-13T00:00:00 GPST").unwrap()
+            );
+            assert_eq!(netrs.satellites.len(), 11);
+
+            let prns = netrs
+                .satellites
+                .iter()
+                .map(|sat| sat.prn + 1)
+                .collect::<Vec<_>>();
+            assert_eq!(prns, [30, 8, 7, 18, 1, 28, 9, 11, 17, 27, 13]);
+
+            let g30 = netrs.satellites[0];
+            let l1 = g30.l1.unwrap();
+            assert_eq!(l1.pseudo_range_mm, 20644587647);
+            assert_eq!(l1.cn0, 476);
+            assert_eq!(l1.phase.unwrap().offset, 259974);
+            assert!((l1.phase_range_m().unwrap() - 20644592.84648).abs() < 1.0E-6);
+
+            let l2 = g30.l2.unwrap();
+            assert!(!l2.is_p_code());
+            assert_eq!(l2.cn0, 522);
+            assert_eq!(l2.range_delta_mm, 7542);
+            assert!((l2.pseudo_range_m(&l1) - 20644595.189).abs() < 1.0E-6);
+            assert_eq!(l2.phase.unwrap().offset, 342980);
+            assert!((l2.phase_range_m(&l1).unwrap() - 20644586.9646).abs() < 1.0E-6);
+        }
+
+        found += 1;
+    }
+
+    assert!(found > 0, "not a single observation decoded");
+}
+
 #[cfg(feature = "flate2")]
 #[test]
 fn gziped_files() {
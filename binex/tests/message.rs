@@ -1,5 +1,6 @@
 use binex::prelude::{
-    EphemerisFrame, Epoch, GPSEphemeris, GPSRaw, Message, Meta, MonumentGeoRecord, Record,
+    EphemerisFrame, Epoch, Error, GPSEphemeris, GPSRaw, Message, Meta, MonumentGeoRecord, NetRSL1,
+    NetRSL2, NetRSObservation, NetRSPhase, NetRSSatellite, ObservationFrame, Record,
 };
 
 #[test]
@@ -105,3 +106,115 @@ fn test_crc16_gps() {
     let parsed = Message::decode(&encoded).unwrap();
     assert_eq!(msg, parsed);
 }
+
+#[test]
+fn test_reversed_gps() {
+    for big_endian in [true, false] {
+        let mut meta = Meta::default();
+        meta.big_endian = big_endian;
+        meta.reversed = true;
+        meta.enhanced_crc = false;
+
+        let msg = Message::new(
+            meta,
+            Record::new_ephemeris_frame(EphemerisFrame::new_gps(GPSEphemeris::default())),
+        );
+
+        // SYNC + MID(1) + MLEN(2) + RLEN + CRC(2) + reversed length(2) + SYNC
+        assert_eq!(msg.encoding_size(), 1 + 1 + 2 + 129 + 2 + 2 + 1);
+
+        let mut encoded = [0; 256];
+        let size = msg.encode(&mut encoded, 256).unwrap();
+        assert_eq!(size, msg.encoding_size());
+
+        if big_endian {
+            assert_eq!(encoded[0], 0xf2); // SYNC
+            assert_eq!(encoded[size - 1], 0xb0); // terminating SYNC
+        } else {
+            assert_eq!(encoded[0], 0xd2); // SYNC
+            assert_eq!(encoded[size - 1], 0xb4); // terminating SYNC
+        }
+
+        let parsed = Message::decode(&encoded).unwrap();
+        assert_eq!(msg, parsed);
+
+        // missing termination
+        match Message::decode(&encoded[..size - 1]) {
+            Err(Error::IncompleteMessage(_)) => {},
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // corrupt termination
+        encoded[size - 1] = 0;
+        match Message::decode(&encoded) {
+            Err(Error::ReversedStream) => {},
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_netrs_observation() {
+    let l1 = NetRSL1 {
+        status: 0x21,
+        cn0: 476,
+        pseudo_range_mm: 20644587647,
+        phase: Some(NetRSPhase {
+            flag: true,
+            offset: -259974,
+        }),
+    };
+
+    let l2 = NetRSL2 {
+        status: 0x18,
+        cn0: 522,
+        range_delta_mm: -7542,
+        phase: None,
+    };
+
+    let obs = NetRSObservation::new(Epoch::from_gpst_seconds(1234567.5))
+        .with_satellite(NetRSSatellite {
+            prn: 29,
+            l1: Some(l1),
+            l2: Some(l2),
+        })
+        .with_satellite(NetRSSatellite {
+            prn: 7,
+            l1: Some(l1),
+            l2: None,
+        });
+
+    // content differs from one satellite to another
+    assert!(obs.per_sv_content);
+
+    for big_endian in [true, false] {
+        let mut meta = Meta::default();
+        meta.big_endian = big_endian;
+        meta.reversed = false;
+        meta.enhanced_crc = false;
+
+        let msg = Message::new(
+            meta,
+            Record::new_observation(ObservationFrame::new_netrs(obs.clone())),
+        );
+
+        // SYNC + MID + MLEN + (subrecord + epoch + flags + 2 PRNs + 2 masks + 10 + 7 + 10) + CRC
+        assert_eq!(msg.encoding_size(), 1 + 1 + 1 + 1 + 6 + 1 + 2 + 2 + 27 + 1);
+
+        let mut encoded = [0; 128];
+        let size = msg.encode(&mut encoded, 128).unwrap();
+        assert_eq!(size, msg.encoding_size());
+
+        assert_eq!(encoded[1], 0x7f); // MID
+        assert_eq!(encoded[3], 0x03); // NetRS subrecord
+
+        let parsed = Message::decode(&encoded).unwrap();
+        assert_eq!(msg, parsed);
+
+        let netrs = parsed.record.as_observation().unwrap().as_netrs().unwrap();
+        let l2 = netrs.satellites[0].l2.unwrap();
+        assert!(l2.is_p_code());
+        assert!((l2.pseudo_range_m(&l1) - 20644580.105).abs() < 1.0E-6);
+        assert!(l2.phase_range_m(&l1).is_none());
+    }
+}
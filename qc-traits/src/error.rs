@@ -0,0 +1,63 @@
+//! Error categorization
+
+/// [ErrorKind] categorizes the errors of the RINEX ecosystem crates, so applications
+/// can decide how to react (retry, skip the file, abort..)
+/// without matching every single variant.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Transient I/O failure (interrupted, timed out, lost connection..):
+    /// the operation may succeed if retried
+    Io,
+    /// Resource is missing or cannot be accessed (not found, permission denied..):
+    /// retrying will not help
+    Unavailable,
+    /// Header section is invalid
+    MalformedHeader,
+    /// Record (file body) is invalid
+    MalformedRecord,
+    /// Valid content or operation that we do not support
+    Unsupported,
+    /// Resource limits were exceeded
+    LimitExceeded,
+    /// Operation was cancelled on user request
+    Cancelled,
+}
+
+impl ErrorKind {
+    /// Returns true if the operation that failed may succeed if attempted again
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Io)
+    }
+    /// Categorizes a [std::io::Error] from its [std::io::ErrorKind]:
+    /// only transient failures are retryable, invalid or truncated
+    /// content is a [Self::MalformedRecord].
+    /// ```
+    /// use rinex_qc_traits::ErrorKind;
+    /// let e = std::io::Error::from(std::io::ErrorKind::TimedOut);
+    /// assert!(ErrorKind::from_io_error(&e).is_retryable());
+    ///
+    /// let e = std::io::Error::from(std::io::ErrorKind::NotFound);
+    /// assert_eq!(ErrorKind::from_io_error(&e), ErrorKind::Unavailable);
+    /// assert!(!ErrorKind::from_io_error(&e).is_retryable());
+    ///
+    /// let e = std::io::Error::from(std::io::ErrorKind::UnexpectedEof);
+    /// assert_eq!(ErrorKind::from_io_error(&e), ErrorKind::MalformedRecord);
+    /// ```
+    pub fn from_io_error(e: &std::io::Error) -> Self {
+        use std::io::ErrorKind as IoErrorKind;
+        match e.kind() {
+            IoErrorKind::Interrupted
+            | IoErrorKind::TimedOut
+            | IoErrorKind::WouldBlock
+            | IoErrorKind::ConnectionRefused
+            | IoErrorKind::ConnectionReset
+            | IoErrorKind::ConnectionAborted
+            | IoErrorKind::NotConnected
+            | IoErrorKind::BrokenPipe => Self::Io,
+            IoErrorKind::InvalidData | IoErrorKind::UnexpectedEof => Self::MalformedRecord,
+            IoErrorKind::Unsupported => Self::Unsupported,
+            _ => Self::Unavailable,
+        }
+    }
+}
@@ -2,6 +2,9 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(docrs, feature(doc_cfg))]
 
+pub mod error;
+pub use error::ErrorKind;
+
 pub mod merge;
 pub use merge::{Error as MergeError, Merge};
 
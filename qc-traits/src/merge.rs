@@ -1,8 +1,10 @@
 //! Merge traits to extend data contexts
+use crate::ErrorKind;
 use thiserror::Error;
 
 /// [Merge] specific Errors.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// When merging B into A, both types should match
     /// otherwise operation in invalid.
@@ -22,6 +24,14 @@ pub enum Error {
     DataProviderAgencyMismatch,
 }
 
+impl Error {
+    /// Returns the [ErrorKind] of this [Error]: merging
+    /// incompatible datasets is not supported.
+    pub fn kind(&self) -> ErrorKind {
+        ErrorKind::Unsupported
+    }
+}
+
 /// Merge Trait is impleted to extend Data Contexts.
 pub trait Merge {
     /// Merge "rhs" dataset into self, to form extend dataset.
@@ -1,9 +1,11 @@
 use crate::processing::{FilterItem, ItemError};
 use hifitime::Duration;
+use std::str::FromStr;
 use thiserror::Error;
 
 /// Decimation filter parsing error
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("invalid decimated item")]
     InvalidDecimItem(#[from] ItemError),
@@ -61,36 +63,94 @@ pub trait Decimate {
     fn decimate_mut(&mut self, f: &DecimationFilter);
 }
 
+/*
+ * Parses a decimation interval, either "30 s" or compact "30s"
+ */
+fn parse_interval(content: &str) -> Option<Duration> {
+    if let Ok(dt) = Duration::from_str(content) {
+        return Some(dt);
+    }
+    let offset = content.find(|c: char| c.is_ascii_alphabetic())?;
+    if offset == 0 {
+        return None;
+    }
+    let (value, unit) = content.split_at(offset);
+    Duration::from_str(&format!("{} {}", value.trim(), unit.trim())).ok()
+}
+
 impl std::str::FromStr for DecimationFilter {
     type Err = Error;
+    /// Parses a [DecimationFilter] from either
+    ///   - a ratio: "10"
+    ///   - an interval: "30 s", "30s" or "dt=30s"
+    ///
+    /// optionally followed by the targetted item: "30 s:G08".
     fn from_str(content: &str) -> Result<Self, Self::Err> {
         let items: Vec<&str> = content.trim().split(':').collect();
-        if let Ok(dt) = Duration::from_str(items[0].trim()) {
+        let item = if items.len() > 1 {
+            Some(FilterItem::from_str(items[1].trim())?)
+        } else {
+            None // no subset description
+        };
+        let attribute = items[0].trim();
+        if let Some(dt) = attribute.strip_prefix("dt=") {
+            let dt = parse_interval(dt.trim())
+                .ok_or(Error::AttributeParsingError(attribute.to_string()))?;
             Ok(Self {
-                item: {
-                    if items.len() > 1 {
-                        let item = FilterItem::from_str(items[1].trim())?;
-                        Some(item)
-                    } else {
-                        None // no subset description
-                    }
-                },
+                item,
                 filter: DecimationFilterType::Duration(dt),
             })
-        } else if let Ok(r) = items[0].trim().parse::<u32>() {
+        } else if let Ok(r) = attribute.parse::<u32>() {
             Ok(Self {
-                item: {
-                    if items.len() > 1 {
-                        let item = FilterItem::from_str(items[1].trim())?;
-                        Some(item)
-                    } else {
-                        None
-                    }
-                },
+                item,
                 filter: DecimationFilterType::Modulo(r),
             })
+        } else if let Some(dt) = parse_interval(attribute) {
+            Ok(Self {
+                item,
+                filter: DecimationFilterType::Duration(dt),
+            })
         } else {
-            Err(Error::AttributeParsingError(items[0].to_string()))
+            Err(Error::AttributeParsingError(attribute.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn decim_from_str() {
+        for (desc, filter) in [
+            ("10", DecimationFilterType::Modulo(10)),
+            (
+                "30 s",
+                DecimationFilterType::Duration(Duration::from_seconds(30.0)),
+            ),
+            (
+                "30s",
+                DecimationFilterType::Duration(Duration::from_seconds(30.0)),
+            ),
+            (
+                "dt=30s",
+                DecimationFilterType::Duration(Duration::from_seconds(30.0)),
+            ),
+            (
+                "dt=10 min",
+                DecimationFilterType::Duration(Duration::from_seconds(600.0)),
+            ),
+        ] {
+            let decim = DecimationFilter::from_str(desc).unwrap();
+            assert_eq!(decim.filter, filter, "failed to parse \"{}\"", desc);
+            assert!(decim.item.is_none());
         }
+        let decim = DecimationFilter::from_str("dt=30s:G08").unwrap();
+        assert_eq!(
+            decim.filter,
+            DecimationFilterType::Duration(Duration::from_seconds(30.0))
+        );
+        assert!(decim.item.is_some());
+        assert!(DecimationFilter::from_str("dt=").is_err());
+        assert!(DecimationFilter::from_str("s").is_err());
     }
 }
@@ -2,6 +2,7 @@ use crate::{preprocessing::TargetItem, Duration};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("unknown decimation target")]
     TargetError(#[from] crate::algorithm::target::Error),
@@ -10,6 +10,7 @@ use gnss_rs::{
 use hifitime::{Duration, Epoch, ParsingError as EpochParsingError};
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ItemError {
     #[error("unknown filter item \"{0}\"")]
     UnknownItem(String),
@@ -25,6 +26,10 @@ pub enum ItemError {
     InvalidElevationAngle,
     #[error("invalid azimuth angle description (0 <= a <= 360)")]
     InvalidAzimuthAngle,
+    #[error("invalid latitude (-90 <= lat <= 90)")]
+    InvalidLatitude,
+    #[error("invalid longitude (-180 <= lon <= 360)")]
+    InvalidLongitude,
     #[error("invalid float number")]
     FloatParsing(#[from] ParseFloatError),
     #[error("sv item parsing")]
@@ -49,6 +54,10 @@ pub enum FilterItem {
     ElevationItem(f64),
     /// Azimuth Angle Item in degrees, 0 <= a <= 360°
     AzimuthItem(f64),
+    /// Latitude Item in decimal degrees, -90 <= lat <= 90°
+    LatitudeItem(f64),
+    /// Longitude Item in decimal degrees, -180 <= lon <= 360°
+    LongitudeItem(f64),
     /// List of spacecrafts described as [SV]
     SvItem(Vec<SV>),
     /// List of [Constellation]s
@@ -135,6 +144,22 @@ impl FilterItem {
         }
         Err(ItemError::InvalidAzimuthAngle)
     }
+    pub(crate) fn from_latitude(content: &str) -> Result<Self, ItemError> {
+        if let Ok(float) = parse_float_payload(content) {
+            if (-90.0..=90.0).contains(&float) {
+                return Ok(Self::LatitudeItem(float));
+            }
+        }
+        Err(ItemError::InvalidLatitude)
+    }
+    pub(crate) fn from_longitude(content: &str) -> Result<Self, ItemError> {
+        if let Ok(float) = parse_float_payload(content) {
+            if (-180.0..=360.0).contains(&float) {
+                return Ok(Self::LongitudeItem(float));
+            }
+        }
+        Err(ItemError::InvalidLongitude)
+    }
     pub(crate) fn from_snr(content: &str) -> Result<Self, ItemError> {
         if let Ok(float) = parse_float_payload(content) {
             Ok(Self::SNRItem(float))
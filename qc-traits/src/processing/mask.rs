@@ -3,6 +3,7 @@ use thiserror::Error;
 
 /// Mask filter parsing errors
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("invalid mask item")]
     InvalidMaskitem(#[from] ItemError),
@@ -183,7 +184,19 @@ impl std::str::FromStr for MaskFilter {
             // after the identifier, in those cases
 
             let start = &cleanedup[..operand_offset];
-            if start[0..1].eq("e") {
+            if start.trim_end().eq("lat") || start.trim_end().eq("lon") {
+                // --> Latitude or Longitude Mask case
+                // operand might be split by a whitespace, from the search above
+                let remainder = cleanedup[operand_offset..].trim_start();
+                let operand = remainder.parse::<MaskOperand>()?;
+                let payload = remainder[operand.formatted_len()..].trim();
+                let item = if start.trim_end().eq("lat") {
+                    FilterItem::from_latitude(payload)?
+                } else {
+                    FilterItem::from_longitude(payload)?
+                };
+                Ok(Self { operand, item })
+            } else if start[0..1].eq("e") {
                 // --> Elevation Mask case
                 let float_offset = operand_offset + operand.formatted_len() + 2;
                 Ok(Self {
@@ -301,6 +314,37 @@ mod test {
         }
     }
     #[test]
+    fn mask_lat_lon() {
+        for (desc, expected) in [
+            (
+                "lat>45.0",
+                Some((MaskOperand::GreaterThan, FilterItem::LatitudeItem(45.0))),
+            ),
+            (
+                "lat <= -30",
+                Some((MaskOperand::LowerEquals, FilterItem::LatitudeItem(-30.0))),
+            ),
+            (
+                "lon>=-10.5",
+                Some((MaskOperand::GreaterEquals, FilterItem::LongitudeItem(-10.5))),
+            ),
+            (
+                " lon < 20",
+                Some((MaskOperand::LowerThan, FilterItem::LongitudeItem(20.0))),
+            ),
+            ("lat > 95.0", None),
+            ("lon > 400.0", None),
+        ] {
+            let mask = MaskFilter::from_str(desc);
+            match expected {
+                Some((operand, item)) => {
+                    assert_eq!(mask.unwrap(), MaskFilter { operand, item }, "{}", desc);
+                },
+                None => assert!(mask.is_err(), "{} should not be valid", desc),
+            }
+        }
+    }
+    #[test]
     fn mask_gnss() {
         for (descriptor, opposite_desc) in [
             (" = GPS", "!= GPS"),
@@ -1,4 +1,5 @@
 //! Processing toolkit, including filter designer.
+use crate::ErrorKind;
 use std::str::FromStr;
 use thiserror::Error;
 
@@ -46,6 +47,7 @@ pub trait RepairTrait {
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("invalid filter")]
     InvalidFilter,
@@ -59,6 +61,14 @@ pub enum Error {
     DecimationFilterParsing(#[from] DecimationError),
 }
 
+impl Error {
+    /// Returns the [ErrorKind] of this [Error]:
+    /// invalid filter descriptions are not supported.
+    pub fn kind(&self) -> ErrorKind {
+        ErrorKind::Unsupported
+    }
+}
+
 /// Preprocessing filters, to preprocess RINEX data prior further analysis.
 /// Filters can apply either on entire RINEX or subsets.
 /// Refer to [TargetItem] definition to understand which data subsets exist.  
@@ -143,8 +153,8 @@ impl std::str::FromStr for Filter {
         let items: Vec<&str> = content.split(':').collect();
 
         let identifier = items[0].trim();
-        if identifier.eq("decim") {
-            let offset = 6; //"decim:"
+        if identifier.eq("decim") || identifier.eq("d") {
+            let offset = content.find(':').unwrap_or(content.len() - 1) + 1; //"decim:" or "d:"
             Ok(Self::Decimation(DecimationFilter::from_str(
                 content[offset..].trim(),
             )?))
@@ -200,6 +210,11 @@ mod test {
             "decim:1 hour",
             "decim:10 min:l1c",
             "decim:1 hour:L1C,L2C,L3C",
+            "d:10",
+            "d:30 s",
+            "d:dt=30s",
+            "d:30 s:G01",
+            "d:dt=30s:L1C,C1C",
         ] {
             let filt = Filter::from_str(desc);
             assert!(filt.is_ok(), "Filter::from_str failed on \"{}\"", desc);
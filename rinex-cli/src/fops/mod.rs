@@ -17,7 +17,7 @@ use clap::ArgMatches;
 
 use rinex::{
     prelude::Rinex,
-    prod::{DataSource, DetailedProductionAttributes, ProductionAttributes, FFU, PPU},
+    prod::{Compression, DataSource, DetailedProductionAttributes, ProductionAttributes, FFU, PPU},
 };
 
 /*
@@ -119,7 +119,10 @@ fn output_filename(
             }
         }
     } else {
+        // compression is only determined by the User
+        let mut prod = prod;
+        prod.compression = gzip.map(|_| Compression::Gzip);
         debug!("{:?}", prod);
-        rinex.standard_filename(short, gzip, Some(prod))
+        rinex.standard_filename(short, None, Some(prod))
     }
 }
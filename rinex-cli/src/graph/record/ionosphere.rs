@@ -53,7 +53,7 @@ pub fn plot_ionospheric_delay(ctx: &QcContext, plot_ctx: &mut PlotContext) {
                              */
                             let sv_position = match ctx.sp3() {
                                 Some(sp3) => sp3.sv_position_interpolate(sv, *t, 11),
-                                None => nav.sv_position_interpolate(sv, *t, 11),
+                                None => nav.sv_position_interpolate(sv, *t),
                             };
                             let sv_position = sv_position?;
                             let (elev, azim) =
@@ -14,12 +14,13 @@ mod report;
 use report::Report;
 
 use rinex_qc::prelude::{QcContext, QcExtraPage};
-use std::path::Path;
+use std::{fs::File, io::Read, path::Path};
 use walkdir::WalkDir;
 
 extern crate gnss_rs as gnss;
 
-use rinex::prelude::Rinex;
+use rinex::prelude::{ErrorKind, Rinex};
+use sinex::Sinex;
 use sp3::prelude::SP3;
 
 use cli::{Cli, Context, RemoteReferenceSite, Workspace};
@@ -63,8 +64,55 @@ pub enum Error {
     CsvError(#[from] CsvError),
 }
 
+impl Error {
+    /*
+     * Process exit code, so scripts can tell
+     * retryable errors from invalid input
+     */
+    fn exit_code(&self) -> i32 {
+        let kind = match self {
+            Self::StdioError(e) => ErrorKind::from_io_error(e),
+            Self::RinexError(e) => e.kind(),
+            Self::MergeError(e) => e.kind(),
+            Self::SplitError(e) => e.kind(),
+            _ => return 1,
+        };
+        match kind {
+            ErrorKind::Io => 2,
+            ErrorKind::MalformedHeader => 3,
+            ErrorKind::MalformedRecord => 4,
+            ErrorKind::Unsupported => 5,
+            ErrorKind::LimitExceeded => 6,
+            ErrorKind::Unavailable => 7,
+            ErrorKind::Cancelled => 8,
+            _ => 1,
+        }
+    }
+}
+
 /*
- * Parses and preprepocess all files passed by User
+ * Returns true if this file looks like a SINEX bias file:
+ * either from its .BIA/.BSX extension, or its "%=BIA" header line.
+ */
+fn is_sinex_bias(path: &Path) -> bool {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_uppercase());
+    if matches!(extension.as_deref(), Some("BIA") | Some("BSX")) {
+        return true;
+    }
+    let mut marker = [0_u8; 5];
+    File::open(path)
+        .and_then(|mut fd| fd.read_exact(&mut marker))
+        .map(|_| &marker == b"%=BIA")
+        .unwrap_or(false)
+}
+
+/*
+ * Parses and preprepocess all files passed by User.
+ * Files that are neither SP3 nor SINEX bias are parsed as RINEX:
+ * the RINEX parsing error is then returned.
  */
 fn user_data_parsing(
     cli: &Cli,
@@ -72,7 +120,7 @@ fn user_data_parsing(
     directories: Vec<&String>,
     max_depth: usize,
     is_rover: bool,
-) -> QcContext {
+) -> Result<QcContext, Error> {
     let mut ctx = QcContext::new(cli.jpl_bpc_update())
         .unwrap_or_else(|e| panic!("failed to initialize a context: {}", e));
 
@@ -104,6 +152,24 @@ fn user_data_parsing(
                             loading.err().unwrap()
                         );
                     }
+                } else if is_sinex_bias(path) {
+                    match Sinex::from_file(&path.to_string_lossy()) {
+                        Ok(sinex) => {
+                            let loading = ctx.load_sinex(path, sinex);
+                            if loading.is_ok() {
+                                info!("Loading SINEX file \"{}\"", path.display());
+                            } else {
+                                warn!(
+                                    "failed to load SINEX file \"{}\": {}",
+                                    path.display(),
+                                    loading.err().unwrap()
+                                );
+                            }
+                        },
+                        Err(e) => {
+                            warn!("failed to parse SINEX file \"{}\": {}", path.display(), e);
+                        },
+                    }
                 } else {
                     warn!("non supported file format \"{}\"", path.display());
                 }
@@ -113,7 +179,8 @@ fn user_data_parsing(
     // load individual files
     for fp in single_files.iter() {
         let path = Path::new(fp);
-        if let Ok(rinex) = Rinex::from_path(path) {
+        let rinex = Rinex::from_path(path);
+        if let Ok(rinex) = rinex {
             let loading = ctx.load_rinex(path, rinex);
             if loading.is_err() {
                 warn!(
@@ -131,8 +198,25 @@ fn user_data_parsing(
                     loading.err().unwrap()
                 );
             }
-        } else {
-            warn!("non supported file format \"{}\"", path.display());
+        } else if is_sinex_bias(path) {
+            match Sinex::from_file(&path.to_string_lossy()) {
+                Ok(sinex) => {
+                    let loading = ctx.load_sinex(path, sinex);
+                    if loading.is_err() {
+                        warn!(
+                            "failed to load SINEX file \"{}\": {}",
+                            path.display(),
+                            loading.err().unwrap()
+                        );
+                    }
+                },
+                Err(e) => {
+                    warn!("failed to parse SINEX file \"{}\": {}", path.display(), e);
+                },
+            }
+        } else if let Err(e) = rinex {
+            error!("failed to parse \"{}\": {}", path.display(), e);
+            return Err(Error::RinexError(e));
         }
     }
     /*
@@ -153,10 +237,17 @@ fn user_data_parsing(
         },
     }
 
-    ctx
+    Ok(ctx)
+}
+
+pub fn main() {
+    if let Err(e) = run() {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(e.exit_code());
+    }
 }
 
-pub fn main() -> Result<(), Error> {
+fn run() -> Result<(), Error> {
     let mut builder = Builder::from_default_env();
     builder
         .target(Target::Stdout)
@@ -178,7 +269,7 @@ pub fn main() -> Result<(), Error> {
         cli.rover_directories(),
         max_recursive_depth,
         true,
-    );
+    )?;
     let ctx_position = data_ctx.reference_position();
     let ctx_stem = Context::context_stem(&mut data_ctx);
 
@@ -242,7 +333,7 @@ pub fn main() -> Result<(), Error> {
                         cli.base_station_directories(),
                         max_recursive_depth,
                         false,
-                    );
+                    )?;
                     // We currently require remote site
                     // to have its geodetic marker declared
                     if let Some(reference_point) = data.reference_position() {
@@ -345,4 +436,4 @@ pub fn main() -> Result<(), Error> {
     }
 
     Ok(())
-} // main
+} // run
@@ -33,7 +33,7 @@ pub use clock::ClockStateProvider;
 
 use rinex::{
     carrier::Carrier,
-    prelude::{Constellation, Rinex},
+    prelude::{Constellation, Observable, Rinex},
 };
 
 use rinex_qc::prelude::QcExtraPage;
@@ -111,84 +111,39 @@ fn rtk_reference_carrier(carrier: RTKCarrier) -> bool {
     )
 }
 
-//use map_3d::{ecef2geodetic, rad2deg, Ellipsoid};
-
-//pub fn tropo_components(meteo: Option<&Rinex>, t: Epoch, lat_ddeg: f64) -> Option<(f64, f64)> {
-//    const MAX_LATDDEG_DELTA: f64 = 15.0;
-//    let max_dt = Duration::from_hours(24.0);
-//    let rnx = meteo?;
-//    let meteo = rnx.header.meteo.as_ref().unwrap();
-//
-//    let delays: Vec<(Observable, f64)> = meteo
-//        .sensors
-//        .iter()
-//        .filter_map(|s| match s.observable {
-//            Observable::ZenithDryDelay => {
-//                let (x, y, z, _) = s.position?;
-//                let (lat, _, _) = ecef2geodetic(x, y, z, Ellipsoid::WGS84);
-//                let lat = rad2deg(lat);
-//                if (lat - lat_ddeg).abs() < MAX_LATDDEG_DELTA {
-//                    let value = rnx
-//                        .zenith_dry_delay()
-//                        .filter(|(t_sens, _)| (*t_sens - t).abs() < max_dt)
-//                        .min_by_key(|(t_sens, _)| (*t_sens - t).abs());
-//                    let (_, value) = value?;
-//                    debug!("{:?} lat={} zdd {}", t, lat_ddeg, value);
-//                    Some((s.observable.clone(), value))
-//                } else {
-//                    None
-//                }
-//            },
-//            Observable::ZenithWetDelay => {
-//                let (x, y, z, _) = s.position?;
-//                let (mut lat, _, _) = ecef2geodetic(x, y, z, Ellipsoid::WGS84);
-//                lat = rad2deg(lat);
-//                if (lat - lat_ddeg).abs() < MAX_LATDDEG_DELTA {
-//                    let value = rnx
-//                        .zenith_wet_delay()
-//                        .filter(|(t_sens, _)| (*t_sens - t).abs() < max_dt)
-//                        .min_by_key(|(t_sens, _)| (*t_sens - t).abs());
-//                    let (_, value) = value?;
-//                    debug!("{:?} lat={} zdd {}", t, lat_ddeg, value);
-//                    Some((s.observable.clone(), value))
-//                } else {
-//                    None
-//                }
-//            },
-//            _ => None,
-//        })
-//        .collect();
-//
-//    if delays.len() < 2 {
-//        None
-//    } else {
-//        let zdd = delays
-//            .iter()
-//            .filter_map(|(obs, value)| {
-//                if obs == &Observable::ZenithDryDelay {
-//                    Some(*value)
-//                } else {
-//                    None
-//                }
-//            })
-//            .reduce(|k, _| k)
-//            .unwrap();
-//
-//        let zwd = delays
-//            .iter()
-//            .filter_map(|(obs, value)| {
-//                if obs == &Observable::ZenithWetDelay {
-//                    Some(*value)
-//                } else {
-//                    None
-//                }
-//            })
-//            .reduce(|k, _| k)
-//            .unwrap();
-//
-//        Some((zwd, zdd))
-//    }
-//}
+/*
+ * Returns (zwd, zdd) in mm, measured by meteo sensors located
+ * in the vicinity of given latitude, nearest in time.
+ */
+pub fn tropo_components(meteo: Option<&Rinex>, t: Epoch, lat_ddeg: f64) -> Option<(f64, f64)> {
+    const MAX_LATDDEG_DELTA: f64 = 15.0;
+    let max_dt = Duration::from_hours(24.0);
+    let rnx = meteo?;
+
+    let delay = |observable: Observable| -> Option<f64> {
+        let sensor = rnx
+            .meteo_sensors()
+            .find(|sensor| sensor.observable == observable)?;
+        let (lat, _, _) = sensor.position?.to_geodetic();
+        if (lat - lat_ddeg).abs() > MAX_LATDDEG_DELTA {
+            return None;
+        }
+        let delays = if observable == Observable::ZenithDryDelay {
+            rnx.zenith_dry_delay()
+        } else {
+            rnx.zenith_wet_delay()
+        };
+        let (_, value) = delays
+            .filter(|(t_sens, _)| (*t_sens - t).abs() < max_dt)
+            .min_by_key(|(t_sens, _)| (*t_sens - t).abs())?;
+        debug!("{:?} lat={} {} {}", t, lat_ddeg, observable, value);
+        Some(value)
+    };
+
+    let zdd = delay(Observable::ZenithDryDelay)?;
+    let zwd = delay(Observable::ZenithWetDelay)?;
+    Some((zwd, zdd))
+}
 
 /*
  * Grabs nearest KB model (in time)
@@ -2,8 +2,8 @@
 use crate::{
     cli::Context,
     positioning::{
-        bd_model, cast_rtk_carrier, kb_model, ng_model, ClockStateProvider, EphemerisSource,
-        RemoteRTKReference,
+        bd_model, cast_rtk_carrier, kb_model, ng_model, tropo_components, ClockStateProvider,
+        EphemerisSource, RemoteRTKReference,
     },
 };
 
@@ -34,6 +34,12 @@ pub fn resolve<'a, 'b, CK: ClockStateProvider, O: OrbitSource>(
     // infaillible, at this point
     let obs_data = ctx.data.observation().unwrap();
 
+    // latitude of possible meteo sensors
+    let rx_lat_ddeg = ctx
+        .data
+        .reference_position()
+        .map(|position| position.to_geodetic().0);
+
     // Optional remote reference site
     // let rtk_compatible = ctx.rtk_compatible();
     // let remote_site = ctx.reference_site.as_ref();
@@ -66,16 +72,19 @@ pub fn resolve<'a, 'b, CK: ClockStateProvider, O: OrbitSource>(
                     }
 
                     if observable.is_pseudorange_observable() {
+                        // satellite code bias correction, when SINEX is provided
+                        let code_bias = ctx.data.code_bias(*sv, observable, *t).unwrap_or(0.0);
+                        let pr = data.obs - code_bias;
                         if let Some(obs) = observations
                             .iter_mut()
                             .filter(|ob| ob.carrier == rtk_carrier)
                             .reduce(|k, _| k)
                         {
-                            obs.set_pseudo_range(data.obs);
+                            obs.set_pseudo_range(pr);
                         } else {
                             observations.push(Observation::pseudo_range(
                                 rtk_carrier,
-                                data.obs,
+                                pr,
                                 data.snr.map(|snr| snr.into()),
                             ));
                         }
@@ -131,7 +140,12 @@ pub fn resolve<'a, 'b, CK: ClockStateProvider, O: OrbitSource>(
                 }
             }
             // customization: Tropo
-            // TODO (Meteo)
+            if let Some((zwd, zdd)) =
+                rx_lat_ddeg.and_then(|lat_ddeg| tropo_components(ctx.data.meteo(), *t, lat_ddeg))
+            {
+                // TODO: forward measured components to the solver
+                debug!("{} ({}) - measured zwd={} zdd={} mm", *t, *sv, zwd, zdd);
+            }
             let tropo = TropoComponents::Unknown;
             candidate.set_tropo_components(tropo);
 
@@ -2,7 +2,7 @@
 use thiserror::Error;
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     ffi::OsStr,
     fs::create_dir_all,
     fs::File,
@@ -12,7 +12,9 @@ use std::{
 
 use rinex::{
     merge::{Error as RinexMergeError, Merge as RinexMerge},
-    prelude::{Almanac, GroundPosition, Rinex, TimeScale},
+    navigation::dilution_of_precision,
+    observation::SNR,
+    prelude::{Almanac, Epoch, GroundPosition, Orbit, Rinex, TimeScale, SV},
     types::Type as RinexType,
     Error as RinexError,
 };
@@ -31,8 +33,16 @@ use anise::{
 #[cfg(feature = "sp3")]
 use sp3::prelude::SP3;
 
+#[cfg(feature = "sinex")]
+use rinex::prelude::Observable;
+
+#[cfg(feature = "sinex")]
+use sinex::{bias::TimeSystem as BiasTimeSystem, Sinex};
+
 use qc_traits::{
-    processing::{Filter, Preprocessing, Repair, RepairTrait},
+    processing::{
+        Filter, FilterItem, MaskFilter, MaskOperand, Masking, Preprocessing, Repair, RepairTrait,
+    },
     Merge, MergeError,
 };
 
@@ -55,6 +65,8 @@ pub enum Error {
     RinexError(#[from] RinexError),
     #[error("failed to extend rinex context")]
     RinexMergeError(#[from] RinexMergeError),
+    #[error("no observation data to export")]
+    MissingObservationRinex,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -79,6 +91,10 @@ pub enum ProductType {
     #[cfg_attr(docsrs, doc(cfg(feature = "sp3")))]
     /// High precision clock data wrapped in SP3 files.
     HighPrecisionOrbit,
+    #[cfg(feature = "sinex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sinex")))]
+    /// Observable specific biases wrapped in SINEX (BIA) files.
+    BiasSolutions,
 }
 
 impl std::fmt::Display for ProductType {
@@ -93,6 +109,8 @@ impl std::fmt::Display for ProductType {
             Self::BroadcastNavigation => write!(f, "Broadcast Navigation (BRDC)"),
             #[cfg(feature = "sp3")]
             Self::HighPrecisionOrbit => write!(f, "High Precision Orbit (SP3)"),
+            #[cfg(feature = "sinex")]
+            Self::BiasSolutions => write!(f, "Bias Solutions (SINEX)"),
         }
     }
 }
@@ -117,6 +135,9 @@ enum BlobData {
     #[cfg(feature = "sp3")]
     /// SP3 content
     Sp3(SP3),
+    #[cfg(feature = "sinex")]
+    /// SINEX content
+    Sinex(Sinex),
 }
 
 impl BlobData {
@@ -152,6 +173,24 @@ impl BlobData {
             _ => None,
         }
     }
+    /// Returns reference to inner SINEX data.
+    #[cfg(feature = "sinex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sinex")))]
+    pub fn as_sinex(&self) -> Option<&Sinex> {
+        match self {
+            Self::Sinex(s) => Some(s),
+            _ => None,
+        }
+    }
+    /// Returns mutable reference to inner SINEX data.
+    #[cfg(feature = "sinex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sinex")))]
+    pub fn as_mut_sinex(&mut self) -> Option<&mut Sinex> {
+        match self {
+            Self::Sinex(s) => Some(s),
+            _ => None,
+        }
+    }
 }
 
 /// [QcContext] is a general structure capable to store most common
@@ -166,6 +205,8 @@ pub struct QcContext {
     pub almanac: Almanac,
     /// ECEF frame
     pub earth_cef: Frame,
+    /// Interpolated [SV] positions (km), see [Self::orbit_interpolation_mut]
+    orbits: BTreeMap<(Epoch, SV), (f64, f64, f64)>,
 }
 
 impl QcContext {
@@ -343,6 +384,7 @@ impl QcContext {
             almanac,
             files: Default::default(),
             blob: Default::default(),
+            orbits: Default::default(),
         })
     }
 
@@ -423,6 +465,8 @@ impl QcContext {
             ProductType::HighPrecisionClock,
             #[cfg(feature = "sp3")]
             ProductType::HighPrecisionOrbit,
+            #[cfg(feature = "sinex")]
+            ProductType::BiasSolutions,
         ] {
             if let Some(paths) = self.files(product) {
                 /*
@@ -516,6 +560,12 @@ impl QcContext {
     pub fn sp3(&self) -> Option<&SP3> {
         self.data(ProductType::HighPrecisionOrbit)?.as_sp3()
     }
+    /// Returns reference to inner SINEX bias data
+    #[cfg(feature = "sinex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sinex")))]
+    pub fn bias(&self) -> Option<&Sinex> {
+        self.data(ProductType::BiasSolutions)?.as_sinex()
+    }
     /// Returns reference to inner [ProductType::Observation] data
     pub fn observation(&self) -> Option<&Rinex> {
         self.data(ProductType::Observation)?.as_rinex()
@@ -572,6 +622,12 @@ impl QcContext {
     pub fn sp3_mut(&mut self) -> Option<&mut SP3> {
         self.data_mut(ProductType::HighPrecisionOrbit)?.as_mut_sp3()
     }
+    /// Returns mutable reference to inner SINEX bias data
+    #[cfg(feature = "sinex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sinex")))]
+    pub fn bias_mut(&mut self) -> Option<&mut Sinex> {
+        self.data_mut(ProductType::BiasSolutions)?.as_mut_sinex()
+    }
     /// Returns mutable reference to inner [ProductType::ANTEX] data
     pub fn antex_mut(&mut self) -> Option<&mut Rinex> {
         self.data_mut(ProductType::ANTEX)?.as_mut_rinex()
@@ -594,6 +650,12 @@ impl QcContext {
     pub fn has_sp3(&self) -> bool {
         self.sp3().is_some()
     }
+    #[cfg(feature = "sinex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sinex")))]
+    /// Returns true if SINEX bias solutions are present in current context
+    pub fn has_bias(&self) -> bool {
+        self.bias().is_some()
+    }
     /// Returns true if at least one [ProductType::DORIS] file is present
     pub fn has_doris(&self) -> bool {
         self.doris().is_some()
@@ -669,6 +731,80 @@ impl QcContext {
         }
         Ok(())
     }
+    /// Load a single SINEX bias (BIA) file into Self.
+    /// When bias solutions were previously loaded, the new solutions
+    /// are appended to the existing ones.
+    #[cfg(feature = "sinex")]
+    pub fn load_sinex(&mut self, path: &Path, sinex: Sinex) -> Result<(), Error> {
+        let solutions = sinex
+            .record
+            .bias_solutions()
+            .ok_or(Error::NonSupportedFileFormat)?;
+        let prod_type = ProductType::BiasSolutions;
+        // extend context blob
+        if let Some(paths) = self
+            .files
+            .iter_mut()
+            .filter_map(|(prod, files)| {
+                if *prod == prod_type {
+                    Some(files)
+                } else {
+                    None
+                }
+            })
+            .reduce(|k, _| k)
+        {
+            if let Some(inner) = self
+                .blob
+                .get_mut(&prod_type)
+                .and_then(|k| k.as_mut_sinex())
+                .and_then(|k| k.record.bias_solutions_mut())
+            {
+                inner.extend_from_slice(solutions);
+                paths.push(path.to_path_buf());
+            }
+        } else {
+            self.blob.insert(prod_type, BlobData::Sinex(sinex));
+            self.files.insert(prod_type, vec![path.to_path_buf()]);
+        }
+        Ok(())
+    }
+    /// Returns the satellite code bias (in meters) that applies to
+    /// this pseudo range [Observable] at `t`, as described by
+    /// SINEX Observable Specific Biases (OSB), if such file was loaded.
+    /// The returned value should be substracted from the raw pseudo range.
+    #[cfg(feature = "sinex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sinex")))]
+    pub fn code_bias(&self, sv: SV, observable: &Observable, t: Epoch) -> Option<f64> {
+        if !observable.is_pseudorange_observable() {
+            return None;
+        }
+        let sinex = self.bias()?;
+        let timescale = match sinex.description.bias_description()?.system {
+            BiasTimeSystem::UTC => TimeScale::UTC,
+            BiasTimeSystem::TAI => TimeScale::TAI,
+            BiasTimeSystem::GNSS(constellation) => constellation.timescale()?,
+        };
+        let t = Self::sinex_datetime(t, timescale)?;
+        let prn = format!("{:x}", sv);
+        let observable = observable.to_string();
+        sinex
+            .satellite_osb(&prn, &observable)
+            .find(|sol| sol.is_valid(t))
+            .and_then(|sol| match sol.unit.as_str() {
+                "ns" => Some(sol.estimate * 1.0E-9 * 299_792_458.0_f64),
+                _ => None,
+            })
+    }
+    /*
+     * Converts Epoch to SINEX datetime, expressed in given timescale
+     */
+    #[cfg(feature = "sinex")]
+    fn sinex_datetime(t: Epoch, timescale: TimeScale) -> Option<chrono::NaiveDateTime> {
+        let (y, m, d, hh, mm, ss, nanos) = t.to_gregorian(timescale);
+        chrono::NaiveDate::from_ymd_opt(y, m as u32, d as u32)?
+            .and_hms_nano_opt(hh as u32, mm as u32, ss as u32, nanos)
+    }
     /// True if Self is compatible with navigation
     pub fn nav_compatible(&self) -> bool {
         self.observation().is_some() && self.brdc_navigation().is_some()
@@ -726,6 +862,45 @@ impl QcContext {
         }
         None
     }
+    /// Returns Dilution of Precision (GDOP, PDOP, HDOP, VDOP) at each Observation [Epoch],
+    /// from the geometry of the satellites in sight, as seen from the [Self::reference_position].
+    /// Requires both Observation and Navigation RINEX, and a reference position.
+    /// Epochs with less than 4 satellites in sight are omitted.
+    pub fn dop(&self) -> Box<dyn Iterator<Item = (Epoch, (f64, f64, f64, f64))> + '_> {
+        let (obs, brdc, reference) = match (
+            self.observation(),
+            self.brdc_navigation(),
+            self.reference_position(),
+        ) {
+            (Some(obs), Some(brdc), Some(reference)) => (obs, brdc, reference),
+            _ => return Box::new([].into_iter()),
+        };
+        let (x0, y0, z0) = reference.to_ecef_wgs84();
+        let (x0_km, y0_km, z0_km) = (x0 / 1000.0, y0 / 1000.0, z0 / 1000.0);
+        Box::new(
+            obs.observation()
+                .filter_map(move |((t, flag), (_, vehicles))| {
+                    if !flag.is_ok() {
+                        return None;
+                    }
+                    let rx_orbit = Orbit::from_position(x0_km, y0_km, z0_km, *t, self.earth_cef);
+                    let geometry = vehicles
+                        .keys()
+                        .filter_map(|sv| {
+                            let el_az_range =
+                                brdc.sv_azimuth_elevation_range(*sv, *t, rx_orbit, &self.almanac)?;
+                            if el_az_range.elevation_deg > 0.0 {
+                                Some((el_az_range.elevation_deg, el_az_range.azimuth_deg))
+                            } else {
+                                None
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    let dop = dilution_of_precision(&geometry)?;
+                    Some((*t, dop))
+                }),
+        )
+    }
     /// Apply preprocessing filter algorithm to mutable [Self].
     /// Filter will apply to all data contained in the context.
     pub fn filter_mut(&mut self, filter: &Filter) {
@@ -752,12 +927,117 @@ impl QcContext {
             data.filter_mut(filter);
         }
     }
+    /// Drops observations from the primary Observation RINEX, of vehicles seen
+    /// below `min_deg` (or above `max_deg`) elevation angle from the [Self::reference_position]
+    /// (see [Rinex::elevation_mask_with_mut]). [SV] positions are interpolated from SP3
+    /// when loaded, broadcast ephemerides are used when SP3 does not describe the [SV].
+    /// When `min_snr` is specified, observations that do not reach this SNR are dropped
+    /// in the same pass. This has no effect without Observation RINEX.
+    pub fn elevation_mask_mut(&mut self, min_deg: f64, max_deg: Option<f64>, min_snr: Option<SNR>) {
+        let reference = self.reference_position();
+        // withdrawn while masking, so the other products are simply borrowed
+        let mut data = match self.blob.remove(&ProductType::Observation) {
+            Some(data) => data,
+            None => return,
+        };
+        if let Some(obs) = data.as_mut_rinex() {
+            let brdc = self.brdc_navigation();
+            #[cfg(feature = "sp3")]
+            let sp3 = self.sp3();
+            let sv_position_km = |sv: SV, t: Epoch| {
+                #[cfg(feature = "sp3")]
+                let position = sp3.and_then(|sp3| sp3.sv_position_interpolate(sv, t, 11));
+                #[cfg(not(feature = "sp3"))]
+                let position = None;
+
+                position.or_else(|| brdc.and_then(|brdc| brdc.sv_position_interpolate(sv, t)))
+            };
+            obs.elevation_mask_with_mut(
+                sv_position_km,
+                &self.almanac,
+                self.earth_cef,
+                min_deg,
+                max_deg,
+                reference,
+                false,
+            );
+            if let Some(min_snr) = min_snr {
+                obs.mask_mut(&MaskFilter {
+                    operand: MaskOperand::GreaterEquals,
+                    item: FilterItem::SNRItem(min_snr.into()),
+                });
+            }
+        }
+        self.blob.insert(ProductType::Observation, data);
+    }
     /// Fix given [Repair] condition
     pub fn repair_mut(&mut self, r: Repair) {
         if let Some(rinex) = self.observation_mut() {
             rinex.repair_mut(r);
         }
     }
+    /// Interpolates [SV] positions at each complete Epoch of the primary
+    /// Observation RINEX (see [Rinex::complete_epoch]), so [Self::sv_position]
+    /// returns them. SP3 is preferred (interpolated with said `order`),
+    /// and broadcast ephemerides are used when SP3 does not describe the [SV].
+    /// Positions are expressed in km, in both cases.
+    pub fn orbit_interpolation_mut(&mut self, order: usize, min_snr: Option<SNR>) {
+        let mut orbits = BTreeMap::new();
+        if let Some(obs) = self.observation() {
+            for (t, signals) in obs.complete_epoch(min_snr) {
+                let vehicles = signals.iter().map(|(sv, _)| *sv).collect::<BTreeSet<_>>();
+                for sv in vehicles {
+                    #[cfg(feature = "sp3")]
+                    let sp3 = self
+                        .sp3()
+                        .and_then(|sp3| sp3.sv_position_interpolate(sv, t, order));
+                    #[cfg(not(feature = "sp3"))]
+                    let sp3 = None;
+
+                    let position = sp3.or_else(|| {
+                        self.brdc_navigation()
+                            .and_then(|brdc| brdc.sv_position_interpolate(sv, t))
+                    });
+                    if let Some(position) = position {
+                        orbits.insert((t, sv), position);
+                    }
+                }
+            }
+        }
+        self.orbits = orbits;
+    }
+    /// [SV] position (km) Iterator. Returns interpolated positions
+    /// if [Self::orbit_interpolation_mut] was run, otherwise the
+    /// positions described by SP3 (preferred) or broadcast ephemerides.
+    pub fn sv_position(&self) -> Box<dyn Iterator<Item = (Epoch, SV, (f64, f64, f64))> + '_> {
+        if !self.orbits.is_empty() {
+            return Box::new(self.orbits.iter().map(|((t, sv), pos)| (*t, *sv, *pos)));
+        }
+        #[cfg(feature = "sp3")]
+        if let Some(sp3) = self.sp3() {
+            return Box::new(sp3.sv_position());
+        }
+        match self.brdc_navigation() {
+            Some(brdc) => brdc.sv_position(),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+    /// Drops incomplete Epochs from the primary Observation RINEX,
+    /// see [Rinex::complete_epoch_filter_mut].
+    pub fn complete_epoch_filter_mut(&mut self, min_snr: Option<SNR>) {
+        if let Some(rinex) = self.observation_mut() {
+            rinex.complete_epoch_filter_mut(min_snr);
+        }
+    }
+    /// Exports the primary [ProductType::Observation] RINEX to a new
+    /// Observation RINEX file, carrying all modifications applied to this context
+    /// (see [Self::filter_mut], [Self::complete_epoch_filter_mut]..).
+    /// Use the ".gz" extension for gzip compressed output.
+    pub fn export_primary(&self, path: &Path) -> Result<(), Error> {
+        let rinex = self.observation().ok_or(Error::MissingObservationRinex)?;
+        rinex.to_file(&path.to_string_lossy())?;
+        Ok(())
+    }
 }
 
 impl std::fmt::Debug for QcContext {
@@ -773,6 +1053,8 @@ impl std::fmt::Debug for QcContext {
             ProductType::ANTEX,
             #[cfg(feature = "sp3")]
             ProductType::HighPrecisionOrbit,
+            #[cfg(feature = "sinex")]
+            ProductType::BiasSolutions,
         ] {
             if let Some(files) = self.files(product) {
                 write!(f, "\n{}: ", product)?;
@@ -782,3 +1064,294 @@ impl std::fmt::Debug for QcContext {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hifitime::Unit;
+    use rinex::{navigation::Ephemeris, prelude::Constellation};
+    use std::str::FromStr;
+
+    /*
+     * Builds a context from the embedded almanac,
+     * which does not require network access.
+     */
+    fn context() -> QcContext {
+        let almanac = Almanac::until_2035().unwrap();
+        let earth_cef = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+        QcContext {
+            files: Default::default(),
+            blob: Default::default(),
+            almanac,
+            earth_cef,
+            orbits: Default::default(),
+        }
+    }
+
+    fn test_resource(path: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join(path)
+    }
+
+    fn load_rinex(ctx: &mut QcContext, path: &str) {
+        let path = test_resource(path);
+        let rinex = Rinex::from_path(&path).unwrap();
+        ctx.load_rinex(&path, rinex).unwrap();
+    }
+
+    /*
+     * Loads the first `hours` of this Observation RINEX
+     */
+    fn load_observation(ctx: &mut QcContext, path: &str, hours: f64) {
+        let path = test_resource(path);
+        let mut rinex = Rinex::from_path(&path).unwrap();
+        let t0 = rinex.first_epoch().unwrap();
+        let record = rinex.record.as_mut_obs().unwrap();
+        record.retain(|(t, _), _| *t < t0 + hours * Unit::Hour);
+        ctx.load_rinex(&path, rinex).unwrap();
+    }
+
+    #[cfg(feature = "sp3")]
+    fn load_sp3(ctx: &mut QcContext, path: &str, excluded: Option<SV>) {
+        let path = test_resource(path);
+        let mut sp3 = SP3::from_file(&path.to_string_lossy()).unwrap();
+        if let Some(excluded) = excluded {
+            sp3.data.retain(|k, _| k.sv != excluded);
+        }
+        ctx.load_sp3(&path, sp3).unwrap();
+    }
+
+    fn count(rinex: &Rinex) -> usize {
+        rinex
+            .observation()
+            .map(|(_, (_, vehicles))| vehicles.len())
+            .sum()
+    }
+
+    #[test]
+    fn elevation_mask_brdc() {
+        let mut ctx = context();
+        load_rinex(
+            &mut ctx,
+            "CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz",
+        );
+        load_rinex(&mut ctx, "NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz");
+
+        let expected = ctx.observation().unwrap().elevation_mask(
+            ctx.brdc_navigation().unwrap(),
+            &ctx.almanac,
+            ctx.earth_cef,
+            10.0,
+            None,
+            ctx.reference_position(),
+            false,
+        );
+        let total = count(ctx.observation().unwrap());
+
+        ctx.elevation_mask_mut(10.0, None, None);
+
+        let masked = ctx.observation().unwrap();
+        assert!(count(masked) < total, "elevation mask did not apply");
+        assert!(masked.observation().eq(expected.observation()));
+        // navigation data is preserved
+        assert!(ctx.brdc_navigation().is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "sp3")]
+    fn elevation_mask_sp3() {
+        // no broadcast ephemerides: vehicles are positioned from SP3
+        let mut ctx = context();
+        load_rinex(
+            &mut ctx,
+            "CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz",
+        );
+        load_sp3(
+            &mut ctx,
+            "SP3/GRG0MGXFIN_20201770000_01D_15M_ORB.SP3.gz",
+            None,
+        );
+
+        let total = count(ctx.observation().unwrap());
+        ctx.elevation_mask_mut(10.0, None, None);
+
+        let masked = ctx.observation().unwrap();
+        assert!(count(masked) < total, "elevation mask did not apply");
+
+        let rx_km = ctx.reference_position().unwrap().to_ecef_wgs84();
+        let rx_km = (rx_km.0 / 1.0E3, rx_km.1 / 1.0E3, rx_km.2 / 1.0E3);
+        let sp3 = ctx.sp3().unwrap();
+        for ((t, _), (_, vehicles)) in masked.observation() {
+            for sv in vehicles.keys() {
+                if let Some(sv_km) = sp3.sv_position_interpolate(*sv, *t, 11) {
+                    let elev = Ephemeris::elevation_azimuth_range(
+                        *t,
+                        &ctx.almanac,
+                        ctx.earth_cef,
+                        sv_km,
+                        rx_km,
+                    )
+                    .unwrap()
+                    .elevation_deg;
+                    assert!(elev >= 10.0, "{}({}) @ {} deg", t, sv, elev);
+                }
+            }
+        }
+    }
+
+    /*
+     * Complete (Epoch, SV) tuples of the primary Observation RINEX
+     */
+    fn complete(ctx: &QcContext) -> BTreeSet<(Epoch, SV)> {
+        ctx.observation()
+            .unwrap()
+            .complete_epoch(None)
+            .flat_map(|(t, signals)| signals.into_iter().map(move |(sv, _)| (t, sv)))
+            .collect()
+    }
+
+    #[test]
+    fn orbit_interpolation_brdc() {
+        let mut ctx = context();
+        load_observation(
+            &mut ctx,
+            "CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz",
+            2.0,
+        );
+        load_rinex(&mut ctx, "NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz");
+
+        ctx.orbit_interpolation_mut(11, None);
+
+        let nav = ctx.brdc_navigation().unwrap();
+        let expected = complete(&ctx)
+            .into_iter()
+            .filter_map(|(t, sv)| Some(((t, sv), nav.sv_position_interpolate(sv, t)?)))
+            .collect::<BTreeMap<_, _>>();
+        assert!(!expected.is_empty());
+
+        // cache is populated, on complete epochs only
+        let cached = ctx
+            .sv_position()
+            .map(|(t, sv, pos)| ((t, sv), pos))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(cached, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "sp3")]
+    fn orbit_interpolation_sp3_brdc_fallback() {
+        let mut ctx = context();
+        load_observation(
+            &mut ctx,
+            "CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz",
+            2.0,
+        );
+        load_rinex(&mut ctx, "NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz");
+
+        // this vehicle is only described by broadcast ephemerides
+        let excluded = complete(&ctx)
+            .into_iter()
+            .map(|(_, sv)| sv)
+            .find(|sv| sv.constellation == Constellation::GPS)
+            .unwrap();
+        load_sp3(
+            &mut ctx,
+            "SP3/GRG0MGXFIN_20201770000_01D_15M_ORB.SP3.gz",
+            Some(excluded),
+        );
+
+        ctx.orbit_interpolation_mut(11, None);
+
+        let (nav, sp3) = (ctx.brdc_navigation().unwrap(), ctx.sp3().unwrap());
+        let (mut from_sp3, mut from_nav) = (0, 0);
+        for (t, sv, pos) in ctx.sv_position() {
+            match sp3.sv_position_interpolate(sv, t, 11) {
+                Some(sp3_pos) => {
+                    assert_eq!(pos, sp3_pos, "{}({}) should come from SP3", t, sv);
+                    from_sp3 += 1;
+                },
+                None => {
+                    assert_eq!(
+                        Some(pos),
+                        nav.sv_position_interpolate(sv, t),
+                        "{}({}) should come from NAV",
+                        t,
+                        sv
+                    );
+                    from_nav += 1;
+                },
+            }
+        }
+        assert!(from_sp3 > 0, "SP3 should be preferred");
+        assert!(from_nav > 0, "NAV should be used as fallback");
+        assert!(ctx.sv_position().any(|(_, sv, _)| sv == excluded));
+    }
+
+    #[test]
+    #[cfg(feature = "sinex")]
+    fn code_bias() {
+        let mut ctx = context();
+        for file in ["example-1a.bia", "example-1b.bia"] {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../sinex/data/BIA/V1")
+                .join(file);
+            let sinex = Sinex::from_file(&path.to_string_lossy()).unwrap();
+            ctx.load_sinex(&path, sinex).unwrap();
+        }
+        assert!(ctx.bias().is_some());
+        assert_eq!(ctx.files[&ProductType::BiasSolutions].len(), 2);
+
+        let g01 = SV::from_str("G01").unwrap();
+        let c1c = Observable::from_str("C1C").unwrap();
+        let t = Epoch::from_str("2016-11-01T00:00:00 GPST").unwrap();
+
+        let bias = ctx.code_bias(g01, &c1c, t).unwrap();
+        assert!((bias - 10.2472E-9 * 299_792_458.0).abs() < 1.0E-6);
+
+        // phase observables are not concerned
+        let l1c = Observable::from_str("L1C").unwrap();
+        assert!(ctx.code_bias(g01, &l1c, t).is_none());
+
+        // outside validity period
+        let t = Epoch::from_str("2016-12-01T00:00:00 GPST").unwrap();
+        assert!(ctx.code_bias(g01, &c1c, t).is_none());
+    }
+
+    #[test]
+    fn dop() {
+        let mut ctx = context();
+        load_observation(
+            &mut ctx,
+            "CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz",
+            2.0,
+        );
+        assert_eq!(ctx.dop().count(), 0, "navigation is required");
+
+        load_rinex(&mut ctx, "NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz");
+
+        // first epoch: only 3 vehicles remain
+        let record = ctx
+            .observation_mut()
+            .and_then(|rinex| rinex.record.as_mut_obs())
+            .unwrap();
+        let ((t0, _), (_, vehicles)) = record.iter_mut().next().unwrap();
+        let retained = vehicles.keys().take(3).copied().collect::<Vec<_>>();
+        vehicles.retain(|sv, _| retained.contains(sv));
+        let t0 = *t0;
+
+        let dop = ctx.dop().collect::<BTreeMap<_, _>>();
+        assert!(!dop.contains_key(&t0), "not enough vehicles");
+        // 30s sampling, over 2 hours
+        assert!(dop.len() > 200, "only {} epochs resolved", dop.len());
+
+        for (t, (gdop, pdop, hdop, vdop)) in dop {
+            assert!(gdop > pdop, "{}: GDOP={} PDOP={}", t, gdop, pdop);
+            assert!(pdop > hdop, "{}: PDOP={} HDOP={}", t, pdop, hdop);
+            assert!(pdop > vdop, "{}: PDOP={} VDOP={}", t, pdop, vdop);
+            // multi GNSS geometry, seen from a ground station
+            assert!(pdop < 5.0, "{}: PDOP={}", t, pdop);
+        }
+    }
+}
@@ -26,6 +26,8 @@ pub mod prelude {
     pub use maud::{html, Markup, Render};
     pub use qc_traits::processing::{Filter, Preprocessing, Repair, RepairTrait};
     pub use rinex::prelude::{Almanac, Error as RinexError, Rinex};
+    #[cfg(feature = "sinex")]
+    pub use sinex::{Error as SinexError, Sinex};
     #[cfg(feature = "sp3")]
     pub use sp3::prelude::{Error as SP3Error, SP3};
     pub use std::path::Path;
@@ -71,6 +71,8 @@ fn html_id(product: &ProductType) -> &str {
         ProductType::MeteoObservation => "meteo",
         #[cfg(feature = "sp3")]
         ProductType::HighPrecisionOrbit => "sp3",
+        #[cfg(feature = "sinex")]
+        ProductType::BiasSolutions => "bias",
     }
 }
 
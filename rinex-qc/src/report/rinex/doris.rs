@@ -7,6 +7,7 @@ use crate::plot::Plot;
 
 use rinex::{
     carrier::Carrier,
+    doris::Satellite,
     prelude::{Observable, Rinex},
 };
 
@@ -46,6 +47,7 @@ impl Render for SignalPage {
 
 /// Doris RINEX QC
 pub struct DorisReport {
+    satellite: Option<Satellite>,
     sampling: SamplingReport,
     signals: HashMap<Carrier, SignalPage>,
 }
@@ -53,6 +55,7 @@ pub struct DorisReport {
 impl DorisReport {
     pub fn new(rinex: &Rinex) -> Self {
         Self {
+            satellite: rinex.doris_satellite().cloned(),
             sampling: SamplingReport::from_rinex(rinex),
             signals: {
                 let mut signals = HashMap::<Carrier, SignalPage>::new();
@@ -76,6 +79,26 @@ impl Render for DorisReport {
     fn render(&self) -> Markup {
         html! {
             table class="table is-bordered" {
+                @if let Some(satellite) = &self.satellite {
+                    tr {
+                        th class="is-info" {
+                            "Satellite"
+                        }
+                        td {
+                            (satellite.name)
+                        }
+                    }
+                    @if let Some(cospar) = &satellite.cospar {
+                        tr {
+                            th class="is-info" {
+                                "COSPAR"
+                            }
+                            td {
+                                (cospar.to_string())
+                            }
+                        }
+                    }
+                }
                 tr {
                     th class="is-info" {
                         "Sampling"
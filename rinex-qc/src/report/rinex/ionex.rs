@@ -19,6 +19,12 @@ pub struct IonexReport {
     reference: Reference,
     description: Option<String>,
     mapping: Option<MappingFunction>,
+    elevation_cutoff: f32,
+    observables: Option<String>,
+    nb_stations: u32,
+    nb_satellites: u32,
+    base_radius: f32,
+    nb_dcbs: usize,
     world_map: Plot,
 }
 
@@ -34,6 +40,12 @@ impl IonexReport {
             mapping: header.mapping.clone(),
             reference: header.reference.clone(),
             description: header.description.clone(),
+            elevation_cutoff: header.elevation_cutoff,
+            observables: header.observables.clone(),
+            nb_stations: header.nb_stations,
+            nb_satellites: header.nb_satellites,
+            base_radius: header.base_radius,
+            nb_dcbs: header.dcbs.len(),
             sampling_interval: rnx.dominant_sample_rate().ok_or(Error::SamplingAnalysis)?,
             world_map: {
                 let mut plot = Plot::world_map(
@@ -48,52 +60,17 @@ impl IonexReport {
                 // one trace(=map) per Epoch
                 for (epoch_index, epoch) in rnx.epoch().enumerate() {
                     let label = epoch.to_string();
-                    let lat = rnx
-                        .tec()
-                        .filter_map(
-                            |(t, lat, _, _, _)| {
-                                if t == epoch {
-                                    Some(lat)
-                                } else {
-                                    None
-                                }
-                            },
-                        )
-                        .collect::<Vec<_>>();
-                    let long = rnx
-                        .tec()
-                        .filter_map(
-                            |(t, _, long, _, _)| {
-                                if t == epoch {
-                                    Some(long)
-                                } else {
-                                    None
-                                }
-                            },
-                        )
-                        .collect::<Vec<_>>();
-                    let tec = rnx
-                        .tec()
-                        .filter_map(
-                            |(t, _, _, _, tec)| {
-                                if t == epoch {
-                                    Some(tec)
-                                } else {
-                                    None
-                                }
-                            },
-                        )
-                        .collect::<Vec<_>>();
+                    let (mut lat, mut long, mut tec) = (Vec::new(), Vec::new(), Vec::new());
+                    for (_, _, plane) in rnx.tec_planes().filter(|(t, _, _)| *t == epoch) {
+                        for ((row, col), value) in plane.iter() {
+                            lat.push(plane.latitude(row));
+                            long.push(plane.longitude(col));
+                            tec.push(value.tec);
+                        }
+                    }
 
-                    let trace = Plot::density_mapbox(
-                        lat.clone(),
-                        long.clone(),
-                        tec,
-                        &label,
-                        0.6,
-                        3,
-                        epoch_index == 0,
-                    );
+                    let trace =
+                        Plot::density_mapbox(lat, long, tec, &label, 0.6, 3, epoch_index == 0);
                     plot.add_trace(trace);
 
                     buttons.push(
@@ -212,6 +189,75 @@ impl Render for IonexReport {
                         }
                     }
                 }
+                tr {
+                    th class="is-info" {
+                        button aria-label="Minimum elevation angle used in TEC map evaluation" data-balloon-pos="right" {
+                            "Elevation cutoff"
+                        }
+                    }
+                    td {
+                        (format!("{:.1}°", self.elevation_cutoff))
+                    }
+                }
+                @if let Some(observables) = &self.observables {
+                    tr {
+                        th class="is-info" {
+                            "Observables"
+                        }
+                        td {
+                            (observables)
+                        }
+                    }
+                } @else {
+                    tr {
+                        th class="is-info" {
+                            "Observables"
+                        }
+                        td {
+                            "Theoretical model"
+                        }
+                    }
+                }
+                @if self.nb_stations > 0 {
+                    tr {
+                        th class="is-info" {
+                            "Number of Stations"
+                        }
+                        td {
+                            (self.nb_stations)
+                        }
+                    }
+                }
+                @if self.nb_satellites > 0 {
+                    tr {
+                        th class="is-info" {
+                            "Number of Satellites"
+                        }
+                        td {
+                            (self.nb_satellites)
+                        }
+                    }
+                }
+                tr {
+                    th class="is-info" {
+                        button aria-label="Mean Earth radius or bottom of height grid" data-balloon-pos="right" {
+                            "Base radius"
+                        }
+                    }
+                    td {
+                        (format!("{:.1} km", self.base_radius))
+                    }
+                }
+                @if self.nb_dcbs > 0 {
+                    tr {
+                        th class="is-info" {
+                            "DCB estimates"
+                        }
+                        td {
+                            (self.nb_dcbs)
+                        }
+                    }
+                }
                 tr {
                     th class="is-info" {
                         "TEC Map"
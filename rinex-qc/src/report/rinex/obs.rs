@@ -6,7 +6,8 @@ use std::collections::HashMap;
 use rinex::{
     carrier::Carrier,
     hardware::{Antenna, Receiver},
-    prelude::{Constellation, Epoch, Observable, Rinex, SV},
+    observation::ObservableCoverage,
+    prelude::{Constellation, Duration, Epoch, Observable, Rinex, SV},
 };
 
 use crate::report::shared::SamplingReport;
@@ -246,6 +247,10 @@ struct ConstellationPage {
     frequencies: HashMap<String, FrequencyPage>,
     /// SV per epoch
     sv_epoch: HashMap<Epoch, Vec<SV>>,
+    /// Noise (1 sigma) estimates, per observable
+    noise: Vec<(Observable, f64)>,
+    /// SV × observable coverage
+    coverage: ObservableCoverage,
 }
 
 impl ConstellationPage {
@@ -282,6 +287,18 @@ impl ConstellationPage {
             cpp_compatible,
             ppp_compatible,
             sv_epoch: rinex.sv_epoch().collect(),
+            coverage: rinex.observable_coverage(),
+            noise: rinex
+                .observation_noise(Duration::from_seconds(300.0))
+                .into_iter()
+                .filter_map(|((c, observable), sigma)| {
+                    if c == constellation {
+                        Some((observable, sigma))
+                    } else {
+                        None
+                    }
+                })
+                .collect(),
         }
     }
 }
@@ -372,6 +389,39 @@ impl Render for ConstellationPage {
                                 (self.frequencies.keys().sorted().join(", "))
                             }
                         }
+                        tr {
+                            th class="is-info" {
+                                button aria-label="Observables each satellite is observed with. Hover a cell for the epoch ratio." data-balloon-pos="right" {
+                                    "Coverage"
+                                }
+                            }
+                            td {
+                                (self.coverage.render())
+                            }
+                        }
+                        @if !self.noise.is_empty() {
+                            tr {
+                                th class="is-info" {
+                                    button aria-label="Third order time differencing: only meaningful on high rate data. Meters for Pseudo Range, cycles for Phase." data-balloon-pos="right" {
+                                        "Noise (1σ)"
+                                    }
+                                }
+                                td {
+                                    table class="table is-bordered" {
+                                        @for (observable, sigma) in self.noise.iter() {
+                                            tr {
+                                                th {
+                                                    (observable.to_string())
+                                                }
+                                                td {
+                                                    (format!("{:.3E}", sigma))
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
                         @for signal in self.frequencies.keys().sorted() {
                             @if let Some(page) = self.frequencies.get(signal) {
                                 tr {
@@ -7,6 +7,7 @@ pub struct QcBiasSummary {
     iono_bias_cancelling: bool,
     iono_bias_model_optimization: bool,
     tropo_bias_model_optimization: bool,
+    code_bias_correction: bool,
 }
 
 impl QcBiasSummary {
@@ -15,6 +16,10 @@ impl QcBiasSummary {
             iono_bias_cancelling: context.cpp_compatible(),
             iono_bias_model_optimization: context.iono_bias_model_optimization(),
             tropo_bias_model_optimization: context.tropo_bias_model_optimization(),
+            #[cfg(feature = "sinex")]
+            code_bias_correction: context.has_bias(),
+            #[cfg(not(feature = "sinex"))]
+            code_bias_correction: false,
         }
     }
 }
@@ -99,6 +104,32 @@ impl Render for QcBiasSummary {
                             }
                         }
                     }
+                    tr {
+                        th {
+                            button aria-label="Satellite code (hardware) bias" data-balloon-pos="up" {
+                                "Code Bias"
+                            }
+                        }
+                        @if self.code_bias_correction {
+                            td {
+                                span class="icon" style="color:green" {
+                                    i class="fa-solid fa-circle-check" {}
+                                }
+                                button aria-label="Pseudo ranges are corrected with Observable Specific Biases (SINEX)." data-balloon-pos="up" {
+                                    "Correction"
+                                }
+                            }
+                        } @else {
+                            td {
+                                span class="icon" style="color:red" {
+                                    i class="fa-solid fa-circle-xmark" {}
+                                }
+                                button aria-label="Code biases cannot be corrected: import a SINEX bias (BIA) file." data-balloon-pos="up" {
+                                    "Correction"
+                                }
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -2,6 +2,7 @@ use crate::preprocessing::TargetItem;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("invalid mask target")]
     InvalidTarget(#[from] crate::algorithm::target::Error),
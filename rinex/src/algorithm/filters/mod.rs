@@ -12,6 +12,7 @@ pub use smoothing::{Smooth, SmoothingFilter, SmoothingType};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("invalid filter description")]
     InvalidDescriptor,
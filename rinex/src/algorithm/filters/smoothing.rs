@@ -22,6 +22,7 @@ pub struct SmoothingFilter {
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("invalid description \"{0}\"")]
     InvalidDescription(String),
@@ -29,6 +29,19 @@ pub enum CalibrationMethod {
     Converted,
 }
 
+impl std::fmt::Display for CalibrationMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Unknown => write!(f, ""),
+            Self::Chamber => write!(f, "CHAMBER"),
+            Self::Field => write!(f, "FIELD"),
+            Self::Robot => write!(f, "ROBOT"),
+            Self::Copied => write!(f, "COPIED"),
+            Self::Converted => write!(f, "CONVERTED"),
+        }
+    }
+}
+
 /// Calibration information
 #[derive(Default, Clone, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
@@ -145,11 +158,15 @@ pub enum AntennaMatcher {
 }
 
 impl AntennaMatcher {
-    #[cfg(feature = "antex")]
-    pub(crate) fn to_lowercase(&self) -> Self {
+    /// Returns true if this [AntennaMatcher] identifies given [RxAntenna]
+    pub fn matches(&self, antenna: &RxAntenna) -> bool {
         match self {
-            Self::IGSCode(code) => Self::IGSCode(code.to_lowercase()),
-            Self::SerialNumber(sn) => Self::SerialNumber(sn.to_lowercase()),
+            Self::IGSCode(code) => antenna.igs_type.to_lowercase().eq(&code.to_lowercase()),
+            Self::SerialNumber(sn) => antenna
+                .serial_number
+                .as_ref()
+                .map(|serial| serial.to_lowercase().eq(&sn.to_lowercase()))
+                .unwrap_or(false),
         }
     }
 }
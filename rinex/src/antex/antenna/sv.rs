@@ -5,6 +5,7 @@ use thiserror::Error;
 use serde::Serialize;
 
 #[derive(Debug, Clone, Error)]
+#[non_exhaustive]
 pub enum SvAntennaParsingError {
     #[error("cospar bad length")]
     CosparBadLength,
@@ -54,8 +55,19 @@ impl std::str::FromStr for Cospar {
 
         Ok(Self {
             launch_year: year,
-            launch_vehicle: s[4..6].to_string(),
+            launch_vehicle: s[5..8].to_string(),
             launch_code: s[8..9].chars().next().unwrap(),
         })
     }
 }
+
+impl std::fmt::Display for Cospar {
+    /// Formats Cospar ID as "YYYY-XXXA"
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{:04}-{}{}",
+            self.launch_year, self.launch_vehicle, self.launch_code
+        )
+    }
+}
@@ -12,7 +12,7 @@ pub use antenna::{
     SvAntenna,
 };
 
-pub use record::{FrequencyDependentData, Record};
+pub use record::{AntennaPhasePattern, FrequencyDependentData, Record};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
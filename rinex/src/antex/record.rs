@@ -7,7 +7,10 @@ use super::{
     antenna::SvAntennaParsingError, Antenna, AntennaSpecific, Calibration, CalibrationMethod,
     Cospar, RxAntenna, SvAntenna,
 };
-use crate::{carrier, linspace::Linspace, merge, merge::Merge, Carrier, Epoch};
+use crate::{
+    carrier, fmt_rinex, formatting::fmt_fixed, linspace::Linspace, merge, merge::Merge, Carrier,
+    Epoch,
+};
 
 #[cfg(feature = "serde")]
 use serde::Serialize;
@@ -20,13 +23,20 @@ pub(crate) fn is_new_epoch(content: &str) -> bool {
     content.contains("START OF ANTENNA")
 }
 
-/// Phase pattern description.
-/// We currently do not support azimuth dependent phase patterns.
+/// Phase pattern description, in millimeters,
+/// for each zenith angle of the antenna zenith grid.
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum AntennaPhasePattern {
     /// Azimuth Independent Phase pattern
     AzimuthIndependentPattern(Vec<f64>),
+    /// Azimuth Dependent Phase pattern
+    AzimuthDependentPattern {
+        /// Azimuth independent (mean) pattern
+        noazi: Vec<f64>,
+        /// Phase pattern, per azimuth angle in degrees
+        azimuths: Vec<(f64, Vec<f64>)>,
+    },
 }
 
 impl Default for AntennaPhasePattern {
@@ -44,14 +54,12 @@ pub struct FrequencyDependentData {
     /// or the Spacecraft Mass Center, if this is an [`SvAntenna`].
     pub apc_eccentricity: (f64, f64, f64),
     /// Antenna Phase Pattern.
-    /// We currently do not support Azimuth Dependent phase patterns.
     pub phase_pattern: AntennaPhasePattern,
 }
 
 /// ANTEX RINEX record content.
 /// Data is a list of Antenna containing several [Frequency] items.
-/// We do not parse RMS frequencies at the moment, but it will
-/// easily be unlocked in near future.
+/// We do not parse RMS frequencies at the moment.
 /*TODO
 /// Record browsing example:
 /// ```
@@ -91,6 +99,7 @@ pub struct FrequencyDependentData {
 pub type Record = Vec<(Antenna, HashMap<Carrier, FrequencyDependentData>)>;
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     #[error("Unknown PCV \"{0}\"")]
     UnknownPcv(String),
@@ -144,6 +153,10 @@ pub enum Error {
     ZenithGridEndParsing,
     #[error("failed to parse spacing of zenith grid")]
     ZenithGridSpacingParsing,
+    #[error("failed to parse azimuth increment")]
+    AzimuthIncrementParsing,
+    #[error("failed to parse phase pattern")]
+    PhasePatternParsing,
 }
 
 fn parse_datetime(content: &str) -> Result<Epoch, Error> {
@@ -222,10 +235,15 @@ fn parse_validity_epoch(content: &str) -> Result<Epoch, Error> {
             .parse::<u8>()
             .map_err(|_| Error::DatetimeSecondsParsing)?;
 
-        nanos = ss[dot + 1..]
-            .trim()
+        // fractional part: scaled to nanoseconds
+        let fraction = ss[dot + 1..].trim();
+        if fraction.len() > 9 {
+            return Err(Error::DatetimeNanosParsing);
+        }
+        nanos = fraction
             .parse::<u32>()
-            .map_err(|_| Error::DatetimeNanosParsing)?;
+            .map_err(|_| Error::DatetimeNanosParsing)?
+            * 10_u32.pow(9 - fraction.len() as u32);
     } else {
         secs = ss
             .parse::<u8>()
@@ -237,6 +255,34 @@ fn parse_validity_epoch(content: &str) -> Result<Epoch, Error> {
     ))
 }
 
+/*
+ * Parses one row of a phase pattern: leading 8 character
+ * description (either NOAZI or azimuth angle), followed by F8.2 values
+ */
+fn parse_pattern_row(line: &str) -> Result<(&str, Vec<f64>), Error> {
+    let (description, mut rem) = line.split_at(std::cmp::min(8, line.len()));
+    let mut values = Vec::<f64>::new();
+    while !rem.trim().is_empty() {
+        let (value, next) = rem.split_at(std::cmp::min(8, rem.len()));
+        let value = value
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| Error::PhasePatternParsing)?;
+        values.push(value);
+        rem = next;
+    }
+    Ok((description.trim(), values))
+}
+
+/*
+ * Returns true if this line is a phase pattern row:
+ * pattern rows are not terminated by any marker
+ */
+fn is_pattern_row(line: &str) -> bool {
+    line.trim_start().starts_with("NOAZI")
+        || (!line.trim().is_empty() && !line.chars().any(|c| c.is_ascii_alphabetic()))
+}
+
 /// Parses entire Antenna block
 /// and all inner frequency entries
 pub(crate) fn parse_antenna(
@@ -248,8 +294,42 @@ pub(crate) fn parse_antenna(
     let mut frequency = Carrier::default();
     let mut freq_data = FrequencyDependentData::default();
     let mut valid_from = Epoch::default();
+    let mut rms = false;
 
     for line in lines {
+        if rms {
+            // RMS values are not supported
+            rms = !line.contains("END OF FREQ RMS");
+            continue;
+        }
+        if is_pattern_row(line) {
+            let (description, values) = parse_pattern_row(line)?;
+            if description.eq("NOAZI") {
+                freq_data.phase_pattern = AntennaPhasePattern::AzimuthIndependentPattern(values);
+            } else {
+                let azimuth = description
+                    .parse::<f64>()
+                    .map_err(|_| Error::PhasePatternParsing)?;
+                // first azimuth: pattern becomes azimuth dependent
+                if let AntennaPhasePattern::AzimuthIndependentPattern(noazi) =
+                    &freq_data.phase_pattern
+                {
+                    freq_data.phase_pattern = AntennaPhasePattern::AzimuthDependentPattern {
+                        noazi: noazi.clone(),
+                        azimuths: Vec::new(),
+                    };
+                }
+                if let AntennaPhasePattern::AzimuthDependentPattern { azimuths, .. } =
+                    &mut freq_data.phase_pattern
+                {
+                    azimuths.push((azimuth, values));
+                }
+            }
+            continue;
+        }
+        if line.len() < 60 {
+            continue;
+        }
         let (content, marker) = line.split_at(60);
         if marker.contains("TYPE / SERIAL NO") {
             let (ant_igs, rem) = content.split_at(16); // IGS V.1.4 does not follow the specs ?
@@ -307,10 +387,11 @@ pub(crate) fn parse_antenna(
             let sinex = content.split_at(20).0;
             antenna.sinex_code = sinex.trim().to_string();
         } else if marker.contains("DAZI") {
-            //let dazi = content.split_at(20).0.trim();
-            //if let Ok(dazi) = f64::from_str(dazi) {
-            //    antenna = antenna.with_dazi(dazi)
-            //}
+            let dazi = content
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| Error::AzimuthIncrementParsing)?;
+            antenna = antenna.with_dazi(dazi);
         } else if marker.contains("# OF FREQUENCIES") {
             /*
              * we actually do not care about this field
@@ -320,6 +401,9 @@ pub(crate) fn parse_antenna(
             let svnn = content.split_at(10).0;
             let sv = SV::from_str(svnn.trim())?;
             frequency = carrier::Carrier::from_sv(sv)?;
+            freq_data = FrequencyDependentData::default();
+        } else if marker.contains("START OF FREQ RMS") {
+            rms = true;
         } else if marker.contains("NORTH / EAST / UP") {
             let (north, rem) = content.split_at(10);
             let (east, rem) = rem.split_at(10);
@@ -366,25 +450,155 @@ pub(crate) fn parse_antenna(
         } else if marker.contains("END OF ANTENNA") {
             break; // end of this block, considered as an `epoch`
                    // if we make a parallel with other types of RINEX
-        } else {
-            // inside phase pattern
         }
-        //    } else if marker.contains("SINEX CODE") {
-        //        let sinex = content.split_at(10).0;
-        //        antenna = antenna.with_sinex_code(sinex.trim())
-        //        if line.contains("NOAZI") {
-        //            frequency = frequency.add_pattern(Pattern::NonAzimuthDependent(values.clone()))
-        //        } else {
-        //            let angle = f64::from_str(content.trim()).unwrap();
-        //            frequency =
-        //                frequency.add_pattern(Pattern::AzimuthDependent((angle, values.clone())))
-        //        }
-        //    }
     }
 
     Ok((antenna, inner))
 }
 
+/*
+ * Formats calibration date, mirroring parse_datetime()
+ */
+fn fmt_datetime(epoch: Epoch) -> String {
+    const MONTHS: [&str; 12] = [
+        "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+    ];
+    let (y, m, d, _, _, _, _) = epoch.to_gregorian_utc();
+    format!(
+        "{:02}-{}-{:02}",
+        y.rem_euclid(100),
+        MONTHS[(m as usize).saturating_sub(1) % 12],
+        d
+    )
+}
+
+/*
+ * Formats calibration validity FROM/UNTIL field
+ */
+fn fmt_validity_epoch(epoch: Epoch) -> String {
+    let (y, m, d, hh, mm, ss, nanos) = epoch.to_gregorian_utc();
+    format!(
+        "{:6}{:6}{:6}{:6}{:6}{}",
+        y,
+        m,
+        d,
+        hh,
+        mm,
+        fmt_fixed(ss as f64 + nanos as f64 * 1.0E-9, 13, 7)
+    )
+}
+
+/*
+ * Formats one row of a phase pattern
+ */
+fn fmt_pattern_row(description: &str, values: &[f64]) -> String {
+    let mut row = format!("{:>8}", description);
+    for value in values {
+        row.push_str(&fmt_fixed(*value, 8, 2));
+    }
+    row
+}
+
+/// Formats entire Antenna block and all inner frequency entries.
+/// Frequencies are described in the order of their ATX frequency code.
+/// Carriers that have no ATX description are not formatted.
+pub(crate) fn fmt_antenna(
+    antenna: &Antenna,
+    frequencies: &HashMap<Carrier, FrequencyDependentData>,
+) -> String {
+    let mut lines = Vec::<String>::new();
+    lines.push(fmt_rinex("", "START OF ANTENNA"));
+
+    let specs = match &antenna.specific {
+        AntennaSpecific::RxAntenna(rx) => format!(
+            "{:<16}{:<24}",
+            rx.igs_type,
+            rx.serial_number.as_deref().unwrap_or("NONE")
+        ),
+        AntennaSpecific::SvAntenna(sv) => {
+            let vehicle = format!("{:x}", sv.sv);
+            format!("{:<20}{:<20}{:<10}{}", sv.igs_type, vehicle, "", sv.cospar)
+        },
+    };
+    lines.push(fmt_rinex(&specs, "TYPE / SERIAL NO"));
+
+    let calibration = &antenna.calibration;
+    lines.push(fmt_rinex(
+        &format!(
+            "{:<20}{:<20}{:6}    {}",
+            calibration.method.to_string(),
+            calibration.agency,
+            calibration.number,
+            fmt_datetime(calibration.date)
+        ),
+        "METH / BY / # / DATE",
+    ));
+    lines.push(fmt_rinex(
+        &format!("  {}", fmt_fixed(antenna.azi_inc, 6, 1)),
+        "DAZI",
+    ));
+
+    let grid = &antenna.zenith_grid;
+    lines.push(fmt_rinex(
+        &format!(
+            "  {}{}{}",
+            fmt_fixed(grid.start, 6, 1),
+            fmt_fixed(grid.end, 6, 1),
+            fmt_fixed(grid.spacing, 6, 1)
+        ),
+        "ZEN1 / ZEN2 / DZEN",
+    ));
+
+    let mut frequencies = frequencies
+        .iter()
+        .filter_map(|(carrier, data)| Some((carrier.to_sv()?, data)))
+        .collect::<Vec<_>>();
+    frequencies.sort_by_key(|(sv, _)| *sv);
+
+    lines.push(fmt_rinex(
+        &format!("{:6}", frequencies.len()),
+        "# OF FREQUENCIES",
+    ));
+
+    if let Some((from, until)) = calibration.validity_period {
+        lines.push(fmt_rinex(&fmt_validity_epoch(from), "VALID FROM"));
+        lines.push(fmt_rinex(&fmt_validity_epoch(until), "VALID UNTIL"));
+    }
+    if !antenna.sinex_code.is_empty() {
+        lines.push(fmt_rinex(&antenna.sinex_code, "SINEX CODE"));
+    }
+
+    for (sv, data) in frequencies {
+        let code = format!("   {:x}", sv);
+        lines.push(fmt_rinex(&code, "START OF FREQUENCY"));
+        let (north, east, up) = data.apc_eccentricity;
+        lines.push(fmt_rinex(
+            &format!(
+                "{}{}{}",
+                fmt_fixed(north, 10, 2),
+                fmt_fixed(east, 10, 2),
+                fmt_fixed(up, 10, 2)
+            ),
+            "NORTH / EAST / UP",
+        ));
+        match &data.phase_pattern {
+            AntennaPhasePattern::AzimuthIndependentPattern(noazi) => {
+                lines.push(fmt_pattern_row("NOAZI", noazi));
+            },
+            AntennaPhasePattern::AzimuthDependentPattern { noazi, azimuths } => {
+                lines.push(fmt_pattern_row("NOAZI", noazi));
+                for (azimuth, values) in azimuths {
+                    lines.push(fmt_pattern_row(&fmt_fixed(*azimuth, 8, 1), values));
+                }
+            },
+        }
+        lines.push(fmt_rinex(&code, "END OF FREQUENCY"));
+    }
+
+    lines.push(fmt_rinex("", "END OF ANTENNA"));
+    lines.join("\n") + "\n"
+}
+
 impl Merge for Record {
     /// Merges `rhs` into `Self` without mutable access at the expense of more memcopies
     fn merge(&self, rhs: &Self) -> Result<Self, merge::Error> {
@@ -0,0 +1,443 @@
+//! BINEX (Binary RINEX) stream decoding
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, HashMap},
+    f64::consts::PI,
+    io::Read,
+    rc::Rc,
+    str::FromStr,
+};
+
+use ::binex::prelude::{
+    Decoder, EphemerisFrame, Error as BinexError, GALEphemeris, GLOEphemeris, GPSEphemeris,
+    NetRSObservation, ObservationFrame, Record as BinexRecord, SBASEphemeris, StreamElement,
+};
+
+use hifitime::{Duration, TimeScale};
+use log::{debug, warn};
+
+use crate::{
+    carrier::Carrier,
+    navigation::{Ephemeris, NavMsgType, NavigationRecordBuilder, OrbitItem},
+    observation::{
+        HeaderFields as ObservationFields, ObservationData, Record as ObservationRecord, SNR,
+    },
+    prelude::{Constellation, Epoch, EpochFlag, Header, Observable, Rinex, SV},
+    record::Record,
+    version::Version,
+    Error,
+};
+
+/// Observables produced from NetRS observations, in header order.
+/// L2 is described as L2C (X) or semi-codeless P(Y) (W) tracking.
+const NETRS_OBSERVABLES: [&str; 9] = [
+    "C1C", "L1C", "S1C", "C2W", "L2W", "S2W", "C2X", "L2X", "S2X",
+];
+
+/// [Read]able wrapper, that lets us know when the end of stream
+/// has been reached, because the [Decoder] does not expose it.
+struct EosReader<R: Read> {
+    reader: R,
+    eos: Rc<Cell<bool>>,
+}
+
+impl<R: Read> Read for EosReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let size = self.reader.read(buf)?;
+        if size == 0 && !buf.is_empty() {
+            self.eos.set(true);
+        }
+        Ok(size)
+    }
+}
+
+/// Converts BINEX PRN (encoded as PRN-1) to [SV]
+fn binex_sv(constellation: Constellation, prn: u8) -> SV {
+    SV::new(constellation, prn.saturating_add(1))
+}
+
+/// Inserts health flag, interpreted as RINEX would
+fn insert_health(eph: &mut Ephemeris, constellation: Constellation, health: u32) {
+    if let Ok(item) = OrbitItem::new("health", &health.to_string(), constellation) {
+        eph.orbits.insert("health".to_string(), item);
+    }
+}
+
+fn gps_ephemeris(gps: &GPSEphemeris) -> (Epoch, SV, Ephemeris) {
+    let sv = binex_sv(Constellation::GPS, gps.sv_prn);
+    let week = gps.toe as u32;
+
+    let toc = Epoch::from_time_of_week(week, (gps.toc as u64) * 1_000_000_000, TimeScale::GPST);
+
+    let mut eph = Ephemeris {
+        clock_bias: gps.clock_offset as f64,
+        clock_drift: gps.clock_drift as f64,
+        clock_drift_rate: gps.clock_drift_rate as f64,
+        ..Default::default()
+    };
+
+    for (key, value) in [
+        ("iode", gps.iode as f64),
+        ("crs", gps.crs as f64),
+        ("deltaN", gps.delta_n_rad_s as f64),
+        ("m0", gps.m0_rad),
+        ("cuc", gps.cuc as f64),
+        ("e", gps.e),
+        ("cus", gps.cus as f64),
+        ("sqrta", gps.sqrt_a),
+        ("toe", gps.toc as f64),
+        ("cic", gps.cic as f64),
+        ("omega0", gps.omega_0_rad),
+        ("cis", gps.cis as f64),
+        ("i0", gps.i0_rad),
+        ("crc", gps.crc as f64),
+        ("omega", gps.omega_rad),
+        ("omegaDot", gps.omega_dot_rad_s as f64),
+        ("idot", gps.i_dot_rad_s as f64),
+        ("svAccuracy", gps.ura_m as f64),
+        ("tgd", gps.tgd as f64),
+        ("iodc", gps.iodc as f64),
+        ("t_tm", gps.tow as f64),
+    ] {
+        eph.set_orbit_f64(key, value);
+    }
+
+    eph.orbits.insert("week".to_string(), OrbitItem::U32(week));
+
+    insert_health(&mut eph, Constellation::GPS, gps.sv_health as u32);
+    (toc, sv, eph)
+}
+
+fn gal_ephemeris(gal: &GALEphemeris) -> (Epoch, SV, Ephemeris) {
+    let sv = binex_sv(Constellation::Galileo, gal.sv_prn);
+    let week = gal.toe_week as u32;
+
+    // GST weeks are aligned to GPST weeks
+    let toc = Epoch::from_time_of_week(week, (gal.toe_s as u64) * 1_000_000_000, TimeScale::GPST)
+        .to_time_scale(TimeScale::GST);
+
+    let mut eph = Ephemeris {
+        clock_bias: gal.clock_offset as f64,
+        clock_drift: gal.clock_drift as f64,
+        clock_drift_rate: gal.clock_drift_rate as f64,
+        ..Default::default()
+    };
+
+    for (key, value) in [
+        ("iodnav", gal.iodnav as f64),
+        ("crs", gal.crs as f64),
+        ("deltaN", gal.delta_n_semi_circles_s as f64 * PI),
+        ("m0", gal.m0_rad),
+        ("cuc", gal.cuc as f64),
+        ("e", gal.e),
+        ("cus", gal.cus as f64),
+        ("sqrta", gal.sqrt_a),
+        ("toe", gal.toe_s as f64),
+        ("cic", gal.cic as f64),
+        ("omega0", gal.omega_0_rad),
+        ("cis", gal.cis as f64),
+        ("i0", gal.i0_rad),
+        ("crc", gal.crc as f64),
+        ("omega", gal.omega_rad),
+        ("omegaDot", gal.omega_dot_semi_circles as f64 * PI),
+        ("idot", gal.idot_semi_circles_s as f64 * PI),
+        ("dataSrc", gal.source as f64),
+        ("sisa", gal.sisa as f64),
+        ("bgdE5aE1", gal.bgd_e5a_e1_s as f64),
+        ("bgdE5bE1", gal.bgd_e5b_e1_s as f64),
+        ("t_tm", gal.tow as f64),
+    ] {
+        eph.set_orbit_f64(key, value);
+    }
+
+    eph.orbits.insert("week".to_string(), OrbitItem::U32(week));
+
+    insert_health(&mut eph, Constellation::Galileo, gal.sv_health as u32);
+    (toc, sv, eph)
+}
+
+fn glo_ephemeris(glo: &GLOEphemeris) -> (Epoch, SV, Ephemeris) {
+    let sv = binex_sv(Constellation::Glonass, glo.slot);
+
+    // day counter is expressed from the GPS origin, time of day is UTC
+    let toc = Epoch::from_gregorian_utc_at_midnight(1980, 1, 6)
+        + Duration::from_days(glo.day as f64)
+        + Duration::from_seconds(glo.tod_s as f64);
+
+    let mut eph = Ephemeris {
+        clock_bias: glo.clock_offset_s,
+        clock_drift: glo.clock_rel_freq_bias,
+        clock_drift_rate: glo.t_k_sec as f64,
+        ..Default::default()
+    };
+
+    for (key, value) in [
+        ("satPosX", glo.x_km),
+        ("velX", glo.vel_x_km),
+        ("accelX", glo.acc_x_km),
+        ("satPosY", glo.y_km),
+        ("velY", glo.vel_y_km),
+        ("accelY", glo.acc_y_km),
+        ("satPosZ", glo.z_km),
+        ("velZ", glo.vel_z_km),
+        ("accelZ", glo.acc_z_km),
+        ("ageOp", glo.age_op_days as f64),
+    ] {
+        eph.set_orbit_f64(key, value);
+    }
+
+    eph.orbits
+        .insert("channel".to_string(), OrbitItem::I8(glo.freq_channel));
+
+    insert_health(&mut eph, Constellation::Glonass, glo.sv_health as u32);
+    (toc, sv, eph)
+}
+
+fn sbas_ephemeris(sbas: &SBASEphemeris) -> (Epoch, SV, Ephemeris) {
+    // SBAS vehicles are identified by their PRN (>100) in BINEX
+    let prn = sbas.sbas_prn.saturating_add(1);
+    let prn = if prn > 100 { prn - 100 } else { prn };
+    let sv = SV::new(Constellation::SBAS, prn);
+
+    let toc = Epoch::from_time_of_week(
+        sbas.toe as u32,
+        (sbas.tow as u64) * 1_000_000_000,
+        TimeScale::GPST,
+    );
+
+    let mut eph = Ephemeris {
+        clock_bias: sbas.clock_offset,
+        clock_drift: sbas.clock_drift,
+        clock_drift_rate: 0.0,
+        ..Default::default()
+    };
+
+    for (key, value) in [
+        ("satPosX", sbas.x_km),
+        ("velX", sbas.vel_x_km),
+        ("accelX", sbas.acc_x_km),
+        ("satPosY", sbas.y_km),
+        ("velY", sbas.vel_y_km),
+        ("accelY", sbas.acc_y_km),
+        ("accuracyCode", sbas.ura as f64),
+        ("satPosZ", sbas.z_km),
+        ("velZ", sbas.vel_z_km),
+        ("accelZ", sbas.acc_z_km),
+        ("iodn", sbas.iodn as f64),
+    ] {
+        eph.set_orbit_f64(key, value);
+    }
+
+    insert_health(&mut eph, Constellation::SBAS, sbas.uint1 as u32);
+    (toc, sv, eph)
+}
+
+/// Converts NetRS observations to RINEX observations,
+/// that are stored in the [ObservationRecord] being built.
+/// Carrier phases are converted to cycles.
+fn netrs_observations(netrs: &NetRSObservation, record: &mut ObservationRecord) {
+    let mut vehicles = BTreeMap::<SV, HashMap<Observable, ObservationData>>::new();
+
+    for sat in netrs.satellites.iter() {
+        // L2 ranges are expressed with respect to L1
+        let l1 = match sat.l1 {
+            Some(l1) => l1,
+            None => continue,
+        };
+
+        let sv = binex_sv(Constellation::GPS, sat.prn);
+        let observations = vehicles.entry(sv).or_default();
+
+        let snr = SNR::from(l1.cn0_dbhz());
+        observations.insert(
+            Observable::PseudoRange("C1C".to_string()),
+            ObservationData::new(l1.pseudo_range_m(), None, Some(snr)),
+        );
+        if let Some(range) = l1.phase_range_m() {
+            observations.insert(
+                Observable::Phase("L1C".to_string()),
+                ObservationData::new(range / Carrier::L1.wavelength(), None, Some(snr)),
+            );
+        }
+        observations.insert(
+            Observable::SSI("S1C".to_string()),
+            ObservationData::new(l1.cn0_dbhz(), None, None),
+        );
+
+        if let Some(l2) = sat.l2 {
+            let code = if l2.is_p_code() { 'W' } else { 'X' };
+            let snr = SNR::from(l2.cn0_dbhz());
+            observations.insert(
+                Observable::PseudoRange(format!("C2{}", code)),
+                ObservationData::new(l2.pseudo_range_m(&l1), None, Some(snr)),
+            );
+            if let Some(range) = l2.phase_range_m(&l1) {
+                observations.insert(
+                    Observable::Phase(format!("L2{}", code)),
+                    ObservationData::new(range / Carrier::L2.wavelength(), None, Some(snr)),
+                );
+            }
+            observations.insert(
+                Observable::SSI(format!("S2{}", code)),
+                ObservationData::new(l2.cn0_dbhz(), None, None),
+            );
+        }
+    }
+
+    if !vehicles.is_empty() {
+        record.insert((netrs.epoch, EpochFlag::Ok), (None, vehicles));
+    }
+}
+
+/// Builds the OBS [Header] that describes this [ObservationRecord]
+fn observation_header(record: &ObservationRecord) -> Header {
+    let codes = NETRS_OBSERVABLES
+        .iter()
+        .filter_map(|code| {
+            let observable = Observable::from_str(code).ok()?;
+            let found = record
+                .values()
+                .flat_map(|(_, vehicles)| vehicles.values())
+                .any(|observations| observations.contains_key(&observable));
+            if found {
+                Some(observable)
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut fields = ObservationFields::default();
+    fields.codes.insert(Constellation::GPS, codes);
+    fields.time_of_first_obs = record.keys().next().map(|(t, _)| *t);
+    fields.time_of_last_obs = record.keys().last().map(|(t, _)| *t);
+
+    Header::basic_obs()
+        .with_version(Version::new(3, 5))
+        .with_constellation(Constellation::GPS)
+        .with_observation_fields(fields)
+}
+
+/// Decodes BINEX stream, builds navigation and observation
+/// records in place. Observations are only decoded when requested.
+fn decode_binex_stream<R: Read>(
+    reader: R,
+    observations: bool,
+) -> (NavigationRecordBuilder, ObservationRecord) {
+    let eos = Rc::new(Cell::new(false));
+
+    let reader = EosReader {
+        reader,
+        eos: Rc::clone(&eos),
+    };
+
+    let decoder = Decoder::new(reader);
+
+    let mut builder = NavigationRecordBuilder::new(Version::new(3, 5));
+    let mut record = ObservationRecord::new();
+
+    for element in decoder {
+        match element {
+            Ok(StreamElement::OpenSource(msg)) => match msg.record {
+                BinexRecord::EphemerisFrame(fr) => {
+                    let (toc, sv, eph) = match fr {
+                        EphemerisFrame::GPS(gps) => gps_ephemeris(&gps),
+                        EphemerisFrame::GAL(gal) => gal_ephemeris(&gal),
+                        EphemerisFrame::GLO(glo) => glo_ephemeris(&glo),
+                        EphemerisFrame::SBAS(sbas) => sbas_ephemeris(&sbas),
+                        EphemerisFrame::GPSRaw(_) => {
+                            debug!("binex: raw gps frame is not supported");
+                            continue;
+                        },
+                    };
+                    builder = builder.with_ephemeris(toc, NavMsgType::LNAV, sv, eph);
+                },
+                BinexRecord::Observation(ObservationFrame::NetRS(netrs)) => {
+                    if observations {
+                        netrs_observations(&netrs, &mut record);
+                    }
+                },
+                _ => {
+                    debug!("binex: non supported record");
+                },
+            },
+            Ok(StreamElement::ClosedSource(_)) => {},
+            Err(BinexError::IncompleteMessage(_)) => {
+                if eos.get() {
+                    // partial stream: message will never conclude
+                    warn!("binex: truncated stream");
+                    break;
+                }
+            },
+            Err(BinexError::ReversedStream) => {
+                warn!("binex: invalid reverse-readable message");
+            },
+            Err(e) => {
+                debug!("binex: {:?}", e);
+            },
+        }
+    }
+
+    (builder, record)
+}
+
+impl Rinex {
+    /// Decodes a BINEX stream, from any [Read]able interface.
+    /// SYNC bytes, endianness and CRC verification are handled by the BINEX decoder,
+    /// for both forward and reverse-readable messages.
+    /// When the stream contains observations (record 0x7f), we return the
+    /// equivalent OBS [Rinex]. Otherwise, we return the NAV [Rinex]
+    /// built from the decoded ephemerides (record 0x01), see [Self::from_binex_nav_stream].
+    /// A partial (truncated) stream is not an error: we return everything
+    /// that was decoded so far.
+    ///
+    /// Current limitations:
+    ///   - observation records are receiver specific: only Trimble NetRS
+    ///     GPS observations (0x7f-03) are supported
+    ///   - reverse-readable messages are decoded in the forward direction
+    ///   - raw (undecoded) GPS frames are dropped
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io::Read;
+    /// use rinex::prelude::*;
+    ///
+    /// let fd = File::open("../test_resources/BIN/mfle20190130.bnx")
+    ///     .unwrap();
+    ///
+    /// // first minutes of this 1 Hz capture
+    /// let rinex = Rinex::from_binex_stream(fd.take(64 * 1024))
+    ///     .unwrap();
+    ///
+    /// assert!(rinex.is_observation_rinex());
+    /// ```
+    pub fn from_binex_stream<R: Read>(reader: R) -> Result<Rinex, Error> {
+        let (builder, record) = decode_binex_stream(reader, true);
+        if record.is_empty() {
+            Ok(builder.build())
+        } else {
+            let header = observation_header(&record);
+            Ok(Rinex::new(header, Record::ObsRecord(record)))
+        }
+    }
+
+    /// Decodes a BINEX stream, from any [Read]able interface,
+    /// and returns the equivalent NAV [Rinex]: decoded ephemerides (record 0x01)
+    /// are converted to [Ephemeris] frames, observations are dropped.
+    /// ```
+    /// use std::fs::File;
+    /// use rinex::prelude::*;
+    ///
+    /// let fd = File::open("../test_resources/BIN/mfle20190130.bnx")
+    ///     .unwrap();
+    ///
+    /// let rinex = Rinex::from_binex_nav_stream(fd)
+    ///     .unwrap();
+    ///
+    /// assert!(rinex.is_navigation_rinex());
+    /// ```
+    pub fn from_binex_nav_stream<R: Read>(reader: R) -> Result<Rinex, Error> {
+        let (builder, _) = decode_binex_stream(reader, false);
+        Ok(builder.build())
+    }
+}
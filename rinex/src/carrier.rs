@@ -72,6 +72,7 @@ pub enum Carrier {
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     /// Unable to parse Carrier from given string content
     #[error("carrier::from_str(\"{0}\")")]
@@ -312,8 +313,8 @@ impl Carrier {
     /// Converts to exact Glonass carrier
     pub fn with_glonass_offset(&self, offset: i8) -> Self {
         match self {
-            Self::L1 => Self::G1(Some(offset)),
-            Self::L2 => Self::G2(Some(offset)),
+            Self::L1 | Self::G1(_) => Self::G1(Some(offset)),
+            Self::L2 | Self::G2(_) => Self::G2(Some(offset)),
             other => *other,
         }
     }
@@ -723,6 +724,36 @@ impl Carrier {
             _ => Err(Error::UnknownSV(sv)),
         }
     }
+    /*
+     * Returns the standard SV description of this frequency,
+     * as used in ATX records. Carriers shared by several constellations
+     * are described with respect to their primary constellation.
+     */
+    pub(crate) fn to_sv(&self) -> Option<SV> {
+        let (constellation, code) = match self {
+            Self::L1 => (Constellation::GPS, 1),
+            Self::L2 => (Constellation::GPS, 2),
+            Self::L5 => (Constellation::GPS, 5),
+            Self::L6 => (Constellation::QZSS, 6),
+            Self::G1(_) => (Constellation::Glonass, 1),
+            Self::G2(_) => (Constellation::Glonass, 2),
+            Self::G3 => (Constellation::Glonass, 3),
+            Self::E1 => (Constellation::Galileo, 1),
+            Self::E5a => (Constellation::Galileo, 5),
+            Self::E6 => (Constellation::Galileo, 6),
+            Self::E5b => (Constellation::Galileo, 7),
+            Self::E5 => (Constellation::Galileo, 8),
+            Self::B1C => (Constellation::BeiDou, 1),
+            Self::B1I => (Constellation::BeiDou, 2),
+            Self::B2A => (Constellation::BeiDou, 5),
+            Self::B3 => (Constellation::BeiDou, 6),
+            Self::B2B => (Constellation::BeiDou, 7),
+            Self::B2 => (Constellation::BeiDou, 8),
+            Self::S => (Constellation::IRNSS, 9),
+            _ => return None,
+        };
+        Some(SV::new(constellation, code))
+    }
     /// Builds Self from DORIS observable
     pub fn from_doris_observable(obs: &Observable) -> Result<Self, Error> {
         let obs = obs.to_string();
@@ -948,4 +979,32 @@ mod test {
             }
         }
     }
+    #[test]
+    fn test_antex_frequency_code() {
+        for carrier in [
+            Carrier::L1,
+            Carrier::L2,
+            Carrier::L5,
+            Carrier::L6,
+            Carrier::G1(None),
+            Carrier::G2(None),
+            Carrier::G3,
+            Carrier::E1,
+            Carrier::E5,
+            Carrier::E5a,
+            Carrier::E5b,
+            Carrier::E6,
+            Carrier::B1C,
+            Carrier::B1I,
+            Carrier::B2,
+            Carrier::B2A,
+            Carrier::B2B,
+            Carrier::B3,
+            Carrier::S,
+        ] {
+            let sv = carrier.to_sv().unwrap();
+            assert_eq!(Carrier::from_sv(sv), Ok(carrier));
+        }
+        assert!(Carrier::U2.to_sv().is_none());
+    }
 }
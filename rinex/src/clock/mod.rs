@@ -3,11 +3,11 @@ pub mod record;
 
 pub use record::{ClockKey, ClockProfile, ClockProfileType, ClockType, Error, Record};
 
-use crate::version::Version;
+use crate::{formatting::fmt_scientific, version::Version};
 use hifitime::TimeScale;
 use std::str::FromStr;
 
-use crate::prelude::DOMES;
+use crate::prelude::{ReferenceFrame, DOMES, SV};
 
 /// Clocks `RINEX` specific header fields
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -30,6 +30,12 @@ pub struct HeaderFields {
     pub work_clock: Vec<WorkClock>,
     /// Types of clock profiles encountered in this file
     pub codes: Vec<ClockProfileType>,
+    /// Terrestrial [ReferenceFrame] of the analysis solution
+    pub frame: Option<ReferenceFrame>,
+    /// Stations contributing to the analysis solution
+    pub solution_stations: Vec<SolutionStation>,
+    /// Satellites contributing to the analysis solution
+    pub solution_satellites: Vec<SV>,
 }
 
 /// Clock used in the analysis and evaluation of this file
@@ -83,9 +89,102 @@ impl WorkClock {
             }
         }
     }
+    /*
+     * Formats this clock, as expected by [Self::parse]
+     */
+    pub(crate) fn format(&self, version: Version) -> String {
+        const LIMIT: Version = Version { major: 3, minor: 4 };
+        let domes = self
+            .domes
+            .as_ref()
+            .map(|d| d.to_string())
+            .unwrap_or_default();
+        let constraint = self
+            .constraint
+            .map(|c| fmt_scientific(c, 20, 12))
+            .unwrap_or_default();
+        if version < LIMIT {
+            format!("{:<4} {:<35}{:>20}", self.name, domes, constraint)
+        } else {
+            format!("{:<10}{:<10}{:>40}", self.name, domes, constraint)
+        }
+    }
+}
+
+/// Station contributing to the analysis solution
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolutionStation {
+    /// Station name
+    pub name: String,
+    /// Station DOMES ID#
+    pub domes: Option<DOMES>,
+    /// Station ECEF coordinates [mm]
+    pub ecef_mm: (i64, i64, i64),
+}
+
+impl SolutionStation {
+    /*
+     * Parses a "SOLN STA NAME / NUM" line.
+     * Content spans 65 columns in V3.04, so we expect the complete line.
+     */
+    pub(crate) fn parse(version: Version, line: &str) -> Option<Self> {
+        const LIMIT: Version = Version { major: 3, minor: 4 };
+        let (name, rem) = if version < LIMIT {
+            (line.get(..4)?, line.get(5..)?)
+        } else {
+            (line.get(..9)?, line.get(10..)?)
+        };
+        let domes = rem.get(..20)?;
+        let mut coords = rem
+            .get(20..55)?
+            .split_ascii_whitespace()
+            .map(|c| c.parse::<i64>());
+        let x = coords.next()?.ok()?;
+        let y = coords.next()?.ok()?;
+        let z = coords.next()?.ok()?;
+        Some(Self {
+            name: name.trim().to_string(),
+            domes: DOMES::from_str(domes.trim()).ok(),
+            ecef_mm: (x, y, z),
+        })
+    }
+    /*
+     * Formats this station, as expected by [Self::parse]
+     */
+    pub(crate) fn format(&self, version: Version) -> String {
+        const LIMIT: Version = Version { major: 3, minor: 4 };
+        let domes = self
+            .domes
+            .as_ref()
+            .map(|d| d.to_string())
+            .unwrap_or_default();
+        let (x, y, z) = self.ecef_mm;
+        if version < LIMIT {
+            format!(
+                "{:<4} {:<20}{:>11} {:>11} {:>11}",
+                self.name, domes, x, y, z
+            )
+        } else {
+            format!(
+                "{:<9} {:<20}{:>11} {:>11} {:>11}",
+                self.name, domes, x, y, z
+            )
+        }
+    }
 }
 
 impl HeaderFields {
+    pub(crate) fn solution_station(&self, station: SolutionStation) -> Self {
+        let mut s = self.clone();
+        s.solution_stations.push(station);
+        s
+    }
+    pub(crate) fn solution_satellites(&self, sv: &[SV]) -> Self {
+        let mut s = self.clone();
+        s.solution_satellites.extend_from_slice(sv);
+        s
+    }
     pub(crate) fn work_clock(&self, clk: WorkClock) -> Self {
         let mut s = self.clone();
         s.work_clock.push(clk);
@@ -121,4 +220,9 @@ impl HeaderFields {
         s.ref_clock = Some(clk.to_string());
         s
     }
+    pub(crate) fn frame(&self, frame: ReferenceFrame) -> Self {
+        let mut s = self.clone();
+        s.frame = Some(frame);
+        s
+    }
 }
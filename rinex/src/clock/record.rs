@@ -5,7 +5,10 @@ use std::collections::BTreeMap;
 use strum_macros::EnumString;
 
 use crate::{
-    epoch, merge,
+    epoch,
+    epoch::epoch_decompose,
+    formatting::{fmt_fixed, fmt_scientific},
+    merge,
     merge::Merge,
     prelude::*,
     prelude::{Duration, SV},
@@ -15,9 +18,10 @@ use crate::{
 };
 
 #[cfg(feature = "processing")]
-use qc_traits::processing::{
-    DecimationFilter, DecimationFilterType, FilterItem, MaskFilter, MaskOperand,
-};
+use qc_traits::processing::{DecimationFilter, FilterItem, MaskFilter, MaskOperand};
+
+#[cfg(feature = "processing")]
+use crate::record::decimated_epochs;
 
 /// [`ClockKey`] describes each [`ClockProfile`] at a specific [Epoch].
 #[derive(Error, PartialEq, Eq, Hash, Clone, Debug, PartialOrd, Ord)]
@@ -82,6 +86,7 @@ impl std::fmt::Display for ClockType {
 
 #[derive(Error, Debug)]
 /// Clocks file parsing & identification errors
+#[non_exhaustive]
 pub enum Error {
     #[error("unknown data code \"{0}\"")]
     UnknownDataCode(String),
@@ -276,47 +281,66 @@ pub(crate) fn parse_epoch(
     ))
 }
 
-/// Writes epoch into stream
-pub(crate) fn fmt_epoch(epoch: &Epoch, key: &ClockKey, prof: &ClockProfile) -> String {
-    let mut lines = String::with_capacity(60);
-    let (y, m, d, hh, mm, ss, _) = epoch.to_gregorian_utc();
-
-    let mut n = 1;
-    if prof.drift.is_some() {
-        n += 1;
-    }
-    if prof.drift_dev.is_some() {
-        n += 1;
-    }
-    if prof.drift_change.is_some() {
-        n += 1;
-    }
-    if prof.drift_change_dev.is_some() {
-        n += 1;
-    }
-
-    lines.push_str(&format!(
-        "{} {}  {} {:02} {:02} {:02} {:02} {:02}.000000  {}   {:.12E}",
-        key.profile_type, key.clock_type, y, m, d, hh, mm, ss, n, prof.bias
-    ));
+/// Writes epoch into stream.
+/// Up to 6 data fields are described (bias, bias sigma, rate, rate sigma,
+/// acceleration, acceleration sigma): 2 on the first line, 4 on the continuation line.
+/// Missing fields that precede a known field are written as zero.
+pub(crate) fn fmt_epoch(
+    version: Version,
+    epoch: &Epoch,
+    key: &ClockKey,
+    prof: &ClockProfile,
+) -> String {
+    const LIMIT: Version = Version { major: 3, minor: 4 };
+    let (y, m, d, hh, mm, ss, nanos) = epoch_decompose(*epoch);
+    let seconds = ss as f64 + nanos as f64 * 1.0E-9;
+
+    let fields = [
+        Some(prof.bias),
+        prof.bias_dev,
+        prof.drift,
+        prof.drift_dev,
+        prof.drift_change,
+        prof.drift_change_dev,
+    ];
+    let n = fields
+        .iter()
+        .rposition(|field| field.is_some())
+        .unwrap_or(0)
+        + 1;
+
+    let (name, width, separator, continuation) = if version < LIMIT {
+        (
+            format!("{:<4}", key.clock_type.to_string()),
+            20,
+            "  ",
+            "   ",
+        )
+    } else {
+        (format!("{:<9}", key.clock_type.to_string()), 21, " ", " ")
+    };
 
-    if let Some(sigma) = prof.bias_dev {
-        lines.push_str(&format!("{:.13E} ", sigma));
-    }
-    lines.push('\n');
-    if let Some(drift) = prof.drift {
-        lines.push_str(&format!("   {:.13E} ", drift));
-        if let Some(sigma) = prof.drift_dev {
-            lines.push_str(&format!("{:.13E} ", sigma));
+    let mut lines = format!(
+        "{} {} {:04} {:02} {:02} {:02} {:02}{}{:3}{}",
+        key.profile_type,
+        name,
+        y,
+        m,
+        d,
+        hh,
+        mm,
+        fmt_fixed(seconds, 10, 6),
+        n,
+        separator,
+    );
+    for (index, field) in fields[..n].iter().enumerate() {
+        if index == 2 {
+            lines.push('\n');
+            lines.push_str(continuation);
         }
-        if let Some(drift_change) = prof.drift_change {
-            lines.push_str(&format!("{:.13E} ", drift_change));
-        }
-        if let Some(sigma) = prof.drift_change_dev {
-            lines.push_str(&format!("{:.13E} ", sigma));
-        }
-        lines.push('\n');
+        lines.push_str(&fmt_scientific(field.unwrap_or(0.0), width, 12));
     }
+    lines.push('\n');
     lines
 }
 
@@ -379,8 +403,8 @@ impl Split for Record {
             .collect();
         Ok((r0, r1))
     }
-    fn split_dt(&self, _duration: Duration) -> Result<Vec<Self>, split::Error> {
-        Ok(Vec::new())
+    fn split_dt(&self, duration: Duration) -> Result<Vec<Self>, split::Error> {
+        Ok(split::split_dt(self, duration, |e| *e))
     }
 }
 
@@ -405,6 +429,18 @@ pub(crate) fn clock_mask_mut(rec: &mut Record, mask: &MaskFilter) {
         },
         MaskOperand::NotEquals => match &mask.item {
             FilterItem::EpochItem(epoch) => rec.retain(|e, _| *e != *epoch),
+            FilterItem::ConstellationItem(mask) => {
+                rec.retain(|_, data| {
+                    data.retain(|sysclk, _| {
+                        if let Some(sv) = sysclk.clock_type.as_sv() {
+                            !mask.contains(&sv.constellation)
+                        } else {
+                            true
+                        }
+                    });
+                    !data.is_empty()
+                });
+            },
             _ => {}, // FilterItem::
         },
         MaskOperand::GreaterEquals => match &mask.item {
@@ -426,35 +462,43 @@ pub(crate) fn clock_mask_mut(rec: &mut Record, mask: &MaskFilter) {
     }
 }
 
+/*
+ * Returns true if this clock belongs to the decimated subset
+ */
 #[cfg(feature = "processing")]
-pub(crate) fn clock_decim_mut(rec: &mut Record, f: &DecimationFilter) {
-    if f.item.is_some() {
-        todo!("targetted decimation not supported yet");
-    }
-    match f.filter {
-        DecimationFilterType::Modulo(r) => {
-            let mut i = 0;
-            rec.retain(|_, _| {
-                let retained = (i % r) == 0;
-                i += 1;
-                retained
-            });
+fn decimated_clock(item: &FilterItem, key: &ClockKey) -> bool {
+    match item {
+        FilterItem::SvItem(svs) => match key.clock_type.as_sv() {
+            Some(sv) => svs.contains(&sv),
+            None => false,
         },
-        DecimationFilterType::Duration(interval) => {
-            let mut last_retained = Option::<Epoch>::None;
-            rec.retain(|e, _| {
-                if let Some(last) = last_retained {
-                    let dt = *e - last;
-                    if dt >= interval {
-                        last_retained = Some(*e);
-                        true
-                    } else {
-                        false
-                    }
-                } else {
-                    last_retained = Some(*e);
-                    true // always retain 1st epoch
+        FilterItem::ConstellationItem(constells) => match key.clock_type.as_sv() {
+            Some(sv) => constells.contains(&sv.constellation),
+            None => false,
+        },
+        // station name or clock profile (AS, AR..)
+        FilterItem::ComplexItem(filter) => filter.iter().any(|f| {
+            key.clock_type.as_station().as_deref() == Some(f.as_str())
+                || ClockProfileType::from_str(f).ok().as_ref() == Some(&key.profile_type)
+        }),
+        _ => false,
+    }
+}
+
+#[cfg(feature = "processing")]
+pub(crate) fn clock_decim_mut(rec: &mut Record, f: &DecimationFilter) {
+    let retained = decimated_epochs(rec.keys().copied(), &f.filter);
+    match &f.item {
+        None => rec.retain(|e, _| retained.contains(e)),
+        Some(item) => {
+            // only decimate the targetted subset,
+            // everything else is preserved at its original rate
+            rec.retain(|e, data| {
+                if retained.contains(e) {
+                    return true;
                 }
+                data.retain(|key, _| !decimated_clock(item, key));
+                !data.is_empty()
             });
         },
     }
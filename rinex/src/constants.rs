@@ -20,6 +20,23 @@ impl Omega {
     pub const GAL: f64 = 7.2921151467E-5;
 }
 
+/// PZ-90 Earth model, used by Glonass ephemerides
+#[allow(dead_code)]
+pub(crate) struct PZ90;
+#[allow(dead_code)]
+impl PZ90 {
+    /// Equatorial radius (km)
+    pub const RADIUS_KM: f64 = 6378.136;
+    /// Second zonal harmonic coefficient (n.a)
+    pub const J20: f64 = 1.0826257E-3;
+    /// PZ-90.11 to WGS84 (ITRF2008) translation (m)
+    pub const WGS84_TRANSLATION_M: (f64, f64, f64) = (-0.003, -0.001, 0.0);
+    /// PZ-90.11 to WGS84 (ITRF2008) rotation (mas)
+    pub const WGS84_ROTATION_MAS: (f64, f64, f64) = (0.019, -0.042, 0.002);
+    /// PZ-90.11 to WGS84 (ITRF2008) scale factor (n.a)
+    pub const WGS84_SCALE: f64 = 0.0;
+}
+
 /// - 2 * sqrt(gm) / c / c
 #[allow(dead_code)]
 pub(crate) struct DtrF;
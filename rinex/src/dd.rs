@@ -0,0 +1,139 @@
+//! Phase double differences, between two receivers observing
+//! the same vehicles at the same epochs.
+use crate::navigation::Ephemeris;
+use crate::prelude::{Almanac, Constellation, Epoch, Frame, Observable, Rinex, SV};
+use std::collections::{BTreeMap, HashMap};
+
+/*
+ * Carrier phase observations [m] sorted per Epoch.
+ * Only sane epochs are retained.
+ */
+fn phase_meters(rnx: &Rinex) -> BTreeMap<Epoch, HashMap<(SV, Observable), f64>> {
+    let mut ret = BTreeMap::<Epoch, HashMap<(SV, Observable), f64>>::new();
+    for ((t, flag), sv, observable, value) in rnx.carrier_phase() {
+        if !flag.is_ok() {
+            continue;
+        }
+        if let Ok(carrier) = observable.carrier(sv.constellation) {
+            ret.entry(t)
+                .or_default()
+                .insert((sv, observable.clone()), value * carrier.wavelength());
+        }
+    }
+    ret
+}
+
+/// Forms between-receiver, between-satellite phase double differences,
+/// expressed in meters, for each ([SV], [Observable]) tuple.
+/// Only epochs and signals observed by both `rover` and `base` contribute.
+/// Each (rover - base) single difference is referenced to the single difference
+/// of the same [Observable] observed on the reference vehicle, within the same
+/// [Constellation]. If `ref_sv` is not specified, the highest vehicle
+/// (as seen from `rover`, or `base` if the rover position is unknown) is selected
+/// at each epoch, for each [Constellation], using `nav` ephemerides.
+/// Elevation angles are resolved with [Ephemeris::elevation_azimuth_range],
+/// in the `earth_cef` body fixed [Frame].
+/// Reference vehicles are not described in the results.
+///
+/// This is a simple geometry-free double difference: ambiguities are not resolved,
+/// it is intended for short baseline noise and multipath assessment.
+/// ```
+/// use rinex::prelude::*;
+/// use rinex::dd::double_differences;
+/// use anise::{
+///     constants::frames::IAU_EARTH_FRAME,
+///     structure::planetocentric::ellipsoid::Ellipsoid,
+/// };
+/// let rover = Rinex::from_file("../test_resources/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz")
+///     .unwrap();
+/// let base = Rinex::from_file("../test_resources/CRNX/V3/MOJN00DNK_R_20201770000_01D_30S_MO.crx.gz")
+///     .unwrap();
+/// let nav = Rinex::from_file("../test_resources/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+///     .unwrap();
+/// // body fixed frame, WGS84 shape
+/// let almanac = Almanac::default();
+/// let earth_cef = Frame {
+///     shape: Some(Ellipsoid::from_spheroid(6378.137, 6356.752314245)),
+///     ..IAU_EARTH_FRAME
+/// };
+/// let dd = double_differences(&rover, &base, &nav, &almanac, earth_cef, None);
+/// for ((sv, observable), dd) in dd {
+///     for (t, dd_m) in dd {
+///         // double difference, in meters
+///     }
+/// }
+/// ```
+pub fn double_differences(
+    rover: &Rinex,
+    base: &Rinex,
+    nav: &Rinex,
+    almanac: &Almanac,
+    earth_cef: Frame,
+    ref_sv: Option<SV>,
+) -> BTreeMap<(SV, Observable), Vec<(Epoch, f64)>> {
+    let mut ret = BTreeMap::<(SV, Observable), Vec<(Epoch, f64)>>::new();
+
+    let rx_km = rover
+        .header
+        .ground_position
+        .or(base.header.ground_position)
+        .map(|position| {
+            let (x, y, z) = position.to_ecef_wgs84();
+            (x / 1.0E3, y / 1.0E3, z / 1.0E3)
+        });
+
+    let base_phases = phase_meters(base);
+
+    for (t, rover_phases) in phase_meters(rover) {
+        let base_phases = match base_phases.get(&t) {
+            Some(phases) => phases,
+            None => continue, // not a common epoch
+        };
+        // single differences
+        let single_diff = rover_phases
+            .iter()
+            .filter_map(|(key, rover)| {
+                let base = base_phases.get(key)?;
+                Some((key.clone(), rover - base))
+            })
+            .collect::<HashMap<_, _>>();
+
+        // reference vehicle, per constellation
+        let mut references = HashMap::<Constellation, SV>::new();
+        if let Some(ref_sv) = ref_sv {
+            references.insert(ref_sv.constellation, ref_sv);
+        } else if let Some(rx_km) = rx_km {
+            let mut highest = HashMap::<Constellation, (SV, f64)>::new();
+            for (sv, _) in single_diff.keys() {
+                let el_az_range = nav.sv_position_interpolate(*sv, t).and_then(|sv_km| {
+                    Ephemeris::elevation_azimuth_range(t, almanac, earth_cef, sv_km, rx_km).ok()
+                });
+                let elev = match el_az_range {
+                    Some(el_az_range) => el_az_range.elevation_deg,
+                    None => continue,
+                };
+                let best = highest.entry(sv.constellation).or_insert((*sv, elev));
+                if elev > best.1 {
+                    *best = (*sv, elev);
+                }
+            }
+            references = highest.into_iter().map(|(c, (sv, _))| (c, sv)).collect();
+        }
+
+        for ((sv, observable), sd) in single_diff.iter() {
+            let reference = match references.get(&sv.constellation) {
+                Some(reference) => *reference,
+                None => continue,
+            };
+            if reference == *sv {
+                continue;
+            }
+            if let Some(ref_sd) = single_diff.get(&(reference, observable.clone())) {
+                ret.entry((*sv, observable.clone()))
+                    .or_default()
+                    .push((t, sd - ref_sd));
+            }
+        }
+    }
+    ret
+}
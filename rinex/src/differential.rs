@@ -4,6 +4,7 @@ use thiserror::Error;
 //use crate::observation::record::ObservationData;
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     #[error("`base` data must be Observation RINEX for this operation")]
     NotObservationBase,
@@ -9,9 +9,11 @@ use crate::{
 use gnss_rs::domes::Error as DomesParsingError;
 
 pub(crate) mod record;
+pub(crate) mod satellite;
 pub(crate) mod station;
 
-pub use record::Record;
+pub use record::{ClockOffset, Record};
+pub use satellite::Satellite;
 pub use station::Station;
 
 #[cfg(feature = "processing")]
@@ -22,6 +24,7 @@ use qc_traits::processing::{FilterItem, MaskFilter, MaskOperand};
 
 /// DORIS Station & record parsing error
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     #[error("invalid station")]
     InvalidStation,
@@ -39,8 +42,8 @@ pub enum Error {
 #[derive(Debug, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HeaderFields {
-    /// Name of the DORIS satellite
-    pub satellite: String,
+    /// DORIS [Satellite] hosting the receiver
+    pub satellite: Option<Satellite>,
     /// Time of First Measurement, expressed in TAI timescale.
     pub time_of_first_obs: Option<Epoch>,
     /// Time of Last Measurement, expressed in TAI timescale.
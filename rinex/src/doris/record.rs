@@ -1,20 +1,24 @@
 use hifitime::Epoch;
 use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
 use thiserror::Error;
 
 use crate::{
     doris::Station,
-    epoch::{parse_in_timescale, ParsingError as EpochParsingError},
+    epoch::{self, parse_in_timescale, ParsingError as EpochParsingError},
+    formatting::fmt_fixed,
     header::Header,
     observable::Observable,
     observation::EpochFlag,
     prelude::TimeScale,
+    types::Type,
 };
 
 #[cfg(feature = "processing")]
-use qc_traits::processing::{
-    DecimationFilter, DecimationFilterType, FilterItem, MaskFilter, MaskOperand,
-};
+use qc_traits::processing::{DecimationFilter, FilterItem, MaskFilter, MaskOperand};
+
+#[cfg(feature = "processing")]
+use crate::record::decimated_epochs;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -30,10 +34,26 @@ pub struct ObservationData {
     pub m2: Option<u8>,
 }
 
+/// DORIS receiver [ClockOffset], as reported on each epoch
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClockOffset {
+    /// Receiver clock offset, in seconds
+    pub offset: f64,
+    /// True if this offset was extrapolated by the receiver
+    pub extrapolated: bool,
+}
+
 /// DORIS RINEX Record content.
 /// Measurements are stored by Kind, by Station and by TAI sampling instant.
-pub type Record =
-    BTreeMap<(Epoch, EpochFlag), BTreeMap<Station, HashMap<Observable, ObservationData>>>;
+/// Measurements are stored as is, scaling described in the header is not applied.
+pub type Record = BTreeMap<
+    (Epoch, EpochFlag),
+    (
+        Option<ClockOffset>,
+        BTreeMap<Station, HashMap<Observable, ObservationData>>,
+    ),
+>;
 
 /// Returns true if following line matches a new DORIS measurement
 pub(crate) fn is_new_epoch(line: &str) -> bool {
@@ -41,13 +61,19 @@ pub(crate) fn is_new_epoch(line: &str) -> bool {
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("failed to parse epoch")]
     EpochError(#[from] EpochParsingError),
+    #[error("failed to parse epoch flag")]
+    EpochFlag(#[from] crate::observation::flag::Error),
     #[error("failed to parse data")]
     ParseFloatError(#[from] std::num::ParseFloatError),
 }
 
+/// Number of observations per line
+const OBSERVATIONS_PER_LINE: usize = 5;
+
 /// DORIS measurement parsing process
 pub(crate) fn parse_epoch(
     header: &Header,
@@ -55,13 +81,15 @@ pub(crate) fn parse_epoch(
 ) -> Result<
     (
         (Epoch, EpochFlag),
+        Option<ClockOffset>,
         BTreeMap<Station, HashMap<Observable, ObservationData>>,
     ),
     Error,
 > {
     let mut obs_idx = 0usize;
     let mut epoch = Epoch::default();
-    let flag = EpochFlag::default();
+    let mut flag = EpochFlag::default();
+    let mut clock_offset = Option::<ClockOffset>::None;
     let mut station = Option::<Station>::None;
     let mut buffer = BTreeMap::<Station, HashMap<Observable, ObservationData>>::new();
 
@@ -87,13 +115,28 @@ pub(crate) fn parse_epoch(
             0 => {
                 /* 1st line gives TAI timestamp, flag, clock offset */
                 let line = line.split_at(2).1; // "> "
-                let offset = "YYYY MM DD HH MM SS.NNNNNNNNN  0".len();
-                let (date, _rem) = line.split_at(offset);
+                let offset = "YYYY MM DD HH MM SS.NNNNNNNNN".len();
+                let (date, rem) = line.split_at(offset);
                 epoch = parse_in_timescale(date, TimeScale::TAI)?;
+
+                let (flag_str, rem) = rem.split_at(std::cmp::min(3, rem.len()));
+                if !flag_str.trim().is_empty() {
+                    flag = EpochFlag::from_str(flag_str.trim())?;
+                }
+
+                // skip number of stations
+                let rem = rem.get(3..).unwrap_or("");
+                let (offset_str, rem) = rem.split_at(std::cmp::min(19, rem.len()));
+                if let Ok(offset) = offset_str.trim().parse::<f64>() {
+                    clock_offset = Some(ClockOffset {
+                        offset,
+                        extrapolated: rem.trim() == "1",
+                    });
+                }
             },
             _ => {
-                let (id, _remainder) = line.split_at(4);
-                //println!("ID : \"{}\" - REMAINDER : \"{}\"", id, remainder); //DBEUG
+                let id = line.get(..3).unwrap_or(line);
+                let line_start = obs_idx;
 
                 if obs_idx == 0 {
                     // parse station identifier
@@ -113,21 +156,35 @@ pub(crate) fn parse_epoch(
                 let identified_station =
                     station.as_ref().expect("failed to identify DORIS station");
 
-                // consume this line
-                let mut offset = 5;
+                // consume this line: (F14.3, I1, I1) per observation
+                let mut offset = 3;
                 let max_offset = line.len();
-                while offset < line.len() {
+                while offset < max_offset {
                     let content = &line[offset..std::cmp::min(max_offset, offset + 16)];
-                    let obs = &content[..12];
-                    let m1 = &content[12..13].trim();
-                    let m2 = &content[13..14].trim();
+                    let obs = content.get(..14).unwrap_or(content).trim();
+                    let m1 = content.get(14..15).unwrap_or("").trim();
+                    let m2 = content.get(15..16).unwrap_or("").trim();
 
                     //println!("obs \"{}\"", obs); //DEBUG
                     //println!("m1 \"{}\"", m1); //DEBUG
                     //println!("m2 \"{}\"", m2); //DEBUG
 
+                    let observable = observables.get(obs_idx).unwrap_or_else(|| {
+                        panic!(
+                            "failed to determine observable for {:?}({:?}) @ {}",
+                            identified_station, epoch, obs_idx
+                        )
+                    });
+
+                    offset += 16;
+                    obs_idx += 1;
+
+                    if obs.is_empty() {
+                        // missing observation
+                        continue;
+                    }
+
                     let value = obs
-                        .trim()
                         .parse::<f64>()
                         .unwrap_or_else(|e| panic!("failed to parse observation: {:?}", e));
 
@@ -147,34 +204,119 @@ pub(crate) fn parse_epoch(
                         None
                     };
 
-                    let observable = observables.get(obs_idx).unwrap_or_else(|| {
-                        panic!(
-                            "failed to determine observable for {:?}({:?}) @ {}",
-                            identified_station, epoch, obs_idx
-                        )
-                    });
-
                     let obsdata = ObservationData { value, m1, m2 };
 
-                    if let Some(station) = buffer.get_mut(identified_station) {
-                        station.insert(observable.clone(), obsdata);
-                    } else {
-                        let inner =
-                            HashMap::from_iter([(Observable::default(), obsdata)].into_iter());
-                        buffer.insert(identified_station.clone(), inner);
-                    }
-
-                    offset += 16;
-                    obs_idx += 1;
+                    buffer
+                        .entry(identified_station.clone())
+                        .or_default()
+                        .insert(observable.clone(), obsdata);
                 }
-                if obs_idx == observables.len() {
+                // a line holds up to 5 observations, trailing blanks may be trimmed
+                obs_idx = line_start + OBSERVATIONS_PER_LINE;
+                if obs_idx >= observables.len() {
                     obs_idx = 0;
                     station = None;
                 }
             },
         }
     }
-    Ok(((epoch, flag), buffer))
+    Ok(((epoch, flag), clock_offset, buffer))
+}
+
+/// Formats one DORIS epoch, with the observable order declared in the header
+pub(crate) fn fmt_epoch(
+    epoch: &Epoch,
+    flag: &EpochFlag,
+    clock_offset: &Option<ClockOffset>,
+    data: &BTreeMap<Station, HashMap<Observable, ObservationData>>,
+    header: &Header,
+) -> String {
+    let mut lines = String::with_capacity(128);
+    let observables = match &header.doris {
+        Some(doris) => &doris.observables,
+        None => return lines,
+    };
+
+    lines.push_str(&format!(
+        "> {}{:>3}{:3}",
+        epoch::format(*epoch, Type::DORIS, header.version.major),
+        flag,
+        data.len()
+    ));
+    if let Some(clock_offset) = clock_offset {
+        lines.push_str(&format!(
+            "{} {}",
+            fmt_fixed(clock_offset.offset, 19, 9),
+            clock_offset.extrapolated as u8
+        ));
+    }
+    lines.push('\n');
+
+    for (station, values) in data.iter() {
+        lines.push_str(&format!("D{:02}", station.key));
+        for (index, observable) in observables.iter().enumerate() {
+            if index > 0 && (index % OBSERVATIONS_PER_LINE) == 0 {
+                lines.push_str("\n   ");
+            }
+            if let Some(data) = values.get(observable) {
+                lines.push_str(&fmt_fixed(data.value, 14, 3));
+                match data.m1 {
+                    Some(m1) => lines.push_str(&format!("{:1}", m1)),
+                    None => lines.push(' '),
+                }
+                match data.m2 {
+                    Some(m2) => lines.push_str(&format!("{:1}", m2)),
+                    None => lines.push(' '),
+                }
+            } else {
+                lines.push_str(&" ".repeat(16));
+            }
+        }
+        lines.push('\n');
+    }
+    lines
+}
+
+/*
+ * Substracts rhs (reference) to this record, per epoch, per station
+ * and per observable. Keys that rhs does not describe are dropped.
+ * Resulting data is not flagged, clock offsets are differenced when
+ * both sides provide one.
+ */
+pub(crate) fn substract_mut(rec: &mut Record, rhs: &Record) {
+    rec.retain(|key, (clock_offset, stations)| {
+        let (ref_clock_offset, ref_stations) = match rhs.get(key) {
+            Some(reference) => reference,
+            None => return false,
+        };
+        *clock_offset = match (&clock_offset, ref_clock_offset) {
+            (Some(clock_offset), Some(ref_clock_offset)) => Some(ClockOffset {
+                offset: clock_offset.offset - ref_clock_offset.offset,
+                extrapolated: clock_offset.extrapolated || ref_clock_offset.extrapolated,
+            }),
+            _ => None,
+        };
+        stations.retain(|station, observations| {
+            let ref_observations = match ref_stations.get(station) {
+                Some(observations) => observations,
+                None => return false,
+            };
+            observations.retain(|observable, data| {
+                if let Some(ref_data) = ref_observations.get(observable) {
+                    *data = ObservationData {
+                        value: data.value - ref_data.value,
+                        m1: None,
+                        m2: None,
+                    };
+                    true
+                } else {
+                    false
+                }
+            });
+            !observations.is_empty()
+        });
+        !stations.is_empty()
+    });
 }
 
 #[cfg(feature = "processing")]
@@ -206,47 +348,63 @@ pub(crate) fn doris_mask_mut(rec: &mut Record, mask: &MaskFilter) {
             },
             _ => {}, //TODO: some other types could apply, like SNR..
         },
-        _ => {},
+        MaskOperand::GreaterEquals => match &mask.item {
+            FilterItem::EpochItem(epoch) => rec.retain(|(e, _), _| *e >= *epoch),
+            _ => {},
+        },
+        MaskOperand::GreaterThan => match &mask.item {
+            FilterItem::EpochItem(epoch) => rec.retain(|(e, _), _| *e > *epoch),
+            _ => {},
+        },
+        MaskOperand::LowerEquals => match &mask.item {
+            FilterItem::EpochItem(epoch) => rec.retain(|(e, _), _| *e <= *epoch),
+            _ => {},
+        },
+        MaskOperand::LowerThan => match &mask.item {
+            FilterItem::EpochItem(epoch) => rec.retain(|(e, _), _| *e < *epoch),
+            _ => {},
+        },
     }
 }
 
+/*
+ * Returns true if this measurement belongs to the decimated subset
+ */
+#[cfg(feature = "processing")]
+fn decimated_measurement(filter: &[String], station: &Station, observable: &Observable) -> bool {
+    filter.iter().any(|f| {
+        station.label == *f
+            || station.site == *f
+            || Observable::from_str(f).ok().as_ref() == Some(observable)
+    })
+}
+
 #[cfg(feature = "processing")]
 pub(crate) fn doris_decim_mut(rec: &mut Record, f: &DecimationFilter) {
-    if f.item.is_some() {
-        todo!("targetted decimation not supported yet");
-    }
-    match f.filter {
-        DecimationFilterType::Modulo(r) => {
-            let mut i = 0;
-            rec.retain(|_, _| {
-                let retained = (i % r) == 0;
-                i += 1;
-                retained
-            });
-        },
-        DecimationFilterType::Duration(interval) => {
-            let mut last_retained = Option::<Epoch>::None;
-            rec.retain(|(e, _), _| {
-                if let Some(last) = last_retained {
-                    let dt = *e - last;
-                    if dt >= interval {
-                        last_retained = Some(*e);
-                        true
-                    } else {
-                        false
-                    }
-                } else {
-                    last_retained = Some(*e);
-                    true // always retain 1st epoch
+    let retained = decimated_epochs(rec.keys().map(|(e, _)| *e), &f.filter);
+    match &f.item {
+        None => rec.retain(|(e, _), _| retained.contains(e)),
+        Some(FilterItem::ComplexItem(filter)) => {
+            // only decimate the targetted stations or observables,
+            // everything else is preserved at its original rate
+            rec.retain(|(e, _), (clk, stations)| {
+                if retained.contains(e) {
+                    return true;
                 }
+                stations.retain(|station, obs| {
+                    obs.retain(|observable, _| !decimated_measurement(filter, station, observable));
+                    !obs.is_empty()
+                });
+                clk.is_some() || !stations.is_empty()
             });
         },
+        Some(_) => {}, // other items do not apply to DORIS data
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{is_new_epoch, parse_epoch};
+    use super::{is_new_epoch, parse_epoch, ClockOffset};
     use crate::{
         doris::record::ObservationData,
         doris::HeaderFields as DorisHeader,
@@ -302,7 +460,7 @@ D01  -3237877.052    -2291024.044    21903595.62311  21903633.08011      -113.10
 D02  -2069899.788     -407871.014     4677242.25714   4677392.20614      -119.050 7
          -111.000 7       437.801        1007.000 0        -2.000 0        74.000 0";
 
-        let ((e, flag), content) =
+        let ((e, flag), clock_offset, content) =
             parse_epoch(&header, content).expect("failed to parse DORIS epoch");
 
         assert_eq!(
@@ -311,6 +469,14 @@ D02  -2069899.788     -407871.014     4677242.25714   4677392.20614      -119.05
             "parsed wrong epoch"
         );
         assert_eq!(flag, EpochFlag::Ok, "parsed wrong epoch flag");
+        assert_eq!(
+            clock_offset,
+            Some(ClockOffset {
+                offset: -0.151364695,
+                extrapolated: false,
+            }),
+            "parsed wrong clock offset"
+        );
 
         let station = Station {
             key: 1,
@@ -331,7 +497,7 @@ D02  -2069899.788     -407871.014     4677242.25714   4677392.20614      -119.05
 
         for (observable, data) in [
             (
-                Observable::from_str("L1C").unwrap(),
+                Observable::from_str("L1").unwrap(),
                 ObservationData {
                     m1: None,
                     m2: None,
@@ -436,7 +602,7 @@ D02  -2069899.788     -407871.014     4677242.25714   4677392.20614      -119.05
 
         for (observable, data) in [
             (
-                Observable::from_str("L1C").unwrap(),
+                Observable::from_str("L1").unwrap(),
                 ObservationData {
                     m1: None,
                     m2: None,
@@ -0,0 +1,70 @@
+//! DORIS satellite (DORIS payload host)
+use crate::prelude::COSPAR;
+
+/// DORIS [Satellite] hosting the DORIS receiver (payload).
+/// DORIS RINEX files are produced on a per satellite basis.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Satellite {
+    /// Satellite name, as described in the header
+    pub name: String,
+    /// COSPAR number (launch information), if known
+    pub cospar: Option<COSPAR>,
+}
+
+impl Satellite {
+    /// Builds a new [Satellite] from its name
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            cospar: None,
+        }
+    }
+    /// Returns 3 letter code used by the IDS (International DORIS Service)
+    /// to designate this [Satellite] in standardized file names.
+    /// Falls back to the first 3 alphanumeric characters of the name
+    /// for unknown vehicles.
+    /// ```
+    /// use rinex::doris::Satellite;
+    /// assert_eq!(Satellite::new("CRYOSAT-2").ids_code(), "cs2");
+    /// assert_eq!(Satellite::new("JASON-3").ids_code(), "ja3");
+    /// assert_eq!(Satellite::new("SENTINEL-3A").ids_code(), "s3a");
+    /// ```
+    pub fn ids_code(&self) -> String {
+        let name = self
+            .name
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        match name.as_str() {
+            "cryosat2" => "cs2".to_string(),
+            "envisat" | "envisat1" => "en1".to_string(),
+            "hy2a" => "h2a".to_string(),
+            "hy2c" => "h2c".to_string(),
+            "hy2d" => "h2d".to_string(),
+            "jason1" => "ja1".to_string(),
+            "jason2" => "ja2".to_string(),
+            "jason3" => "ja3".to_string(),
+            "saral" => "srl".to_string(),
+            "sentinel3a" => "s3a".to_string(),
+            "sentinel3b" => "s3b".to_string(),
+            "sentinel6a" | "sentinel6mf" => "s6a".to_string(),
+            "spot4" => "sp4".to_string(),
+            "spot5" => "sp5".to_string(),
+            "swot" => "swo".to_string(),
+            "topexposeidon" | "topex" => "tpx".to_string(),
+            name => name.chars().take(3).collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for Satellite {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(cospar) = &self.cospar {
+            write!(f, " ({})", cospar)?;
+        }
+        Ok(())
+    }
+}
@@ -10,6 +10,7 @@ use std::str::FromStr;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ParsingError {
     #[error("failed to parse epoch")]
     HifitimeParsingError(#[from] HifitimeParsingError),
@@ -42,10 +43,10 @@ pub(crate) fn now() -> Epoch {
     Epoch::now().unwrap_or(Epoch::from_gregorian_utc_at_midnight(2000, 1, 1))
 }
 
-/*
- * Formats given epoch to string, matching standard specifications
- */
-pub(crate) fn format(epoch: Epoch, t: Type, revision: u8) -> String {
+/// Formats given [Epoch] as expected by given RINEX [Type] and major revision.
+/// This only describes the date and time fields, not the epoch flag nor the
+/// epoch content.
+pub fn format(epoch: Epoch, t: Type, revision: u8) -> String {
     let (y, m, d, hh, mm, ss, nanos) = epoch_decompose(epoch);
 
     match t {
@@ -102,6 +103,10 @@ pub(crate) fn format(epoch: Epoch, t: Type, revision: u8) -> String {
                 format!("{:04} {:02} {:02} {:02} {:02} {:02}", y, m, d, hh, mm, ss)
             }
         },
+        Type::DORIS => format!(
+            "{:04} {:02} {:02} {:02} {:02} {:>2}.{:09}",
+            y, m, d, hh, mm, ss, nanos,
+        ),
         Type::IonosphereMaps => format!(
             "{:04}   {:>2}    {:>2}    {:>2}    {:>2}    {:>2}",
             y, m, d, hh, mm, ss
@@ -0,0 +1,232 @@
+//! File operations that do not require parsing the record,
+//! for example to fix header fields of archived files.
+use crate::{
+    fmt_rinex,
+    formatting::fmt_fixed,
+    ground_position::GroundPosition,
+    hardware::Antenna,
+    header::{Header, ParsingError},
+    reader::BufferedReader,
+    writer::BufferedWriter,
+    ErrorKind,
+};
+use std::io::{BufRead, Write};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("file i/o error")]
+    IoError(#[from] std::io::Error),
+    #[error("patched header is invalid")]
+    HeaderParsingError(#[from] ParsingError),
+    #[error("END OF HEADER not found")]
+    UnterminatedHeader,
+}
+
+impl Error {
+    /// Returns the [ErrorKind] of this [Error]
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::IoError(e) => ErrorKind::from_io_error(e),
+            Self::HeaderParsingError(e) => e.kind(),
+            Self::UnterminatedHeader => ErrorKind::MalformedHeader,
+        }
+    }
+}
+
+/// [HeaderPatch] describes the [Header] fields to be replaced
+/// by [patch_header]. Fields left to None are preserved.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HeaderPatch {
+    /// Geodetic marker name
+    pub marker: Option<String>,
+    /// Agency
+    pub agency: Option<String>,
+    /// Observer
+    pub observer: Option<String>,
+    /// Receiver [Antenna]: model and serial number,
+    /// eccentricities when they are defined.
+    pub antenna: Option<Antenna>,
+    /// Approximate station position
+    pub position: Option<GroundPosition>,
+}
+
+impl HeaderPatch {
+    /// Patches geodetic marker name
+    pub fn with_marker(&self, name: &str) -> Self {
+        let mut s = self.clone();
+        s.marker = Some(name.to_string());
+        s
+    }
+    /// Patches agency
+    pub fn with_agency(&self, agency: &str) -> Self {
+        let mut s = self.clone();
+        s.agency = Some(agency.to_string());
+        s
+    }
+    /// Patches observer
+    pub fn with_observer(&self, observer: &str) -> Self {
+        let mut s = self.clone();
+        s.observer = Some(observer.to_string());
+        s
+    }
+    /// Patches receiver [Antenna]
+    pub fn with_antenna(&self, antenna: Antenna) -> Self {
+        let mut s = self.clone();
+        s.antenna = Some(antenna);
+        s
+    }
+    /// Patches approximate station position
+    pub fn with_position(&self, position: GroundPosition) -> Self {
+        let mut s = self.clone();
+        s.position = Some(position);
+        s
+    }
+    /*
+     * Header lines (label, content) described by Self.
+     * `observer_agency` is the current content
+     * of the OBSERVER / AGENCY field, preserved when only one of them is patched.
+     */
+    fn lines(&self, observer_agency: &str) -> Vec<(&'static str, String)> {
+        let mut lines = Vec::new();
+        if let Some(marker) = &self.marker {
+            lines.push(("MARKER NAME", format!("{:.60}", marker)));
+        }
+        if self.observer.is_some() || self.agency.is_some() {
+            let observer = observer_agency.get(..20).unwrap_or(observer_agency);
+            let agency = observer_agency.get(20..).unwrap_or("");
+            let observer = self.observer.as_deref().unwrap_or(observer.trim());
+            let agency = self.agency.as_deref().unwrap_or(agency.trim());
+            lines.push((
+                "OBSERVER / AGENCY",
+                format!("{:<20.20}{:.40}", observer, agency),
+            ));
+        }
+        if let Some(antenna) = &self.antenna {
+            lines.push((
+                "ANT # / TYPE",
+                format!("{:<20.20}{:.20}", antenna.model, antenna.sn),
+            ));
+            if antenna.height.is_some() || antenna.eastern.is_some() || antenna.northern.is_some() {
+                lines.push((
+                    "ANTENNA: DELTA H/E/N",
+                    format!(
+                        "{}{}{}",
+                        fmt_fixed(antenna.height.unwrap_or(0.0), 14, 4),
+                        fmt_fixed(antenna.eastern.unwrap_or(0.0), 14, 4),
+                        fmt_fixed(antenna.northern.unwrap_or(0.0), 14, 4)
+                    ),
+                ));
+            }
+        }
+        if let Some(position) = &self.position {
+            lines.push(("APPROX POSITION XYZ", format!("{:X}", position)));
+        }
+        lines
+    }
+}
+
+/*
+ * Returns label of given header line
+ */
+fn header_label(line: &str) -> &str {
+    line.get(60..).unwrap_or("").trim()
+}
+
+/// Patches the header of the RINEX file located at `path`, in place.
+/// Only the header lines targeted by [HeaderPatch] are replaced, or inserted
+/// prior END OF HEADER when they did not exist. The file body is copied byte to byte.
+/// Gzip compressed files (.gz) are decompressed and compressed back.
+/// The patched file is only committed once its header has been parsed successfully.
+/// ```no_run
+/// use rinex::fileops::{patch_header, HeaderPatch};
+/// let patch = HeaderPatch::default()
+///     .with_marker("ESBC")
+///     .with_agency("SDFE");
+/// patch_header("ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz", patch)
+///     .unwrap();
+/// ```
+pub fn patch_header(path: &str, patch: HeaderPatch) -> Result<(), Error> {
+    let tmp_path = match path.strip_suffix(".gz") {
+        Some(stem) => format!("{}.patch.gz", stem),
+        None => format!("{}.patch", path),
+    };
+    if let Err(e) = write_patched(path, &tmp_path, &patch) {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    // verify patched content
+    let header = BufferedReader::new(&tmp_path)
+        .map_err(Error::from)
+        .and_then(|mut reader| Header::new(&mut reader).map_err(Error::from));
+    if let Err(e) = header {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/*
+ * Streams `path` into `tmp_path`, patching the header on the fly
+ */
+fn write_patched(path: &str, tmp_path: &str, patch: &HeaderPatch) -> Result<(), Error> {
+    let mut reader = BufferedReader::new(path)?;
+    let mut writer = BufferedWriter::new(tmp_path)?;
+
+    let mut header = Vec::<Vec<u8>>::new();
+    let mut terminated = false;
+    loop {
+        let mut line = Vec::new();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        let content = String::from_utf8_lossy(&line);
+        terminated = header_label(content.trim_end()) == "END OF HEADER";
+        header.push(line);
+        if terminated {
+            break;
+        }
+    }
+    if !terminated {
+        return Err(Error::UnterminatedHeader);
+    }
+
+    let observer_agency = header
+        .iter()
+        .map(|line| String::from_utf8_lossy(line).trim_end().to_string())
+        .find(|line| header_label(line).starts_with("OBSERVER"))
+        .map(|line| line.chars().take(60).collect::<String>())
+        .unwrap_or_default();
+
+    let mut patched = patch.lines(&observer_agency);
+    for line in header.iter() {
+        let content = String::from_utf8_lossy(line);
+        let termination = match &content[content.trim_end_matches(['\r', '\n']).len()..] {
+            "" => "\n",
+            termination => termination,
+        };
+        let label = match header_label(content.trim_end()) {
+            "OBSERVER /AGENCY" => "OBSERVER / AGENCY",
+            label => label,
+        };
+        if label == "END OF HEADER" {
+            // insert fields that did not exist
+            for (label, content) in patched.drain(..) {
+                write!(writer, "{}{}", fmt_rinex(&content, label), termination)?;
+            }
+            writer.write_all(line)?;
+        } else if let Some(pos) = patched.iter().position(|(l, _)| *l == label) {
+            let (label, content) = patched.remove(pos);
+            write!(writer, "{}{}", fmt_rinex(&content, label), termination)?;
+        } else {
+            writer.write_all(line)?;
+        }
+    }
+
+    // copy record
+    std::io::copy(&mut reader, &mut writer)?;
+    writer.finish()?;
+    Ok(())
+}
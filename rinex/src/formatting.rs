@@ -0,0 +1,70 @@
+//! Fixed width numerical formatting, shared by all writers.
+//!
+//! RINEX describes floating point values as Fortran F`w.d` fields.
+//! Values are rounded to `d` decimal digits with ties away from zero,
+//! which matches RNX2CRX behavior, then formatted from the resulting
+//! integer, so the produced digits never depend on the float formatter.
+//! Any value that is representable at the target precision
+//! is therefore preserved bit-exactly through a format/parse cycle.
+
+/*
+ * Largest integer magnitude that is exactly representable as f64 (2^53)
+ */
+const MAX_EXACT_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+/// Formats `value` as F`width`.`precision` field: right aligned,
+/// rounded to `precision` decimal digits with ties away from zero.
+/// Unlike Fortran, which fills it with asterisks, the field grows
+/// if `value` does not fit in `width`.
+pub(crate) fn fmt_fixed(value: f64, width: usize, precision: usize) -> String {
+    let scaled = (value * 10.0_f64.powi(precision as i32)).round();
+    if !scaled.is_finite() || scaled.abs() >= MAX_EXACT_INTEGER {
+        // not an integer number of decimal units anymore:
+        // let the float formatter handle it
+        return format!("{:width$.precision$}", value);
+    }
+
+    let units = scaled.abs() as u64;
+    let divisor = 10_u64.pow(precision as u32);
+    // negative zero is not signed
+    let sign = if scaled < 0.0 { "-" } else { "" };
+
+    let content = if precision == 0 {
+        format!("{}{}", sign, units)
+    } else {
+        format!(
+            "{}{}.{:0precision$}",
+            sign,
+            units / divisor,
+            units % divisor,
+        )
+    };
+    format!("{:>width$}", content)
+}
+
+/// Formats `value` as Fortran E`width`.`precision` field: right aligned,
+/// normalized mantissa (0.1 <= |m| < 1) of `precision` significant digits,
+/// and two digit exponent at least, for example "  -0.123456789012E-03".
+pub(crate) fn fmt_scientific(value: f64, width: usize, precision: usize) -> String {
+    if !value.is_finite() {
+        return format!("{:>width$}", value);
+    }
+    if value == 0.0 {
+        let content = format!("0.{:0precision$}E+00", 0);
+        return format!("{:>width$}", content);
+    }
+    // Rust normalizes the mantissa to 1 <= |m| < 10
+    let formatted = format!("{:.*e}", precision.saturating_sub(1), value);
+    let (mantissa, exponent) = formatted.split_once('e').unwrap();
+    let exponent = exponent.parse::<i32>().unwrap() + 1;
+    let sign = if mantissa.starts_with('-') { "-" } else { "" };
+    let digits = mantissa.trim_start_matches('-').replace('.', "");
+    let content = format!(
+        "{}0.{}E{}{:02}",
+        sign,
+        digits,
+        if exponent < 0 { '-' } else { '+' },
+        exponent.abs()
+    );
+    format!("{:>width$}", content)
+}
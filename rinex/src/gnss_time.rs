@@ -5,6 +5,7 @@
 /// System Time corrections decoding error
 #[derive(Error, Debug)]
 #[derive(PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     /// Failed to decode time systems
     #[error("failed to decode time systems")]
@@ -1,19 +1,82 @@
 use dms_coordinates::DMS;
 use map_3d::{deg2rad, ecef2geodetic, geodetic2ecef, rad2deg, Ellipsoid};
 
+use crate::formatting::fmt_fixed;
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "qc")]
 use maud::{html, Markup, Render};
 
+use thiserror::Error;
+
+/// [ReferenceFrame] parsing error
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("unknown reference frame \"{0}\"")]
+    UnknownFrame(String),
+}
+
+/// Terrestrial [ReferenceFrame] in which a [GroundPosition] is expressed
+#[derive(Default, Copy, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReferenceFrame {
+    /// World Geodetic System 1984,
+    /// assumed when the file does not declare its frame
+    #[default]
+    WGS84,
+    /// International Terrestrial Reference Frame, identified
+    /// by its realization year. IGS realizations (IGS14, IGb14, IGS20..)
+    /// are aligned to the ITRF of the same year.
+    ITRF(u16),
+}
+
+impl std::fmt::Display for ReferenceFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::WGS84 => write!(f, "WGS84"),
+            Self::ITRF(year) => write!(f, "ITRF{}", year),
+        }
+    }
+}
+
+impl std::str::FromStr for ReferenceFrame {
+    type Err = Error;
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let frame = content.trim().to_uppercase();
+        if frame.eq("WGS84") || frame.eq("WGS-84") {
+            return Ok(Self::WGS84);
+        }
+        let year = if let Some(year) = frame.strip_prefix("ITRF") {
+            year
+        } else if let Some(year) = frame.strip_prefix("IGS") {
+            year
+        } else if let Some(year) = frame.strip_prefix("IGB") {
+            year
+        } else {
+            return Err(Error::UnknownFrame(content.to_string()));
+        };
+        let value = year
+            .parse::<u16>()
+            .map_err(|_| Error::UnknownFrame(content.to_string()))?;
+        match (year.len(), value) {
+            (2, 88..=99) => Ok(Self::ITRF(1900 + value)),
+            (2, _) => Ok(Self::ITRF(2000 + value)),
+            (4, _) => Ok(Self::ITRF(value)),
+            _ => Err(Error::UnknownFrame(content.to_string())),
+        }
+    }
+}
+
 #[derive(Default, Copy, Debug, Clone, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct GroundPosition(f64, f64, f64);
+pub struct GroundPosition(f64, f64, f64, ReferenceFrame);
 
 impl From<(f64, f64, f64)> for GroundPosition {
     fn from(xyz: (f64, f64, f64)) -> Self {
-        Self(xyz.0, xyz.1, xyz.2)
+        Self(xyz.0, xyz.1, xyz.2, ReferenceFrame::default())
     }
 }
 
@@ -26,13 +89,24 @@ impl From<GroundPosition> for (f64, f64, f64) {
 impl GroundPosition {
     /// Builds Self from ECEF WGS84 coordinates
     pub fn from_ecef_wgs84(pos: (f64, f64, f64)) -> Self {
-        Self(pos.0, pos.1, pos.2)
+        Self(pos.0, pos.1, pos.2, ReferenceFrame::WGS84)
     }
     /// Builds Self from Geodetic coordinates in ddeg
     pub fn from_geodetic(pos: (f64, f64, f64)) -> Self {
         let (x, y, z) = pos;
         let (x, y, z) = geodetic2ecef(deg2rad(x), deg2rad(y), deg2rad(z), Ellipsoid::WGS84);
-        Self(x, y, z)
+        Self(x, y, z, ReferenceFrame::WGS84)
+    }
+    /// Tags Self with the [ReferenceFrame] these coordinates are expressed in.
+    /// Coordinates are not transformed.
+    pub fn with_reference_frame(&self, frame: ReferenceFrame) -> Self {
+        let mut s = *self;
+        s.3 = frame;
+        s
+    }
+    /// Returns the [ReferenceFrame] these coordinates are expressed in
+    pub fn reference_frame(&self) -> ReferenceFrame {
+        self.3
     }
     /// Converts Self to ECEF WGS84
     pub fn to_ecef_wgs84(&self) -> (f64, f64, f64) {
@@ -52,7 +126,7 @@ impl GroundPosition {
 
 impl std::fmt::Display for GroundPosition {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "WGS84 ({}m {}m {}m)", self.0, self.1, self.2)
+        write!(f, "{} ({}m {}m {}m)", self.3, self.0, self.1, self.2)
     }
 }
 
@@ -61,7 +135,13 @@ impl std::fmt::Display for GroundPosition {
  */
 impl std::fmt::UpperHex for GroundPosition {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{:14.4}{:14.4}{:14.4}", self.0, self.1, self.2)
+        write!(
+            f,
+            "{}{}{}",
+            fmt_fixed(self.0, 14, 4),
+            fmt_fixed(self.1, 14, 4),
+            fmt_fixed(self.2, 14, 4)
+        )
     }
 }
 
@@ -74,7 +154,7 @@ impl Render for GroundPosition {
             table {
                 tr {
                     th {
-                        "ECEF (WGS84)"
+                        (format!("ECEF ({})", self.3))
                     }
                 }
                 tr {
@@ -1,6 +1,6 @@
 //! RINEX decompression module
 use super::{numdiff::NumDiff, textdiff::TextDiff, Error};
-use crate::{is_rinex_comment, prelude::*};
+use crate::{formatting::fmt_fixed, is_rinex_comment, prelude::*};
 
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -64,7 +64,10 @@ fn format_epoch(
                 // fits in a single line
                 result.push_str(systems);
                 if let Some(value) = clock_offset {
-                    result.push_str(&format!("  {:3.9}", (value as f64) / 1000.0_f64))
+                    // F12.9 field follows the 12 vehicle slots
+                    let padding = 36_usize.saturating_sub(systems.len());
+                    result.push_str(&" ".repeat(padding));
+                    result.push_str(&fmt_fixed(value as f64 / 1000.0, 12, 9));
                 }
             } else {
                 // does not fit in a single line
@@ -75,7 +78,7 @@ fn format_epoch(
                         if i == 12 {
                             // first line,
                             if let Some(value) = clock_offset {
-                                result.push_str(&format!("  {:3.9}", (value as f64) / 1000.0_f64))
+                                result.push_str(&fmt_fixed(value as f64 / 1000.0, 12, 9));
                             }
                         }
                         // tab indent
@@ -100,9 +103,12 @@ fn format_epoch(
             }
             let (epoch, _) = content.split_at(35);
             result.push_str(&epoch.replace('&', " "));
-            //TODO clock offset
+            // 6X + F15.12
             if let Some(value) = clock_offset {
-                result.push_str(&format!("         {:3.12}", (value as f64) / 1000.0_f64))
+                result.push_str(&format!(
+                    "      {}",
+                    fmt_fixed(value as f64 / 1000.0, 15, 12)
+                ))
             }
         },
     }
@@ -520,8 +526,8 @@ impl Decompressor {
                                     // store them
                                     .to_string();
                                 result.push_str(&format!(
-                                    "{:13.3}{}{} ",
-                                    *data as f64 / 1000_f64,
+                                    "{}{}{} ",
+                                    fmt_fixed(*data as f64 / 1000.0, 13, 3),
                                     lli,
                                     ssi
                                 )); //F14.3
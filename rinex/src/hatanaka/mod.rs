@@ -10,6 +10,7 @@ pub mod decompressor;
 pub use decompressor::Decompressor;
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("I/O error")]
     IoError(#[from] std::io::Error),
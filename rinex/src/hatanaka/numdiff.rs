@@ -2,6 +2,7 @@ use std::collections::VecDeque;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("maximal compression order is 7")]
     MaximalCompressionOrder,
@@ -2,11 +2,16 @@
 use crate::{
     antex, clock,
     clock::ClockProfileType,
-    clock::WorkClock,
-    doris::{Error as DorisError, HeaderFields as DorisHeader, Station as DorisStation},
-    epoch::parse_ionex_utc as parse_ionex_utc_epoch,
+    clock::{SolutionStation, WorkClock},
+    comment_lines,
+    doris::{
+        Error as DorisError, HeaderFields as DorisHeader, Satellite as DorisSatellite,
+        Station as DorisStation,
+    },
+    epoch::{epoch_decompose, parse_ionex_utc as parse_ionex_utc_epoch},
     fmt_comment, fmt_rinex,
-    ground_position::GroundPosition,
+    formatting::fmt_fixed,
+    ground_position::{GroundPosition, ReferenceFrame},
     hardware::{Antenna, Receiver, SvAntenna},
     ionex,
     leap::{Error as LeapParsingError, Leap},
@@ -23,9 +28,10 @@ use crate::{
     observation,
     observation::{Crinex, HeaderFields as ObservationHeader},
     prelude::{Constellation, Duration, Epoch, TimeScale, COSPAR, DOMES, SV},
-    reader::BufferedReader,
+    reader::{BufferedReader, Limit, LimitExceeded},
     types::Type,
     version::Version,
+    ErrorKind,
 };
 
 use std::collections::HashMap;
@@ -157,6 +163,7 @@ pub struct Header {
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ParsingError {
     #[error("failed to parse version from \"{0}\"")]
     VersionParsing(String),
@@ -198,6 +205,29 @@ pub enum ParsingError {
     DorisError(#[from] DorisError),
     #[error("failed to parse cospar number")]
     CosparParsing(#[from] CosparParsingError),
+    #[error("{which} limit exceeded ({limit})")]
+    LimitExceeded { which: Limit, limit: u64 },
+}
+
+impl From<LimitExceeded> for ParsingError {
+    fn from(e: LimitExceeded) -> Self {
+        Self::LimitExceeded {
+            which: e.which,
+            limit: e.limit,
+        }
+    }
+}
+
+impl ParsingError {
+    /// Returns the [ErrorKind] of this [ParsingError]
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::AntennaReceiverError(e) => ErrorKind::from_io_error(e),
+            Self::VersionNotSupported(_) | Self::TypeParsing(_) => ErrorKind::Unsupported,
+            Self::LimitExceeded { .. } => ErrorKind::LimitExceeded,
+            _ => ErrorKind::MalformedHeader,
+        }
+    }
 }
 
 fn parse_formatted_month(content: &str) -> Result<u8, ParsingError> {
@@ -278,7 +308,10 @@ impl Header {
         // iterate on a line basis
         let lines = reader.lines();
         for l in lines {
-            let line = l.unwrap();
+            let line = l.map_err(|e| match LimitExceeded::from_io_error(&e) {
+                Some(limit) => ParsingError::from(limit),
+                None => ParsingError::from(e),
+            })?;
             if line.len() < 60 {
                 continue; // --> invalid header content
             }
@@ -593,7 +626,18 @@ impl Header {
                 }
             } else if marker.contains("SENSOR MOD/TYPE/ACC") {
                 if let Ok(sensor) = meteo::sensor::Sensor::from_str(content) {
-                    meteo.sensors.push(sensor)
+                    // sensor may already be located, when position came first
+                    if let Some(located) = meteo
+                        .sensors
+                        .iter_mut()
+                        .find(|s| s.observable == sensor.observable)
+                    {
+                        located.model = sensor.model;
+                        located.sensor_type = sensor.sensor_type;
+                        located.accuracy = sensor.accuracy;
+                    } else {
+                        meteo.sensors.push(sensor)
+                    }
                 }
             } else if marker.contains("SENSOR POS XYZ/H") {
                 /*
@@ -631,11 +675,20 @@ impl Header {
                     h.to_string(),
                 )))?;
 
-                for sensor in meteo.sensors.iter_mut() {
-                    if sensor.observable == observable {
-                        *sensor = sensor.with_position(GroundPosition::from_ecef_wgs84((x, y, z)));
-                        *sensor = sensor.with_height(h);
-                    }
+                let position = GroundPosition::from_ecef_wgs84((x, y, z));
+                if let Some(sensor) = meteo
+                    .sensors
+                    .iter_mut()
+                    .find(|s| s.observable == observable)
+                {
+                    *sensor = sensor.with_position(position).with_height(h);
+                } else {
+                    // described prior its model: latch position
+                    meteo.sensors.push(
+                        meteo::sensor::Sensor::new(observable)
+                            .with_position(position)
+                            .with_height(h),
+                    );
                 }
             } else if marker.contains("LEAP SECOND") {
                 let leap_str = content.split_at(40).0.trim();
@@ -643,17 +696,17 @@ impl Header {
                     leap = Some(lleap)
                 }
             } else if marker.contains("DOI") {
-                let (content, _) = content.split_at(40); //  TODO: confirm please
+                // V4: A60 digital object identifier
                 doi = Some(content.trim().to_string())
             } else if marker.contains("MERGED FILE") {
                 //TODO V > 3
                 // nb# of merged files
             } else if marker.contains("STATION INFORMATION") {
-                let url = content.split_at(40).0; //TODO confirm please
-                station_url = url.trim().to_string()
+                // V4: A60 URL
+                station_url = content.trim().to_string()
             } else if marker.contains("LICENSE OF USE") {
-                let lic = content.split_at(40).0; //TODO confirm please
-                license = Some(lic.trim().to_string())
+                // V4: A60 license
+                license = Some(content.trim().to_string())
             } else if marker.contains("WAVELENGTH FACT L1/2") {
                 //TODO
             } else if marker.contains("APPROX POSITION XYZ") {
@@ -852,6 +905,25 @@ impl Header {
                 }
             } else if marker.contains("STATION CLK REF") {
                 clock = clock.refclock(content.trim());
+            } else if marker.contains("# OF SOLN STA / TRF") {
+                let frame = content.get(10..).unwrap_or("");
+                if let Some(frame) = frame.split_ascii_whitespace().next() {
+                    if let Ok(frame) = ReferenceFrame::from_str(frame) {
+                        clock = clock.frame(frame);
+                    }
+                }
+            } else if marker.contains("SOLN STA NAME / NUM") {
+                if let Some(station) = SolutionStation::parse(version, &line) {
+                    clock = clock.solution_station(station);
+                }
+            } else if marker.contains("PRN LIST") {
+                // V3.04 lists 16 vehicles, overflowing the 60 column content
+                let list = line.split("PRN LIST").next().unwrap_or("");
+                let sv = list
+                    .split_ascii_whitespace()
+                    .filter_map(|sv| SV::from_str(sv).ok())
+                    .collect::<Vec<_>>();
+                clock = clock.solution_satellites(&sv);
             } else if marker.contains("SIGNAL STRENGHT UNIT") {
                 //TODO
             } else if marker.contains("INTERVAL") {
@@ -865,6 +937,9 @@ impl Header {
                 }
             } else if marker.contains("COSPAR NUMBER") {
                 cospar = Some(COSPAR::from_str(content.trim())?);
+            } else if marker.contains("SATELLITE NAME") {
+                // DORIS special case
+                doris.satellite = Some(DorisSatellite::new(content.trim()));
             } else if marker.contains("GLONASS SLOT / FRQ #") {
                 //TODO
                 // This should be used when dealing with Glonass carriers
@@ -1053,9 +1128,13 @@ impl Header {
                 if let Ok(f) = f32::from_str(content.trim()) {
                     ionex = ionex.with_base_radius(f);
                 }
-            } else if marker.contains("MAPPING FUCTION") {
-                if let Ok(mf) = ionex::MappingFunction::from_str(content.trim()) {
-                    ionex = ionex.with_mapping_function(mf);
+            } else if marker.contains("MAPPING FUNCTION") {
+                // NONE: no mapping function, e.g. altimetry
+                let content = content.trim();
+                if !content.is_empty() && content != "NONE" {
+                    if let Ok(mf) = ionex::MappingFunction::from_str(content) {
+                        ionex = ionex.with_mapping_function(mf);
+                    }
                 }
             } else if marker.contains("# OF STATIONS") {
                 // IONEX
@@ -1088,8 +1167,15 @@ impl Header {
                 let grid = Self::parse_grid(content)?;
                 ionex = ionex.with_longitude_grid(grid);
             } else if marker.contains("PRN / BIAS / RMS") {
-                // differential PR code analysis
-                //TODO
+                // IONEX DCBs: vehicle biases
+                if let Some((src, bias, rms)) = Self::parse_ionex_sv_dcb(content, &ionex) {
+                    ionex = ionex.with_dcb(src, (bias, rms));
+                }
+            } else if marker.contains("STATION / BIAS / RMS") {
+                // IONEX DCBs: ground station biases
+                if let Some((src, bias, rms)) = Self::parse_ionex_station_dcb(content) {
+                    ionex = ionex.with_dcb(src, (bias, rms));
+                }
             } else if marker.contains("L2 / L1 DATE OFFSET") {
                 // DORIS special case
                 let content = content[1..].trim();
@@ -1107,6 +1193,18 @@ impl Header {
             }
         }
 
+        // tag ground position with the declared frame
+        if let Some(frame) = clock.frame {
+            if let Some(position) = &mut ground_position {
+                *position = position.with_reference_frame(frame);
+            }
+        }
+
+        // DORIS: COSPAR number identifies the host satellite
+        if let Some(satellite) = &mut doris.satellite {
+            satellite.cospar = cospar.clone();
+        }
+
         Ok(Header {
             version,
             rinex_type,
@@ -1189,6 +1287,13 @@ impl Header {
         }
     }
 
+    /// Returns the terrestrial [ReferenceFrame] declared by this file,
+    /// which is only the case of Clock RINEX ("# OF SOLN STA / TRF").
+    /// None when the file does not declare its frame.
+    pub fn reference_frame(&self) -> Option<ReferenceFrame> {
+        self.clock.as_ref().and_then(|clk| clk.frame)
+    }
+
     /// Creates a Basic Header structure
     /// for Mixed Constellation Navigation RINEX
     pub fn basic_nav() -> Self {
@@ -1268,10 +1373,19 @@ impl Header {
         s
     }
 
-    /// adds comments to Self
+    /// Replaces comments of Self. Multi-line and lengthy comments
+    /// are split into valid comment lines (60 characters at most).
     pub fn with_comments(&self, c: Vec<String>) -> Self {
         let mut s = self.clone();
-        s.comments = c.clone();
+        s.comments = c.iter().flat_map(|c| comment_lines(c)).collect();
+        s
+    }
+
+    /// Adds one comment to Self. Multi-line and lengthy comments
+    /// are split into valid comment lines (60 characters at most).
+    pub fn with_comment(&self, c: &str) -> Self {
+        let mut s = self.clone();
+        s.comments.extend(comment_lines(c));
         s
     }
 
@@ -1417,14 +1531,63 @@ impl Header {
                     f,
                     "{}",
                     fmt_rinex(
-                        &format!("{:6}.{:02}           CLOCK DATA", major, minor),
+                        &format!(
+                            "{:6}.{:02}           CLOCK DATA          {}",
+                            major,
+                            minor,
+                            self.constellation
+                                .map(|c| format!("{:x}", c))
+                                .unwrap_or_default()
+                        ),
+                        "RINEX VERSION / TYPE"
+                    )
+                )
+            },
+            Type::DORIS => {
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(
+                        &format!("{:6}.{:02}           O                   D", major, minor),
                         "RINEX VERSION / TYPE"
                     )
                 )
             },
-            Type::DORIS => todo!("doris formatting"),
-            Type::AntennaData => todo!("antex formatting"),
-            Type::IonosphereMaps => todo!("ionex formatting"),
+            Type::AntennaData => {
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(
+                        &format!(
+                            "{:6}.{}            {:x}",
+                            major,
+                            minor,
+                            self.constellation.unwrap_or(Constellation::Mixed)
+                        ),
+                        "ANTEX VERSION / SYST"
+                    )
+                )
+            },
+            Type::IonosphereMaps => {
+                let system = match self.ionex.as_ref().map(|ionex| &ionex.reference) {
+                    Some(ionex::RefSystem::GnssConstellation(Constellation::Mixed)) | None => {
+                        "GNSS".to_string()
+                    },
+                    Some(ionex::RefSystem::GnssConstellation(c)) => format!("{:X}", c),
+                    Some(system) => system.to_string(),
+                };
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(
+                        &format!(
+                            "{:6}.{}            IONOSPHERE MAPS     {}",
+                            major, minor, system
+                        ),
+                        "IONEX VERSION / TYPE"
+                    )
+                )
+            },
         }
     }
     /*
@@ -1437,12 +1600,39 @@ impl Header {
             Type::NavigationData => Ok(()),
             Type::ClockData => self.fmt_clock_rinex(f),
             Type::IonosphereMaps => self.fmt_ionex(f),
-            Type::AntennaData => Ok(()), // FIXME
-            Type::DORIS => Ok(()),       // FIXME
+            Type::AntennaData => self.fmt_antex(f),
+            Type::DORIS => self.fmt_doris_rinex(f),
+        }
+    }
+    /*
+     * ANTEX fields formatting
+     */
+    fn fmt_antex(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Some(antex) = &self.antex {
+            let (pcv, rel_type) = match &antex.pcv_type {
+                antex::Pcv::Absolute => ("A", ""),
+                antex::Pcv::Relative(rel_type) => ("R", rel_type.as_str()),
+            };
+            writeln!(
+                f,
+                "{}",
+                fmt_rinex(
+                    &format!(
+                        "{:<20}{:<20}{}",
+                        pcv,
+                        rel_type,
+                        antex.reference_ant_sn.as_deref().unwrap_or("")
+                    ),
+                    "PCV TYPE / REFANT"
+                )
+            )?;
         }
+        Ok(())
     }
     /*
-     * Clock Data fields formatting
+     * Clock Data fields formatting.
+     * Solution stations and satellites are not retained when parsing,
+     * so they are not listed.
      */
     fn fmt_clock_rinex(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         if let Some(clock) = &self.clock {
@@ -1465,25 +1655,283 @@ impl Header {
                     fmt_rinex(&format!("   {:x}", ts), "TIME SYSTEM ID")
                 )?;
             }
-            // TODO: missing fields
-            //if let Some(agency) = &clock.agency {
-            //    writeln!(
-            //        f,
-            //        "{}",
-            //        fmt_rinex(
-            //            &format!("{:<5} {}", agency.code, agency.name),
-            //            "ANALYSIS CENTER"
-            //        )
-            //    )?;
-            //}
+            // station
+            if let Some(site) = &clock.site {
+                let domes = clock
+                    .domes
+                    .as_ref()
+                    .map(|d| d.to_string())
+                    .unwrap_or_default();
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(&format!("{:<4} {}", site, domes), "STATION NAME / NUM")
+                )?;
+            }
+            if let Some(ref_clock) = &clock.ref_clock {
+                writeln!(f, "{}", fmt_rinex(ref_clock, "STATION CLK REF"))?;
+            }
+            // analysis center
+            if clock.igs.is_some() || clock.full_name.is_some() {
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(
+                        &format!(
+                            "{:<3}  {}",
+                            clock.igs.as_deref().unwrap_or(""),
+                            clock.full_name.as_deref().unwrap_or("")
+                        ),
+                        "ANALYSIS CENTER"
+                    )
+                )?;
+            }
+            // reference clocks
+            if !clock.work_clock.is_empty() {
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(&format!("{:6}", clock.work_clock.len()), "# OF CLK REF")
+                )?;
+                for work_clock in clock.work_clock.iter() {
+                    writeln!(
+                        f,
+                        "{}",
+                        fmt_rinex(&work_clock.format(self.version), "ANALYSIS CLK REF")
+                    )?;
+                }
+            }
+            // V3.04 widens some lines to 65 columns
+            let fmt_wide = |content: &str, marker: &str| {
+                if self.version < Version::new(3, 4) {
+                    fmt_rinex(content, marker)
+                } else {
+                    format!("{:<65}{}", content, marker)
+                }
+            };
+            // solution stations & frame
+            if clock.frame.is_some() || !clock.solution_stations.is_empty() {
+                let frame = clock
+                    .frame
+                    .as_ref()
+                    .map(|frame| frame.to_string())
+                    .unwrap_or_default();
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(
+                        &format!("{:6}    {}", clock.solution_stations.len(), frame),
+                        "# OF SOLN STA / TRF"
+                    )
+                )?;
+                for station in clock.solution_stations.iter() {
+                    writeln!(
+                        f,
+                        "{}",
+                        fmt_wide(&station.format(self.version), "SOLN STA NAME / NUM")
+                    )?;
+                }
+            }
+            // solution satellites
+            if !clock.solution_satellites.is_empty() {
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(
+                        &format!("{:6}", clock.solution_satellites.len()),
+                        "# OF SOLN SATS"
+                    )
+                )?;
+                let nb_per_line = if self.version < Version::new(3, 4) {
+                    15
+                } else {
+                    16
+                };
+                for chunk in clock.solution_satellites.chunks(nb_per_line) {
+                    let list = chunk
+                        .iter()
+                        .map(|sv| format!("{:x}", sv))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    writeln!(f, "{}", fmt_wide(&list, "PRN LIST"))?;
+                }
+            }
+        }
+        Ok(())
+    }
+    /*
+     * DORIS fields formatting
+     */
+    fn fmt_doris_rinex(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Some(doris) = &self.doris {
+            // DORIS meteo observables are described by a single letter
+            let descriptor = |observable: &Observable| match observable {
+                Observable::Pressure => "P".to_string(),
+                Observable::Temperature => "T".to_string(),
+                Observable::HumidityRate => "H".to_string(),
+                observable => observable.to_string(),
+            };
+            if let Some(satellite) = &doris.satellite {
+                writeln!(f, "{}", fmt_rinex(&satellite.name, "SATELLITE NAME"))?;
+            }
+            if let Some(cospar) = &self.cospar {
+                writeln!(f, "{}", fmt_rinex(&cospar.to_string(), "COSPAR NUMBER"))?;
+            }
+            // observables
+            for (i, observables) in doris.observables.chunks(13).enumerate() {
+                let mut line = if i == 0 {
+                    format!("D{:5}", doris.observables.len())
+                } else {
+                    "      ".to_string()
+                };
+                for observable in observables {
+                    line.push_str(&format!("{:>4}", descriptor(observable)));
+                }
+                writeln!(f, "{}", fmt_rinex(&line, "SYS / # / OBS TYPES"))?;
+            }
+            // time of first and last measurements, in TAI
+            for (e, marker) in [
+                (doris.time_of_first_obs, "TIME OF FIRST OBS"),
+                (doris.time_of_last_obs, "TIME OF LAST OBS"),
+            ] {
+                if let Some(e) = e {
+                    let (y, m, d, hh, mm, ss, nanos) = epoch_decompose(e);
+                    writeln!(
+                        f,
+                        "{}",
+                        fmt_rinex(
+                            &format!(
+                                "  {:04}    {:02}    {:02}    {:02}    {:02}   {:02}.{:07}     DOR",
+                                y,
+                                m,
+                                d,
+                                hh,
+                                mm,
+                                ss,
+                                nanos / 100
+                            ),
+                            marker
+                        )
+                    )?;
+                }
+            }
+            // scaling, grouped by factor
+            let mut factors = Vec::<u16>::new();
+            for observable in doris.observables.iter() {
+                if let Some(factor) = doris.scaling.get(observable) {
+                    if !factors.contains(factor) {
+                        factors.push(*factor);
+                    }
+                }
+            }
+            for factor in factors {
+                let observables = doris
+                    .observables
+                    .iter()
+                    .filter(|observable| doris.scaling.get(*observable) == Some(&factor))
+                    .collect::<Vec<_>>();
+                let mut line = format!("D {:4}  {:2}", factor, observables.len());
+                for observable in observables {
+                    line.push_str(&format!("{:>4}", descriptor(observable)));
+                }
+                writeln!(f, "{}", fmt_rinex(&line, "SYS / SCALE FACTOR"))?;
+            }
+            writeln!(
+                f,
+                "{}",
+                fmt_rinex(
+                    &format!(
+                        "D{}",
+                        fmt_fixed(doris.l2_l1_date_offset.to_microseconds(), 16, 3)
+                    ),
+                    "L2 / L1 DATE OFFSET"
+                )
+            )?;
+            // stations
+            writeln!(
+                f,
+                "{}",
+                fmt_rinex(&format!("{:6}", doris.stations.len()), "# OF STATIONS")
+            )?;
+            for station in doris.stations.iter() {
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(&station.to_string(), "STATION REFERENCE")
+                )?;
+            }
         }
         Ok(())
     }
     /*
      * IONEX fields formatting
      */
-    fn fmt_ionex(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    pub(crate) fn fmt_ionex(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         if let Some(ionex) = &self.ionex {
+            if let Some(desc) = &ionex.description {
+                writeln!(f, "{}", fmt_rinex(desc, "DESCRIPTION"))?;
+            }
+            for (t, marker) in [
+                (ionex.epoch_of_first_map, "EPOCH OF FIRST MAP"),
+                (ionex.epoch_of_last_map, "EPOCH OF LAST MAP"),
+            ] {
+                let (y, m, d, hh, mm, ss, _) = t.to_gregorian_utc();
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(
+                        &format!("{:6}{:6}{:6}{:6}{:6}{:6}", y, m, d, hh, mm, ss),
+                        marker
+                    )
+                )?;
+            }
+            // mapping func
+            if let Some(func) = &ionex.mapping {
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(&format!("  {}", func.token()), "MAPPING FUNCTION")
+                )?;
+            } else {
+                writeln!(f, "{}", fmt_rinex("  NONE", "MAPPING FUNCTION"))?;
+            }
+            // elevation cutoff
+            writeln!(
+                f,
+                "{}",
+                fmt_rinex(
+                    &fmt_fixed(ionex.elevation_cutoff as f64, 8, 1),
+                    "ELEVATION CUTOFF"
+                )
+            )?;
+            // observables
+            writeln!(
+                f,
+                "{}",
+                fmt_rinex(
+                    ionex.observables.as_deref().unwrap_or(""),
+                    "OBSERVABLES USED"
+                )
+            )?;
+            if ionex.nb_stations > 0 {
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(&format!("{:6}", ionex.nb_stations), "# OF STATIONS")
+                )?;
+            }
+            if ionex.nb_satellites > 0 {
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex(&format!("{:6}", ionex.nb_satellites), "# OF SATELLITES")
+                )?;
+            }
+            writeln!(
+                f,
+                "{}",
+                fmt_rinex(&fmt_fixed(ionex.base_radius as f64, 8, 1), "BASE RADIUS")
+            )?;
             writeln!(
                 f,
                 "{}",
@@ -1531,26 +1979,66 @@ impl Header {
                     "LON1 / LON2 / DLON"
                 )
             )?;
-            // elevation cutoff
             writeln!(
                 f,
                 "{}",
-                fmt_rinex(&format!("{}", ionex.elevation_cutoff), "ELEVATION CUTOFF")
+                fmt_rinex(&format!("{:6}", ionex.exponent), "EXPONENT")
             )?;
-            // mapping func
-            if let Some(func) = &ionex.mapping {
+            // DCBs
+            if !ionex.dcbs.is_empty() {
+                let mut svs = Vec::<(SV, (f64, f64))>::new();
+                let mut stations = Vec::<(&String, (f64, f64))>::new();
+                for (src, dcb) in ionex.dcbs.iter() {
+                    match src {
+                        ionex::BiasSource::SpaceVehicle(sv) => svs.push((*sv, *dcb)),
+                        ionex::BiasSource::Station(station) => stations.push((station, *dcb)),
+                    }
+                }
+                svs.sort_by(|(a, _), (b, _)| a.cmp(b));
+                stations.sort_by(|(a, _), (b, _)| a.cmp(b));
                 writeln!(
                     f,
                     "{}",
-                    fmt_rinex(&format!("{:?}", func), "MAPPING FUNCTION")
+                    fmt_rinex("DIFFERENTIAL CODE BIASES", "START OF AUX DATA")
+                )?;
+                for (sv, (bias, rms)) in svs {
+                    writeln!(
+                        f,
+                        "{}",
+                        fmt_rinex(
+                            &format!(
+                                "   {:x}{:02}{}{}",
+                                sv.constellation,
+                                sv.prn,
+                                fmt_fixed(bias, 10, 3),
+                                fmt_fixed(rms, 10, 3)
+                            ),
+                            "PRN / BIAS / RMS"
+                        )
+                    )?;
+                }
+                for (station, (bias, rms)) in stations {
+                    writeln!(
+                        f,
+                        "{}",
+                        fmt_rinex(
+                            &format!(
+                                "      {:<4}{:16}{}{}",
+                                station,
+                                "",
+                                fmt_fixed(bias, 10, 3),
+                                fmt_fixed(rms, 10, 3)
+                            ),
+                            "STATION / BIAS / RMS"
+                        )
+                    )?;
+                }
+                writeln!(
+                    f,
+                    "{}",
+                    fmt_rinex("DIFFERENTIAL CODE BIASES", "END OF AUX DATA")
                 )?;
-            } else {
-                writeln!(f, "{}", fmt_rinex("NONE", "MAPPING FUNCTION"))?;
             }
-            // time of first map
-            writeln!(f, "{}", fmt_rinex("TODO", "EPOCH OF FIRST MAP"))?;
-            // time of last map
-            writeln!(f, "{}", fmt_rinex("TODO", "EPOCH OF LAST MAP"))?;
         }
         Ok(())
     }
@@ -1664,6 +2152,54 @@ impl Header {
         }
         Ok(())
     }
+    /*
+     * Parse IONEX vehicle DCB: "   G01    -7.516     0.007".
+     * A blank constellation refers to the IONEX system.
+     */
+    fn parse_ionex_sv_dcb(
+        content: &str,
+        ionex: &ionex::HeaderFields,
+    ) -> Option<(ionex::BiasSource, f64, f64)> {
+        if content.len() < 6 {
+            return None;
+        }
+        let (sys, rem) = content.split_at(3);
+        let (sys, rem) = rem.split_at(1);
+        let (prn, rem) = rem.split_at(2);
+        let constellation = if sys.trim().is_empty() {
+            match ionex.reference {
+                ionex::RefSystem::GnssConstellation(c) if c != Constellation::Mixed => c,
+                _ => Constellation::GPS,
+            }
+        } else {
+            Constellation::from_str(sys).ok()?
+        };
+        let prn = prn.trim().parse::<u8>().ok()?;
+        let mut items = rem.split_whitespace();
+        let bias = items.next()?.parse::<f64>().ok()?;
+        let rms = items.next()?.parse::<f64>().ok()?;
+        Some((
+            ionex::BiasSource::SpaceVehicle(SV::new(constellation, prn)),
+            bias,
+            rms,
+        ))
+    }
+    /*
+     * Parse IONEX station DCB: "   G  AJAC 10077M005      25.095     0.011"
+     */
+    fn parse_ionex_station_dcb(content: &str) -> Option<(ionex::BiasSource, f64, f64)> {
+        if content.len() < 10 {
+            return None;
+        }
+        let station = content[6..10].trim();
+        if station.is_empty() {
+            return None;
+        }
+        let mut items = content[10..].split_whitespace().rev();
+        let rms = items.next()?.parse::<f64>().ok()?;
+        let bias = items.next()?.parse::<f64>().ok()?;
+        Some((ionex::BiasSource::Station(station.to_string()), bias, rms))
+    }
     /*
      * Parse IONEX grid
      */
@@ -1892,6 +2428,12 @@ impl std::fmt::Display for Header {
         self.fmt_rinex_version_type(f)?;
         self.fmt_comments(f)?;
 
+        // ANTEX does not describe anything else
+        if self.rinex_type == Type::AntennaData {
+            self.fmt_rinex_dependent(f)?;
+            return writeln!(f, "{}", fmt_rinex("", "END OF HEADER"));
+        }
+
         // PGM / RUN BY / DATE
         writeln!(
             f,
@@ -1912,6 +2454,19 @@ impl std::fmt::Display for Header {
             )
         )?;
 
+        // V4 identification fields
+        if self.version.major > 3 {
+            if let Some(doi) = &self.doi {
+                writeln!(f, "{}", fmt_rinex(doi, "DOI"))?;
+            }
+            if let Some(license) = &self.license {
+                writeln!(f, "{}", fmt_rinex(license, "LICENSE OF USE"))?;
+            }
+            if !self.station_url.is_empty() {
+                writeln!(f, "{}", fmt_rinex(&self.station_url, "STATION INFORMATION"))?;
+            }
+        }
+
         if let Some(marker) = &self.geodetic_marker {
             writeln!(f, "{}", fmt_rinex(&marker.name, "MARKER NAME"))?;
             if let Some(number) = marker.number() {
@@ -1943,7 +2498,12 @@ impl std::fmt::Display for Header {
                     f,
                     "{}",
                     fmt_rinex(
-                        &format!("{:14.4}{:14.4}{:14.4}", coords.0, coords.1, coords.2),
+                        &format!(
+                            "{}{}{}",
+                            fmt_fixed(coords.0, 14, 4),
+                            fmt_fixed(coords.1, 14, 4),
+                            fmt_fixed(coords.2, 14, 4)
+                        ),
                         "APPROX POSITION XYZ"
                     )
                 )?;
@@ -1953,10 +2513,10 @@ impl std::fmt::Display for Header {
                 "{}",
                 fmt_rinex(
                     &format!(
-                        "{:14.4}{:14.4}{:14.4}",
-                        antenna.height.unwrap_or(0.0),
-                        antenna.eastern.unwrap_or(0.0),
-                        antenna.northern.unwrap_or(0.0)
+                        "{}{}{}",
+                        fmt_fixed(antenna.height.unwrap_or(0.0), 14, 4),
+                        fmt_fixed(antenna.eastern.unwrap_or(0.0), 14, 4),
+                        fmt_fixed(antenna.northern.unwrap_or(0.0), 14, 4)
                     ),
                     "ANTENNA: DELTA H/E/N"
                 )
@@ -2016,37 +2576,96 @@ impl std::fmt::Display for Header {
 }
 
 impl Header {
+    /// Special marker used when marking Self as Merged file
+    const MERGE_MARKER: &'static str = "FILE MERGE";
     /*
-     * Macro to be used when marking Self as Merged file
+     * Comment to be used when marking Self as Merged file.
+     * Standardized as "rustrnx-$VERSION FILE MERGE #$SEQ $ISO_EPOCH $TIMESCALE",
+     * where $ISO_EPOCH is the first epoch contributed by the merged file.
      */
-    fn merge_comment(timestamp: Epoch) -> String {
-        let (y, m, d, hh, mm, ss, _) = timestamp.to_gregorian_utc();
+    fn merge_comment(seq: usize, boundary: Epoch) -> String {
         format!(
-            "rustrnx-{:<11} FILE MERGE          {}{}{} {}{}{} {:x}",
-            env!("CARGO_PKG_VERSION"),
-            y,
-            m,
-            d,
-            hh,
-            mm,
-            ss,
-            timestamp.time_scale
+            "rustrnx-{:<6} {} #{} {}",
+            env!("CARGO_PKG_VERSION")
+                .split('-')
+                .next()
+                .unwrap_or_default(),
+            Self::MERGE_MARKER,
+            seq,
+            boundary.round(Duration::from_seconds(1.0)),
         )
     }
-}
-
-impl Merge for Header {
-    /// Merges `rhs` into `Self` without mutable access, at the expense of memcopies
-    fn merge(&self, rhs: &Self) -> Result<Self, MergeError> {
-        if self.rinex_type != rhs.rinex_type {
-            return Err(MergeError::FileTypeMismatch);
+    /*
+     * Parses epoch described in a FILE MERGE comment.
+     * Supports our own format and the compact "YYYYMMDD HHMMSS TS" format
+     * emitted by gfzrnx and teqc-like tools.
+     */
+    fn parse_merge_comment(comment: &str) -> Option<Epoch> {
+        let offset = comment.find(Self::MERGE_MARKER)?;
+        let content = comment[offset + Self::MERGE_MARKER.len()..].trim();
+        // drop our sequence number, if any
+        let content = match content.strip_prefix('#') {
+            Some(content) => content.split_once(' ').map(|(_, rem)| rem.trim())?,
+            None => content,
+        };
+        if let Ok(epoch) = Epoch::from_str(content) {
+            return Some(epoch);
         }
-        let mut lhs = self.clone();
-        lhs.merge_mut(rhs)?;
-        Ok(lhs)
+        let mut items = content.split_ascii_whitespace();
+        let (date, time) = (items.next()?, items.next()?);
+        if date.len() != 8 || time.len() != 6 {
+            return None;
+        }
+        let ts = match items.next() {
+            Some(ts) => TimeScale::from_str(ts).ok()?,
+            None => TimeScale::UTC,
+        };
+        Some(Epoch::from_gregorian(
+            date[0..4].parse::<i32>().ok()?,
+            date[4..6].parse::<u8>().ok()?,
+            date[6..8].parse::<u8>().ok()?,
+            time[0..2].parse::<u8>().ok()?,
+            time[2..4].parse::<u8>().ok()?,
+            time[4..6].parse::<u8>().ok()?,
+            0,
+            ts,
+        ))
     }
-    /// Merges `rhs` into `Self` in place
-    fn merge_mut(&mut self, rhs: &Self) -> Result<(), MergeError> {
+    /// Returns number of `FILE MERGE` comments contained in Self
+    pub(crate) fn merge_comments(&self) -> usize {
+        self.comments
+            .iter()
+            .filter(|c| c.contains(Self::MERGE_MARKER))
+            .count()
+    }
+    /// Returns all merge boundaries (seams) described in Self, in chronological order.
+    /// Supported `FILE MERGE` comments are:
+    ///  - our own format: `rustrnx-0.17.0 FILE MERGE #1 2021-01-01T12:00:00 GPST`
+    ///  - gfzrnx (and teqc-like tools) format: `gfzrnx-1.15-8044    FILE MERGE          20210101 120000 UTC`.
+    ///
+    /// NB: third party tools tend to stamp the date of the merge operation itself,
+    /// not the first epoch of the merged file.
+    pub fn merge_boundaries(&self) -> Vec<Epoch> {
+        let mut boundaries = self
+            .comments
+            .iter()
+            .filter_map(|c| Self::parse_merge_comment(c))
+            .collect::<Vec<_>>();
+        boundaries.sort();
+        boundaries.dedup();
+        boundaries
+    }
+    /*
+     * Marks Self as merged, at given boundary Epoch
+     */
+    pub(crate) fn push_merge_comment(&mut self, boundary: Epoch) {
+        let seq = self.merge_comments() + 1;
+        self.comments.push(Self::merge_comment(seq, boundary));
+    }
+    /*
+     * Merges all fields of rhs into Self, but does not mark Self as merged
+     */
+    pub(crate) fn merge_fields_mut(&mut self, rhs: &Self) -> Result<(), MergeError> {
         if self.rinex_type != rhs.rinex_type {
             return Err(MergeError::FileTypeMismatch);
         }
@@ -2165,6 +2784,7 @@ impl Merge for Header {
             if let Some(rhs) = &rhs.doris {
                 merge_time_of_first_obs(&mut lhs.time_of_first_obs, &rhs.time_of_first_obs);
                 merge_time_of_last_obs(&mut lhs.time_of_last_obs, &rhs.time_of_last_obs);
+                merge_mut_option(&mut lhs.satellite, &rhs.satellite);
                 merge_mut_unique_vec(&mut lhs.stations, &rhs.stations);
                 merge_mut_unique_vec(&mut lhs.observables, &rhs.observables);
                 //TODO: merge_scaling();
@@ -2204,10 +2824,34 @@ impl Merge for Header {
                 }
             }
         }
-        // add special comment
-        let now = Epoch::now()?;
-        let merge_comment = Self::merge_comment(now);
-        self.comments.push(merge_comment);
+        Ok(())
+    }
+}
+
+impl Merge for Header {
+    /// Merges `rhs` into `Self` without mutable access, at the expense of memcopies
+    fn merge(&self, rhs: &Self) -> Result<Self, MergeError> {
+        if self.rinex_type != rhs.rinex_type {
+            return Err(MergeError::FileTypeMismatch);
+        }
+        let mut lhs = self.clone();
+        lhs.merge_mut(rhs)?;
+        Ok(lhs)
+    }
+    /// Merges `rhs` into `Self` in place
+    fn merge_mut(&mut self, rhs: &Self) -> Result<(), MergeError> {
+        self.merge_fields_mut(rhs)?;
+        // boundary: first epoch contributed by rhs, when known
+        let boundary = match (&rhs.obs, &rhs.doris) {
+            (Some(obs), _) if obs.time_of_first_obs.is_some() => obs.time_of_first_obs,
+            (_, Some(doris)) if doris.time_of_first_obs.is_some() => doris.time_of_first_obs,
+            _ => None,
+        };
+        let boundary = match boundary {
+            Some(boundary) => boundary,
+            None => Epoch::now()?,
+        };
+        self.push_merge_comment(boundary);
         Ok(())
     }
 }
@@ -33,6 +33,20 @@ pub enum MappingFunction {
     /// Q-factor
     #[strum(serialize = "QFAC")]
     QFac,
+    /// Other mapping function, described by its header token
+    #[strum(default)]
+    Other(String),
+}
+
+impl MappingFunction {
+    /// Returns the token describing this [MappingFunction] in IONEX headers
+    pub fn token(&self) -> &str {
+        match self {
+            Self::CosZ => "COSZ",
+            Self::QFac => "QFAC",
+            Self::Other(token) => token,
+        }
+    }
 }
 
 impl std::fmt::Display for MappingFunction {
@@ -40,6 +54,7 @@ impl std::fmt::Display for MappingFunction {
         match self {
             Self::CosZ => write!(f, "Cos(z)"),
             Self::QFac => write!(f, "Q-factor"),
+            Self::Other(token) => write!(f, "{}", token),
         }
     }
 }
@@ -172,7 +187,7 @@ impl HeaderFields {
     /// Returns true if this Ionosphere Maps describes
     /// a theoretical model, not measured data
     pub fn is_theoretical_model(&self) -> bool {
-        self.observables.is_some()
+        self.observables.is_none()
     }
     /// Copies self and set number of stations
     pub fn with_nb_stations(&self, n: u32) -> Self {
@@ -301,6 +316,14 @@ mod test {
         assert_eq!(func.unwrap(), MappingFunction::QFac);
         let content = "DONT";
         let func = MappingFunction::from_str(content);
-        assert!(func.is_err());
+        assert!(func.is_ok());
+        assert_eq!(func.unwrap(), MappingFunction::Other("DONT".to_string()));
+        for func in [
+            MappingFunction::CosZ,
+            MappingFunction::QFac,
+            MappingFunction::Other("MSLM".to_string()),
+        ] {
+            assert_eq!(MappingFunction::from_str(func.token()).unwrap(), func);
+        }
     }
 }
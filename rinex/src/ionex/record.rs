@@ -1,14 +1,18 @@
-use crate::{merge, merge::Merge, prelude::Duration, prelude::*, split, split::Split};
+use crate::{
+    ionex::Grid, linspace::Linspace, merge, merge::Merge, prelude::Duration, prelude::*, split,
+    split::Split,
+};
 
-use crate::epoch;
-use std::collections::{BTreeMap, HashMap};
+use crate::{epoch, fmt_rinex, formatting::fmt_fixed};
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use thiserror::Error;
 
 #[cfg(feature = "processing")]
-use qc_traits::processing::{
-    DecimationFilter, DecimationFilterType, FilterItem, MaskFilter, MaskOperand,
-};
+use qc_traits::processing::{DecimationFilter, FilterItem, MaskFilter, MaskOperand};
+
+#[cfg(feature = "processing")]
+use crate::record::decimated_epochs;
 
 pub(crate) fn is_new_tec_plane(line: &str) -> bool {
     line.contains("START OF TEC MAP")
@@ -18,12 +22,69 @@ pub(crate) fn is_new_rms_plane(line: &str) -> bool {
     line.contains("START OF RMS MAP")
 }
 
-/*
- * Don't know what Height maps are actually
- */
-// pub(crate) fn is_new_height_map(line: &str) -> bool {
-//     line.contains("START OF HEIGHT MAP")
-// }
+pub(crate) fn is_new_height_plane(line: &str) -> bool {
+    line.contains("START OF HEIGHT MAP")
+}
+
+/// Kind of map being described
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+enum MapKind {
+    /// TEC map
+    #[default]
+    Tec,
+    /// RMS(tec) map
+    Rms,
+    /// Height map
+    Height,
+}
+
+impl MapKind {
+    fn from_marker(marker: &str) -> Self {
+        if is_new_rms_plane(marker) {
+            Self::Rms
+        } else if is_new_height_plane(marker) {
+            Self::Height
+        } else {
+            Self::Tec
+        }
+    }
+    /// Map name, as used in START/END OF MAP markers
+    fn marker(&self) -> &str {
+        match self {
+            Self::Tec => "TEC",
+            Self::Rms => "RMS",
+            Self::Height => "HEIGHT",
+        }
+    }
+    /// Value this map describes, in given [TEC] structure
+    fn value(&self, tec: &TEC) -> Option<f64> {
+        match self {
+            Self::Tec => Some(tec.tec),
+            Self::Rms => tec.rms,
+            Self::Height => tec.height,
+        }
+    }
+    /// Wraps this value into a [TEC] structure
+    fn tec(&self, value: f64) -> TEC {
+        match self {
+            Self::Tec => TEC {
+                tec: value,
+                rms: None,
+                height: None,
+            },
+            Self::Rms => TEC {
+                tec: 0.0_f64, // DONT CARE
+                rms: Some(value),
+                height: None,
+            },
+            Self::Height => TEC {
+                tec: 0.0_f64, // DONT CARE
+                rms: None,
+                height: Some(value),
+            },
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -32,9 +93,204 @@ pub struct TEC {
     pub tec: f64,
     /// RMS(tec)
     pub rms: Option<f64>,
+    /// Height of this TEC value, expressed in km, when provided by
+    /// an additional height map.
+    pub height: Option<f64>,
+}
+
+/// [TECPlane] is one map (at a given altitude and epoch) sampled
+/// over the IONEX grid. Storage is row-major: one row per latitude
+/// and one column per longitude, following the grid definition order.
+/// Cells not described by the file, or flagged as missing (9999), are empty.
+/// ```
+/// use rinex::prelude::*;
+/// let rnx = Rinex::from_file("../test_resources/IONEX/V1/CKMG0020.22I.gz")
+///     .unwrap();
+/// for (_, _, plane) in rnx.tec_planes() {
+///     // latitude rows, longitude columns
+///     let (nlat, nlon) = plane.shape();
+///     assert_eq!((nlat, nlon), (71, 73));
+///     let matrix = plane.as_matrix();
+///     assert_eq!(matrix.len(), nlat);
+///     assert_eq!(matrix[0].len(), nlon);
+///     // ready to be plotted as a heatmap,
+///     // coordinates are obtained with
+///     let (lat, lon) = (plane.latitude(0), plane.longitude(0));
+///     assert_eq!((lat, lon), (87.5, -180.0));
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TECPlane {
+    /// first latitude, in millidegrees
+    lat0: i32,
+    /// latitude increment, in millidegrees
+    dlat: i32,
+    /// first longitude, in millidegrees
+    lon0: i32,
+    /// longitude increment, in millidegrees
+    dlon: i32,
+    /// number of rows (latitudes)
+    nlat: usize,
+    /// number of columns (longitudes)
+    nlon: usize,
+    /// row-major cells
+    cells: Vec<Option<TEC>>,
 }
 
-pub type TECPlane = HashMap<(i32, i32), TEC>;
+/*
+ * Grid axis as (start, spacing) in millidegrees, and number of points
+ */
+fn millidegrees(linspace: &Linspace) -> (i32, i32, usize) {
+    (
+        (linspace.start * 1000.0).round() as i32,
+        (linspace.spacing * 1000.0).round() as i32,
+        linspace.length(),
+    )
+}
+
+impl TECPlane {
+    /// Builds an empty [TECPlane] covering given [Grid]
+    pub(crate) fn new(grid: &Grid) -> Self {
+        let (lat0, dlat, nlat) = millidegrees(&grid.latitude);
+        let (lon0, dlon, nlon) = millidegrees(&grid.longitude);
+        Self {
+            lat0,
+            dlat,
+            lon0,
+            dlon,
+            nlat,
+            nlon,
+            cells: vec![None; nlat * nlon],
+        }
+    }
+    /// Returns plane dimensions as (rows, columns),
+    /// that is (number of latitudes, number of longitudes)
+    pub fn shape(&self) -> (usize, usize) {
+        (self.nlat, self.nlon)
+    }
+    /// Returns number of cells actually described
+    pub fn len(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.is_some()).count()
+    }
+    /// Returns true if no cells are described
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns latitude of given row, in ddeg
+    pub fn latitude(&self, row: usize) -> f64 {
+        (self.lat0 + row as i32 * self.dlat) as f64 / 1000.0
+    }
+    /// Returns longitude of given column, in ddeg
+    pub fn longitude(&self, col: usize) -> f64 {
+        (self.lon0 + col as i32 * self.dlon) as f64 / 1000.0
+    }
+    /// Returns [TEC] at given (row, column), if described
+    pub fn cell(&self, row: usize, col: usize) -> Option<&TEC> {
+        if row < self.nlat && col < self.nlon {
+            self.cells[row * self.nlon + col].as_ref()
+        } else {
+            None
+        }
+    }
+    /// Returns [TEC] at given coordinates (in ddeg), if they match
+    /// a grid point and it is described.
+    pub fn get(&self, latitude: f64, longitude: f64) -> Option<&TEC> {
+        let index = self.index(
+            (latitude * 1000.0).round() as i32,
+            (longitude * 1000.0).round() as i32,
+        )?;
+        self.cells[index].as_ref()
+    }
+    /// Iterates over described cells, in row-major order,
+    /// as ((row, column), [TEC])
+    pub fn iter(&self) -> Box<dyn Iterator<Item = ((usize, usize), &TEC)> + '_> {
+        Box::new(self.cells.iter().enumerate().filter_map(|(index, cell)| {
+            cell.as_ref()
+                .map(|tec| ((index / self.nlon, index % self.nlon), tec))
+        }))
+    }
+    /// Returns TEC values as a row-major matrix (one row per latitude).
+    /// Cells that are missing in the file are set to None.
+    pub fn as_matrix(&self) -> Vec<Vec<Option<f64>>> {
+        (0..self.nlat)
+            .map(|row| {
+                (0..self.nlon)
+                    .map(|col| self.cell(row, col).map(|tec| tec.tec))
+                    .collect()
+            })
+            .collect()
+    }
+    /*
+     * Cell index of given coordinates, in millidegrees
+     */
+    fn index(&self, latitude: i32, longitude: i32) -> Option<usize> {
+        let row = Self::axis_index(self.lat0, self.dlat, self.nlat, latitude)?;
+        let col = Self::axis_index(self.lon0, self.dlon, self.nlon, longitude)?;
+        Some(row * self.nlon + col)
+    }
+    fn axis_index(start: i32, spacing: i32, length: usize, value: i32) -> Option<usize> {
+        let offset = value - start;
+        if spacing == 0 {
+            return if offset == 0 { Some(0) } else { None };
+        }
+        if offset % spacing != 0 {
+            return None;
+        }
+        let index = offset / spacing;
+        if index >= 0 && (index as usize) < length {
+            Some(index as usize)
+        } else {
+            None
+        }
+    }
+    /*
+     * Coordinates of given cell, in millidegrees
+     */
+    fn coordinates(&self, row: usize, col: usize) -> (i32, i32) {
+        (
+            self.lat0 + row as i32 * self.dlat,
+            self.lon0 + col as i32 * self.dlon,
+        )
+    }
+    /*
+     * Mutable access to given cell, coordinates expressed in millidegrees.
+     * Returns None for coordinates out of grid.
+     */
+    fn cell_mut(&mut self, latitude: i32, longitude: i32) -> Option<&mut Option<TEC>> {
+        let index = self.index(latitude, longitude)?;
+        self.cells.get_mut(index)
+    }
+    /*
+     * Stores given value, coordinates expressed in millidegrees.
+     * Values out of grid are dropped.
+     */
+    fn insert(&mut self, latitude: i32, longitude: i32, tec: TEC) {
+        if let Some(cell) = self.cell_mut(latitude, longitude) {
+            *cell = Some(tec);
+        }
+    }
+    /*
+     * Retains cells whose coordinates (in ddeg) pass the test
+     */
+    #[cfg(feature = "processing")]
+    pub(crate) fn retain<F: Fn(f64, f64) -> bool>(&mut self, f: F) {
+        for row in 0..self.nlat {
+            for col in 0..self.nlon {
+                if !f(self.latitude(row), self.longitude(col)) {
+                    self.cells[row * self.nlon + col] = None;
+                }
+            }
+        }
+    }
+    /*
+     * Iterates over described cells, with their coordinates in millidegrees
+     */
+    fn iter_coordinates(&self) -> impl Iterator<Item = ((i32, i32), &TEC)> + '_ {
+        self.iter()
+            .map(|((row, col), tec)| (self.coordinates(row, col), tec))
+    }
+}
 
 /// IONEX contains 2D (fixed altitude) or 3D Ionosphere Maps.
 /// See [Rinex::ionex] and related feature for more information.
@@ -63,6 +319,7 @@ pub type TECPlane = HashMap<(i32, i32), TEC>;
 pub type Record = BTreeMap<(Epoch, i32), TECPlane>;
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     #[error("failed to parse map index from \"{0}\"")]
     MapIndexParsing(String),
@@ -76,17 +333,6 @@ pub enum Error {
     EpochParsing(#[from] epoch::ParsingError),
 }
 
-/*
- * Merges `rhs` into `lhs`
- */
-fn merge_plane_mut(lhs: &mut TECPlane, rhs: &TECPlane) {
-    for (coord, tec) in rhs {
-        if lhs.get(coord).is_none() {
-            lhs.insert(*coord, tec.clone());
-        }
-    }
-}
-
 /*
  * Parses following map, which can either be
  *  - a TEC map
@@ -97,11 +343,10 @@ fn merge_plane_mut(lhs: &mut TECPlane, rhs: &TECPlane) {
 pub(crate) fn parse_plane(
     content: &str,
     header: &mut Header,
-    is_rms_plane: bool,
 ) -> Result<(Epoch, i32, TECPlane), Error> {
     let lines = content.lines();
     let mut epoch = Epoch::default();
-    let mut plane = TECPlane::with_capacity(128);
+    let mut kind = MapKind::default();
 
     // this can't fail at this point
     let ionex = header
@@ -109,17 +354,19 @@ pub(crate) fn parse_plane(
         .as_mut()
         .expect("faulty ionex context: missing specific header definitions");
 
+    let mut plane = TECPlane::new(&ionex.grid);
+
     // current {lat, lon} within current grid def.
     let mut latitude = 0_i32;
     let mut longitude = 0_i32;
     let mut altitude = 0_i32;
-    let mut dlon = (ionex.grid.longitude.spacing * 1000.0) as i32;
+    let mut dlon = (ionex.grid.longitude.spacing * 1000.0).round() as i32;
 
     for line in lines {
         if line.len() > 60 {
             let (content, marker) = line.split_at(60);
             if marker.contains("START OF") {
-                continue; // skip that one
+                kind = MapKind::from_marker(marker);
             } else if marker.contains("END OF") && marker.contains("MAP") {
                 let index = content.split_at(6).0;
                 let index = index.trim();
@@ -167,10 +414,10 @@ pub(crate) fn parse_plane(
                     h.to_string(),
                 )))?;
 
-                altitude = (alt.round() * 100.0_f64) as i32;
-                latitude = (lat.round() * 1000.0_f64) as i32;
-                longitude = (lon1.round() * 1000.0_f64) as i32;
-                dlon = (dlon_f64.round() * 1000.0_f64) as i32;
+                altitude = (alt * 100.0_f64).round() as i32;
+                latitude = (lat * 1000.0_f64).round() as i32;
+                longitude = (lon1 * 1000.0_f64).round() as i32;
+                dlon = (dlon_f64 * 1000.0_f64).round() as i32;
 
                 // debug
                 // println!("NEW GRID : h: {} lat : {} lon : {}, dlon: {}", altitude, latitude, longitude, dlon);
@@ -185,24 +432,14 @@ pub(crate) fn parse_plane(
                 // parsing TEC values
                 for item in line.split_ascii_whitespace() {
                     if let Ok(v) = item.trim().parse::<i32>() {
-                        let mut value = v as f64;
-                        // current scaling
-                        value *= 10.0_f64.powf(ionex.exponent as f64);
+                        // 9999: value is missing
+                        if v != 9999 {
+                            let mut value = v as f64;
+                            // current scaling
+                            value *= 10.0_f64.powf(ionex.exponent as f64);
 
-                        let tec = match is_rms_plane {
-                            true => {
-                                TEC {
-                                    tec: 0.0_f64, // DONT CARE
-                                    rms: Some(value),
-                                }
-                            },
-                            false => TEC {
-                                tec: value,
-                                rms: None,
-                            },
-                        };
-
-                        plane.insert((latitude, longitude), tec);
+                            plane.insert(latitude, longitude, kind.tec(value));
+                        }
                     }
 
                     longitude += dlon;
@@ -215,24 +452,14 @@ pub(crate) fn parse_plane(
             // parsing TEC values
             for item in line.split_ascii_whitespace() {
                 if let Ok(v) = item.trim().parse::<i32>() {
-                    let mut value = v as f64;
-                    // current scaling
-                    value *= 10.0_f64.powf(ionex.exponent as f64);
-
-                    let tec = match is_rms_plane {
-                        true => {
-                            TEC {
-                                tec: 0.0_f64, // DONT CARE
-                                rms: Some(value),
-                            }
-                        },
-                        false => TEC {
-                            tec: value,
-                            rms: None,
-                        },
-                    };
-
-                    plane.insert((latitude, longitude), tec);
+                    // 9999: value is missing
+                    if v != 9999 {
+                        let mut value = v as f64;
+                        // current scaling
+                        value *= 10.0_f64.powf(ionex.exponent as f64);
+
+                        plane.insert(latitude, longitude, kind.tec(value));
+                    }
                 }
 
                 longitude += dlon;
@@ -244,6 +471,124 @@ pub(crate) fn parse_plane(
     Ok((epoch, altitude, plane))
 }
 
+/*
+ * Inserts a freshly parsed plane into the record.
+ * TEC, RMS and Height maps describing the same (epoch, altitude)
+ * are combined, on a per coordinates basis.
+ */
+pub(crate) fn insert_plane(rec: &mut Record, epoch: Epoch, altitude: i32, plane: TECPlane) {
+    if let Some(rec_plane) = rec.get_mut(&(epoch, altitude)) {
+        for (coords, tec) in plane.iter_coordinates() {
+            if let Some(cell) = rec_plane.cell_mut(coords.0, coords.1) {
+                if let Some(rec_tec) = cell {
+                    if tec.rms.is_some() {
+                        rec_tec.rms = tec.rms;
+                    } else if tec.height.is_some() {
+                        rec_tec.height = tec.height;
+                    } else {
+                        rec_tec.tec = tec.tec;
+                    }
+                } else {
+                    *cell = Some(tec.clone());
+                }
+            }
+        }
+    } else {
+        rec.insert((epoch, altitude), plane);
+    }
+}
+
+/*
+ * Formats one map of given kind, describing all altitudes of this epoch.
+ * Values are scaled down by 10^exponent, missing values are set to 9999.
+ */
+fn fmt_map(
+    kind: MapKind,
+    index: usize,
+    epoch: Epoch,
+    planes: &[(i32, &TECPlane)],
+    exponent: i8,
+) -> String {
+    let marker = kind.marker();
+    let scaling = 10.0_f64.powf(exponent as f64);
+    let mut lines = Vec::<String>::new();
+    lines.push(fmt_rinex(
+        &format!("{:6}", index),
+        &format!("START OF {} MAP", marker),
+    ));
+    let (y, m, d, hh, mm, ss, _) = epoch.to_gregorian_utc();
+    lines.push(fmt_rinex(
+        &format!("{:6}{:6}{:6}{:6}{:6}{:6}", y, m, d, hh, mm, ss),
+        "EPOCH OF CURRENT MAP",
+    ));
+    for (altitude, plane) in planes {
+        let (nlat, nlon) = plane.shape();
+        for row in 0..nlat {
+            lines.push(fmt_rinex(
+                &format!(
+                    "  {}{}{}{}{}",
+                    fmt_fixed(plane.latitude(row), 6, 1),
+                    fmt_fixed(plane.longitude(0), 6, 1),
+                    fmt_fixed(plane.longitude(nlon.saturating_sub(1)), 6, 1),
+                    fmt_fixed(plane.dlon as f64 / 1000.0, 6, 1),
+                    fmt_fixed(*altitude as f64 / 100.0, 6, 1),
+                ),
+                "LAT/LON1/LON2/DLON/H",
+            ));
+            let values = (0..nlon)
+                .map(|col| {
+                    plane
+                        .cell(row, col)
+                        .and_then(|tec| kind.value(tec))
+                        .map(|value| (value / scaling).round() as i32)
+                        .unwrap_or(9999)
+                })
+                .collect::<Vec<_>>();
+            for chunk in values.chunks(16) {
+                lines.push(chunk.iter().map(|value| format!("{:5}", value)).collect());
+            }
+        }
+    }
+    lines.push(fmt_rinex(
+        &format!("{:6}", index),
+        &format!("END OF {} MAP", marker),
+    ));
+    lines.join("\n") + "\n"
+}
+
+/*
+ * Formats the entire record: all TEC maps are described first,
+ * followed by RMS maps and Height maps, when those are provided.
+ */
+pub(crate) fn fmt_record(rec: &Record, header: &Header) -> String {
+    let exponent = header
+        .ionex
+        .as_ref()
+        .map(|ionex| ionex.exponent)
+        .unwrap_or(-1);
+
+    let mut maps = BTreeMap::<Epoch, Vec<(i32, &TECPlane)>>::new();
+    for ((epoch, altitude), plane) in rec.iter() {
+        maps.entry(*epoch).or_default().push((*altitude, plane));
+    }
+
+    let mut content = String::new();
+    for kind in [MapKind::Tec, MapKind::Rms, MapKind::Height] {
+        let mut index = 0;
+        for (epoch, planes) in maps.iter() {
+            let described = planes
+                .iter()
+                .any(|(_, plane)| plane.iter().any(|(_, tec)| kind.value(tec).is_some()));
+            if kind != MapKind::Tec && !described {
+                continue;
+            }
+            index += 1;
+            content.push_str(&fmt_map(kind, index, *epoch, planes, exponent));
+        }
+    }
+    content
+}
+
 impl Merge for Record {
     /// Merges `rhs` into `Self` without mutable access at the expense of more memcopies
     fn merge(&self, rhs: &Self) -> Result<Self, merge::Error> {
@@ -255,15 +600,22 @@ impl Merge for Record {
     fn merge_mut(&mut self, rhs: &Self) -> Result<(), merge::Error> {
         for (eh, plane) in rhs {
             if let Some(lhs_plane) = self.get_mut(eh) {
-                for (latlon, plane) in plane {
-                    if let Some(tec) = lhs_plane.get_mut(latlon) {
-                        if let Some(rms) = plane.rms {
-                            if tec.rms.is_none() {
-                                tec.rms = Some(rms);
+                for (coords, plane) in plane.iter_coordinates() {
+                    if let Some(cell) = lhs_plane.cell_mut(coords.0, coords.1) {
+                        if let Some(tec) = cell {
+                            if let Some(rms) = plane.rms {
+                                if tec.rms.is_none() {
+                                    tec.rms = Some(rms);
+                                }
                             }
+                            if let Some(height) = plane.height {
+                                if tec.height.is_none() {
+                                    tec.height = Some(height);
+                                }
+                            }
+                        } else {
+                            *cell = Some(plane.clone());
                         }
-                    } else {
-                        lhs_plane.insert(*latlon, plane.clone());
                     }
                 }
             } else {
@@ -298,36 +650,59 @@ impl Split for Record {
             .collect();
         Ok((before, after))
     }
-    fn split_dt(&self, _duration: Duration) -> Result<Vec<Self>, split::Error> {
-        Ok(Vec::new())
+    fn split_dt(&self, duration: Duration) -> Result<Vec<Self>, split::Error> {
+        Ok(split::split_dt(self, duration, |(e, _)| *e))
     }
 }
 
+/*
+ * Crops all planes spatially, removes planes that end up empty
+ */
+#[cfg(feature = "processing")]
+fn ionex_crop_mut<F: Fn(f64, f64) -> bool>(rec: &mut Record, f: F) {
+    rec.retain(|_, plane| {
+        plane.retain(&f);
+        !plane.is_empty()
+    });
+}
+
 #[cfg(feature = "processing")]
 pub(crate) fn ionex_mask_mut(rec: &mut Record, mask: &MaskFilter) {
     match mask.operand {
         MaskOperand::Equals => match mask.item {
             FilterItem::EpochItem(epoch) => rec.retain(|(e, _), _| *e == epoch),
+            FilterItem::LatitudeItem(lat) => ionex_crop_mut(rec, |lat_i, _| lat_i == lat),
+            FilterItem::LongitudeItem(lon) => ionex_crop_mut(rec, |_, lon_i| lon_i == lon),
             _ => {}, // FilterItem:: does not apply
         },
         MaskOperand::NotEquals => match mask.item {
             FilterItem::EpochItem(epoch) => rec.retain(|(e, _), _| *e != epoch),
+            FilterItem::LatitudeItem(lat) => ionex_crop_mut(rec, |lat_i, _| lat_i != lat),
+            FilterItem::LongitudeItem(lon) => ionex_crop_mut(rec, |_, lon_i| lon_i != lon),
             _ => {}, // FilterItem:: does not apply
         },
         MaskOperand::GreaterEquals => match mask.item {
             FilterItem::EpochItem(epoch) => rec.retain(|(e, _), _| *e >= epoch),
+            FilterItem::LatitudeItem(lat) => ionex_crop_mut(rec, |lat_i, _| lat_i >= lat),
+            FilterItem::LongitudeItem(lon) => ionex_crop_mut(rec, |_, lon_i| lon_i >= lon),
             _ => {}, // FilterItem:: does not apply
         },
         MaskOperand::GreaterThan => match mask.item {
             FilterItem::EpochItem(epoch) => rec.retain(|(e, _), _| *e > epoch),
+            FilterItem::LatitudeItem(lat) => ionex_crop_mut(rec, |lat_i, _| lat_i > lat),
+            FilterItem::LongitudeItem(lon) => ionex_crop_mut(rec, |_, lon_i| lon_i > lon),
             _ => {}, // FilterItem:: does not apply
         },
         MaskOperand::LowerEquals => match mask.item {
             FilterItem::EpochItem(epoch) => rec.retain(|(e, _), _| *e <= epoch),
+            FilterItem::LatitudeItem(lat) => ionex_crop_mut(rec, |lat_i, _| lat_i <= lat),
+            FilterItem::LongitudeItem(lon) => ionex_crop_mut(rec, |_, lon_i| lon_i <= lon),
             _ => {}, // FilterItem:: does not apply
         },
         MaskOperand::LowerThan => match mask.item {
             FilterItem::EpochItem(epoch) => rec.retain(|(e, _), _| *e < epoch),
+            FilterItem::LatitudeItem(lat) => ionex_crop_mut(rec, |lat_i, _| lat_i < lat),
+            FilterItem::LongitudeItem(lon) => ionex_crop_mut(rec, |_, lon_i| lon_i < lon),
             _ => {}, // FilterItem:: does not apply
         },
     }
@@ -336,35 +711,12 @@ pub(crate) fn ionex_mask_mut(rec: &mut Record, mask: &MaskFilter) {
 #[cfg(feature = "processing")]
 pub(crate) fn ionex_decim_mut(rec: &mut Record, f: &DecimationFilter) {
     if f.item.is_some() {
-        todo!("targetted decimation not supported yet");
-    }
-    match f.filter {
-        DecimationFilterType::Modulo(r) => {
-            let mut i = 0;
-            rec.retain(|_, _| {
-                let retained = (i % r) == 0;
-                i += 1;
-                retained
-            });
-        },
-        DecimationFilterType::Duration(interval) => {
-            let mut last_retained = Option::<Epoch>::None;
-            rec.retain(|(e, _), _| {
-                if let Some(last) = last_retained {
-                    let dt = *e - last;
-                    if dt >= interval {
-                        last_retained = Some(*e);
-                        true
-                    } else {
-                        false
-                    }
-                } else {
-                    last_retained = Some(*e);
-                    true // always retain 1st epoch
-                }
-            });
-        },
+        // TEC maps are the only content: no subset can be targetted
+        return;
     }
+    // 3D maps: all planes of one epoch are either retained or dropped
+    let retained = decimated_epochs(rec.keys().map(|(e, _)| *e), &f.filter);
+    rec.retain(|(e, _), _| retained.contains(e));
 }
 
 #[cfg(test)]
@@ -381,12 +733,12 @@ mod test {
         assert!(is_new_rms_plane(
             "1                                                      START OF RMS MAP"
         ));
-        // assert_eq!(
-        //     is_new_height_map(
-        //         "1                                                      START OF HEIGHT MAP"
-        //     ),
-        //     true
-        // );
+        assert!(is_new_height_plane(
+            "1                                                      START OF HEIGHT MAP"
+        ));
+        assert!(!is_new_rms_plane(
+            "1                                                      START OF HEIGHT MAP"
+        ));
     }
     //#[test]
     //fn test_merge_map2d() {
@@ -6,6 +6,7 @@ use gnss::prelude::Constellation;
 
 /// Reference System parsing error
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("unknown reference system")]
     UnknownRefSystem,
@@ -54,7 +55,12 @@ pub enum ObsSystem {
 
 impl std::fmt::Display for ObsSystem {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.write_str(&self.to_string())
+        match self {
+            Self::BENt => f.write_str("BEN"),
+            Self::ENVisat => f.write_str("ENV"),
+            Self::ERS => f.write_str("ERS"),
+            Self::IRI => f.write_str("IRI"),
+        }
     }
 }
 
@@ -75,7 +81,11 @@ pub enum Model {
 
 impl std::fmt::Display for Model {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.write_str(&self.to_string())
+        match self {
+            Self::MIX => f.write_str("MIX"),
+            Self::NNS => f.write_str("NNS"),
+            Self::TOP => f.write_str("TOP"),
+        }
     }
 }
 
@@ -122,5 +132,13 @@ mod test {
             default,
             RefSystem::GnssConstellation(Constellation::default())
         );
+        for code in ["BEN", "ENV", "ERS", "IRI"] {
+            let system = ObsSystem::from_str(code).unwrap();
+            assert_eq!(system.to_string(), code);
+        }
+        for code in ["MIX", "NNS", "TOP"] {
+            let model = Model::from_str(code).unwrap();
+            assert_eq!(model.to_string(), code);
+        }
     }
 }
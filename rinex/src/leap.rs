@@ -23,6 +23,7 @@ pub struct Leap {
 
 /// `Leap` parsing related errors
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("failed to parse leap integer number")]
     ParseIntError(#[from] std::num::ParseIntError),
@@ -5,7 +5,6 @@
 
 extern crate gnss_rs as gnss;
 
-#[cfg(feature = "qc")]
 extern crate rinex_qc_traits as qc_traits;
 
 pub mod antex;
@@ -13,6 +12,7 @@ pub mod carrier;
 pub mod clock;
 pub mod doris;
 pub mod epoch;
+pub mod fileops;
 pub mod gnss_time;
 pub mod hardware;
 pub mod hatanaka;
@@ -28,8 +28,17 @@ pub mod split;
 pub mod types;
 pub mod version;
 
+#[cfg(feature = "binex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "binex")))]
+mod binex;
+
+#[cfg(all(feature = "obs", feature = "nav"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "obs", feature = "nav"))))]
+pub mod dd;
+
 mod bibliography;
 mod constants;
+mod formatting; // fixed width numerical formatting
 mod ground_position;
 mod leap; // leap second
 mod linspace; // grid and linear spacing
@@ -51,11 +60,14 @@ extern crate num_derive;
 extern crate lazy_static;
 
 pub mod reader;
-use reader::BufferedReader;
+use reader::{BufferedReader, Limit, ParsingOptions};
 
 pub mod writer;
 use writer::BufferedWriter;
 
+pub mod progress;
+use progress::ProgressHandle;
+
 use std::collections::{BTreeMap, HashMap};
 use std::io::Write; //, Read};
 use std::path::Path;
@@ -65,12 +77,13 @@ use itertools::Itertools;
 use thiserror::Error;
 
 use antex::{Antenna, AntennaSpecific, FrequencyDependentData};
-use doris::record::ObservationData as DorisObservationData;
+use doris::record::{ClockOffset as DorisClockOffset, ObservationData as DorisObservationData};
+
 use epoch::epoch_decompose;
 use ionex::TECPlane;
 use navigation::NavFrame;
-use observable::Observable;
-use observation::{Crinex, ObservationData};
+use observable::{remap_observables_map_mut, remap_observables_vec_mut, Observable};
+use observation::{Crinex, ObservationData, ObservationStream};
 use version::Version;
 
 use production::{DataSource, DetailedProductionAttributes, ProductionAttributes, FFU, PPU};
@@ -85,14 +98,19 @@ pub mod prelude {
     #[cfg(feature = "obs")]
     pub use crate::carrier::Carrier;
     #[cfg(feature = "clock")]
-    pub use crate::clock::{ClockKey, ClockProfile, ClockProfileType, ClockType, WorkClock};
+    pub use crate::clock::{
+        ClockKey, ClockProfile, ClockProfileType, ClockType, SolutionStation, WorkClock,
+    };
     pub use crate::doris::Station;
-    pub use crate::ground_position::GroundPosition;
+    pub use crate::ground_position::{GroundPosition, ReferenceFrame};
     pub use crate::header::Header;
     pub use crate::observable::Observable;
     pub use crate::observation::EpochFlag;
+    pub use crate::progress::{Progress, ProgressHandle};
+    pub use crate::reader::ParsingOptions;
+    pub use crate::record::ParseWarning;
     pub use crate::types::Type as RinexType;
-    pub use crate::{Error, Rinex};
+    pub use crate::{Error, ErrorKind, Rinex};
     // pub re-export
     #[cfg(feature = "nav")]
     pub use anise::{
@@ -113,7 +131,7 @@ pub mod prelude {
 /// Package dedicated to file production.
 pub mod prod {
     pub use crate::production::{
-        DataSource, DetailedProductionAttributes, ProductionAttributes, FFU, PPU,
+        Compression, DataSource, DetailedProductionAttributes, ProductionAttributes, FFU, PPU,
     };
 }
 
@@ -177,11 +195,50 @@ pub(crate) fn fmt_rinex(content: &str, marker: &str) -> String {
     }
 }
 
+/*
+ * Splits given content into valid comment lines: one per embedded line,
+ * wrapped at 60 characters. Tabulations and control characters are replaced
+ * by whitespaces, non ASCII characters by '?', so the column alignment is preserved.
+ */
+pub(crate) fn comment_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let line = line
+            .chars()
+            .map(|c| {
+                if c.is_ascii_control() {
+                    ' '
+                } else if c.is_ascii() {
+                    c
+                } else {
+                    '?'
+                }
+            })
+            .collect::<String>();
+        let line = line.trim_end();
+        if line.is_empty() {
+            lines.push(String::new());
+        } else {
+            // ASCII only: byte chunks are char chunks
+            for chunk in line.as_bytes().chunks(60) {
+                lines.push(String::from_utf8_lossy(chunk).to_string());
+            }
+        }
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
 /*
  * macro to generate comments with standardized formatting
  */
 pub(crate) fn fmt_comment(content: &str) -> String {
-    fmt_rinex(content, "COMMENT")
+    comment_lines(content)
+        .iter()
+        .map(|line| fmt_rinex(line, "COMMENT"))
+        .join("\n")
 }
 
 #[derive(Clone, Default, Debug, PartialEq)]
@@ -265,6 +322,7 @@ pub struct Rinex {
 
 #[derive(Error, Debug)]
 /// `RINEX` Parsing related errors
+#[non_exhaustive]
 pub enum Error {
     #[error("header parsing error")]
     HeaderParsingError(#[from] header::ParsingError),
@@ -272,6 +330,49 @@ pub enum Error {
     RecordError(#[from] record::Error),
     #[error("file i/o error")]
     IoError(#[from] std::io::Error),
+    #[error("{which} limit exceeded ({limit})")]
+    LimitExceeded { which: Limit, limit: u64 },
+    #[error("observable \"{0}\" already exists")]
+    ObservableCollision(Observable),
+    #[error("observable \"{0}\" can't be described in RINEX2")]
+    NonRepresentableObservable(Observable),
+    #[error("operation cancelled")]
+    Cancelled,
+}
+
+pub use qc_traits::ErrorKind;
+
+impl Error {
+    /*
+     * Converts record errors, while preserving limits and cancellation
+     */
+    fn from_record_error(e: record::Error) -> Self {
+        match e {
+            record::Error::LimitExceeded { which, limit } => Self::LimitExceeded { which, limit },
+            record::Error::Cancelled => Self::Cancelled,
+            e => Self::RecordError(e),
+        }
+    }
+    /// Returns the [ErrorKind] of this [Error]
+    /// ```
+    /// use rinex::prelude::*;
+    /// let err = Rinex::from_file("../test_resources/OBS/V3/not_a_file.22O")
+    ///     .unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::Unavailable);
+    /// assert!(!err.kind().is_retryable());
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::HeaderParsingError(e) => e.kind(),
+            Self::RecordError(e) => e.kind(),
+            Self::IoError(e) => ErrorKind::from_io_error(e),
+            Self::LimitExceeded { .. } => ErrorKind::LimitExceeded,
+            Self::ObservableCollision(_) | Self::NonRepresentableObservable(_) => {
+                ErrorKind::Unsupported
+            },
+            Self::Cancelled => ErrorKind::Cancelled,
+        }
+    }
 }
 
 impl Rinex {
@@ -366,7 +467,7 @@ impl Rinex {
     /// This can be used to "force" compression of a RINEX1 into CRINEX3
     pub fn rnx2crnx3(&self) -> Self {
         let mut s = self.clone();
-        s.rnx2crnx1_mut();
+        s.rnx2crnx3_mut();
         s
     }
 
@@ -414,12 +515,35 @@ impl Rinex {
     /// If you're working with Observation, Navigation or Meteo data,
     /// and prefered shorter filenames (V2 like format): force short to "true".
     /// Otherwse, we will prefer modern V3 like formats.
-    /// Use "suffix" to append a custom suffix like ".gz" for example.
+    /// The compression suffix (like ".gz") of the original file name, or described
+    /// by "custom", is appended automatically. Use "suffix" to override it
+    /// with a custom suffix, or Some("") to drop it.
     /// NB this will only output uppercase filenames (as per standard specs).
     /// ```
+    /// use std::str::FromStr;
     /// use rinex::prelude::*;
+    /// use rinex::prod::{Compression, ProductionAttributes};
     /// // Parse a File that follows standard naming conventions
     /// // and verify we generate something correct
+    /// let rinex = Rinex::from_file("../test_resources/CRNX/V3/ACOR00ESP_R_20213550000_01D_30S_MO.crx")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     rinex.standard_filename(false, None, None),
+    ///     "ACOR00ESP_R_20213550000_01D_30S_MO.crx",
+    /// );
+    /// // compression is preserved
+    /// let attrs = ProductionAttributes::from_str("ACOR00ESP_R_20213550000_01D_30S_MO.crx.gz")
+    ///     .unwrap();
+    /// assert_eq!(attrs.compression, Some(Compression::Gzip));
+    /// assert_eq!(
+    ///     rinex.standard_filename(false, None, Some(attrs.clone())),
+    ///     "ACOR00ESP_R_20213550000_01D_30S_MO.crx.gz",
+    /// );
+    /// // unless overridden
+    /// assert_eq!(
+    ///     rinex.standard_filename(false, Some(""), Some(attrs)),
+    ///     "ACOR00ESP_R_20213550000_01D_30S_MO.crx",
+    /// );
     /// ```
     pub fn standard_filename(
         &self,
@@ -680,10 +804,58 @@ impl Rinex {
                     )
                 }
             },
+            RinexType::DORIS => {
+                // DORIS files are named after the host satellite
+                let name = match &custom {
+                    Some(ref custom) => custom.name.to_lowercase(),
+                    None => {
+                        if let Some(satellite) = header
+                            .doris
+                            .as_ref()
+                            .and_then(|doris| doris.satellite.as_ref())
+                        {
+                            satellite.ids_code()
+                        } else if let Some(attr) = &self.prod_attr {
+                            attr.name.to_lowercase()
+                        } else {
+                            "xxx".to_string()
+                        }
+                    },
+                };
+                let ddd = match &custom {
+                    Some(ref custom) => format!("{:03}", custom.doy),
+                    None => {
+                        if let Some(epoch) = self.first_epoch() {
+                            let ddd = epoch.day_of_year().round() as u32;
+                            format!("{:03}", ddd)
+                        } else {
+                            "ddd".to_string()
+                        }
+                    },
+                };
+                let yy = match &custom {
+                    Some(ref custom) => format!("{:02}", custom.year - 2_000),
+                    None => {
+                        if let Some(epoch) = self.first_epoch() {
+                            let yy = epoch_decompose(epoch).0;
+                            format!("{:02}", yy - 2_000)
+                        } else {
+                            "yy".to_string()
+                        }
+                    },
+                };
+                ProductionAttributes::doris_format(&name, &ddd, &yy)
+            },
             rinex => unimplemented!("{} format", rinex),
         };
+        let compression = match &custom {
+            Some(custom) => custom.compression,
+            None => self.prod_attr.as_ref().and_then(|attr| attr.compression),
+        };
         if let Some(suffix) = suffix {
             filename.push_str(suffix);
+        } else if let Some(compression) = compression {
+            filename.push_str(&compression.to_string());
         }
         filename
     }
@@ -815,21 +987,56 @@ impl Rinex {
 
     /// See [Self::from_file]
     pub fn from_path(path: &Path) -> Result<Rinex, Error> {
-        let fullpath = path.to_string_lossy().to_string();
+        Self::from_path_with_options(path, ParsingOptions::default())
+    }
+
+    /// Builds a `RINEX` from given file fullpath, enforcing said [ParsingOptions].
+    /// This is typically used when parsing untrusted content, to protect
+    /// against gzip bombs and abnormally large files.
+    /// Returns [Error::LimitExceeded] as soon as one of the limits is reached.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let options = ParsingOptions::default()
+    ///     .with_max_epochs(10);
+    /// let rinex = Rinex::from_file_with_options("../test_resources/OBS/V3/DUTH0630.22O", options);
+    /// assert!(rinex.is_err());
+    /// ```
+    pub fn from_file_with_options(fullpath: &str, options: ParsingOptions) -> Result<Rinex, Error> {
+        Self::from_path_with_options(Path::new(fullpath), options)
+    }
 
-        // create buffered reader
-        let mut reader = BufferedReader::new(&fullpath)?;
+    /// See [Self::from_file_with_options]
+    pub fn from_path_with_options(path: &Path, options: ParsingOptions) -> Result<Rinex, Error> {
+        Self::from_path_with_progress(path, options, None)
+    }
 
-        // Parse header fields
-        let mut header = Header::new(&mut reader)?;
+    /// Builds a `RINEX` from given file fullpath, enforcing said [ParsingOptions],
+    /// like [Self::from_file_with_options], while notifying `progress` of each parsed epoch.
+    /// Parsing stops as soon as `progress` requests it, returning [Error::Cancelled].
+    /// Refer to [ProgressHandle] for an example.
+    pub fn from_file_with_progress(
+        fullpath: &str,
+        options: ParsingOptions,
+        progress: &mut ProgressHandle,
+    ) -> Result<Rinex, Error> {
+        Self::from_path_with_progress(Path::new(fullpath), options, Some(progress))
+    }
 
-        // Parse file body (record content)
-        // Comments might serve some fileops like "splice".
-        let (record, comments) = record::parse_record(&mut reader, &mut header)?;
+    /*
+     * Parses given file, with possible progress notifications
+     */
+    fn from_path_with_progress(
+        path: &Path,
+        options: ParsingOptions,
+        progress: Option<&mut ProgressHandle>,
+    ) -> Result<Rinex, Error> {
+        let fullpath = path.to_string_lossy().to_string();
+        let reader = BufferedReader::new_with_options(&fullpath, options)?;
+        let (mut rinex, _) = Self::parse_lenient(reader, progress)?;
 
         // Parse / identify production attributes
         // that only exist in the filename.
-        let prod_attr = match path.file_name() {
+        rinex.prod_attr = match path.file_name() {
             Some(filename) => {
                 let filename = filename.to_string_lossy().to_string();
                 if let Ok(attrs) = ProductionAttributes::from_str(&filename) {
@@ -841,11 +1048,156 @@ impl Rinex {
             _ => None,
         };
 
-        Ok(Rinex {
+        Ok(rinex)
+    }
+
+    /// Builds a `RINEX` from any [std::io::BufRead] implementation,
+    /// for example an in-memory buffer or a network stream.
+    /// gzip compressed content is identified from the content itself
+    /// (requires the flate2 feature), CRINEX from the header.
+    /// Unlike [Self::from_file], production attributes cannot be
+    /// recovered, since they only exist in the file name.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::io::Cursor;
+    /// let content = std::fs::read("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// let rinex = Rinex::from_reader(Cursor::new(content))
+    ///     .unwrap();
+    /// assert!(rinex.is_observation_rinex());
+    /// ```
+    pub fn from_reader<R: std::io::BufRead>(reader: R) -> Result<Rinex, Error> {
+        Self::from_reader_with_options(reader, ParsingOptions::default())
+    }
+
+    /// Builds a `RINEX` from any [std::io::BufRead] implementation,
+    /// like [Self::from_reader], enforcing said [ParsingOptions].
+    pub fn from_reader_with_options<R: std::io::BufRead>(
+        reader: R,
+        options: ParsingOptions,
+    ) -> Result<Rinex, Error> {
+        let reader = BufferedReader::from_reader_with_options(reader, options)?;
+        let (rinex, _) = Self::parse_lenient(reader, None)?;
+        Ok(rinex)
+    }
+
+    /// Builds a `RINEX` from given file fullpath, like [Self::from_file],
+    /// but also reports the record entries (epochs) that we could not parse,
+    /// so one corrupt entry does not go unnoticed. Those are skipped,
+    /// the rest of the file is preserved. I/O and header errors remain fatal.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let (rinex, warnings) = Rinex::from_file_lenient("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// for warning in warnings.iter() {
+    ///     println!("skipped {}", warning);
+    /// }
+    /// ```
+    pub fn from_file_lenient(fullpath: &str) -> Result<(Rinex, Vec<ParseWarning>), Error> {
+        Self::from_path_lenient(Path::new(fullpath))
+    }
+
+    /// See [Self::from_file_lenient]
+    pub fn from_path_lenient(path: &Path) -> Result<(Rinex, Vec<ParseWarning>), Error> {
+        let fullpath = path.to_string_lossy().to_string();
+        let reader = BufferedReader::new(&fullpath)?;
+        let (mut rinex, warnings) = Self::parse_lenient(reader, None)?;
+        rinex.prod_attr = path
+            .file_name()
+            .and_then(|filename| ProductionAttributes::from_str(&filename.to_string_lossy()).ok());
+        Ok((rinex, warnings))
+    }
+
+    /*
+     * Parses header and record from given reader,
+     * reports the record entries that we could not parse
+     */
+    fn parse_lenient(
+        mut reader: BufferedReader,
+        progress: Option<&mut ProgressHandle>,
+    ) -> Result<(Rinex, Vec<ParseWarning>), Error> {
+        // Parse header fields
+        let mut header = Header::new(&mut reader).map_err(|e| match e {
+            header::ParsingError::LimitExceeded { which, limit } => {
+                Error::LimitExceeded { which, limit }
+            },
+            e => Error::HeaderParsingError(e),
+        })?;
+
+        // Parse file body (record content)
+        // Comments might serve some fileops like "splice".
+        let (record, comments, warnings) =
+            record::parse_record_with_progress(&mut reader, &mut header, progress)
+                .map_err(Error::from_record_error)?;
+
+        let rinex = Rinex {
             header,
             record,
             comments,
-            prod_attr,
+            prod_attr: None,
+        };
+        Ok((rinex, warnings))
+    }
+
+    /// Parses the header of given Observation RINEX (or CRINEX) file,
+    /// then returns an [ObservationStream] that yields one epoch at a time,
+    /// without building the entire record. This is typically used
+    /// to process very large files with bounded memory.
+    /// Refer to [ObservationStream] for an example.
+    pub fn epochs_iter_from_file(fullpath: &str) -> Result<ObservationStream, Error> {
+        ObservationStream::from_file(fullpath)
+    }
+
+    /// Counts the frames contained in given Navigation RINEX file,
+    /// without building the record. Each frame is parsed then dropped,
+    /// so this is a bounded memory alternative to [Self::from_file], typically
+    /// used to inspect very large (or untrusted) files, prior parsing them.
+    /// Each frame counts as one epoch regarding [ParsingOptions::max_epochs],
+    /// see [Self::nav_frame_count_with_options].
+    /// ```
+    /// use rinex::prelude::*;
+    /// let count = Rinex::nav_frame_count("../test_resources/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// assert!(count > 0);
+    /// ```
+    pub fn nav_frame_count(fullpath: &str) -> Result<usize, Error> {
+        Self::nav_frame_count_with_options(fullpath, ParsingOptions::default())
+    }
+
+    /// Counts the frames contained in given Navigation RINEX file, like
+    /// [Self::nav_frame_count], enforcing said [ParsingOptions].
+    /// Returns [Error::LimitExceeded] as soon as one of the limits is reached.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let options = ParsingOptions::default()
+    ///     .with_max_epochs(2);
+    /// let count = Rinex::nav_frame_count_with_options(
+    ///     "../test_resources/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx",
+    ///     options);
+    /// assert!(count.is_err());
+    /// ```
+    pub fn nav_frame_count_with_options(
+        fullpath: &str,
+        options: ParsingOptions,
+    ) -> Result<usize, Error> {
+        let mut reader = BufferedReader::new_with_options(fullpath, options)?;
+
+        let header = Header::new(&mut reader).map_err(|e| match e {
+            header::ParsingError::LimitExceeded { which, limit } => {
+                Error::LimitExceeded { which, limit }
+            },
+            e => Error::HeaderParsingError(e),
+        })?;
+
+        if header.rinex_type != types::Type::NavigationData {
+            return Err(Error::RecordError(record::Error::TypeError(
+                header.rinex_type.to_string(),
+            )));
+        }
+
+        navigation::record::count_frames(&mut reader, &header).map_err(|e| match e {
+            record::Error::LimitExceeded { which, limit } => Error::LimitExceeded { which, limit },
+            e => Error::RecordError(e),
         })
     }
 
@@ -901,20 +1253,46 @@ impl Rinex {
 
     /// Generates a new RINEX = Self(=RINEX(A)) - RHS(=RINEX(B)).
     /// Therefore RHS is considered reference.
-    /// This operation is typically used to compare two GNSS receivers.
+    /// This operation is typically used to compare two GNSS receivers,
+    /// or two meteo sensors. Data that RHS does not describe is dropped.
     /// Both RINEX formats must match otherwise this will panic.
-    /// This is only available to Observation RINEX files.
+    /// This is only available to Observation, Meteo and DORIS RINEX files.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/MET/V2/abvi0010.15m")
+    ///     .unwrap();
+    /// let null = rinex.substract(&rinex);
+    /// for (_, observations) in null.meteo() {
+    ///     for (_, value) in observations {
+    ///         assert_eq!(*value, 0.0);
+    ///     }
+    /// }
+    /// ```
     pub fn substract(&self, rhs: &Self) -> Self {
+        match (&self.record, &rhs.record) {
+            (record::Record::MeteoRecord(lhs_rec), record::Record::MeteoRecord(rhs_rec)) => {
+                let mut record = lhs_rec.clone();
+                meteo::record::substract_mut(&mut record, rhs_rec);
+                return Rinex::new(self.header.clone(), record::Record::MeteoRecord(record));
+            },
+            (record::Record::DorisRecord(lhs_rec), record::Record::DorisRecord(rhs_rec)) => {
+                let mut record = lhs_rec.clone();
+                doris::record::substract_mut(&mut record, rhs_rec);
+                return Rinex::new(self.header.clone(), record::Record::DorisRecord(record));
+            },
+            _ => {},
+        }
+
         let mut record = observation::Record::default();
         let lhs_rec = self
             .record
             .as_obs()
-            .expect("can only substract observation data");
+            .expect("can only substract observation, meteo or doris data");
 
         let rhs_rec = rhs
             .record
             .as_obs()
-            .expect("can only substract observation data");
+            .expect("can only substract observation, meteo or doris data");
 
         for ((epoch, flag), (clk, svnn)) in lhs_rec {
             if let Some((ref_clk, ref_svnn)) = rhs_rec.get(&(*epoch, *flag)) {
@@ -1017,43 +1395,84 @@ impl Rinex {
         }
         false
     }
+    /// Returns all merge boundaries (seams) of a `merged` RINEX,
+    /// in chronological order. Empty if Self is not [Self::is_merged].
+    /// Each boundary is described by a `FILE MERGE` comment, refer to
+    /// [Header::merge_boundaries] for the supported formats.
+    pub fn merge_boundaries(&self) -> Vec<Epoch> {
+        self.header.merge_boundaries()
+    }
+    /// Returns the number of files that were merged together to form Self.
+    /// This is 1 for a file that was never merged.
+    pub fn merged_file_count(&self) -> usize {
+        self.header.merge_comments() + 1
+    }
 
-    /// Removes all observations where receiver phase lock was lost.   
-    /// This is only relevant on OBS RINEX.
+    /// Retains only observations where receiver phase lock was lost
+    /// (cycle slip is possible), as flagged by the receiver.
+    /// This is typically used to locate and inspect cycle slips.
+    /// This is only relevant on OBS RINEX, see [Self::lli_and_mask_mut].
+    /// ```
+    /// use rinex::prelude::*;
+    /// let mut rinex = Rinex::from_file("../test_resources/OBS/V3/LARM0010.22O")
+    ///     .unwrap();
+    /// rinex.lock_loss_filter_mut();
+    /// assert_eq!(rinex.epoch().count(), 2);
+    /// ```
     pub fn lock_loss_filter_mut(&mut self) {
         self.lli_and_mask_mut(observation::LliFlags::LOCK_LOSS)
     }
+    /// [Self::lock_loss_filter_mut] immutable implementation.
+    pub fn lock_loss_filter(&self) -> Self {
+        let mut c = self.clone();
+        c.lock_loss_filter_mut();
+        c
+    }
 
-    /// Applies given AND mask in place, to all observations.
+    /// Applies given AND mask in place, to all observations:
+    /// retains only observations whose LLI flag intersects the mask.
+    /// This also drops observations that did not come with an LLI flag.
+    /// Vehicles that end up empty are removed, so are Epochs that end up empty
+    /// and do not come with a receiver clock offset.
+    /// Epochs that are not flagged OK (events, cycle slip records..) are preserved.
     /// This has no effect on non observation records.
-    /// This also drops observations that did not come with an LLI flag.  
-    /// Only relevant on OBS RINEX.
     pub fn lli_and_mask_mut(&mut self, mask: observation::LliFlags) {
-        if !self.is_observation_rinex() {
-            return; // nothing to browse
-        }
-        let record = self.record.as_mut_obs().unwrap();
-        for (_e, (_clk, sv)) in record.iter_mut() {
-            for (_sv, obs) in sv.iter_mut() {
-                obs.retain(|_, data| {
-                    if let Some(lli) = data.lli {
-                        lli.intersects(mask)
-                    } else {
-                        false // drops data with no LLI attached
-                    }
-                })
-            }
+        if let Some(record) = self.record.as_mut_obs() {
+            record.retain(|(_, flag), (clk, vehicles)| {
+                if !flag.is_ok() {
+                    return true;
+                }
+                vehicles.retain(|_, observations| {
+                    observations.retain(|_, data| {
+                        if let Some(lli) = data.lli {
+                            lli.intersects(mask)
+                        } else {
+                            false // drops data with no LLI attached
+                        }
+                    });
+                    !observations.is_empty()
+                });
+                clk.is_some() || !vehicles.is_empty()
+            });
         }
     }
 
-    /// [`Rinex::lli_and_mask`] immutable implementation.   
+    /// [`Rinex::lli_and_mask_mut`] immutable implementation.
     /// Only relevant on OBS RINEX.
     pub fn lli_and_mask(&self, mask: observation::LliFlags) -> Self {
         let mut c = self.clone();
         c.lli_and_mask_mut(mask);
         c
     }
-    /// Aligns Phase observations at origin
+    /// Aligns Phase observations at origin: for each [SV] and phase [Observable],
+    /// the first encountered value is substracted to the whole series,
+    /// which therefore starts at zero. This is typically used to visualize phase continuity.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let mut rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// rinex.observation_phase_align_origin_mut();
+    /// ```
     pub fn observation_phase_align_origin_mut(&mut self) {
         let mut init_phases: HashMap<SV, HashMap<Observable, f64>> = HashMap::new();
         if let Some(r) = self.record.as_mut_obs() {
@@ -1061,16 +1480,12 @@ impl Rinex {
                 for (sv, observations) in vehicles.iter_mut() {
                     for (observable, data) in observations.iter_mut() {
                         if observable.is_phase_observable() {
-                            if let Some(init_phase) = init_phases.get_mut(sv) {
-                                if init_phase.get(observable).is_none() {
-                                    init_phase.insert(observable.clone(), data.obs);
-                                }
-                            } else {
-                                let mut map: HashMap<Observable, f64> = HashMap::new();
-                                map.insert(observable.clone(), data.obs);
-                                init_phases.insert(*sv, map);
-                            }
-                            data.obs -= init_phases.get(sv).unwrap().get(observable).unwrap();
+                            let init_phase = init_phases
+                                .entry(*sv)
+                                .or_default()
+                                .entry(observable.clone())
+                                .or_insert(data.obs);
+                            data.obs -= *init_phase;
                         }
                     }
                 }
@@ -1078,22 +1493,29 @@ impl Rinex {
         }
     }
     /// Aligns Phase observations at origin,
-    /// immutable implementation
+    /// immutable implementation, see [Self::observation_phase_align_origin_mut]
     pub fn observation_phase_align_origin(&self) -> Self {
         let mut s = self.clone();
         s.observation_phase_align_origin_mut();
         s
     }
-    /// Converts all Phase Data to Carrier Cycles by multiplying all phase points
-    /// by the carrier signal wavelength.
-    pub fn observation_phase_carrier_cycles_mut(&mut self) {
+    /*
+     * Scales phase observations by their carrier wavelength,
+     * or its inverse. Observables that do not resolve to
+     * a carrier for this constellation are left untouched.
+     */
+    fn observation_phase_wavelength_scaling_mut(&mut self, inverse: bool) {
         if let Some(r) = self.record.as_mut_obs() {
             for (_, (_, vehicles)) in r.iter_mut() {
                 for (sv, observations) in vehicles.iter_mut() {
                     for (observable, data) in observations.iter_mut() {
                         if observable.is_phase_observable() {
                             if let Ok(carrier) = observable.carrier(sv.constellation) {
-                                data.obs *= carrier.wavelength();
+                                if inverse {
+                                    data.obs /= carrier.wavelength();
+                                } else {
+                                    data.obs *= carrier.wavelength();
+                                }
                             }
                         }
                     }
@@ -1101,15 +1523,241 @@ impl Rinex {
             }
         }
     }
+    /// Converts all Phase observations from carrier cycles to meters,
+    /// by multiplying them by the carrier signal wavelength.
+    /// Phase observations that cannot be associated to a carrier
+    /// (for this constellation) are left untouched.
+    /// See [Self::observation_phase_to_cycles_mut] for the reciprocal operation.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let mut rinex = Rinex::from_file("../test_resources/OBS/V2/AJAC3550.21O")
+    ///     .unwrap();
+    /// rinex.observation_phase_to_meters_mut();
+    /// ```
+    pub fn observation_phase_to_meters_mut(&mut self) {
+        self.observation_phase_wavelength_scaling_mut(false);
+    }
+    /// Converts all Phase observations from carrier cycles to meters,
+    /// immutable implementation, see [Self::observation_phase_to_meters_mut].
+    pub fn observation_phase_to_meters(&self) -> Self {
+        let mut s = self.clone();
+        s.observation_phase_to_meters_mut();
+        s
+    }
+    /// Converts all Phase observations from meters back to carrier cycles,
+    /// by dividing them by the carrier signal wavelength.
+    /// Phase observations that cannot be associated to a carrier
+    /// (for this constellation) are left untouched.
+    /// This is the reciprocal of [Self::observation_phase_to_meters_mut].
+    pub fn observation_phase_to_cycles_mut(&mut self) {
+        self.observation_phase_wavelength_scaling_mut(true);
+    }
+    /// Converts all Phase observations from meters back to carrier cycles,
+    /// immutable implementation, see [Self::observation_phase_to_cycles_mut].
+    pub fn observation_phase_to_cycles(&self) -> Self {
+        let mut s = self.clone();
+        s.observation_phase_to_cycles_mut();
+        s
+    }
+
+    /// Renames [Observable]s in both header and record, following the remapping table.
+    /// This is typically used to harmonize data coming from heterogeneous receivers,
+    /// that label the same physical signal differently (for example `C2X` versus `C2L`),
+    /// prior a [Merge] operation. A remapped observation overwrites any existing
+    /// observation already labeled with the new name.
+    /// Only relevant on OBS, Meteo and DORIS RINEX.
+    pub fn remap_observables_mut(&mut self, map: &HashMap<Observable, Observable>) {
+        if let Some(obs) = &mut self.header.obs {
+            for codes in obs.codes.values_mut() {
+                remap_observables_vec_mut(codes, map);
+            }
+            obs.scaling = obs
+                .scaling
+                .drain()
+                .map(|((c, k), v)| ((c, map.get(&k).cloned().unwrap_or(k)), v))
+                .collect();
+        }
+        if let Some(meteo) = &mut self.header.meteo {
+            remap_observables_vec_mut(&mut meteo.codes, map);
+            for sensor in meteo.sensors.iter_mut() {
+                if let Some(observable) = map.get(&sensor.observable) {
+                    sensor.observable = observable.clone();
+                }
+            }
+        }
+        if let Some(doris) = &mut self.header.doris {
+            remap_observables_vec_mut(&mut doris.observables, map);
+            remap_observables_map_mut(&mut doris.scaling, map);
+        }
+        if let Some(r) = self.record.as_mut_obs() {
+            for (_, (_, vehicles)) in r.iter_mut() {
+                for (_, observations) in vehicles.iter_mut() {
+                    remap_observables_map_mut(observations, map);
+                }
+            }
+        } else if let Some(r) = self.record.as_mut_meteo() {
+            for (_, observations) in r.iter_mut() {
+                remap_observables_map_mut(observations, map);
+            }
+        } else if let Some(r) = self.record.as_mut_doris() {
+            for (_, (_, stations)) in r.iter_mut() {
+                for (_, observations) in stations.iter_mut() {
+                    remap_observables_map_mut(observations, map);
+                }
+            }
+        }
+    }
+    /// Renames [Observable]s in both header and record, following the remapping table.
+    /// Immutable implementation of [Self::remap_observables_mut].
+    pub fn remap_observables(&self, map: &HashMap<Observable, Observable>) -> Self {
+        let mut s = self.clone();
+        s.remap_observables_mut(map);
+        s
+    }
 
-    /// Converts all Phase Data to Carrier Cycles by multiplying all phase points
-    /// by the carrier signal wavelength.
-    pub fn observation_phase_carrier_cycles(&self) -> Self {
+    /// Applies a uniform time shift to all Epochs of this OBS RINEX,
+    /// including the header time of first and last observation.
+    /// This is typically used to correct a file whose time tags were
+    /// produced without (or with an erroneous) leap second.
+    /// Does not apply to other RINEX types.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let mut rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// let first = rinex.first_epoch().unwrap();
+    /// rinex.shift_epochs_mut(Duration::from_seconds(18.0));
+    /// assert_eq!(rinex.first_epoch(), Some(first + Duration::from_seconds(18.0)));
+    /// ```
+    pub fn shift_epochs_mut(&mut self, dt: Duration) {
+        if let Some(r) = self.record.as_mut_obs() {
+            *r = std::mem::take(r)
+                .into_iter()
+                .map(|((e, flag), entry)| ((e + dt, flag), entry))
+                .collect();
+            if let Some(obs) = &mut self.header.obs {
+                if let Some(t) = &mut obs.time_of_first_obs {
+                    *t += dt;
+                }
+                if let Some(t) = &mut obs.time_of_last_obs {
+                    *t += dt;
+                }
+            }
+        }
+    }
+    /// Applies a uniform time shift to all Epochs.
+    /// Immutable implementation of [Self::shift_epochs_mut].
+    pub fn shift_epochs(&self, dt: Duration) -> Self {
         let mut s = self.clone();
-        s.observation_phase_carrier_cycles_mut();
+        s.shift_epochs_mut(dt);
         s
     }
 
+    /// Inserts a derived (synthetic) [Observable], for example smoothed pseudo ranges
+    /// or a signal combination, into this OBS RINEX so it is preserved when formatted.
+    /// The new code is registered for said [Constellation] in the header section,
+    /// and its origin is tagged in a comment. Data points that do not match
+    /// said [Constellation] are dropped, missing data points are formatted as blanks.
+    /// Returns [Error::ObservableCollision] if this code already exists.
+    /// Does not apply to other RINEX types.
+    /// ```
+    /// use std::str::FromStr;
+    /// use rinex::prelude::*;
+    ///
+    /// let mut rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// let c1c = Observable::from_str("C1C").unwrap();
+    /// let c1s = Observable::from_str("C1S").unwrap();
+    ///
+    /// let smoothed = rinex
+    ///     .observation()
+    ///     .flat_map(|((t, _), (_, vehicles))| {
+    ///         vehicles.iter().filter_map(|(sv, observations)| {
+    ///             let data = observations.get(&c1c)?;
+    ///             Some((*t, *sv, data.obs))
+    ///         })
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// rinex.insert_derived_observable_mut(Constellation::GPS, c1s.clone(), smoothed.into_iter())
+    ///     .unwrap();
+    ///
+    /// let codes = &rinex.header.obs.as_ref().unwrap().codes;
+    /// assert!(codes[&Constellation::GPS].contains(&c1s));
+    ///
+    /// // codes must remain unique
+    /// let collision = rinex.insert_derived_observable_mut(Constellation::GPS, c1c, std::iter::empty());
+    /// assert!(collision.is_err());
+    /// ```
+    pub fn insert_derived_observable_mut(
+        &mut self,
+        constellation: Constellation,
+        observable: Observable,
+        values: impl Iterator<Item = (Epoch, SV, f64)>,
+    ) -> Result<(), Error> {
+        let obs = match &mut self.header.obs {
+            Some(obs) => obs,
+            None => return Ok(()),
+        };
+        let codes = obs.codes.entry(constellation).or_default();
+        if codes.contains(&observable) {
+            return Err(Error::ObservableCollision(observable));
+        }
+        codes.push(observable.clone());
+
+        self.header.comments.push(format!(
+            "rustrnx-{:<6} DERIVED OBSERVABLE {:x} {}",
+            env!("CARGO_PKG_VERSION")
+                .split('-')
+                .next()
+                .unwrap_or_default(),
+            constellation,
+            observable,
+        ));
+
+        if let Some(r) = self.record.as_mut_obs() {
+            for (t, sv, value) in values {
+                let matches = if constellation == Constellation::SBAS {
+                    sv.constellation.is_sbas()
+                } else {
+                    sv.constellation == constellation
+                };
+                if !matches {
+                    continue;
+                }
+                // EpochFlag::Ok sorts first: this is the first key at this Epoch
+                let key = r
+                    .range((t, EpochFlag::Ok)..)
+                    .map(|(k, _)| *k)
+                    .next()
+                    .filter(|(e, _)| *e == t)
+                    .unwrap_or((t, EpochFlag::Ok));
+                let (_, vehicles) = r.entry(key).or_insert((None, BTreeMap::new()));
+                vehicles
+                    .entry(sv)
+                    .or_default()
+                    .insert(observable.clone(), ObservationData::new(value, None, None));
+            }
+        }
+        Ok(())
+    }
+    /// Inserts a signal combination, as returned by `Combine::combine`,
+    /// as a derived [Observable]. See [Self::insert_derived_observable_mut].
+    pub fn insert_combination_mut(
+        &mut self,
+        constellation: Constellation,
+        observable: Observable,
+        combination: &BTreeMap<SV, BTreeMap<(Epoch, EpochFlag), f64>>,
+    ) -> Result<(), Error> {
+        self.insert_derived_observable_mut(
+            constellation,
+            observable,
+            combination.iter().flat_map(|(sv, values)| {
+                values.iter().map(move |((t, _), value)| (*t, *sv, *value))
+            }),
+        )
+    }
+
     /// Writes self into given file.   
     /// Both header + record will strictly follow RINEX standards.   
     /// Record: refer to supported RINEX types.
@@ -1125,13 +1773,130 @@ impl Rinex {
     ///   * [Self::guess_production_attributes] helps generate standardized filenames for
     ///     files that do not follow naming conventions
     pub fn to_file(&self, path: &str) -> Result<(), Error> {
-        let mut writer = BufferedWriter::new(path)?;
-        write!(writer, "{}", self.header)?;
-        self.record.to_file(&self.header, &mut writer)?;
-        Ok(())
+        self.check_representable()?;
+        let writer = BufferedWriter::new(path)?;
+        self.format(writer)
+    }
+    /// Writes self into given file, with gzip compression at desired
+    /// `level` (0: none, 9: best, 6 being used by [Self::to_file]),
+    /// whatever the file extension.
+    /// CRINEX content is compacted prior compression,
+    /// which produces standard `.crx.gz` products directly.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rnx = Rinex::from_file("../test_resources/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz")
+    ///   .unwrap();
+    /// assert!(rnx.to_gzip_file("ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz", 9).is_ok());
+    /// ```
+    #[cfg(feature = "flate2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "flate2")))]
+    pub fn to_gzip_file(&self, path: &str, level: u32) -> Result<(), Error> {
+        self.check_representable()?;
+        let writer = BufferedWriter::new_gzip(path, level)?;
+        self.format(writer)
+    }
+    /// Formats this [Rinex] into any [Write] implementation,
+    /// for example an in-memory buffer or a network stream.
+    /// This produces the same content as [Self::to_file], without compression.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// let mut content = Vec::<u8>::new();
+    /// rinex.to_writer(&mut content)
+    ///     .unwrap();
+    /// assert!(!content.is_empty());
+    /// ```
+    pub fn to_writer<W: Write>(&self, w: W) -> Result<(), Error> {
+        self.check_representable()?;
+        let writer = BufferedWriter::from_writer(w);
+        self.format(writer)
     }
-}
-
+    /*
+     * Verifies Self can be formatted in its own revision
+     */
+    fn check_representable(&self) -> Result<(), Error> {
+        if self.header.version.major < 3 {
+            if let Some(obs) = &self.header.obs {
+                // V2 only supports 2 character codes
+                let observable = obs
+                    .codes
+                    .values()
+                    .flatten()
+                    .find(|observable| observable.to_string().len() > 2);
+                if let Some(observable) = observable {
+                    return Err(Error::NonRepresentableObservable(observable.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Writes self into given file, like [Self::to_file], while notifying
+    /// `progress` of each formatted epoch. Content is first produced into
+    /// a `.part` file, renamed to `path` on completion: if the operation
+    /// is cancelled, it returns [Error::Cancelled] and no file is left behind.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::ops::ControlFlow;
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// let mut progress = ProgressHandle::new(|_| ControlFlow::Continue(()));
+    /// rinex.to_file_with_progress("DUTH0630.22O", &mut progress)
+    ///     .unwrap();
+    /// assert!(std::path::Path::new("DUTH0630.22O").exists());
+    /// let _ = std::fs::remove_file("DUTH0630.22O");
+    /// ```
+    pub fn to_file_with_progress(
+        &self,
+        path: &str,
+        progress: &mut ProgressHandle,
+    ) -> Result<(), Error> {
+        self.check_representable()?;
+        let staging = format!("{}.part", path);
+        let writer = BufferedWriter::new_staged(&staging, path)?;
+        match self.format_with_progress(writer, Some(progress)) {
+            Ok(_) => {
+                std::fs::rename(&staging, path)?;
+                Ok(())
+            },
+            Err(e) => {
+                let _ = std::fs::remove_file(&staging);
+                Err(e)
+            },
+        }
+    }
+    /*
+     * Formats Self into given writer
+     */
+    fn format(&self, writer: BufferedWriter) -> Result<(), Error> {
+        self.format_with_progress(writer, None)
+    }
+    /*
+     * Formats Self into given writer, with possible progress notifications
+     */
+    fn format_with_progress(
+        &self,
+        mut writer: BufferedWriter,
+        progress: Option<&mut ProgressHandle>,
+    ) -> Result<(), Error> {
+        write!(writer, "{}", self.header)?;
+        self.record
+            .to_file_with_progress(&self.header, &mut writer, progress)
+            .map_err(Error::from_record_error)?;
+        writer.finish()?;
+        Ok(())
+    }
+    /// Writes the [Header] section only, into a new file.
+    /// This is typically used to generate skeletons.
+    /// Like [Self::to_file], the file is gzip compressed if `path` terminates with .gz.
+    pub fn write_header_only(&self, path: &str) -> Result<(), Error> {
+        let mut writer = BufferedWriter::new(path)?;
+        write!(writer, "{}", self.header)?;
+        writer.finish()?;
+        Ok(())
+    }
+}
+
 /*
  * Sampling related methods
  */
@@ -1586,7 +2351,7 @@ impl Rinex {
         } else if self.record.as_doris().is_some() {
             Box::new(
                 self.doris()
-                    .flat_map(|(_, stations)| {
+                    .flat_map(|(_, (_, stations))| {
                         stations
                             .iter()
                             .flat_map(|(_, observables)| observables.iter().map(|(k, _)| k))
@@ -1716,7 +2481,10 @@ impl Rinex {
         dyn Iterator<
                 Item = (
                     &(Epoch, EpochFlag),
-                    &BTreeMap<Station, HashMap<Observable, DorisObservationData>>,
+                    &(
+                        Option<DorisClockOffset>,
+                        BTreeMap<Station, HashMap<Observable, DorisObservationData>>,
+                    ),
                 ),
             > + '_,
     > {
@@ -1744,7 +2512,22 @@ impl Rinex {
 // use std::str::FromStr;
 
 #[cfg(feature = "obs")]
-use crate::observation::{record::code_multipath, LliFlags, SNR};
+use crate::observation::{
+    record::{code_multipath, merge_conflicts, multipath_rms},
+    LliFlags, MergeConflict, Sentinels, SNR,
+};
+
+#[cfg(feature = "obs")]
+use crate::observation::{
+    repair::{phase_resets, repair_cycle_slips, PHASE_RESET_THRESHOLD},
+    CycleSlipReport, ObservableCoverage, PseudorangeUnit,
+};
+
+#[cfg(feature = "obs")]
+use crate::observation::noise::observation_noise;
+
+#[cfg(feature = "obs")]
+use crate::observation::smoothing::{hatch_smoothing, HATCH_SMOOTHING_WINDOW};
 
 /*
  * OBS RINEX specific methods: only available on crate feature.
@@ -1866,6 +2649,7 @@ impl Rinex {
     /// use rinex::prelude::Rinex;
     /// let rnx = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
     ///     .unwrap();
+    /// assert_eq!(rnx.epoch_anomalies().count(), 0); // no abnormal event
     /// ```
     pub fn epoch_anomalies(&self) -> Box<dyn Iterator<Item = (Epoch, EpochFlag)> + '_> {
         Box::new(self.epoch_flag().filter_map(
@@ -1884,6 +2668,7 @@ impl Rinex {
     /// use rinex::prelude::Rinex;
     /// let rnx = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
     ///     .unwrap();
+    /// assert_eq!(rnx.epoch_ok().count(), rnx.epoch().count());
     /// ```
     pub fn epoch_ok(&self) -> Box<dyn Iterator<Item = Epoch> + '_> {
         Box::new(
@@ -1897,6 +2682,7 @@ impl Rinex {
     /// use rinex::prelude::Rinex;
     /// let rnx = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
     ///     .unwrap();
+    /// assert_eq!(rnx.epoch_cs().count(), 0); // no cycle slip declared
     /// ```
     pub fn epoch_cs(&self) -> Box<dyn Iterator<Item = Epoch> + '_> {
         Box::new(self.epoch_flag().filter_map(|(e, f)| {
@@ -1907,12 +2693,12 @@ impl Rinex {
             }
         }))
     }
-    /// Returns an iterator over receiver clock offsets, expressed in seconds.
-    /// Such information is kind of rare (modern / dual frequency receivers?)
-    /// and we don't have a compelling example yet.
+    /// Returns an iterator over receiver clock offsets, expressed in seconds,
+    /// in chronological order. Epochs that do not describe the receiver
+    /// clock offset (which is optional) are not exposed.
     /// ```
     /// use rinex::prelude::Rinex;
-    /// let rnx = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    /// let rnx = Rinex::from_file("../test_resources/OBS/V3/VLNS0010.22O")
     ///     .unwrap();
     /// for ((epoch, flag), clk) in rnx.recvr_clock() {
     ///     // epoch: [hifitime::Epoch]
@@ -1925,6 +2711,55 @@ impl Rinex {
                 .filter_map(|(e, (clk, _))| clk.as_ref().map(|clk| (*e, *clk))),
         )
     }
+    /// Detects receiver clock steering events (like millisecond resets)
+    /// in the [Self::recvr_clock] series. The clock drift is estimated
+    /// as the median rate of the series, and an event is reported whenever
+    /// the offset departs from that drift by more than 1 µs between two consecutive epochs.
+    /// Returns the [Epoch] of the first sample following each event, and the step size in seconds.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/VLNS0010.22O")
+    ///     .unwrap();
+    /// // this receiver clock is not steered
+    /// assert!(rinex.clock_steering_events().is_empty());
+    /// ```
+    pub fn clock_steering_events(&self) -> Vec<(Epoch, f64)> {
+        const THRESHOLD: f64 = 1.0E-6;
+        let samples = self
+            .recvr_clock()
+            .map(|((t, _), clk)| (t, clk))
+            .collect::<Vec<_>>();
+        let mut rates = samples
+            .windows(2)
+            .filter_map(|pair| {
+                let dt = (pair[1].0 - pair[0].0).to_seconds();
+                let rate = (pair[1].1 - pair[0].1) / dt;
+                if dt > 0.0 && rate.is_finite() {
+                    Some(rate)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        if rates.is_empty() {
+            return Vec::new();
+        }
+        // median is not affected by the events themselves
+        rates.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let drift = rates[rates.len() / 2];
+        samples
+            .windows(2)
+            .filter_map(|pair| {
+                let dt = (pair[1].0 - pair[0].0).to_seconds();
+                let step = (pair[1].1 - pair[0].1) - drift * dt;
+                if dt > 0.0 && step.abs() > THRESHOLD {
+                    Some((pair[1].0, step))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
     /// Returns an iterator over phase data, expressed in (whole) carrier cycles.
     /// If Self is a High Precision RINEX (scaled RINEX), data is correctly scaled.
     /// High precision RINEX allows up to 100 pico carrier cycle precision.
@@ -1952,18 +2787,8 @@ impl Rinex {
             vehicles.iter().flat_map(|(sv, observations)| {
                 observations.iter().filter_map(|(observable, obsdata)| {
                     if observable.is_phase_observable() {
-                        if let Some(header) = &self.header.obs {
-                            // apply a scaling (if any), otherwise preserve data precision
-                            if let Some(scaling) =
-                                header.scaling(sv.constellation, observable.clone())
-                            {
-                                Some((*e, *sv, observable, obsdata.obs / *scaling as f64))
-                            } else {
-                                Some((*e, *sv, observable, obsdata.obs))
-                            }
-                        } else {
-                            Some((*e, *sv, observable, obsdata.obs))
-                        }
+                        let value = obsdata.obs / self.observation_scaling(sv, observable);
+                        Some((*e, *sv, observable, value))
                     } else {
                         None
                     }
@@ -1971,6 +2796,18 @@ impl Rinex {
             })
         }))
     }
+    /*
+     * Returns the scaling factor declared for this observable (high precision RINEX),
+     * 1.0 otherwise, which preserves data precision.
+     */
+    fn observation_scaling(&self, sv: &SV, observable: &Observable) -> f64 {
+        self.header
+            .obs
+            .as_ref()
+            .and_then(|header| header.scaling(sv.constellation, observable.clone()))
+            .map(|scaling| *scaling as f64)
+            .unwrap_or(1.0)
+    }
     /// Returns an iterator over pseudo range observations.
     /// ```
     /// use rinex::prelude::*;
@@ -2037,8 +2874,114 @@ impl Rinex {
             }
         }))
     }
-    /// Returns an iterator over doppler shifts. A positive doppler
-    /// means SV is moving towards receiver.
+    /// Returns the Geometry Free combination of phase observations, expressed in meters:
+    /// λa * Φa - λb * Φb, where a and b are the two lowest carrier frequencies
+    /// observed for each constellation, a being the highest.
+    /// Phase observations are correctly scaled, when Self is a High Precision RINEX.
+    /// SV and Epochs that do not observe both carriers are skipped.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// for (t, sv, gf) in rinex.geometry_free_phase() {
+    ///     // gf: meters
+    /// }
+    /// ```
+    pub fn geometry_free_phase(&self) -> Box<dyn Iterator<Item = (Epoch, SV, f64)> + '_> {
+        self.geometry_free(true)
+    }
+    /// Returns the Geometry Free combination of pseudo range observations, expressed in meters:
+    /// Pb - Pa, where a and b are the two lowest carrier frequencies
+    /// observed for each constellation, a being the highest.
+    /// SV and Epochs that do not observe both carriers are skipped.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// for (t, sv, gf) in rinex.geometry_free_pseudo_range() {
+    ///     // gf: meters
+    /// }
+    /// ```
+    pub fn geometry_free_pseudo_range(&self) -> Box<dyn Iterator<Item = (Epoch, SV, f64)> + '_> {
+        self.geometry_free(false)
+    }
+    /*
+     * Geometry free combination, either on phase or pseudo range observations.
+     */
+    fn geometry_free(&self, phase: bool) -> Box<dyn Iterator<Item = (Epoch, SV, f64)> + '_> {
+        let physics = |observable: &Observable| {
+            if phase {
+                observable.is_phase_observable()
+            } else {
+                observable.is_pseudorange_observable()
+            }
+        };
+        // two lowest carriers, per constellation
+        let mut carriers = HashMap::<Constellation, Vec<Carrier>>::new();
+        for (_, (_, vehicles)) in self.observation() {
+            for (sv, observations) in vehicles {
+                for observable in observations.keys().filter(|obs| physics(*obs)) {
+                    if let Ok(carrier) = observable.carrier(sv.constellation) {
+                        let list = carriers.entry(sv.constellation).or_default();
+                        if !list.contains(&carrier) {
+                            list.push(carrier);
+                        }
+                    }
+                }
+            }
+        }
+        for list in carriers.values_mut() {
+            list.sort_by(|a, b| a.frequency().partial_cmp(&b.frequency()).unwrap());
+            list.truncate(2);
+        }
+
+        let mut ret = Vec::<(Epoch, SV, f64)>::new();
+        for ((t, _), (_, vehicles)) in self.observation() {
+            for (sv, observations) in vehicles {
+                let (lowest, highest) = match carriers.get(&sv.constellation) {
+                    Some(list) if list.len() == 2 => (list[0], list[1]),
+                    _ => continue,
+                };
+                let mut observables = observations
+                    .keys()
+                    .filter(|obs| physics(*obs))
+                    .collect::<Vec<_>>();
+                observables.sort();
+
+                // picks first observation (in alphabetical order) on this carrier,
+                // converted to meters
+                let meters = |carrier: Carrier| -> Option<f64> {
+                    let observable = observables
+                        .iter()
+                        .find(|obs| obs.carrier(sv.constellation).ok() == Some(carrier))?;
+                    let mut value = observations.get(*observable)?.obs;
+                    if let Some(header) = &self.header.obs {
+                        if let Some(scaling) =
+                            header.scaling(sv.constellation, (*observable).clone())
+                        {
+                            value /= *scaling as f64;
+                        }
+                    }
+                    if phase {
+                        value *= carrier.wavelength();
+                    }
+                    Some(value)
+                };
+
+                if let (Some(a), Some(b)) = (meters(highest), meters(lowest)) {
+                    let gf = if phase { a - b } else { b - a };
+                    ret.push((*t, *sv, gf));
+                }
+            }
+        }
+        Box::new(ret.into_iter())
+    }
+    /// Returns an iterator over doppler shifts, expressed in Hz.
+    /// As per RINEX convention, a positive doppler shift means
+    /// the SV is approaching the receiver, a negative doppler shift means
+    /// the SV is moving away.
+    /// If Self is a High Precision RINEX (scaled RINEX), data is correctly scaled.
+    /// Returns an empty iterator if Self is not an Observation RINEX.
     /// ```
     /// use rinex::prelude::*;
     /// use rinex::observable;
@@ -2061,9 +3004,20 @@ impl Rinex {
     ) -> Box<dyn Iterator<Item = ((Epoch, EpochFlag), SV, &Observable, f64)> + '_> {
         Box::new(self.observation().flat_map(|(e, (_, vehicles))| {
             vehicles.iter().flat_map(|(sv, observations)| {
-                observations.iter().filter_map(|(obs, obsdata)| {
-                    if obs.is_doppler_observable() {
-                        Some((*e, *sv, obs, obsdata.obs))
+                observations.iter().filter_map(|(observable, obsdata)| {
+                    if observable.is_doppler_observable() {
+                        if let Some(header) = &self.header.obs {
+                            // apply a scaling (if any), otherwise preserve data precision
+                            if let Some(scaling) =
+                                header.scaling(sv.constellation, observable.clone())
+                            {
+                                Some((*e, *sv, observable, obsdata.obs / *scaling as f64))
+                            } else {
+                                Some((*e, *sv, observable, obsdata.obs))
+                            }
+                        } else {
+                            Some((*e, *sv, observable, obsdata.obs))
+                        }
                     } else {
                         None
                     }
@@ -2071,7 +3025,11 @@ impl Rinex {
             })
         }))
     }
-    /// Returns an iterator over signal strength observations.
+    /// Returns an iterator over signal strength observations (S-codes like `S1` or `S2C`),
+    /// as stored in the record, usually expressed in dB-Hz.
+    /// Unlike [Self::snr], these are actual observations, not the SNR indicator
+    /// that may be attached to any observation.
+    /// If Self is a High Precision RINEX (scaled RINEX), data is correctly scaled.
     /// ```
     /// use rinex::prelude::*;
     /// use rinex::observable;
@@ -2088,13 +3046,24 @@ impl Rinex {
     ///             None
     ///         }
     ///     });
+    /// for ((epoch, flag), sv, s1) in ssi_l1 {
+    ///     assert!(s1 > 0.0);
+    /// }
     /// ```
     pub fn ssi(&self) -> Box<dyn Iterator<Item = ((Epoch, EpochFlag), SV, &Observable, f64)> + '_> {
         Box::new(self.observation().flat_map(|(e, (_, vehicles))| {
             vehicles.iter().flat_map(|(sv, observations)| {
-                observations.iter().filter_map(|(obs, obsdata)| {
-                    if obs.is_ssi_observable() {
-                        Some((*e, *sv, obs, obsdata.obs))
+                observations.iter().filter_map(|(observable, obsdata)| {
+                    if observable.is_ssi_observable() {
+                        // apply a scaling (if any), otherwise preserve data precision
+                        let scaling = self
+                            .header
+                            .obs
+                            .as_ref()
+                            .and_then(|header| header.scaling(sv.constellation, observable.clone()))
+                            .map(|scaling| *scaling as f64)
+                            .unwrap_or(1.0);
+                        Some((*e, *sv, observable, obsdata.obs / scaling))
                     } else {
                         None
                     }
@@ -2132,6 +3101,68 @@ impl Rinex {
             })
         }))
     }
+    /// Returns SNR for said [SV] and [Observable], resampled over the `grid`, typically
+    /// to obtain continuous plots. When `observable` is a signal strength observable
+    /// (S-code), the observation itself is used, otherwise we use the SNR indicator
+    /// attached to `observable`, expressed in dB-Hz. Observations are descaled
+    /// when the header declares a scaling factor (high precision RINEX).
+    /// Gaps of a single missing sample (with respect to [Self::dominant_sample_rate])
+    /// are linearly interpolated. Larger gaps remain None.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use rinex::observable;
+    /// use std::str::FromStr;
+    ///
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/ACOR00ESP_R_20213550000_01D_30S_MO.rnx")
+    ///     .unwrap();
+    ///
+    /// let g01 = SV::from_str("G01").unwrap();
+    /// let grid = rinex.timeseries().unwrap();
+    ///
+    /// for (t, snr) in rinex.snr_interpolated(g01, &observable!("S1C"), grid) {
+    ///     if let Some(snr) = snr {
+    ///         // dB-Hz
+    ///     }
+    /// }
+    /// ```
+    pub fn snr_interpolated(
+        &self,
+        sv: SV,
+        observable: &Observable,
+        grid: TimeSeries,
+    ) -> Box<dyn Iterator<Item = (Epoch, Option<f64>)> + '_> {
+        let is_ssi = observable.is_ssi_observable();
+        let scaling = self.observation_scaling(&sv, observable);
+        let samples = self
+            .observation()
+            .filter_map(|((t, _), (_, vehicles))| {
+                let data = vehicles.get(&sv)?.get(observable)?;
+                if is_ssi {
+                    Some((*t, data.obs / scaling))
+                } else {
+                    Some((*t, f64::from(data.snr?)))
+                }
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let max_gap = self.dominant_sample_rate().map(|dt| dt * 2.0);
+
+        Box::new(grid.map(move |t| {
+            if let Some(value) = samples.get(&t) {
+                return (t, Some(*value));
+            }
+            let interpolated = max_gap.and_then(|max_gap| {
+                let (t0, y0) = samples.range(..t).next_back()?;
+                let (t1, y1) = samples.range(t..).next()?;
+                if *t1 - *t0 > max_gap {
+                    return None;
+                }
+                let dy = (y1 - y0) / (*t1 - *t0).to_seconds();
+                Some(y0 + dy * (t - *t0).to_seconds())
+            });
+            (t, interpolated)
+        }))
+    }
     /// Returns an Iterator over LLI flags that might be associated to an Observation.
     /// ```
     /// use rinex::*;
@@ -2163,6 +3194,21 @@ impl Rinex {
     /// "Complete" Epochs are Epochs were both Phase and Pseudo Range
     /// observations are present on two carriers, sane sampling conditions are met
     /// and an optional minimal SNR criteria is met (disregarded if None).
+    /// Each [SV] is listed once per secondary carrier (L1 being the primary carrier),
+    /// sorted by [SV] then by [Carrier]. Signals whose [Carrier] cannot be identified
+    /// are disregarded, and so are observations that do not come with an SNR indication
+    /// when a criteria is specified.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use rinex::observation::SNR;
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// for (t, signals) in rinex.complete_epoch(Some(SNR::from(30.0))) {
+    ///     for (sv, carrier) in signals {
+    ///         // L1 + carrier dual frequency candidate
+    ///     }
+    /// }
+    /// ```
     pub fn complete_epoch(
         &self,
         min_snr: Option<SNR>,
@@ -2174,7 +3220,7 @@ impl Rinex {
                         let mut list: Vec<(SV, Carrier)> = Vec::new();
                         for (sv, observables) in vehicles {
                             let mut l1_pr_ph = (false, false);
-                            let mut lx_pr_ph: HashMap<Carrier, (bool, bool)> = HashMap::new();
+                            let mut lx_pr_ph: BTreeMap<Carrier, (bool, bool)> = BTreeMap::new();
                             for (observable, observation) in observables {
                                 if !observable.is_phase_observable()
                                     && !observable.is_pseudorange_observable()
@@ -2225,7 +3271,136 @@ impl Rinex {
                 .filter(|(_sv, list)| !list.is_empty()),
         )
     }
+    /// Retains "complete" Epochs only, as defined by [Self::complete_epoch].
+    /// Within retained Epochs, each [SV] only retains observations made on [Carrier]s
+    /// that are part of a complete pair: the L1 primary carrier and the secondary carriers.
+    /// Vehicles and Epochs that end up empty are removed.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let mut rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// rinex.complete_epoch_filter_mut(None);
+    /// ```
+    pub fn complete_epoch_filter_mut(&mut self, min_snr: Option<SNR>) {
+        let complete = self
+            .complete_epoch(min_snr)
+            .collect::<HashMap<Epoch, Vec<(SV, Carrier)>>>();
+        if let Some(r) = self.record.as_mut_obs() {
+            r.retain(|(e, _), (_, vehicles)| {
+                let signals = match complete.get(e) {
+                    Some(signals) => signals,
+                    None => return false,
+                };
+                vehicles.retain(|sv, observables| {
+                    observables.retain(|observable, _| {
+                        match Carrier::from_observable(sv.constellation, observable) {
+                            Ok(Carrier::L1) => signals.iter().any(|(svnn, _)| svnn == sv),
+                            Ok(carrier) => signals.contains(&(*sv, carrier)),
+                            Err(_) => false,
+                        }
+                    });
+                    !observables.is_empty()
+                });
+                !vehicles.is_empty()
+            });
+        }
+    }
+    /// Drops observations that are known receiver sentinel values,
+    /// flagging invalid or saturated data, following the [Sentinels] definition.
+    /// Vehicles and Epochs that end up empty are removed.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use rinex::observation::Sentinels;
+    /// let mut rinex = Rinex::from_file("../test_resources/OBS/V3/VLNS0010.22O")
+    ///     .unwrap();
+    /// // also drop null phase observations
+    /// let sentinels = Sentinels::default()
+    ///     .with_phase(0.0);
+    /// rinex.drop_sentinels_mut(&sentinels);
+    /// ```
+    pub fn drop_sentinels_mut(&mut self, sentinels: &Sentinels) {
+        if let Some(r) = self.record.as_mut_obs() {
+            r.retain(|_, (_, vehicles)| {
+                vehicles.retain(|_, observables| {
+                    observables
+                        .retain(|observable, data| !sentinels.is_sentinel(observable, data.obs));
+                    !observables.is_empty()
+                });
+                !vehicles.is_empty()
+            });
+        }
+    }
+    /// Drops observations that are known receiver sentinel values,
+    /// as defined by [Sentinels::default]: null pseudo ranges and
+    /// values that saturate the RINEX format. See [Self::drop_sentinels_mut]
+    /// to customize the sentinel values.
+    pub fn drop_sentinel_values_mut(&mut self) {
+        self.drop_sentinels_mut(&Sentinels::default());
+    }
+    /// Drops observations that are known receiver sentinel values,
+    /// immutable implementation, see [Self::drop_sentinel_values_mut].
+    pub fn drop_sentinel_values(&self) -> Self {
+        let mut s = self.clone();
+        s.drop_sentinel_values_mut();
+        s
+    }
+    /// Drops Epochs where fewer than `min` [SV] were observed,
+    /// which is typically used to retain Epochs that allow positioning (`min=4`).
+    /// Refer to [Self::retain_min_constellation_sv_epochs_mut] to only
+    /// account for one [Constellation].
+    /// ```
+    /// use rinex::prelude::*;
+    /// let mut rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// rinex.retain_min_sv_epochs_mut(4);
+    /// for (_, (_, vehicles)) in rinex.observation() {
+    ///     assert!(vehicles.len() >= 4);
+    /// }
+    /// ```
+    pub fn retain_min_sv_epochs_mut(&mut self, min: usize) {
+        if let Some(r) = self.record.as_mut_obs() {
+            r.retain(|_, (_, vehicles)| vehicles.len() >= min);
+        }
+    }
+    /// Drops Epochs where fewer than `min` [SV] were observed,
+    /// immutable implementation, see [Self::retain_min_sv_epochs_mut].
+    pub fn retain_min_sv_epochs(&self, min: usize) -> Self {
+        let mut s = self.clone();
+        s.retain_min_sv_epochs_mut(min);
+        s
+    }
+    /// Drops Epochs where fewer than `min` [SV] of given [Constellation] were observed,
+    /// for example to retain Epochs that allow GPS only positioning.
+    /// Other constellations are preserved within retained Epochs.
+    pub fn retain_min_constellation_sv_epochs_mut(
+        &mut self,
+        constellation: Constellation,
+        min: usize,
+    ) {
+        if let Some(r) = self.record.as_mut_obs() {
+            r.retain(|_, (_, vehicles)| {
+                vehicles
+                    .keys()
+                    .filter(|sv| sv.constellation == constellation)
+                    .count()
+                    >= min
+            });
+        }
+    }
+    /// Drops Epochs where fewer than `min` [SV] of given [Constellation] were observed,
+    /// immutable implementation, see [Self::retain_min_constellation_sv_epochs_mut].
+    pub fn retain_min_constellation_sv_epochs(
+        &self,
+        constellation: Constellation,
+        min: usize,
+    ) -> Self {
+        let mut s = self.clone();
+        s.retain_min_constellation_sv_epochs_mut(constellation, min);
+        s
+    }
     /// Returns Code Multipath bias estimates, for sampled code combination and per SV.
+    /// Each pseudo range is combined with two phase observations on distinct carriers
+    /// (classical MP1/MP2 combinations). Epochs lacking them are skipped.
     /// Refer to [Bibliography::ESABookVol1] and [Bibliography::MpTaoglas].
     pub fn code_multipath(
         &self,
@@ -2236,6 +3411,380 @@ impl Rinex {
             HashMap::new()
         }
     }
+    /// Returns the RMS code multipath (in meters) per [SV] and pseudo range code,
+    /// over the whole file, which is a standard site quality metric.
+    /// [Self::code_multipath] is only known up to the phase ambiguities: each continuous
+    /// arc is centered on its mean value. A new arc starts on every phase lock loss
+    /// ([LliFlags::LOCK_LOSS]), every epoch that is not flagged OK, every data gap
+    /// (with respect to [Self::dominant_sample_rate]) and every multipath jump
+    /// larger than 5 m, which catches cycle slips the receiver did not flag.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/ACOR00ESP_R_20213550000_01D_30S_MO.rnx")
+    ///     .unwrap();
+    /// let rms = rinex.multipath_rms();
+    /// let g08_c1c = rms.get(&(SV::new(Constellation::GPS, 8), Observable::from_str("C1C").unwrap()))
+    ///     .unwrap();
+    /// assert!(*g08_c1c < 0.1);
+    /// ```
+    pub fn multipath_rms(&self) -> BTreeMap<(SV, Observable), f64> {
+        if let Some(r) = self.record.as_obs() {
+            multipath_rms(r, self.dominant_sample_rate())
+        } else {
+            BTreeMap::new()
+        }
+    }
+    /// Returns observations that exist in both `self` and `rhs`, with different values.
+    /// [Merge] preserves the `self` value in this case, so this lists all
+    /// `rhs` observations that [Merge::merge_mut] will drop. Returns an empty list
+    /// if either is not an Observation RINEX.
+    /// ```
+    /// use rinex::prelude::*;
+    ///
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// // no conflicts with itself
+    /// assert!(rinex.merge_conflicts(&rinex).is_empty());
+    /// ```
+    pub fn merge_conflicts(&self, rhs: &Self) -> Vec<MergeConflict> {
+        match (self.record.as_obs(), rhs.record.as_obs()) {
+            (Some(lhs), Some(rhs)) => merge_conflicts(lhs, rhs),
+            _ => Vec::new(),
+        }
+    }
+    /// Identifies the unit pseudo range observations are expressed in,
+    /// by comparing their median magnitude to expected GNSS ranges
+    /// (roughly 20,000 km for MEO vehicles, up to 40,000 km for GEO).
+    /// Some malformed files express pseudo ranges in kilometers, see
+    /// [Self::normalize_pseudorange_units_mut].
+    /// ```
+    /// use rinex::prelude::*;
+    /// use rinex::observation::PseudorangeUnit;
+    ///
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// assert_eq!(rinex.detect_pseudorange_units(), PseudorangeUnit::Meters);
+    /// ```
+    pub fn detect_pseudorange_units(&self) -> PseudorangeUnit {
+        let mut values = self
+            .pseudo_range()
+            .map(|(_, sv, observable, value)| {
+                let scaling = self
+                    .header
+                    .obs
+                    .as_ref()
+                    .and_then(|header| header.scaling(sv.constellation, observable.clone()))
+                    .map(|scaling| *scaling as f64)
+                    .unwrap_or(1.0);
+                (value / scaling).abs()
+            })
+            .filter(|value| value.is_finite() && *value > 0.0)
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            return PseudorangeUnit::Unknown;
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let median = values[values.len() / 2];
+        if (1.0E6..1.0E8).contains(&median) {
+            PseudorangeUnit::Meters
+        } else if (1.0E3..1.0E5).contains(&median) {
+            PseudorangeUnit::Kilometers
+        } else {
+            PseudorangeUnit::Unknown
+        }
+    }
+    /// Converts pseudo range observations to meters, if they were identified
+    /// as kilometers by [Self::detect_pseudorange_units]. Returns the
+    /// unit that was identified.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use rinex::observation::PseudorangeUnit;
+    ///
+    /// let mut rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// // pseudo ranges are already expressed in meters: this has no effect
+    /// assert_eq!(rinex.normalize_pseudorange_units_mut(), PseudorangeUnit::Meters);
+    /// ```
+    pub fn normalize_pseudorange_units_mut(&mut self) -> PseudorangeUnit {
+        let unit = self.detect_pseudorange_units();
+        if unit == PseudorangeUnit::Kilometers {
+            if let Some(r) = self.record.as_mut_obs() {
+                for (_, (_, vehicles)) in r.iter_mut() {
+                    for (_, observations) in vehicles.iter_mut() {
+                        for (observable, data) in observations.iter_mut() {
+                            if observable.is_pseudorange_observable() {
+                                data.obs *= 1.0E3;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        unit
+    }
+    /// Returns Melbourne-Wübbena combination, per [SV] and [Epoch], expressed in wide-lane cycles.
+    /// It combines wide-lane phase and narrow-lane code from two carrier frequencies,
+    /// which cancels out the geometry, clocks and ionospheric delay. The remaining
+    /// wide-lane ambiguity is very stable and makes this combination the
+    /// preferred cycle slip indicator. This is [Combination::MelbourneWubbena],
+    /// with data scaling applied (high precision RINEX), converted to wide-lane cycles.
+    /// A value is only emitted when both phase and both matching pseudo range
+    /// observations exist (C codes, or P codes for V2 files). When several
+    /// combinations exist for one vehicle, the first one in alphabetical order is retained.
+    /// Refer to [Bibliography::ESAGnssCombination].
+    /// ```
+    /// use rinex::prelude::*;
+    ///
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    ///
+    /// let mut last = std::collections::HashMap::<SV, f64>::new();
+    /// for (t, sv, mw) in rinex.melbourne_wubbena() {
+    ///     if let Some(prev) = last.insert(sv, mw) {
+    ///         if (mw - prev).abs() > 4.0 {
+    ///             println!("{} {}: possible cycle slip", t, sv);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn melbourne_wubbena(&self) -> Box<dyn Iterator<Item = (Epoch, SV, f64)> + '_> {
+        let record = match self.record.as_obs() {
+            Some(record) => record,
+            None => return Box::new(std::iter::empty()),
+        };
+
+        let has_scaling = self
+            .header
+            .obs
+            .as_ref()
+            .map(|header| !header.scaling.is_empty())
+            .unwrap_or(false);
+
+        let combinations = if has_scaling {
+            let mut record = record.clone();
+            for (_, (_, vehicles)) in record.iter_mut() {
+                for (sv, observations) in vehicles.iter_mut() {
+                    for (observable, data) in observations.iter_mut() {
+                        data.obs /= self.observation_scaling(sv, observable);
+                    }
+                }
+            }
+            record.combine(Combination::MelbourneWubbena)
+        } else {
+            record.combine(Combination::MelbourneWubbena)
+        };
+
+        let mut values = BTreeMap::<(Epoch, SV), f64>::new();
+        for ((lhs, reference), vehicles) in combinations
+            .into_iter()
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        {
+            for (sv, data) in vehicles {
+                let lhs = Carrier::from_observable(sv.constellation, &lhs);
+                let reference = Carrier::from_observable(sv.constellation, &reference);
+                let (lhs, reference) = match (lhs, reference) {
+                    (Ok(lhs), Ok(reference)) => (lhs, reference),
+                    _ => continue,
+                };
+                let lambda_wl = 1.0 / (1.0 / reference.wavelength() - 1.0 / lhs.wavelength());
+                for ((t, _), mw) in data {
+                    let mw = mw / lambda_wl;
+                    if mw.is_finite() {
+                        values.entry((t, sv)).or_insert(mw);
+                    }
+                }
+            }
+        }
+
+        Box::new(values.into_iter().map(|((t, sv), mw)| (t, sv, mw)))
+    }
+    /// Estimates receiver noise (1 sigma) of each phase and pseudo range [Observable],
+    /// per [Constellation], to be used in stochastic modeling (solver weights).
+    /// Third order time differences cancel the geometry and clock terms, which
+    /// is only true on high rate data (typically 1 Hz). Robust (MAD based) estimates
+    /// are obtained per [SV], then aggregated (median) per [Observable].
+    /// Arcs are interrupted by data gaps, lock losses and epoch events,
+    /// and those shorter than `window` are disregarded.
+    /// Estimates are expressed in the observable unit: meters for pseudo range, cycles for phase.
+    /// Only relevant on OBS RINEX.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// let noise = rinex.observation_noise(Duration::from_seconds(30.0));
+    /// for ((constellation, observable), sigma) in noise {
+    ///     println!("{}({}): {}", constellation, observable, sigma);
+    /// }
+    /// ```
+    pub fn observation_noise(
+        &self,
+        window: Duration,
+    ) -> BTreeMap<(Constellation, Observable), f64> {
+        let sampling = self.dominant_sample_rate();
+        match (self.record.as_obs(), sampling) {
+            (Some(r), Some(sampling)) => {
+                observation_noise(r, self.header.obs.as_ref(), sampling, window)
+            },
+            _ => BTreeMap::new(),
+        }
+    }
+    /// Returns [ObservableCoverage], describing how many epochs each [SV]
+    /// is observed with each [Observable]. Only relevant on OBS RINEX.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V2/AJAC3550.21O")
+    ///     .unwrap();
+    /// let coverage = rinex.observable_coverage();
+    /// for (sv, counts) in coverage.counts.iter() {
+    ///     for (observable, count) in counts.iter() {
+    ///         println!("{}({}): {}/{}", sv, observable, count, coverage.epochs);
+    ///     }
+    /// }
+    /// ```
+    pub fn observable_coverage(&self) -> ObservableCoverage {
+        self.record
+            .as_obs()
+            .map(ObservableCoverage::new)
+            .unwrap_or_default()
+    }
+    /// Experimental: repairs carrier phase cycle slips by Doppler integration.
+    /// This is mostly intended to single frequency users.
+    /// At each slip, either flagged by the receiver (LLI) or detected by comparing
+    /// the phase jump to the integrated Doppler shift, we estimate the integer
+    /// number of cycles and correct all subsequent phase observations, only if the
+    /// residual is below 0.3 cycles. Jumps are never corrected when the confidence is low,
+    /// nor across gaps larger than `max_gap`: a new arc starts, to which previous
+    /// repairs still apply.
+    /// Each repair is described in the header comments, and the returned
+    /// [CycleSlipReport] lists both repaired and unrepairable slips.
+    /// Only relevant on OBS RINEX.
+    /// ```
+    /// use rinex::prelude::*;
+    ///
+    /// let mut rinex = Rinex::from_file("../test_resources/OBS/V3/ACOR00ESP_R_20213550000_01D_30S_MO.rnx")
+    ///     .unwrap();
+    ///
+    /// let report = rinex.repair_cycle_slips_mut(Duration::from_seconds(30.0));
+    /// for slip in report.unrepairable {
+    ///     println!("{} {} {}: could not repair", slip.epoch, slip.sv, slip.observable);
+    /// }
+    /// ```
+    pub fn repair_cycle_slips_mut(&mut self, max_gap: Duration) -> CycleSlipReport {
+        let report = match self.record.as_mut_obs() {
+            Some(r) => repair_cycle_slips(r, self.header.obs.as_ref(), max_gap),
+            None => return CycleSlipReport::default(),
+        };
+        for slip in report.repaired.iter() {
+            self.header.comments.push(format!(
+                "rustrnx CS REPAIR {:x} {} {} {:+}",
+                slip.sv,
+                slip.observable,
+                slip.epoch,
+                slip.cycles.unwrap_or_default()
+            ));
+        }
+        report
+    }
+    /// Applies a Hatch filter to all pseudo range observations: each pseudo range
+    /// is smoothed using the carrier phase of the same signal (for example C1C with L1C),
+    /// which greatly reduces code noise and multipath, prior single point positioning.
+    /// The filter is reset on each lock loss (LLI), each epoch that is not flagged OK,
+    /// like [EpochFlag::CycleSlip], and each data gap that exceeds the sampling interval
+    /// (header value, otherwise [Self::dominant_sample_rate]). Pseudo ranges that do not come with a phase
+    /// observation are left untouched, so are Glonass FDMA signals of vehicles
+    /// whose channel is not described in the header. Only relevant on OBS RINEX.
+    /// The filter window is capped to 100 samples, see [Self::hatch_smoothing_window_mut].
+    /// ```
+    /// use rinex::prelude::*;
+    /// let mut rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// rinex.hatch_smoothing_mut();
+    /// ```
+    pub fn hatch_smoothing_mut(&mut self) {
+        self.hatch_smoothing_window_mut(HATCH_SMOOTHING_WINDOW);
+    }
+    /// [Self::hatch_smoothing_mut] immutable implementation.
+    pub fn hatch_smoothing(&self) -> Self {
+        let mut s = self.clone();
+        s.hatch_smoothing_mut();
+        s
+    }
+    /// Applies a Hatch filter like [Self::hatch_smoothing_mut], where the filter
+    /// memory is capped to `window` samples. Long windows smooth the code noise further
+    /// but accumulate the code/carrier ionospheric divergence: adapt the window
+    /// to the sampling period.
+    pub fn hatch_smoothing_window_mut(&mut self, window: u32) {
+        let sampling = self
+            .header
+            .sampling_interval
+            .or_else(|| self.dominant_sample_rate());
+        if let Some(r) = self.record.as_mut_obs() {
+            hatch_smoothing(
+                r,
+                self.header.obs.as_ref(),
+                &self.header.glo_channels,
+                sampling,
+                window,
+            );
+        }
+    }
+    /// [Self::hatch_smoothing_window_mut] immutable implementation.
+    pub fn hatch_smoothing_window(&self, window: u32) -> Self {
+        let mut s = self.clone();
+        s.hatch_smoothing_window_mut(window);
+        s
+    }
+    /// Returns every [Epoch] where the integer ambiguity of this phase [Observable]
+    /// likely reset, for this [SV]: either the receiver flagged a lock loss ([LliFlags::LOCK_LOSS]),
+    /// or the phase jumped with respect to the previous samples.
+    /// The default threshold is 1000 cycles, see [Self::phase_reset_epochs_with_threshold].
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/ACOR00ESP_R_20213550000_01D_30S_MO.rnx")
+    ///     .unwrap();
+    /// let l1c = Observable::from_str("L1C").unwrap();
+    /// for t in rinex.phase_reset_epochs(SV::new(Constellation::GPS, 1), &l1c) {
+    ///     // ambiguity should be solved again
+    /// }
+    /// ```
+    pub fn phase_reset_epochs(
+        &self,
+        sv: SV,
+        observable: &Observable,
+    ) -> Box<dyn Iterator<Item = Epoch> + '_> {
+        self.phase_reset_epochs_with_threshold(sv, observable, PHASE_RESET_THRESHOLD)
+    }
+    /// Identifies ambiguity resets like [Self::phase_reset_epochs], where a phase jump
+    /// is reported when the phase deviates by more than `threshold` cycles
+    /// from its linear prediction, based on the two previous samples.
+    /// Natural phase accelerations grow with the sampling period, adapt the
+    /// threshold accordingly.
+    pub fn phase_reset_epochs_with_threshold(
+        &self,
+        sv: SV,
+        observable: &Observable,
+        threshold: f64,
+    ) -> Box<dyn Iterator<Item = Epoch> + '_> {
+        if let Some(r) = self.record.as_obs() {
+            Box::new(phase_resets(
+                r,
+                self.header.obs.as_ref(),
+                sv,
+                observable,
+                threshold,
+            ))
+        } else {
+            Box::new([].into_iter())
+        }
+    }
+    /// [Self::repair_cycle_slips_mut] immutable implementation.
+    pub fn carrier_phase_cycle_slips_repaired(&self, max_gap: Duration) -> (Self, CycleSlipReport) {
+        let mut s = self.clone();
+        let report = s.repair_cycle_slips_mut(max_gap);
+        (s, report)
+    }
 }
 
 #[cfg(feature = "nav")]
@@ -2243,6 +3792,9 @@ use crate::navigation::{
     BdModel, EopMessage, Ephemeris, IonMessage, KbModel, NavMsgType, NgModel, StoMessage,
 };
 
+#[cfg(feature = "nav")]
+use nalgebra::{Matrix3, Vector3};
+
 /*
  * NAV RINEX specific methods: only available on crate feature.
  * Either specific Iterators, or meaningful data we can extract.
@@ -2329,6 +3881,123 @@ impl Rinex {
         let (toc, _, eph) = self.sv_ephemeris(sv, t)?;
         eph.kepler2position(sv, toc, t)
     }
+    /// Evaluates [SV] ECEF position, expressed as (x, y, z) in [km], at any [Epoch] `t`,
+    /// not only at publication time, from the ephemeris selected by [Self::sv_ephemeris].
+    /// Keplerian ephemerides are evaluated at `t`, while Glonass state vectors are
+    /// integrated up to `t` (refer to [Ephemeris::glonass_position_velocity]).
+    /// Returns None if no ephemeris is valid at `t` (refer to [Ephemeris::max_dtoe]).
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/CBW100NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// let g20 = SV::from_str("G20").unwrap();
+    /// let t = Epoch::from_str("2021-01-01T16:21:30 GPST").unwrap();
+    /// let (x_km, y_km, z_km) = rinex.sv_position_interpolate(g20, t)
+    ///     .unwrap();
+    /// ```
+    pub fn sv_position_interpolate(&self, sv: SV, t: Epoch) -> Option<(f64, f64, f64)> {
+        let state = self.sv_orbit(sv, t)?.to_cartesian_pos_vel();
+        Some((state[0], state[1], state[2]))
+    }
+    /// [SV] ECEF position Iterator, expressed as (x, y, z) in [km].
+    /// Position is resolved at each ToC [Epoch], from the corresponding Ephemeris.
+    /// Glonass positions are converted from PZ-90 to WGS84 like other vehicles.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// for (epoch, sv, (x_km, y_km, z_km)) in rinex.sv_position() {
+    ///     // ECEF coordinates
+    /// }
+    /// ```
+    pub fn sv_position(&self) -> Box<dyn Iterator<Item = (Epoch, SV, (f64, f64, f64))> + '_> {
+        Box::new(self.ephemeris().filter_map(|(toc, (_, sv, eph))| {
+            let orbit = eph.kepler2position(sv, *toc, *toc)?;
+            let state = orbit.to_cartesian_pos_vel();
+            Some((*toc, sv, (state[0], state[1], state[2])))
+        }))
+    }
+    /// Samples the ground track of said [SV], that is the geodetic latitude and longitude
+    /// (both in degrees) of the sub-satellite point, every `dt` across the time span
+    /// of this file. Samples where no ephemeris is valid are omitted.
+    /// This is typically used for map plotting.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// let g01 = SV::from_str("G01").unwrap();
+    /// for (t, lat_ddeg, long_ddeg) in rinex.sv_ground_track(g01, Duration::from_seconds(60.0)) {
+    ///     // sub-satellite point
+    /// }
+    /// ```
+    pub fn sv_ground_track(&self, sv: SV, dt: Duration) -> Vec<(Epoch, f64, f64)> {
+        let mut track = Vec::<(Epoch, f64, f64)>::new();
+        let (start, end) = match (self.first_epoch(), self.last_epoch()) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return track,
+        };
+        if dt <= Duration::ZERO {
+            return track;
+        }
+        let mut t = start;
+        while t <= end {
+            if let Some((x_km, y_km, z_km)) = self.sv_position_interpolate(sv, t) {
+                let position =
+                    GroundPosition::from_ecef_wgs84((x_km * 1.0E3, y_km * 1.0E3, z_km * 1.0E3));
+                let (lat, long, _) = position.to_geodetic();
+                track.push((t, lat, long));
+            }
+            t += dt;
+        }
+        track
+    }
+    /// [SV] ECEF velocity Iterator, expressed as (vx, vy, vz) in [km/s].
+    /// Velocity is obtained by differentiating each pair of consecutive
+    /// [Self::sv_position] samples, for each [SV], using the actual sample spacing.
+    /// Velocity is assigned to the later [Epoch] of each pair, therefore the first
+    /// sample of each [SV] is never described, nor are vehicles that were only sampled once.
+    /// Refer to [Self::sv_speed_kepler] for an analytical (more accurate) approach.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// for (epoch, sv, (vx_km_s, vy_km_s, vz_km_s)) in rinex.sv_speed() {
+    ///     // ECEF velocity
+    /// }
+    /// ```
+    pub fn sv_speed(&self) -> Box<dyn Iterator<Item = (Epoch, SV, (f64, f64, f64))> + '_> {
+        let mut positions = BTreeMap::<SV, Vec<(Epoch, (f64, f64, f64))>>::new();
+        for (t, sv, pos) in self.sv_position() {
+            positions.entry(sv).or_default().push((t, pos));
+        }
+        let mut speed = Vec::<(Epoch, SV, (f64, f64, f64))>::new();
+        for (sv, mut samples) in positions {
+            samples.sort_by_key(|(t, _)| *t);
+            for pair in samples.windows(2) {
+                let (t0, (x0, y0, z0)) = pair[0];
+                let (t1, (x1, y1, z1)) = pair[1];
+                let dt = (t1 - t0).to_seconds();
+                if dt == 0.0 {
+                    continue; // same ToC
+                }
+                speed.push((t1, sv, ((x1 - x0) / dt, (y1 - y0) / dt, (z1 - z0) / dt)));
+            }
+        }
+        speed.sort_by_key(|(t, _, _)| *t);
+        Box::new(speed.into_iter())
+    }
+    /// [SV] ECEF velocity Iterator, expressed as (vx, vy, vz) in [km/s].
+    /// Unlike [Self::sv_speed], velocity is analytically derived from the
+    /// Keplerian elements (or directly picked up for GEO and Glonass vehicles),
+    /// at each ToC [Epoch].
+    pub fn sv_speed_kepler(&self) -> Box<dyn Iterator<Item = (Epoch, SV, (f64, f64, f64))> + '_> {
+        Box::new(self.ephemeris().filter_map(|(toc, (_, sv, eph))| {
+            let (_, vel) = eph.kepler2position_velocity(sv, *toc, *toc)?;
+            Some((*toc, sv, vel))
+        }))
+    }
     /// Returns [SV] attitude vector (if we can) at specified [Epoch] `t`
     /// with respect to specified reference point expressed as an [Orbit].
     /// [Self] must be NAV RINEX.
@@ -2345,28 +4014,183 @@ impl Rinex {
             .ok()?;
         Some(azelrange)
     }
+    /// Predicts visibility of each [SV] from given ground position, during said
+    /// `window`, by sampling broadcast positions every `step`. Samples where the
+    /// vehicle is above `min_elev` (in degrees) are merged into (start, end) intervals.
+    /// Vehicles are only predicted while an ephemeris is valid (refer to [Ephemeris::max_dtoe]):
+    /// vehicles that never rise above the mask, or that run out of ephemeris, are not
+    /// described. This is typically used to plan an observation session.
+    /// Elevation angles are resolved with [Ephemeris::elevation_azimuth_range],
+    /// in the `earth_cef` body fixed [Frame].
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    /// use anise::{
+    ///     constants::frames::IAU_EARTH_FRAME,
+    ///     structure::planetocentric::ellipsoid::Ellipsoid,
+    /// };
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    /// // body fixed frame, WGS84 shape
+    /// let almanac = Almanac::default();
+    /// let earth_cef = Frame {
+    ///     shape: Some(Ellipsoid::from_spheroid(6378.137, 6356.752314245)),
+    ///     ..IAU_EARTH_FRAME
+    /// };
+    /// // ESBC site
+    /// let site = GroundPosition::from_ecef_wgs84((3582105.2910, 532589.7313, 5232754.8054));
+    /// let window = (
+    ///     Epoch::from_str("2020-06-25T08:00:00 GPST").unwrap(),
+    ///     Epoch::from_str("2020-06-25T10:00:00 GPST").unwrap(),
+    /// );
+    /// let step = Duration::from_seconds(60.0);
+    /// let visibility = rinex.predict_visibility(&almanac, earth_cef, site, window, step, 10.0);
+    /// for (sv, passes) in visibility {
+    ///     for (rise, set) in passes {
+    ///         println!("{} visible from {} to {}", sv, rise, set);
+    ///     }
+    /// }
+    /// ```
+    pub fn predict_visibility(
+        &self,
+        almanac: &Almanac,
+        earth_cef: Frame,
+        ref_pos: GroundPosition,
+        window: (Epoch, Epoch),
+        step: Duration,
+        min_elev: f64,
+    ) -> BTreeMap<SV, Vec<(Epoch, Epoch)>> {
+        let mut ret = BTreeMap::<SV, Vec<(Epoch, Epoch)>>::new();
+        if step <= Duration::ZERO {
+            return ret;
+        }
+        let (start, end) = window;
+        let rx_km = ref_pos.to_ecef_wgs84();
+        let rx_km = (rx_km.0 / 1.0E3, rx_km.1 / 1.0E3, rx_km.2 / 1.0E3);
+        for sv in self.sv().unique() {
+            let mut intervals = Vec::<(Epoch, Epoch)>::new();
+            let mut current = Option::<(Epoch, Epoch)>::None;
+            let mut t = start;
+            while t <= end {
+                let visible = self
+                    .sv_position_interpolate(sv, t)
+                    .and_then(|sv_km| {
+                        Ephemeris::elevation_azimuth_range(t, almanac, earth_cef, sv_km, rx_km).ok()
+                    })
+                    .map(|el_az_range| el_az_range.elevation_deg >= min_elev)
+                    .unwrap_or(false);
+                if visible {
+                    current = match current {
+                        Some((rise, _)) => Some((rise, t)),
+                        None => Some((t, t)),
+                    };
+                } else if let Some(interval) = current.take() {
+                    intervals.push(interval);
+                }
+                t += step;
+            }
+            if let Some(interval) = current {
+                intervals.push(interval);
+            }
+            if !intervals.is_empty() {
+                ret.insert(sv, intervals);
+            }
+        }
+        ret
+    }
     /// Ephemeris selection method. Use this method to select Ephemeris
     /// for [SV] at [Epoch], to be used in navigation.
     /// Returns (ToC, ToE and ephemeris frame).
-    /// Note that ToE = ToC for GEO/SBAS vehicles, because this field does not exist.
+    /// Note that ToE = ToC for GEO/SBAS and Glonass vehicles, because this field does not exist.
+    /// Glonass ephemerides are selected by closest ToC, in either direction.
+    /// Broadcast health is not considered, refer to [Self::sv_ephemeris_healthy].
     pub fn sv_ephemeris(&self, sv: SV, t: Epoch) -> Option<(Epoch, Epoch, &Ephemeris)> {
+        self.sv_ephemeris_selection(sv, t, |_, _| true)
+    }
+    /// Same as [Self::sv_ephemeris], but rejects frames whose broadcast health word
+    /// marks [SV] as unusable, refer to [Ephemeris::is_healthy] for the
+    /// interpretation of each constellation. Frames whose health
+    /// word is not decoded (SBAS) are retained.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use rinex::navigation::NavMsgType;
+    /// use std::str::FromStr;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// let t = Epoch::from_str("2021-01-01T10:00:00 GPST").unwrap();
+    /// let sv = SV::from_str("G01").unwrap();
+    /// if let Some((_, _, eph)) = rinex.sv_ephemeris_healthy(sv, t) {
+    ///     assert_eq!(eph.is_healthy(sv, NavMsgType::LNAV), Some(true));
+    /// }
+    /// ```
+    pub fn sv_ephemeris_healthy(&self, sv: SV, t: Epoch) -> Option<(Epoch, Epoch, &Ephemeris)> {
+        self.sv_ephemeris_selection(sv, t, |msg, eph| eph.is_healthy(sv, msg) != Some(false))
+    }
+    /// Same as [Self::sv_ephemeris], but only selects frames that
+    /// describe given Issue Of Data (Ephemeris), refer to [Ephemeris::iode],
+    /// and whose clock and orbit records were issued together
+    /// (refer to [Ephemeris::is_iod_consistent]).
+    /// This is typically used to pair an orbit with the clock (or correction)
+    /// data of the same issue, when several sets are valid at the same time.
+    /// Glonass frames do not describe an IODE and are never selected.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// let t = Epoch::from_str("2021-01-01T10:00:00 GPST").unwrap();
+    /// let sv = SV::from_str("G01").unwrap();
+    /// if let Some((_, _, eph)) = rinex.sv_ephemeris(sv, t) {
+    ///     let iode = eph.iode().unwrap();
+    ///     let (_, _, same) = rinex.sv_ephemeris_iode(sv, t, iode)
+    ///         .unwrap();
+    ///     assert_eq!(same.iode(), Some(iode));
+    /// }
+    /// ```
+    pub fn sv_ephemeris_iode(
+        &self,
+        sv: SV,
+        t: Epoch,
+        iode: u32,
+    ) -> Option<(Epoch, Epoch, &Ephemeris)> {
+        self.sv_ephemeris_selection(sv, t, |_, eph| {
+            eph.iode() == Some(iode) && eph.is_iod_consistent()
+        })
+    }
+    /*
+     * Ephemeris selection, among frames that pass the test
+     */
+    fn sv_ephemeris_selection<F: Fn(NavMsgType, &Ephemeris) -> bool>(
+        &self,
+        sv: SV,
+        t: Epoch,
+        f: F,
+    ) -> Option<(Epoch, Epoch, &Ephemeris)> {
         let sv_ts = sv.constellation.timescale()?;
+        let ephemeris = self
+            .ephemeris()
+            .filter(move |(_, (msg_i, sv_i, eph_i))| *sv_i == sv && f(*msg_i, eph_i))
+            .map(|(t_i, (_, _, eph_i))| (t_i, eph_i));
         if sv.constellation.is_sbas() {
-            let (toc, (_, _, eph)) = self
-                .ephemeris()
-                .filter(|(t_i, (_, sv_i, eph_i))| sv == *sv_i)
-                .reduce(|k, _| k)?;
+            let (toc, eph) = ephemeris.reduce(|k, _| k)?;
             Some((*toc, *toc, eph))
-        } else {
-            self.ephemeris()
-                .filter_map(|(t_i, (_, sv_i, eph_i))| {
-                    if sv_i == sv {
-                        if eph_i.is_valid(sv, t) && t >= *t_i {
-                            let toe = eph_i.toe(sv_ts)?;
-                            Some((*t_i, toe, eph_i))
-                        } else {
-                            None
-                        }
+        } else if sv.constellation == Constellation::Glonass {
+            let max_dtoe = Ephemeris::max_dtoe(sv.constellation)?;
+            ephemeris
+                .filter_map(|(t_i, eph_i)| {
+                    if (t - *t_i).abs() < max_dtoe {
+                        Some((*t_i, *t_i, eph_i))
+                    } else {
+                        None
+                    }
+                })
+                .min_by_key(|(toc_i, _, _)| (t - *toc_i).abs())
+        } else {
+            ephemeris
+                .filter_map(|(t_i, eph_i)| {
+                    if eph_i.is_valid(sv, t) && t >= *t_i {
+                        let toe = eph_i.toe(sv_ts)?;
+                        Some((*t_i, toe, eph_i))
                     } else {
                         None
                     }
@@ -2374,6 +4198,109 @@ impl Rinex {
                 .min_by_key(|(toc_i, _, _)| (t - *toc_i).abs())
         }
     }
+    /// Returns broadcast health indication Iterator, true meaning
+    /// the vehicle is usable. Refer to [Ephemeris::is_healthy] for the
+    /// decoded constellations: frames that we cannot interpret are not exposed.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// for (toc, sv, healthy) in rinex.ephemeris_health() {
+    ///     if !healthy {
+    ///         println!("{}({}) should not be used", toc, sv);
+    ///     }
+    /// }
+    /// ```
+    pub fn ephemeris_health(&self) -> Box<dyn Iterator<Item = (Epoch, SV, bool)> + '_> {
+        Box::new(
+            self.ephemeris()
+                .filter_map(|(t, (msg, sv, eph))| Some((*t, sv, eph.is_healthy(sv, msg)?))),
+        )
+    }
+    /// Returns Issue Of Data (Ephemeris) Iterator, as (ToC, [SV], IODE).
+    /// Refer to [Ephemeris::iode] for the supported constellations:
+    /// frames that do not describe an IODE (Glonass) are not exposed.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// for (toc, sv, iode) in rinex.ephemeris_iode() {
+    ///     // IODE changes when a new orbit set is broadcast
+    /// }
+    /// ```
+    pub fn ephemeris_iode(&self) -> Box<dyn Iterator<Item = (Epoch, SV, u32)> + '_> {
+        Box::new(
+            self.ephemeris()
+                .filter_map(|(t, (_, sv, eph))| Some((*t, sv, eph.iode()?))),
+        )
+    }
+    /// Returns, for each [SV], the continuous time spans during which a valid
+    /// Ephemeris exists (refer to [Self::sv_ephemeris] and [Ephemeris::max_dtoe]).
+    /// Each span is expressed as (start, end), `end` being excluded.
+    /// Overlapping or adjacent validity windows are merged, so any hole
+    /// between two spans is a period where this [SV] cannot be resolved.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/CBW100NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// for (sv, spans) in rinex.ephemeris_coverage() {
+    ///     for (start, end) in spans {
+    ///         assert!(end > start);
+    ///     }
+    /// }
+    /// ```
+    pub fn ephemeris_coverage(&self) -> BTreeMap<SV, Vec<(Epoch, Epoch)>> {
+        let mut coverage = BTreeMap::<SV, Vec<(Epoch, Epoch)>>::new();
+        let windows = self.ephemeris().filter_map(|(toc, (_, sv, eph))| {
+            let max_dtoe = Ephemeris::max_dtoe(sv.constellation)?;
+            if sv.constellation == Constellation::Glonass {
+                Some((sv, (*toc - max_dtoe, *toc + max_dtoe)))
+            } else if sv.constellation.is_sbas() {
+                Some((sv, (*toc, *toc + max_dtoe)))
+            } else {
+                let toe = eph.toe(sv.constellation.timescale()?)?;
+                Some((sv, (std::cmp::max(*toc, toe), toe + max_dtoe)))
+            }
+        });
+        for (sv, window) in windows {
+            coverage.entry(sv).or_default().push(window);
+        }
+        for spans in coverage.values_mut() {
+            spans.sort();
+            let mut merged = Vec::<(Epoch, Epoch)>::with_capacity(spans.len());
+            for (start, end) in spans.drain(..) {
+                match merged.last_mut() {
+                    Some((_, last)) if start <= *last => {
+                        *last = std::cmp::max(*last, end);
+                    },
+                    _ => merged.push((start, end)),
+                }
+            }
+            *spans = merged;
+        }
+        coverage
+    }
+    /// [SV] broadcast group delay (s) Iterator, for single frequency users of said [Carrier].
+    /// Refer to [Ephemeris::group_delay] for the per constellation definitions.
+    /// Ephemerides that do not describe a delay for this [Carrier] are omitted.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use rinex::carrier::Carrier;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/CBW100NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// for (toc, sv, tgd) in rinex.sv_group_delay(Carrier::L1) {
+    ///     assert!(tgd.abs() < 1.0E-6);
+    /// }
+    /// ```
+    pub fn sv_group_delay(
+        &self,
+        carrier: Carrier,
+    ) -> Box<dyn Iterator<Item = (Epoch, SV, f64)> + '_> {
+        Box::new(self.ephemeris().filter_map(move |(e, (_, sv, eph))| {
+            let delay = eph.group_delay(sv, carrier)?;
+            Some((*e, sv, delay))
+        }))
+    }
     /// [SV] embedded clock offset (s), drift (s.s⁻¹) and drift rate (s.s⁻²) Iterator.
     /// ```
     /// use rinex::prelude::*;
@@ -2392,6 +4319,86 @@ impl Rinex {
                 .map(|(e, (_, sv, data))| (*e, sv, data.sv_clock())),
         )
     }
+    /// Evaluates [SV] broadcast clock offset (s) at [Epoch] `t`,
+    /// from the ephemeris published at `t`, otherwise the one
+    /// selected by [Self::sv_ephemeris]:
+    /// af0 + af1 * (t - toc) + af2 * (t - toc)².
+    /// Returns None if no ephemeris is valid at `t` (refer to [Ephemeris::max_dtoe]).
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/CBW100NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// let g20 = SV::from_str("G20").unwrap();
+    /// let t = Epoch::from_str("2021-01-01T16:30:00 GPST").unwrap();
+    /// let offset = rinex.sv_clock_interpolate(t, g20)
+    ///     .unwrap();
+    /// ```
+    pub fn sv_clock_interpolate(&self, t: Epoch, sv: SV) -> Option<f64> {
+        let (toc, eph) = self.sv_clock_ephemeris(sv, t)?;
+        let (a0, a1, a2) = eph.sv_clock();
+        let dt = (t - toc).to_seconds();
+        Some(a0 + a1 * dt + a2 * dt.powi(2))
+    }
+    /*
+     * Ephemeris selection for clock evaluation: the frame published at `t`
+     * is preferred, because [Ephemeris::is_valid] excludes t = toe,
+     * otherwise we rely on [Self::sv_ephemeris].
+     */
+    fn sv_clock_ephemeris(&self, sv: SV, t: Epoch) -> Option<(Epoch, &Ephemeris)> {
+        self.ephemeris()
+            .find(|(t_i, (_, sv_i, _))| **t_i == t && *sv_i == sv)
+            .map(|(t_i, (_, _, eph_i))| (*t_i, eph_i))
+            .or_else(|| {
+                let (toc, _, eph) = self.sv_ephemeris(sv, t)?;
+                Some((toc, eph))
+            })
+    }
+    /// Evaluates [SV] clock offset (s) at [Epoch] `t`, like [Self::sv_clock_interpolate],
+    /// corrected for the relativistic effect due to orbit eccentricity
+    /// (refer to [Ephemeris::relativistic_correction]).
+    /// When `group_delay` is set, the broadcast group delay of the reference signal
+    /// (L1, E1 or B1I, refer to [Ephemeris::group_delay]) is also substracted:
+    /// this applies to single frequency users only.
+    /// Glonass and SBAS broadcast clock offsets already include the relativistic correction,
+    /// they are returned as is.
+    /// Returns None if no ephemeris is valid at `t`, if group delay is requested but missing,
+    /// or if the eccentric anomaly cannot be solved.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V3/CBW100NLD_R_20210010000_01D_MN.rnx")
+    ///     .unwrap();
+    /// let g20 = SV::from_str("G20").unwrap();
+    /// let t = Epoch::from_str("2021-01-01T16:30:00 GPST").unwrap();
+    /// let (_, _, offset) = rinex.sv_clock_corrected(t, g20, false)
+    ///     .unwrap();
+    /// ```
+    pub fn sv_clock_corrected(
+        &self,
+        t: Epoch,
+        sv: SV,
+        group_delay: bool,
+    ) -> Option<(Epoch, SV, f64)> {
+        let (toc, eph) = self.sv_clock_ephemeris(sv, t)?;
+        let (a0, a1, a2) = eph.sv_clock();
+        let dt = (t - toc).to_seconds();
+        let mut offset = a0 + a1 * dt + a2 * dt.powi(2);
+        match sv.constellation {
+            Constellation::Glonass => {},
+            c if c.is_sbas() => {},
+            _ => offset += eph.relativistic_correction(sv, t)?,
+        }
+        if group_delay {
+            let carrier = match sv.constellation {
+                Constellation::Galileo => Carrier::E1,
+                Constellation::BeiDou => Carrier::B1I,
+                _ => Carrier::L1,
+            };
+            offset -= eph.group_delay(sv, carrier)?;
+        }
+        Some((t, sv, offset))
+    }
     /*
      * [IonMessage] Iterator
      */
@@ -2516,9 +4523,11 @@ impl Rinex {
                 .filter_map(|(t, (_, _, ion))| ion.as_bdgim().map(|model| (t, *model))),
         )
     }
-    /// Returns Ionospheric delay compensation, to apply at "t" desired Epoch
-    /// and desired location. NB: we only support Klobuchar models at the moment,
-    /// as we don't know how to convert other models (feel free to contribute).
+    /// Returns Ionospheric delay compensation [m], to apply at "t" desired Epoch,
+    /// for a signal observed at elevation and azimuth angles [rad]
+    /// from desired location. NB: we only support Klobuchar models at the moment:
+    /// NeQuick-G and BDGIM models are ignored, and None is returned
+    /// when no Klobuchar model is available.
     /// "t" must be within a 24 hour time frame of the oldest model.
     /// When working with RINEX2/3, the model is published at midnight
     /// and you should expect discontinuities when a new model is being published.
@@ -2532,24 +4541,22 @@ impl Rinex {
         carrier: Carrier,
     ) -> Option<f64> {
         // determine nearest in time
-        let (_, (model_sv, model)) = self
+        let (_, (model_sv, kb)) = self
             .ionod_correction_models()
             .filter_map(|(t_i, (_, sv_i, msg_i))| {
-                // TODO
-                // calculations currently limited to KB model: implement others
-                let _ = msg_i.as_klobuchar()?;
+                // NeQuick-G and BDGIM are not evaluated:
+                // they require external model data (CCIR maps, MODIP grid
+                // and BDGIM non broadcast coefficients)
+                let kb = *msg_i.as_klobuchar()?;
                 // At most 1 day from publication time
                 if t_i <= t && (t - t_i) < 24.0 * Unit::Hour {
-                    Some((t_i, (sv_i, msg_i)))
+                    Some((t_i, (sv_i, kb)))
                 } else {
                     None
                 }
             })
             .min_by_key(|(t_i, _)| (t - *t_i))?;
 
-        // TODO
-        // calculations currently limited to KB model: implement others
-        let kb = model.as_klobuchar().unwrap();
         let h_km = match model_sv.constellation {
             Constellation::BeiDou => 375.0,
             // we only expect BDS or GPS here,
@@ -2616,6 +4623,37 @@ impl Rinex {
             })
         }))
     }
+    /// Returns the ECEF to ECI rotation matrix at [Epoch] `t`, built from
+    /// the [EopMessage] whose reference [Epoch] is the closest to `t`.
+    /// This is sufficient for broadcast-accuracy transformations: refer to
+    /// [EopMessage::ecef_to_eci] for the approximations being made.
+    /// Returns None if Self does not contain any [EopMessage] (NAV V4 only).
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    /// let rinex = Rinex::from_file("../test_resources/NAV/V4/BRD400DLR_S_20230710000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    /// let t = Epoch::from_str("2023-03-12T12:20:00 GPST").unwrap();
+    /// let rotation = rinex.ecef_to_eci(t)
+    ///     .unwrap();
+    /// ```
+    pub fn ecef_to_eci(&self, t: Epoch) -> Option<Matrix3<f64>> {
+        let (t_eop, (_, _, eop)) = self
+            .earth_orientation()
+            .min_by_key(|(t_eop, _)| (t - **t_eop).abs())?;
+        Some(eop.ecef_to_eci(*t_eop, t))
+    }
+    /// Returns the ECI to ECEF rotation matrix at [Epoch] `t`,
+    /// which is the inverse of [Self::ecef_to_eci].
+    pub fn eci_to_ecef(&self, t: Epoch) -> Option<Matrix3<f64>> {
+        Some(self.ecef_to_eci(t)?.transpose())
+    }
+    /// Rotates ECEF (x, y, z) coordinates to ECI coordinates at [Epoch] `t`,
+    /// using [Self::ecef_to_eci]. Coordinates are expressed in the same unit.
+    pub fn rotate_position(&self, t: Epoch, ecef: (f64, f64, f64)) -> Option<(f64, f64, f64)> {
+        let eci = self.ecef_to_eci(t)? * Vector3::new(ecef.0, ecef.1, ecef.2);
+        Some((eci[0], eci[1], eci[2]))
+    }
     /// Forms a Ut1 Provider as an [DeltaTaiUt1] Iterator from [Self] which must
     /// be a NAV V4 RINEX file with EOP messages.
     pub fn ut1_provider(&self) -> Box<dyn Iterator<Item = DeltaTaiUt1> + '_> {
@@ -2629,6 +4667,134 @@ impl Rinex {
     }
 }
 
+/*
+ * OBS RINEX methods that require Navigation data
+ */
+#[cfg(all(feature = "obs", feature = "nav"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "obs", feature = "nav"))))]
+impl Rinex {
+    /// Drops observations of vehicles that were seen below `min_deg` (or above `max_deg`)
+    /// elevation angle, as seen from the receiver position. [SV] positions are resolved
+    /// at each Epoch from `nav` ephemerides, elevation angles are then resolved with
+    /// [Ephemeris::elevation_azimuth_range], in the `earth_cef` body fixed [Frame].
+    /// The receiver position is `ref_pos`, when specified,
+    /// otherwise it is picked up from Self (or `nav`) header.
+    /// Observations of vehicles whose elevation cannot be determined (no ephemeris available)
+    /// are dropped when `drop_unknown` is set, preserved otherwise.
+    /// Epochs that end up empty are removed, unless they come with a receiver clock offset.
+    /// Epochs that are not flagged OK (events, cycle slip records..) are preserved.
+    /// This has no effect if the receiver position is unknown.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use anise::{
+    ///     constants::frames::IAU_EARTH_FRAME,
+    ///     structure::planetocentric::ellipsoid::Ellipsoid,
+    /// };
+    /// let mut rinex = Rinex::from_file("../test_resources/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz")
+    ///     .unwrap();
+    /// let nav = Rinex::from_file("../test_resources/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+    ///     .unwrap();
+    /// // body fixed frame, WGS84 shape
+    /// let almanac = Almanac::default();
+    /// let earth_cef = Frame {
+    ///     shape: Some(Ellipsoid::from_spheroid(6378.137, 6356.752314245)),
+    ///     ..IAU_EARTH_FRAME
+    /// };
+    /// // drop everything below 10°, and vehicles we have no ephemeris for
+    /// rinex.elevation_mask_mut(&nav, &almanac, earth_cef, 10.0, None, None, true);
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn elevation_mask_mut(
+        &mut self,
+        nav: &Rinex,
+        almanac: &Almanac,
+        earth_cef: Frame,
+        min_deg: f64,
+        max_deg: Option<f64>,
+        ref_pos: Option<GroundPosition>,
+        drop_unknown: bool,
+    ) {
+        let ref_pos = ref_pos
+            .or(self.header.ground_position)
+            .or(nav.header.ground_position);
+        self.elevation_mask_with_mut(
+            |sv, t| nav.sv_position_interpolate(sv, t),
+            almanac,
+            earth_cef,
+            min_deg,
+            max_deg,
+            ref_pos,
+            drop_unknown,
+        );
+    }
+    /// Drops observations of vehicles that were seen below `min_deg` (or above `max_deg`)
+    /// elevation angle, like [Self::elevation_mask_mut], but [SV] positions (ECEF, in km)
+    /// are resolved at each Epoch by `sv_position_km`, for example from SP3 data.
+    /// The receiver position is `ref_pos`, when specified, otherwise it is picked up
+    /// from Self header.
+    #[allow(clippy::too_many_arguments)]
+    pub fn elevation_mask_with_mut<F: Fn(SV, Epoch) -> Option<(f64, f64, f64)>>(
+        &mut self,
+        sv_position_km: F,
+        almanac: &Almanac,
+        earth_cef: Frame,
+        min_deg: f64,
+        max_deg: Option<f64>,
+        ref_pos: Option<GroundPosition>,
+        drop_unknown: bool,
+    ) {
+        let position = match ref_pos.or(self.header.ground_position) {
+            Some(position) => position,
+            None => return,
+        };
+        let rx_km = position.to_ecef_wgs84();
+        let rx_km = (rx_km.0 / 1.0E3, rx_km.1 / 1.0E3, rx_km.2 / 1.0E3);
+        if let Some(r) = self.record.as_mut_obs() {
+            r.retain(|(t, flag), (clk, vehicles)| {
+                if !flag.is_ok() {
+                    return true;
+                }
+                vehicles.retain(|sv, _| {
+                    let el_az_range = sv_position_km(*sv, *t).and_then(|sv_km| {
+                        Ephemeris::elevation_azimuth_range(*t, almanac, earth_cef, sv_km, rx_km)
+                            .ok()
+                    });
+                    let elev = match el_az_range {
+                        Some(el_az_range) => el_az_range.elevation_deg,
+                        None => return !drop_unknown, // unknown elevation
+                    };
+                    elev >= min_deg && max_deg.map(|max| elev <= max).unwrap_or(true)
+                });
+                clk.is_some() || !vehicles.is_empty()
+            });
+        }
+    }
+    /// [Self::elevation_mask_mut] immutable implementation.
+    #[allow(clippy::too_many_arguments)]
+    pub fn elevation_mask(
+        &self,
+        nav: &Rinex,
+        almanac: &Almanac,
+        earth_cef: Frame,
+        min_deg: f64,
+        max_deg: Option<f64>,
+        ref_pos: Option<GroundPosition>,
+        drop_unknown: bool,
+    ) -> Self {
+        let mut s = self.clone();
+        s.elevation_mask_mut(
+            nav,
+            almanac,
+            earth_cef,
+            min_deg,
+            max_deg,
+            ref_pos,
+            drop_unknown,
+        );
+        s
+    }
+}
+
 /*
  * Meteo RINEX specific methods: only available on crate feature.
  * Either specific Iterators, or meaningful data we can extract.
@@ -2636,6 +4802,26 @@ impl Rinex {
 #[cfg(feature = "meteo")]
 #[cfg_attr(docsrs, doc(cfg(feature = "meteo")))]
 impl Rinex {
+    /// Returns iterator over meteo [meteo::sensor::Sensor]s described in the header,
+    /// with their model, type, accuracy and possible location.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/MET/V2/cari0010.07m")
+    ///     .unwrap();
+    /// for sensor in rinex.meteo_sensors() {
+    ///     println!("{}: {:?}", sensor.observable, sensor.model);
+    ///     if let Some(position) = sensor.position {
+    ///         let (lat, lon, _) = position.to_geodetic();
+    ///         println!("located at {}°N {}°E", lat, lon);
+    ///     }
+    /// }
+    /// ```
+    pub fn meteo_sensors(&self) -> Box<dyn Iterator<Item = &meteo::sensor::Sensor> + '_> {
+        match &self.header.meteo {
+            Some(meteo) => Box::new(meteo.sensors.iter()),
+            None => Box::new([].iter()),
+        }
+    }
     /// Returns temperature data iterator, values expressed in Celcius degrees
     /// ```
     /// use rinex::prelude::*;
@@ -2915,19 +5101,65 @@ impl Merge for Rinex {
     }
     /// Merges `rhs` into `Self` in place
     fn merge_mut(&mut self, rhs: &Self) -> Result<(), merge::Error> {
-        self.header.merge_mut(&rhs.header)?;
+        self.merge_mut_with_progress(rhs, None)
+    }
+}
+
+impl Rinex {
+    /// Merges `rhs` into a copy of `Self`, like [Merge::merge], while notifying
+    /// `progress` of each merged epoch. When cancelled, this returns
+    /// [merge::Error::Cancelled] and `Self` is left untouched.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::ops::ControlFlow;
+    /// let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// let mut epochs = 0;
+    /// let mut progress = ProgressHandle::new(move |progress: Progress| {
+    ///     assert_eq!(progress.epochs, epochs + 1);
+    ///     epochs = progress.epochs;
+    ///     ControlFlow::Continue(())
+    /// });
+    /// let merged = rinex.merge_with_progress(&rinex, &mut progress)
+    ///     .unwrap();
+    /// assert_eq!(merged.epoch().count(), rinex.epoch().count());
+    /// ```
+    pub fn merge_with_progress(
+        &self,
+        rhs: &Self,
+        progress: &mut ProgressHandle,
+    ) -> Result<Self, merge::Error> {
+        let mut lhs = self.clone();
+        lhs.merge_mut_with_progress(rhs, Some(progress))?;
+        Ok(lhs)
+    }
+    /*
+     * Merges `rhs` into `Self` in place, with possible progress notifications
+     */
+    fn merge_mut_with_progress(
+        &mut self,
+        rhs: &Self,
+        progress: Option<&mut ProgressHandle>,
+    ) -> Result<(), merge::Error> {
+        self.header.merge_fields_mut(&rhs.header)?;
+        // boundary: first epoch contributed by rhs
+        let boundary = match rhs.first_epoch() {
+            Some(t) => t,
+            None => Epoch::now()?,
+        };
         if !self.is_antex() {
             if self.epoch().count() == 0 {
                 // lhs is empty : overwrite
                 self.record = rhs.record.clone();
             } else if rhs.epoch().count() != 0 {
                 // real merge
-                self.record.merge_mut(&rhs.record)?;
+                self.record.merge_mut_with_progress(&rhs.record, progress)?;
             }
         } else {
             // real merge
-            self.record.merge_mut(&rhs.record)?;
+            self.record.merge_mut_with_progress(&rhs.record, progress)?;
         }
+        self.header.push_merge_comment(boundary);
         Ok(())
     }
 }
@@ -2951,8 +5183,55 @@ impl Split for Rinex {
             },
         ))
     }
-    fn split_dt(&self, _duration: Duration) -> Result<Vec<Self>, split::Error> {
-        Ok(Vec::new())
+    /// Splits `Self` into a serie of [Rinex] spanning at most `duration`.
+    /// Observation headers and production attributes are updated per chunk,
+    /// so [Self::standard_filename] describes each chunk correctly.
+    fn split_dt(&self, duration: Duration) -> Result<Vec<Self>, split::Error> {
+        let records = self.record.split_dt(duration)?;
+        let t0 = self.first_epoch();
+        Ok(records
+            .into_iter()
+            .map(|record| {
+                let mut rinex = Self {
+                    header: self.header.clone(),
+                    comments: self.comments.clone(),
+                    record,
+                    prod_attr: self.prod_attr.clone(),
+                };
+                let (first, last) = match (rinex.first_epoch(), rinex.last_epoch()) {
+                    (Some(first), Some(last)) => (first, last),
+                    _ => return rinex,
+                };
+                if let Some(obs) = &mut rinex.header.obs {
+                    if obs.time_of_first_obs.is_some() {
+                        obs.time_of_first_obs = Some(first);
+                    }
+                    if obs.time_of_last_obs.is_some() {
+                        obs.time_of_last_obs = Some(last);
+                    }
+                }
+                if let Some(attr) = &mut rinex.prod_attr {
+                    // nominal chunk start
+                    let dt = duration.total_nanoseconds();
+                    let start = match t0 {
+                        Some(t0) if dt > 0 => {
+                            let n = (first - t0).total_nanoseconds() / dt;
+                            t0 + Duration::from_total_nanoseconds(n * dt)
+                        },
+                        _ => first,
+                    };
+                    let (y, _, _, hh, mm, _, _) = epoch_decompose(start);
+                    attr.year = y as u32;
+                    attr.doy = start.day_of_year().round() as u32;
+                    if let Some(details) = &mut attr.details {
+                        details.hh = hh;
+                        details.mm = mm;
+                        details.ppu = PPU::from(duration);
+                    }
+                }
+                rinex
+            })
+            .collect())
     }
 }
 
@@ -3090,6 +5369,97 @@ impl Rinex {
             })
         }))
     }
+    /// Interpolates [SV] clock bias (in seconds) at desired [Epoch] `t`,
+    /// from the satellite clock analysis results ([ClockProfileType::AS]).
+    /// Linear interpolation is used between the two surrounding samples,
+    /// the sample is returned as is when `t` is a sampling instant.
+    /// Returns None when `t` is not surrounded by two samples.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    /// let rinex = Rinex::from_file("../test_resources/CLK/V2/COD20352.CLK")
+    ///     .unwrap();
+    /// let g01 = SV::from_str("G01").unwrap();
+    /// let t = Epoch::from_str("2019-01-08T00:00:15 GPST").unwrap();
+    /// let bias = rinex.precise_sv_clock_interpolate(g01, t)
+    ///     .unwrap();
+    /// ```
+    pub fn precise_sv_clock_interpolate(&self, sv: SV, t: Epoch) -> Option<f64> {
+        let samples = self
+            .precise_sv_clock()
+            .filter_map(|(t_i, sv_i, prof_type, prof)| {
+                if sv_i == sv && prof_type == ClockProfileType::AS {
+                    Some((t_i, prof.bias))
+                } else {
+                    None
+                }
+            });
+        let mut before = Option::<(Epoch, f64)>::None;
+        for (t_i, bias) in samples {
+            if t_i == t {
+                return Some(bias);
+            } else if t_i < t {
+                before = Some((t_i, bias));
+            } else {
+                let (t_0, bias_0) = before?;
+                let dt = (t_i - t_0).to_seconds();
+                let mut interpolated = (t_i - t).to_seconds() / dt * bias_0;
+                interpolated += (t - t_0).to_seconds() / dt * bias;
+                return Some(interpolated);
+            }
+        }
+        None
+    }
+    /// Interpolates [SV] clock bias (in seconds) at desired [Epoch] `t`
+    /// (see [Self::precise_sv_clock_interpolate]) and adapts it to a single signal,
+    /// described by its `observable`. Returns (raw, corrected) clock biases.
+    ///
+    /// Precise clock products are referenced to the ionosphere free combination
+    /// of specific signals, for each constellation (for example GPS P1/P2,
+    /// Galileo E1/E5a). Users of other signals, or of a single frequency, need to
+    /// correct the clock for the signal specific bias, returned by the `biases` callback
+    /// in seconds, for given [SV] and [Observable].
+    ///
+    /// Sign convention follows Bias-SINEX observable specific biases (OSB):
+    /// the bias is contained in the observation and must be subtracted from it,
+    /// and the OSB of the reference ionosphere free combination is zero.
+    /// Moving the bias to the satellite clock therefore yields
+    /// `corrected = raw - bias`, so you can pass the OSB of the
+    /// signal (converted from ns to s) straight away. Differential code biases
+    /// (like legacy P1-C1 DCBs) must be converted to OSBs first.
+    ///
+    /// When no callback is provided, or it does not describe this signal,
+    /// corrected and raw values are identical.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::str::FromStr;
+    /// let rinex = Rinex::from_file("../test_resources/CLK/V2/COD20352.CLK")
+    ///     .unwrap();
+    /// let g01 = SV::from_str("G01").unwrap();
+    /// let c1c = Observable::from_str("C1C").unwrap();
+    /// let t = Epoch::from_str("2019-01-08T00:00:15 GPST").unwrap();
+    ///
+    /// // OSB lookup, typically from a Bias-SINEX product (ns)
+    /// let osb = |_sv: SV, _obs: &Observable| -> Option<f64> {
+    ///     Some(10.2472 * 1.0E-9)
+    /// };
+    /// let (raw, corrected) = rinex.precise_sv_clock_for_signal(g01, t, &c1c, Some(&osb))
+    ///     .unwrap();
+    /// assert!((raw - corrected - 10.2472E-9).abs() < 1.0E-15);
+    /// ```
+    pub fn precise_sv_clock_for_signal(
+        &self,
+        sv: SV,
+        t: Epoch,
+        observable: &Observable,
+        biases: Option<&dyn Fn(SV, &Observable) -> Option<f64>>,
+    ) -> Option<(f64, f64)> {
+        let raw = self.precise_sv_clock_interpolate(sv, t)?;
+        let bias = biases
+            .and_then(|biases| biases(sv, observable))
+            .unwrap_or(0.0);
+        Some((raw, raw - bias))
+    }
     /// Returns Iterator over Clock RINEX content for Ground Station clocks only (not onboard clocks)
     pub fn precise_station_clock(
         &self,
@@ -3142,11 +5512,11 @@ impl Rinex {
     /// ```
     pub fn tec(&self) -> Box<dyn Iterator<Item = (Epoch, f64, f64, f64, f64)> + '_> {
         Box::new(self.ionex().flat_map(|((e, h), plane)| {
-            plane.iter().map(|((lat, lon), tec)| {
+            plane.iter().map(move |((row, col), tec)| {
                 (
                     *e,
-                    *lat as f64 / 1000.0_f64,
-                    *lon as f64 / 1000.0_f64,
+                    plane.latitude(row),
+                    plane.longitude(col),
                     *h as f64 / 100.0_f64,
                     tec.tec,
                 )
@@ -3168,12 +5538,12 @@ impl Rinex {
     /// ```
     pub fn tec_rms(&self) -> Box<dyn Iterator<Item = (Epoch, f64, f64, f64, f64)> + '_> {
         Box::new(self.ionex().flat_map(|((e, h), plane)| {
-            plane.iter().filter_map(|((lat, lon), tec)| {
+            plane.iter().filter_map(move |((row, col), tec)| {
                 tec.rms.map(|rms| {
                     (
                         *e,
-                        *lat as f64 / 1000.0_f64,
-                        *lon as f64 / 1000.0_f64,
+                        plane.latitude(row),
+                        plane.longitude(col),
                         *h as f64 / 100.0_f64,
                         rms,
                     )
@@ -3181,6 +5551,60 @@ impl Rinex {
             })
         }))
     }
+    /// Returns an iterator over TEC heights exclusively,
+    /// when this IONEX provides Height maps.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rnx = Rinex::from_file("../test_resources/IONEX/V1/CKMG0020.22I.gz")
+    ///     .unwrap();
+    /// // this product does not provide height maps
+    /// assert_eq!(rnx.tec_height().count(), 0);
+    /// for (t, lat, lon, alt, height) in rnx.tec_height() {
+    ///     // t: Epoch
+    ///     // lat: ddeg
+    ///     // lon: ddeg
+    ///     // alt: km
+    ///     // height: km (f64)
+    /// }
+    /// ```
+    pub fn tec_height(&self) -> Box<dyn Iterator<Item = (Epoch, f64, f64, f64, f64)> + '_> {
+        Box::new(self.ionex().flat_map(|((e, h), plane)| {
+            plane.iter().filter_map(move |((row, col), tec)| {
+                tec.height.map(|height| {
+                    (
+                        *e,
+                        plane.latitude(row),
+                        plane.longitude(col),
+                        *h as f64 / 100.0_f64,
+                        height,
+                    )
+                })
+            })
+        }))
+    }
+    /// Iterates over IONEX maps as (Epoch, altitude (km), [TECPlane]),
+    /// for efficient bulk access to each grid.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rnx = Rinex::from_file("../test_resources/IONEX/V1/CKMG0020.22I.gz")
+    ///     .unwrap();
+    /// let grid = &rnx.header.ionex.as_ref().unwrap().grid;
+    /// let shape = (grid.latitude.length(), grid.longitude.length());
+    /// for (t, alt, plane) in rnx.tec_planes() {
+    ///     // t: Epoch
+    ///     // alt: km
+    ///     assert_eq!(alt, 350.0);
+    ///     // row-major TEC grid: one row per latitude
+    ///     assert_eq!(plane.shape(), shape);
+    ///     let tec = plane.as_matrix();
+    /// }
+    /// ```
+    pub fn tec_planes(&self) -> Box<dyn Iterator<Item = (Epoch, f64, &TECPlane)> + '_> {
+        Box::new(
+            self.ionex()
+                .map(|((e, h), plane)| (*e, *h as f64 / 100.0_f64, plane)),
+        )
+    }
     /// Returns 2D fixed altitude value, expressed in km, in case self is a 2D IONEX.
     /// ```
     /// use rinex::prelude::*;
@@ -3211,7 +5635,7 @@ impl Rinex {
         }
     }
     /// Returns 2D TEC plane at specified altitude and time.
-    /// Refer to the header.grid specification for its width and height.
+    /// Its dimensions are given by [TECPlane::shape].
     pub fn tec_plane(&self, t: Epoch, h: f64) -> Option<&TECPlane> {
         self.ionex()
             .filter_map(|((e, alt), plane)| {
@@ -3233,6 +5657,63 @@ impl Rinex {
             (ionex.grid.latitude.end, ionex.grid.longitude.end),
         ))
     }
+    /// Returns [ionex::MappingFunction] used in TEC determination,
+    /// None means no mapping function was used (e.g altimetry).
+    pub fn tec_mapping_function(&self) -> Option<&ionex::MappingFunction> {
+        self.header.ionex.as_ref()?.mapping.as_ref()
+    }
+    /// Returns minimum elevation angle (in degrees) used in TEC determination.
+    pub fn tec_elevation_cutoff(&self) -> Option<f32> {
+        Some(self.header.ionex.as_ref()?.elevation_cutoff)
+    }
+    /// Returns verbose description of observables used in TEC determination.
+    /// None when the maps are based off a theoretical model.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rnx = Rinex::from_file("../test_resources/IONEX/V1/jplg0010.17i.gz")
+    ///     .unwrap();
+    /// assert_eq!(rnx.tec_observables(), Some("One-way carrier phase leveled to code"));
+    /// assert_eq!(rnx.tec_nb_stations(), Some(170));
+    /// assert_eq!(rnx.tec_nb_satellites(), Some(31));
+    /// assert_eq!(rnx.tec_base_radius(), Some(6371.0));
+    /// ```
+    pub fn tec_observables(&self) -> Option<&str> {
+        self.header.ionex.as_ref()?.observables.as_deref()
+    }
+    /// Returns number of ground stations that contributed to TEC determination,
+    /// if known.
+    pub fn tec_nb_stations(&self) -> Option<u32> {
+        let n = self.header.ionex.as_ref()?.nb_stations;
+        if n > 0 {
+            Some(n)
+        } else {
+            None
+        }
+    }
+    /// Returns number of satellites that contributed to TEC determination,
+    /// if known.
+    pub fn tec_nb_satellites(&self) -> Option<u32> {
+        let n = self.header.ionex.as_ref()?.nb_satellites;
+        if n > 0 {
+            Some(n)
+        } else {
+            None
+        }
+    }
+    /// Returns mean Earth radius, or bottom of height grid, in km.
+    pub fn tec_base_radius(&self) -> Option<f32> {
+        Some(self.header.ionex.as_ref()?.base_radius)
+    }
+    /// Returns Iterator over Differential Code Biases estimated
+    /// in TEC determination, as (bias, rms) values in ns.
+    pub fn tec_dcbs(&self) -> Box<dyn Iterator<Item = (&ionex::BiasSource, (f64, f64))> + '_> {
+        Box::new(
+            self.header
+                .ionex
+                .iter()
+                .flat_map(|ionex| ionex.dcbs.iter().map(|(src, dcb)| (src, *dcb))),
+        )
+    }
 }
 
 /*
@@ -3287,41 +5768,65 @@ impl Rinex {
         matcher: AntennaMatcher,
         freq: Carrier,
     ) -> Option<(f64, f64, f64)> {
-        let to_match = matcher.to_lowercase();
         self.antex_valid_calibrations(now)
             .filter_map(|(ant, freqdata)| match &ant.specific {
-                AntennaSpecific::RxAntenna(rx_ant) => match &to_match {
-                    AntennaMatcher::IGSCode(code) => {
-                        if rx_ant.igs_type.to_lowercase().eq(code) {
-                            freqdata
-                                .get(&freq)
-                                .map(|freqdata| freqdata.apc_eccentricity)
-                        } else {
-                            None
-                        }
-                    },
-                    AntennaMatcher::SerialNumber(sn) => {
-                        if rx_ant.igs_type.to_lowercase().eq(sn) {
-                            freqdata
-                                .get(&freq)
-                                .map(|freqdata| freqdata.apc_eccentricity)
-                        } else {
-                            None
-                        }
-                    },
+                AntennaSpecific::RxAntenna(rx_ant) => {
+                    if matcher.matches(rx_ant) {
+                        freqdata
+                            .get(&freq)
+                            .map(|freqdata| freqdata.apc_eccentricity)
+                    } else {
+                        None
+                    }
                 },
                 _ => None,
             })
             .reduce(|k, _| k) // we're expecting a single match here
     }
+    /// Retains only the receiver antennas identified by one of
+    /// the given [AntennaMatcher]s. Spacecraft antennas are dropped.
+    /// This is typically used to extract a site specific antenna
+    /// database from a large IGS ATX file:
+    /// ```
+    /// use rinex::prelude::*;
+    /// let mut rinex = Rinex::from_file("../test_resources/ATX/V1/igs14_small.atx.gz")
+    ///     .unwrap();
+    /// rinex.antex_retain(&[AntennaMatcher::IGSCode("JPSLEGANT_E".to_string())]);
+    /// assert_eq!(rinex.antennas().count(), 1);
+    /// ```
+    pub fn antex_retain(&mut self, matcher: &[AntennaMatcher]) {
+        if let Some(rec) = self.record.as_mut_antex() {
+            rec.retain(|(ant, _)| match &ant.specific {
+                AntennaSpecific::RxAntenna(rx_ant) => {
+                    matcher.iter().any(|matcher| matcher.matches(rx_ant))
+                },
+                _ => false,
+            });
+        }
+    }
 }
 
+#[cfg(feature = "doris")]
+use doris::Satellite as DorisSatellite;
+
 /*
  * DORIS special features
  */
 #[cfg(feature = "doris")]
 #[cfg_attr(docsrs, doc(cfg(feature = "doris")))]
 impl Rinex {
+    /// Returns DORIS [DorisSatellite] hosting the receiver, as described in the header.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("../test_resources/DOR/V3/cs2rx18164.gz")
+    ///     .unwrap();
+    /// let satellite = rinex.doris_satellite().unwrap();
+    /// assert_eq!(satellite.name, "CRYOSAT-2");
+    /// assert_eq!(satellite.ids_code(), "cs2");
+    /// ```
+    pub fn doris_satellite(&self) -> Option<&DorisSatellite> {
+        self.header.doris.as_ref()?.satellite.as_ref()
+    }
     /// Returns Stations Iterator
     pub fn stations(&self) -> Box<dyn Iterator<Item = &Station> + '_> {
         if let Some(doris) = &self.header.doris {
@@ -3339,7 +5844,7 @@ impl Rinex {
     ///     println!("{}@{}: {} °C", station.domes, epoch, value);
     /// }
     pub fn doris_temperature(&self) -> Box<dyn Iterator<Item = (Epoch, &Station, f64)> + '_> {
-        Box::new(self.doris().flat_map(|((epoch, _), stations)| {
+        Box::new(self.doris().flat_map(|((epoch, _), (_, stations))| {
             stations.iter().flat_map(move |(station, observables)| {
                 observables.iter().filter_map(move |(observable, data)| {
                     if *observable == Observable::Temperature {
@@ -3360,7 +5865,7 @@ impl Rinex {
     ///     println!("{}@{}: {} hPa", station.domes, epoch, value);
     /// }
     pub fn doris_pressure(&self) -> Box<dyn Iterator<Item = (Epoch, &Station, f64)> + '_> {
-        Box::new(self.doris().flat_map(|((epoch, _), stations)| {
+        Box::new(self.doris().flat_map(|((epoch, _), (_, stations))| {
             stations.iter().flat_map(move |(station, observables)| {
                 observables.iter().filter_map(move |(observable, data)| {
                     if *observable == Observable::Pressure {
@@ -3381,7 +5886,7 @@ impl Rinex {
     ///     println!("{}@{}: {}%", station.domes, epoch, value);
     /// }
     pub fn doris_humidity(&self) -> Box<dyn Iterator<Item = (Epoch, &Station, f64)> + '_> {
-        Box::new(self.doris().flat_map(|((epoch, _), stations)| {
+        Box::new(self.doris().flat_map(|((epoch, _), (_, stations))| {
             stations.iter().flat_map(move |(station, observables)| {
                 observables.iter().filter_map(move |(observable, data)| {
                     if *observable == Observable::HumidityRate {
@@ -3404,7 +5909,7 @@ impl Rinex {
     pub fn doris_phase(
         &self,
     ) -> Box<dyn Iterator<Item = (Epoch, &Station, &Observable, f64)> + '_> {
-        Box::new(self.doris().flat_map(|((epoch, _), stations)| {
+        Box::new(self.doris().flat_map(|((epoch, _), (_, stations))| {
             stations.iter().flat_map(move |(station, observables)| {
                 observables.iter().filter_map(move |(observable, data)| {
                     if observable.is_phase_observable() {
@@ -3427,7 +5932,7 @@ impl Rinex {
     pub fn doris_pseudo_range(
         &self,
     ) -> Box<dyn Iterator<Item = (Epoch, &Station, &Observable, f64)> + '_> {
-        Box::new(self.doris().flat_map(move |((epoch, _), stations)| {
+        Box::new(self.doris().flat_map(move |((epoch, _), (_, stations))| {
             stations.iter().flat_map(move |(station, observables)| {
                 observables.iter().filter_map(move |(observable, data)| {
                     if observable.is_pseudorange_observable() {
@@ -3460,7 +5965,7 @@ impl Rinex {
     pub fn doris_rx_power(
         &self,
     ) -> Box<dyn Iterator<Item = (Epoch, &Station, &Observable, f64)> + '_> {
-        Box::new(self.doris().flat_map(|((epoch, _), stations)| {
+        Box::new(self.doris().flat_map(|((epoch, _), (_, stations))| {
             stations.iter().flat_map(move |(station, observables)| {
                 observables.iter().filter_map(move |(observable, data)| {
                     if observable.is_power_observable() {
@@ -3514,6 +6019,40 @@ mod test {
         }
     }
     #[test]
+    fn fmt_multiline_comments() {
+        let paragraph = "a lengthy paragraph, ".repeat(5);
+        let desc = format!(
+            "{}\n{}\n\n\tindented, with ünicode\r\nend",
+            paragraph, paragraph
+        );
+        assert!(desc.len() > 200);
+
+        let header = Header::basic_obs().with_comment(&desc);
+        let formatted = header.to_string();
+
+        let comments = formatted
+            .lines()
+            .filter(|line| line.ends_with("COMMENT"))
+            .collect::<Vec<_>>();
+        assert_eq!(comments.len(), header.comments.len());
+        assert_eq!(comments.len(), 7);
+
+        for line in comments.iter() {
+            assert!(line.is_ascii(), "comments should be ASCII");
+            assert_eq!(line.len(), 67, "invalid comment line \"{}\"", line);
+            assert_eq!(line.find("COMMENT"), Some(60));
+            assert!(is_rinex_comment(line), "should be valid comment");
+        }
+
+        assert_eq!(comments[0].split_at(60).0, &paragraph[..60]);
+        assert_eq!(comments[4].split_at(60).0.trim_end(), "");
+        assert_eq!(
+            comments[5].split_at(60).0.trim_end(),
+            " indented, with ?nicode"
+        );
+        assert_eq!(comments[6].split_at(60).0.trim_end(), "end");
+    }
+    #[test]
     fn fmt_observables_v3() {
         for (desc, expected) in [
 ("R    9 C1C L1C S1C C2C C2P L2C L2P S2C S2P",
@@ -3,10 +3,9 @@ use thiserror::Error;
 
 /// Grid definition Error
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
-    #[error("faulty grid definition: `start` and `end` must be multiples of each other")]
-    GridStartEndError,
-    #[error("faulty grid definition: `start` and `end` must be multiples of `spacing`")]
+    #[error("faulty grid definition: `end` is not reachable from `start` with given `spacing`")]
     GridSpacingError,
 }
 
@@ -26,27 +25,26 @@ pub struct Linspace {
 impl Linspace {
     /// Builds a new Linear space
     pub fn new(start: f64, end: f64, spacing: f64) -> Result<Self, Error> {
-        let r = end.rem(start);
         /*
-         * End / Start must be multiple of one another
+         * `end` must be reachable from `start`
          */
-        if r == 0.0 {
-            if end.rem(spacing) == 0.0 {
-                Ok(Self {
-                    start,
-                    end,
-                    spacing,
-                })
-            } else {
-                Err(Error::GridSpacingError)
-            }
+        if (end - start).rem(spacing) == 0.0 {
+            Ok(Self {
+                start,
+                end,
+                spacing,
+            })
         } else {
-            Err(Error::GridStartEndError)
+            Err(Error::GridSpacingError)
         }
     }
-    // Returns grid length, in terms of data points
+    /// Returns grid length, in terms of data points (`start` and `end` included)
     pub fn length(&self) -> usize {
-        (self.end / self.spacing).floor() as usize
+        if self.spacing == 0.0 {
+            1
+        } else {
+            ((self.end - self.start) / self.spacing).round().max(0.0) as usize + 1
+        }
     }
     /// Returns true if self is a single point space
     pub fn is_single_point(&self) -> bool {
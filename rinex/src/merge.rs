@@ -1,5 +1,6 @@
 //! RINEX File merging (combination)
 use crate::prelude::Epoch;
+use crate::ErrorKind;
 use hifitime::errors::HifitimeError;
 use std::cmp::{Eq, PartialEq};
 use std::collections::HashMap;
@@ -10,6 +11,7 @@ use thiserror::Error;
 
 /// Merge operation related error(s)
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("file type mismatch: cannot merge different RINEX together")]
     FileTypeMismatch,
@@ -25,6 +27,20 @@ pub enum Error {
     IonexBaseRadiusMismatch,
     #[error("failed to retrieve system time for merge ops date")]
     HifitimeError(#[from] HifitimeError),
+    #[error("operation cancelled")]
+    Cancelled,
+}
+
+impl Error {
+    /// Returns the [ErrorKind] of this [Error]
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            // system time is not available on this platform
+            Self::HifitimeError(_) => ErrorKind::Unsupported,
+            Self::Cancelled => ErrorKind::Cancelled,
+            _ => ErrorKind::Unsupported,
+        }
+    }
 }
 
 /*
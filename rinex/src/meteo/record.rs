@@ -1,6 +1,6 @@
 use crate::{
-    epoch, merge, merge::Merge, prelude::Duration, prelude::*, split, split::Split, types::Type,
-    version, Observable,
+    epoch, formatting::fmt_fixed, merge, merge::Merge, prelude::Duration, prelude::*, split,
+    split::Split, types::Type, version, Observable,
 };
 
 use std::collections::{BTreeMap, HashMap};
@@ -8,9 +8,10 @@ use std::str::FromStr;
 use thiserror::Error;
 
 #[cfg(feature = "processing")]
-use qc_traits::processing::{
-    DecimationFilter, DecimationFilterType, FilterItem, MaskFilter, MaskOperand,
-};
+use qc_traits::processing::{DecimationFilter, FilterItem, MaskFilter, MaskOperand};
+
+#[cfg(feature = "processing")]
+use crate::record::decimated_epochs;
 
 /*
  * Meteo RINEX specific record type.
@@ -44,6 +45,7 @@ pub(crate) fn is_new_epoch(line: &str, v: version::Version) -> bool {
 
 #[derive(Error, Debug)]
 /// Meteo Data `Record` parsing specific errors
+#[non_exhaustive]
 pub enum Error {
     #[error("failed to parse epoch")]
     EpochParsingError(#[from] epoch::ParsingError),
@@ -129,7 +131,7 @@ pub(crate) fn fmt_epoch(
     for obscode in observables {
         index += 1;
         if let Some(data) = data.get(obscode) {
-            lines.push_str(&format!("{:7.1}", data));
+            lines.push_str(&fmt_fixed(*data, 7, 1));
         } else {
             lines.push_str("       ");
         }
@@ -141,6 +143,28 @@ pub(crate) fn fmt_epoch(
     Ok(lines)
 }
 
+/*
+ * Substracts rhs (reference) to this record, per epoch and per observable.
+ * Epochs and observables that rhs does not describe are dropped.
+ */
+pub(crate) fn substract_mut(rec: &mut Record, rhs: &Record) {
+    rec.retain(|epoch, observations| {
+        if let Some(ref_observations) = rhs.get(epoch) {
+            observations.retain(|observable, value| {
+                if let Some(ref_value) = ref_observations.get(observable) {
+                    *value -= ref_value;
+                    true
+                } else {
+                    false
+                }
+            });
+            !observations.is_empty()
+        } else {
+            false
+        }
+    });
+}
+
 impl Merge for Record {
     fn merge(&self, rhs: &Self) -> Result<Self, merge::Error> {
         let mut lhs = self.clone();
@@ -189,8 +213,8 @@ impl Split for Record {
             .collect();
         Ok((r0, r1))
     }
-    fn split_dt(&self, _duration: Duration) -> Result<Vec<Self>, split::Error> {
-        Ok(Vec::new())
+    fn split_dt(&self, duration: Duration) -> Result<Vec<Self>, split::Error> {
+        Ok(split::split_dt(self, duration, |e| *e))
     }
 }
 
@@ -264,35 +288,25 @@ pub(crate) fn meteo_mask_mut(rec: &mut Record, mask: &MaskFilter) {
 
 #[cfg(feature = "processing")]
 pub(crate) fn meteo_decim_mut(rec: &mut Record, f: &DecimationFilter) {
-    if f.item.is_some() {
-        todo!("targetted decimation not supported yet");
-    }
-    match f.filter {
-        DecimationFilterType::Modulo(r) => {
-            let mut i = 0;
-            rec.retain(|_, _| {
-                let retained = (i % r) == 0;
-                i += 1;
-                retained
-            });
-        },
-        DecimationFilterType::Duration(interval) => {
-            let mut last_retained = Option::<Epoch>::None;
-            rec.retain(|e, _| {
-                if let Some(last) = last_retained {
-                    let dt = *e - last;
-                    if dt >= interval {
-                        last_retained = Some(*e);
-                        true
-                    } else {
-                        false
-                    }
-                } else {
-                    last_retained = Some(*e);
-                    true // always retain 1st epoch
+    let retained = decimated_epochs(rec.keys().copied(), &f.filter);
+    match &f.item {
+        None => rec.retain(|e, _| retained.contains(e)),
+        Some(FilterItem::ComplexItem(filter)) => {
+            // only decimate the targetted observables,
+            // other observables are preserved at their original rate
+            let observables = filter
+                .iter()
+                .filter_map(|f| Observable::from_str(f).ok())
+                .collect::<Vec<_>>();
+            rec.retain(|e, data| {
+                if retained.contains(e) {
+                    return true;
                 }
+                data.retain(|code, _| !observables.contains(code));
+                !data.is_empty()
             });
         },
+        Some(_) => {}, // other items do not apply to meteo data
     }
 }
 
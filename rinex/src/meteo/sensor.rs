@@ -1,4 +1,5 @@
 //! Meteo sensor
+use crate::formatting::fmt_fixed;
 use crate::observable;
 use crate::prelude::GroundPosition;
 use crate::Observable;
@@ -71,6 +72,7 @@ impl Render for Sensor {
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ParseSensorError {
     #[error("observable parsing error")]
     ObservableParsingErro(#[from] observable::ParsingError),
@@ -129,7 +131,7 @@ impl std::fmt::Display for Sensor {
         }
 
         if let Some(accuracy) = self.accuracy {
-            write!(f, "{:^11.1}", accuracy)?
+            write!(f, "{:^11}", fmt_fixed(accuracy as f64, 0, 1))?
         } else {
             write!(f, "{:11}", "")?
         }
@@ -137,11 +139,10 @@ impl std::fmt::Display for Sensor {
 
         if let Some(pos) = self.position {
             let (x, y, z) = pos.to_ecef_wgs84();
-            write!(f, "{:14.4}", x)?;
-            write!(f, "{:14.4}", y)?;
-            write!(f, "{:14.4}", z)?;
             let h = self.height.unwrap_or(0.0);
-            write!(f, "{:14.4}", h)?;
+            for value in [x, y, z, h] {
+                write!(f, "{}", fmt_fixed(value, 14, 4))?;
+            }
             writeln!(f, " {} SENSOR POS XYZ/H", self.observable)?
         }
         Ok(())
@@ -0,0 +1,159 @@
+//! NAV RINEX builder
+use super::{EopMessage, Ephemeris, IonMessage, NavFrame, NavMsgType, Record, StoMessage};
+use crate::prelude::{Constellation, Epoch, Header, Rinex, SV};
+use crate::{record, version::Version};
+
+/// [NavigationRecordBuilder] helps create Navigation [Record]s
+/// (and complete NAV [Rinex]) from decoded frames.
+/// This is typically used by converters that need to emit NAV RINEX.
+/// ```
+/// use rinex::prelude::*;
+/// use rinex::version::Version;
+/// use rinex::navigation::{Ephemeris, NavMsgType, NavigationRecordBuilder};
+///
+/// let t = Epoch::from_gregorian_utc_at_midnight(2021, 1, 1);
+/// let g01 = SV::new(Constellation::GPS, 1);
+///
+/// let rinex = NavigationRecordBuilder::new(Version::new(3, 4))
+///     .with_ephemeris(t, NavMsgType::LNAV, g01, Ephemeris::default())
+///     .build();
+///
+/// assert!(rinex.is_navigation_rinex());
+/// assert_eq!(rinex.header.constellation, Some(Constellation::GPS));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NavigationRecordBuilder {
+    /// RINEX revision to be produced
+    version: Version,
+    /// Record being built
+    record: Record,
+}
+
+impl NavigationRecordBuilder {
+    /// Creates a new [NavigationRecordBuilder] targetting this RINEX [Version]
+    pub fn new(version: Version) -> Self {
+        Self {
+            version,
+            record: Record::new(),
+        }
+    }
+    /// Adds one [NavFrame] published at [Epoch].
+    /// Identical frames are only stored once.
+    /// The record is built in place: [Self] is consumed and returned.
+    pub fn with_frame(mut self, t: Epoch, frame: NavFrame) -> Self {
+        let frames = self.record.entry(t).or_default();
+        if !frames.contains(&frame) {
+            frames.push(frame);
+        }
+        self
+    }
+    /// Adds one [Ephemeris] for said [SV], published at ToC [Epoch]
+    pub fn with_ephemeris(self, toc: Epoch, msg: NavMsgType, sv: SV, eph: Ephemeris) -> Self {
+        self.with_frame(toc, NavFrame::Eph(msg, sv, eph))
+    }
+    /// Adds one [IonMessage] published at [Epoch].
+    /// NB: ionospheric models are described as frames in RINEX4 only.
+    /// In older revisions, they are moved to the header section.
+    pub fn with_ionosphere_model(self, t: Epoch, msg: NavMsgType, sv: SV, ion: IonMessage) -> Self {
+        self.with_frame(t, NavFrame::Ion(msg, sv, ion))
+    }
+    /// Adds one [StoMessage] published at [Epoch].
+    /// NB: system time offsets are only formatted in RINEX4.
+    pub fn with_system_time_offset(
+        self,
+        t: Epoch,
+        msg: NavMsgType,
+        sv: SV,
+        sto: StoMessage,
+    ) -> Self {
+        self.with_frame(t, NavFrame::Sto(msg, sv, sto))
+    }
+    /// Adds one [EopMessage] published at [Epoch].
+    /// NB: earth orientation parameters only exist in RINEX4.
+    pub fn with_earth_orientation(
+        self,
+        t: Epoch,
+        msg: NavMsgType,
+        sv: SV,
+        eop: EopMessage,
+    ) -> Self {
+        self.with_frame(t, NavFrame::Eop(msg, sv, eop))
+    }
+    /// Returns a basic NAV [Header] that matches the content of this builder:
+    /// the [Constellation] is set to Mixed as soon as several systems are described.
+    pub fn header(&self) -> Header {
+        let mut constellations = self
+            .record
+            .values()
+            .flatten()
+            .map(|fr| match fr {
+                NavFrame::Eph(_, sv, _)
+                | NavFrame::Eop(_, sv, _)
+                | NavFrame::Ion(_, sv, _)
+                | NavFrame::Sto(_, sv, _) => sv.constellation,
+            })
+            .collect::<Vec<_>>();
+        constellations.sort();
+        constellations.dedup();
+
+        let constellation = match constellations.len() {
+            1 => {
+                let c = constellations[0];
+                if c.is_sbas() {
+                    Constellation::SBAS
+                } else {
+                    c
+                }
+            },
+            _ => Constellation::Mixed,
+        };
+
+        let mut header = Header::basic_nav()
+            .with_version(self.version)
+            .with_constellation(constellation);
+
+        if self.version.major < 4 {
+            // ION frames are described in the header section prior RINEX4
+            for (_, sv, ion) in self.record.values().flatten().filter_map(|fr| fr.as_ion()) {
+                header.ionod_corrections.insert(sv.constellation, *ion);
+            }
+        }
+        header
+    }
+    /// Returns the Navigation [Record] built so far
+    pub fn record(&self) -> Record {
+        if self.version.major < 4 {
+            // ION/STO/EOP frames do not exist prior RINEX4
+            self.record
+                .iter()
+                .filter_map(|(t, frames)| {
+                    let frames = frames
+                        .iter()
+                        .filter(|fr| fr.as_eph().is_some())
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if frames.is_empty() {
+                        None
+                    } else {
+                        Some((*t, frames))
+                    }
+                })
+                .collect()
+        } else {
+            self.record.clone()
+        }
+    }
+    /// Builds a complete NAV [Rinex], ready to be formatted
+    pub fn build(self) -> Rinex {
+        let header = self.header();
+        let mut record = self.record;
+        if self.version.major < 4 {
+            // ION/STO/EOP frames do not exist prior RINEX4
+            record.retain(|_, frames| {
+                frames.retain(|fr| fr.as_eph().is_some());
+                !frames.is_empty()
+            });
+        }
+        Rinex::new(header, record::Record::NavRecord(record))
+    }
+}
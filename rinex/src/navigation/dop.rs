@@ -0,0 +1,63 @@
+//! Dilution of Precision
+use nalgebra::{Matrix4, Vector4};
+
+/// Computes the Dilution of Precision (GDOP, PDOP, HDOP, VDOP)
+/// from the geometry of the satellites in sight, described by
+/// their (elevation, azimuth) angles in degrees, as seen from the receiver.
+/// Requires at least 4 satellites in sight, returns None when the
+/// geometry is degenerate.
+/// ```
+/// use rinex::navigation::dilution_of_precision;
+/// let geometry = [(90.0, 0.0), (30.0, 0.0), (30.0, 120.0), (30.0, 240.0)];
+/// let (gdop, pdop, hdop, vdop) = dilution_of_precision(&geometry).unwrap();
+/// assert!(gdop > pdop);
+/// assert!(pdop > hdop);
+/// assert!(pdop > vdop);
+/// ```
+pub fn dilution_of_precision(sv_elev_azim_deg: &[(f64, f64)]) -> Option<(f64, f64, f64, f64)> {
+    if sv_elev_azim_deg.len() < 4 {
+        return None;
+    }
+    // G^T G, where each row of G is the (local ENU) line of sight + clock term
+    let mut gtg = Matrix4::<f64>::zeros();
+    for (elev, azim) in sv_elev_azim_deg.iter() {
+        let (elev, azim) = (elev.to_radians(), azim.to_radians());
+        let los = Vector4::new(
+            -elev.cos() * azim.sin(),
+            -elev.cos() * azim.cos(),
+            -elev.sin(),
+            1.0,
+        );
+        gtg += los * los.transpose();
+    }
+    let q = gtg.try_inverse()?;
+    let (q_e, q_n, q_u, q_t) = (q[(0, 0)], q[(1, 1)], q[(2, 2)], q[(3, 3)]);
+    Some((
+        (q_e + q_n + q_u + q_t).sqrt(),
+        (q_e + q_n + q_u).sqrt(),
+        (q_e + q_n).sqrt(),
+        q_u.sqrt(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::dilution_of_precision;
+    #[test]
+    fn dop_geometry() {
+        assert!(dilution_of_precision(&[(90.0, 0.0), (30.0, 0.0), (30.0, 120.0)]).is_none());
+
+        let mut geometry = vec![(90.0, 0.0), (30.0, 0.0), (30.0, 120.0), (30.0, 240.0)];
+        let (gdop, pdop, hdop, vdop) = dilution_of_precision(&geometry).unwrap();
+        assert!(gdop > pdop);
+        assert!((pdop.powi(2) - hdop.powi(2) - vdop.powi(2)).abs() < 1.0E-9);
+
+        // more satellites in sight: better geometry
+        geometry.extend_from_slice(&[(45.0, 60.0), (45.0, 180.0), (45.0, 300.0), (10.0, 90.0)]);
+        let (_, more_pdop, _, _) = dilution_of_precision(&geometry).unwrap();
+        assert!(
+            more_pdop < pdop,
+            "pdop should decrease with more satellites in sight"
+        );
+    }
+}
@@ -4,8 +4,12 @@ use crate::prelude::*;
 use std::str::FromStr;
 use thiserror::Error;
 
+#[cfg(feature = "nav")]
+use nalgebra::{Matrix3, Rotation3, Vector3};
+
 /// EopMessage Parsing error
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     #[error("failed to parse epoch")]
     EpochParsingError(#[from] epoch::ParsingError),
@@ -31,7 +35,62 @@ pub struct EopMessage {
     pub delta_ut1: (f64, f64, f64),
 }
 
+/*
+ * Evaluates (value, 1st derivative, 2nd derivative) at dt (days)
+ */
+fn taylor(coeffs: (f64, f64, f64), dt: f64) -> f64 {
+    coeffs.0 + coeffs.1 * dt + coeffs.2 * dt * dt / 2.0
+}
+
+/*
+ * Greenwich Mean Sidereal Time (IAU-82 model), in radians,
+ * from given Julian date (UT1)
+ */
+#[cfg(feature = "nav")]
+fn gmst(jd_ut1: f64) -> f64 {
+    let t = (jd_ut1 - 2_451_545.0) / 36_525.0;
+    let seconds =
+        67_310.548_41 + (876_600.0 * 3_600.0 + 8_640_184.812_866) * t + 0.093_104 * t.powi(2)
+            - 6.2E-6 * t.powi(3);
+    (seconds.rem_euclid(86_400.0) / 240.0).to_radians()
+}
+
 impl EopMessage {
+    /// Returns pole coordinates (xp, yp) in arc-seconds, evaluated at [Epoch] `t`,
+    /// `t_eop` being the reference [Epoch] of this message.
+    pub fn pole_coordinates(&self, t_eop: Epoch, t: Epoch) -> (f64, f64) {
+        let dt = (t - t_eop).to_seconds() / 86_400.0;
+        (taylor(self.x, dt), taylor(self.y, dt))
+    }
+    /// Returns UT1-UTC in seconds, evaluated at [Epoch] `t`,
+    /// `t_eop` being the reference [Epoch] of this message.
+    pub fn delta_ut1(&self, t_eop: Epoch, t: Epoch) -> f64 {
+        let dt = (t - t_eop).to_seconds() / 86_400.0;
+        taylor(self.delta_ut1, dt)
+    }
+    /// Returns the ECEF to ECI rotation matrix at [Epoch] `t`,
+    /// `t_eop` being the reference [Epoch] of this message.
+    /// Earth rotation is described by the Greenwich Mean Sidereal Time (IAU-82),
+    /// evaluated in UT1 using the broadcast UT1-UTC, and polar motion by the
+    /// broadcast pole coordinates. Precession and nutation are not modeled:
+    /// the inertial frame is the mean equator and equinox of date, which
+    /// differs from J2000/GCRF by up to a few arc-minutes (precession since J2000).
+    #[cfg(feature = "nav")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "nav")))]
+    pub fn ecef_to_eci(&self, t_eop: Epoch, t: Epoch) -> Matrix3<f64> {
+        let (xp, yp) = self.pole_coordinates(t_eop, t);
+        let (xp, yp) = ((xp / 3_600.0).to_radians(), (yp / 3_600.0).to_radians());
+
+        let jd_ut1 = t.to_mjd_utc_days() + 2_400_000.5 + self.delta_ut1(t_eop, t) / 86_400.0;
+        let theta = gmst(jd_ut1);
+
+        // W = R2(xp) R1(yp) polar motion, then R3(-theta) Earth rotation
+        let earth_rotation = Rotation3::from_axis_angle(&Vector3::z_axis(), theta);
+        let polar_motion = Rotation3::from_axis_angle(&Vector3::y_axis(), -xp)
+            * Rotation3::from_axis_angle(&Vector3::x_axis(), -yp);
+
+        (earth_rotation * polar_motion).into_inner()
+    }
     pub(crate) fn parse(
         mut lines: std::str::Lines<'_>,
         ts: TimeScale,
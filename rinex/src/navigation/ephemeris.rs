@@ -1,4 +1,5 @@
 use super::{orbits::closest_nav_standards, NavMsgType, OrbitItem};
+use crate::carrier::Carrier;
 use crate::constants::Constants;
 use crate::{
     constants, epoch,
@@ -32,6 +33,7 @@ use nalgebra::{self as na, Matrix3, Rotation, Rotation3, Vector4};
 
 /// Parsing errors
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     #[error("missing data")]
     MissingData,
@@ -271,6 +273,62 @@ impl EphemerisHelper {
     }
 }
 
+/*
+ * Glonass equations of motion, in the rotating PZ-90 frame.
+ * State is position [km] and velocity [km/s], accel is the
+ * broadcast luni-solar acceleration [km/s²].
+ */
+#[cfg(feature = "nav")]
+fn glonass_derivatives(state: &[f64; 6], accel: (f64, f64, f64)) -> [f64; 6] {
+    let gm_km3_s2 = constants::GM::GLO * 1.0E-9;
+    let omega = constants::Omega::GLO;
+    let (x, y, z, vx, vy, vz) = (state[0], state[1], state[2], state[3], state[4], state[5]);
+
+    let r = (x.powi(2) + y.powi(2) + z.powi(2)).sqrt();
+    let mu = -gm_km3_s2 / r.powi(3);
+    let j2 =
+        -1.5 * constants::PZ90::J20 * gm_km3_s2 * constants::PZ90::RADIUS_KM.powi(2) / r.powi(5);
+    let zz = 5.0 * z.powi(2) / r.powi(2);
+
+    [
+        vx,
+        vy,
+        vz,
+        mu * x + j2 * x * (1.0 - zz) + omega.powi(2) * x + 2.0 * omega * vy + accel.0,
+        mu * y + j2 * y * (1.0 - zz) + omega.powi(2) * y - 2.0 * omega * vx + accel.1,
+        mu * z + j2 * z * (3.0 - zz) + accel.2,
+    ]
+}
+
+/*
+ * PZ-90.11 to WGS84 frame conversion (7 parameters Helmert transform),
+ * for position [km] and velocity [km/s] vectors.
+ * See GOST 32453-2017.
+ */
+#[cfg(feature = "nav")]
+pub(crate) fn pz90_to_wgs84(
+    pos: (f64, f64, f64),
+    vel: (f64, f64, f64),
+) -> ((f64, f64, f64), (f64, f64, f64)) {
+    let mas = std::f64::consts::PI / 180.0 / 3600.0 / 1000.0;
+    let (wx, wy, wz) = constants::PZ90::WGS84_ROTATION_MAS;
+    let (wx, wy, wz) = (wx * mas, wy * mas, wz * mas);
+    let scale = 1.0 + constants::PZ90::WGS84_SCALE;
+    let rotate = |(x, y, z): (f64, f64, f64)| {
+        (
+            scale * (x + wz * y - wy * z),
+            scale * (-wz * x + y + wx * z),
+            scale * (wy * x - wx * y + z),
+        )
+    };
+    let (dx, dy, dz) = constants::PZ90::WGS84_TRANSLATION_M;
+    let (x, y, z) = rotate(pos);
+    (
+        (x + dx * 1.0E-3, y + dy * 1.0E-3, z + dz * 1.0E-3),
+        rotate(vel),
+    )
+}
+
 /// Ephermeris NAV frame type
 #[derive(Default, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
@@ -367,14 +425,68 @@ impl Ephemeris {
     pub(crate) fn get_week(&self) -> Option<u32> {
         self.orbits.get("week").and_then(|value| value.as_u32())
     }
+    /// Returns true if the broadcast health word marks this vehicle as usable.
+    /// The health word depends on the [SV] constellation and the [NavMsgType]
+    /// of this frame. Returns None if the health word does not exist or is not decoded
+    /// for this constellation, refer to [OrbitItem::is_healthy].
+    pub fn is_healthy(&self, sv: SV, msg: NavMsgType) -> Option<bool> {
+        self.orbits.get(Self::health_field(sv, msg))?.is_healthy()
+    }
+    /*
+     * Returns the field name of the broadcast health word, which
+     * depends on the constellation and message type
+     */
+    fn health_field(sv: SV, msg: NavMsgType) -> &'static str {
+        match (sv.constellation, msg) {
+            (Constellation::GPS | Constellation::QZSS, NavMsgType::CNAV | NavMsgType::CNV2) => {
+                "svHealth"
+            },
+            (Constellation::BeiDou, NavMsgType::CNV1 | NavMsgType::CNV2 | NavMsgType::CNV3) => {
+                "health"
+            },
+            (Constellation::BeiDou, _) => "satH1",
+            _ => "health",
+        }
+    }
+    /// Returns the Issue Of Data (Ephemeris), which identifies the broadcast orbit set:
+    /// IODE for GPS, QZSS and BeiDou CNAV, IODnav for Galileo,
+    /// AODE for BeiDou D1/D2 and IODN for SBAS. Does not exist for Glonass.
+    pub fn iode(&self) -> Option<u32> {
+        ["iode", "iodnav", "aode", "iodn"]
+            .iter()
+            .find_map(|field| self.orbits.get(*field)?.as_f64())
+            .map(|iode| iode as u32)
+    }
+    /// Returns the Issue Of Data (Clock), which identifies the broadcast clock set:
+    /// IODC for GPS, QZSS and BeiDou CNAV, AODC for BeiDou D1/D2.
+    pub fn iodc(&self) -> Option<u32> {
+        ["iodc", "aodc"]
+            .iter()
+            .find_map(|field| self.orbits.get(*field)?.as_f64())
+            .map(|iodc| iodc as u32)
+    }
+    /// Returns false if the clock and orbit records of this frame were not issued
+    /// together: IODE should match the 8 LSB of IODC. This can only be verified
+    /// when both IODE and IODC exist (GPS, QZSS and BeiDou CNAV),
+    /// other frames are considered consistent.
+    pub fn is_iod_consistent(&self) -> bool {
+        if self.orbits.contains_key("aode") {
+            return true; // AODE/AODC are not related
+        }
+        match (self.iode(), self.iodc()) {
+            (Some(iode), Some(iodc)) => iode == iodc & 0xff,
+            _ => true,
+        }
+    }
     /// Returns TGD (if value exists) as [Duration]
     pub fn tgd(&self) -> Option<Duration> {
         let tgd_s = self.get_orbit_f64("tgd")?;
         Some(Duration::from_seconds(tgd_s))
     }
-    /// Return ToE expressed as [Epoch]
+    /// Return ToE expressed as [Epoch].
+    /// For GPS and QZSS CNAV frames, ToE is the ToC.
+    /// Modern frames do not describe a week counter: it is deduced from the ToC.
     pub fn toe(&self, sv_ts: TimeScale) -> Option<Epoch> {
-        // TODO: in CNAV V4 TOC is said to be TOE... ...
         let week = self.get_week()?;
         let sec = self.get_orbit_f64("toe")?;
         let week_dur = Duration::from_days((week * 7) as f64);
@@ -394,7 +506,13 @@ impl Ephemeris {
      * get Adot field in CNAV ephemeris
      */
     pub(crate) fn a_dot(&self) -> Option<f64> {
-        self.get_orbit_f64("a_dot")
+        self.get_orbit_f64("adot")
+    }
+    /*
+     * get Delta n0 dot field in CNAV ephemeris
+     */
+    pub(crate) fn delta_n0_dot(&self) -> Option<f64> {
+        self.get_orbit_f64("deltaN0Dot")
     }
     /// Parse Ephemeris (V2/V3) from line iterator
     pub(crate) fn parse_v2v3(
@@ -500,6 +618,8 @@ impl Ephemeris {
             clock_drift_rate = 0.0_f64; // drift rate null: non existing
         }
 
+        Self::resolve_cnav_toe(msg, sv, epoch, &mut orbits);
+
         Ok((
             epoch,
             sv,
@@ -511,6 +631,45 @@ impl Ephemeris {
             },
         ))
     }
+    /*
+     * Modern (CNAV) frames do not describe the week counter,
+     * and GPS/QZSS CNAV frames do not describe ToE either, because ToE = ToC.
+     * We complete the frame so ToE can be resolved like legacy frames.
+     */
+    fn resolve_cnav_toe(
+        msg: NavMsgType,
+        sv: SV,
+        toc: Epoch,
+        orbits: &mut HashMap<String, OrbitItem>,
+    ) {
+        let (toc_week, toc_sow, toe) = match (sv.constellation, msg) {
+            (Constellation::GPS | Constellation::QZSS, NavMsgType::CNAV | NavMsgType::CNV2) => {
+                let (week, nanos) = toc.to_time_scale(TimeScale::GPST).to_time_of_week();
+                let sow = nanos as f64 * 1.0E-9;
+                (week, sow, sow)
+            },
+            (Constellation::BeiDou, NavMsgType::CNV1 | NavMsgType::CNV2 | NavMsgType::CNV3) => {
+                let (week, nanos) = toc.to_time_scale(TimeScale::BDT).to_time_of_week();
+                let toe = match orbits.get("toe").and_then(|item| item.as_f64()) {
+                    Some(toe) => toe,
+                    None => return,
+                };
+                (week, nanos as f64 * 1.0E-9, toe)
+            },
+            _ => return,
+        };
+        // ToE may be expressed in a neighboring week
+        let half_week = 302_400.0_f64;
+        let week = if toe - toc_sow > half_week {
+            toc_week.saturating_sub(1)
+        } else if toc_sow - toe > half_week {
+            toc_week + 1
+        } else {
+            toc_week
+        };
+        orbits.insert("toe".to_string(), OrbitItem::from(toe));
+        orbits.insert("week".to_string(), OrbitItem::from(week));
+    }
 }
 
 #[cfg(feature = "nav")]
@@ -525,9 +684,28 @@ impl Ephemeris {
             omega: self.get_orbit_f64("omega")?,
             omega_0: self.get_orbit_f64("omega0")?,
             m_0: self.get_orbit_f64("m0")?,
-            toe: self.get_orbit_f64("toe")?,
+            // null ToE (start of week) is legal
+            toe: self.orbits.get("toe")?.as_f64()?,
         })
     }
+    /// Returns orbital plane description, as (inclination, RAAN) in degrees,
+    /// RAAN being the Right Ascension of the Ascending Node at ToE, within [0, 360[.
+    /// RAAN is referenced to the start of week, so vehicles of a same constellation
+    /// can be grouped per orbital plane, for example for ground track projections.
+    /// This only applies to MEO Ephemerides, not GEO and Glonass.
+    pub fn orbital_plane(&self) -> Option<(f64, f64)> {
+        let i0 = self.get_orbit_f64("i0")?;
+        let omega0 = self.get_orbit_f64("omega0")?;
+        // null values are legal here
+        let toe = self
+            .orbits
+            .get("toe")
+            .and_then(|item| item.as_f64())
+            .unwrap_or(0.0);
+        let omega_dot = self.get_orbit_f64("omegaDot").unwrap_or(0.0);
+        let raan = (omega0 + omega_dot * toe).to_degrees().rem_euclid(360.0);
+        Some((i0.to_degrees(), raan))
+    }
     /// Creates new Ephemeris with given [`OrbitItem`]
     pub fn with_orbit(&self, key: &str, orbit: OrbitItem) -> Self {
         let mut s = self.clone();
@@ -559,7 +737,10 @@ impl Ephemeris {
             cis: self.get_orbit_f64("cis")?,
             crc: self.get_orbit_f64("crc")?,
             crs: self.get_orbit_f64("crs")?,
-            dn: self.get_orbit_f64("deltaN")?,
+            // CNAV: Delta n0
+            dn: self
+                .get_orbit_f64("deltaN")
+                .or(self.get_orbit_f64("deltaN0"))?,
             i_dot: self.get_orbit_f64("idot")?,
             omega_dot: self.get_orbit_f64("omegaDot")?,
         })
@@ -586,6 +767,84 @@ impl Ephemeris {
         let dt = t.to_time_scale(sv_ts) - toe;
         Some(dt.to_seconds())
     }
+    /// Solves Kepler's equation M = E - e sin(E) iteratively,
+    /// for given mean anomaly `m_k` (rad) and eccentricity `e`.
+    /// Returns None if solution did not converge
+    /// within [constants::MaxIterNumber::KEPLER] iterations.
+    fn eccentric_anomaly(m_k: f64, e: f64) -> Option<f64> {
+        let mut e_k_lst: f64 = 0.0;
+        for _ in 0..constants::MaxIterNumber::KEPLER {
+            let e_k = m_k + e * e_k_lst.sin();
+            if !e_k.is_finite() {
+                return None;
+            }
+            if (e_k - e_k_lst).abs() < 1e-10 {
+                return Some(e_k);
+            }
+            e_k_lst = e_k;
+        }
+        None
+    }
+    /// Returns relativistic clock correction (s) for [SV] at [Epoch] `t`,
+    /// due to orbit eccentricity: F * e * sqrt(a) * sin(E_k).
+    /// This does not apply to SBAS and Glonass.
+    /// Returns None if Keplerian elements are missing or if
+    /// eccentric anomaly could not be solved.
+    pub fn relativistic_correction(&self, sv: SV, t: Epoch) -> Option<f64> {
+        let gm_m3_s2 = Constants::gm(sv);
+        let dtr_f = Constants::dtr_f(sv);
+
+        let t_k = self.t_k(sv, t)?;
+        let mut kepler = self.kepler()?;
+        let perturbations = self.perturbations()?;
+
+        if let Some(a_dot) = self.a_dot() {
+            kepler.a += a_dot * t_k;
+        }
+
+        let n = (gm_m3_s2 / kepler.a.powi(3)).sqrt() + perturbations.dn;
+        let m_k = kepler.m_0 + n * t_k;
+        let e_k = Self::eccentric_anomaly(m_k, kepler.e)?;
+        Some(dtr_f * kepler.e * kepler.a.sqrt() * e_k.sin())
+    }
+    /// Returns broadcast group delay (s) for [SV], that applies
+    /// to single frequency users of said [Carrier]:
+    ///   - GPS, QZSS: TGD on L1, scaled by (f_L1/f_L2)² on L2
+    ///   - Galileo: BGD E1/E5b (or E1/E5a when missing) on E1,
+    ///     BGD E1/E5a (resp. E1/E5b) scaled by (f_E1/f_E5a)² (resp. (f_E1/f_E5b)²) on E5a (resp. E5b)
+    ///   - BeiDou: TGD1 on B1I, TGD2 on B2I (referenced to B3I),
+    ///     and CNAV group delays on B1C, B2a and B2b.
+    ///
+    /// Returns None when the delay is not broadcast, or does not apply to this [Carrier].
+    pub fn group_delay(&self, sv: SV, carrier: Carrier) -> Option<f64> {
+        let gamma =
+            |c: Carrier, reference: Carrier| (reference.frequency() / c.frequency()).powi(2);
+        match (sv.constellation, carrier) {
+            (Constellation::GPS | Constellation::QZSS, Carrier::L1) => self.get_orbit_f64("tgd"),
+            (Constellation::GPS | Constellation::QZSS, Carrier::L2) => {
+                Some(self.get_orbit_f64("tgd")? * gamma(Carrier::L2, Carrier::L1))
+            },
+            (Constellation::Galileo, Carrier::E1) => self
+                .get_orbit_f64("bgdE5bE1")
+                .or(self.get_orbit_f64("bgdE5aE1")),
+            (Constellation::Galileo, Carrier::E5a) => {
+                Some(self.get_orbit_f64("bgdE5aE1")? * gamma(Carrier::E5a, Carrier::E1))
+            },
+            (Constellation::Galileo, Carrier::E5b) => {
+                Some(self.get_orbit_f64("bgdE5bE1")? * gamma(Carrier::E5b, Carrier::E1))
+            },
+            (Constellation::BeiDou, Carrier::B1I) => self
+                .get_orbit_f64("tgd1b1b3")
+                .or(self.get_orbit_f64("tgdb1b3")),
+            (Constellation::BeiDou, Carrier::B2I) => self
+                .get_orbit_f64("tgd2b2b3")
+                .or(self.get_orbit_f64("tgdb2b3")),
+            (Constellation::BeiDou, Carrier::B1C) => self.get_orbit_f64("tgdB1Cp"),
+            (Constellation::BeiDou, Carrier::B2A) => self.get_orbit_f64("tgdB2ap"),
+            (Constellation::BeiDou, Carrier::B2B) => self.get_orbit_f64("tgdB2bI"),
+            _ => None,
+        }
+    }
     /// Form ephemerisHelper.
     /// This does not apply to SBAS and Glonass.
     fn ephemeris_helper(&self, sv: SV, t_sv: Epoch, t: Epoch) -> Option<EphemerisHelper> {
@@ -609,24 +868,19 @@ impl Ephemeris {
         }
 
         let n0 = (gm_m3_s2 / kepler.a.powi(3)).sqrt(); // average angular velocity
-        let n = n0 + perturbations.dn; // corrected mean angular velocity
+        let mut n = n0 + perturbations.dn; // corrected mean angular velocity
+        if let Some(dn_dot) = self.delta_n0_dot() {
+            n += 0.5 * dn_dot * t_k; // CNAV
+        }
         let m_k = kepler.m_0 + n * t_k; // average anomaly
 
-        // Iterative calculation of e_k
-        let mut e_k_lst: f64 = 0.0;
-        let mut e_k: f64 = 0.0;
-        let mut i = 0;
-        loop {
-            e_k = m_k + kepler.e * e_k_lst.sin();
-            if (e_k - e_k_lst).abs() < 1e-10 {
-                break;
-            }
-            i += 1;
-            e_k_lst = e_k;
-        }
-        if i >= constants::MaxIterNumber::KEPLER {
-            error!("{} kepler iteration overflow", sv);
-        }
+        let e_k = match Self::eccentric_anomaly(m_k, kepler.e) {
+            Some(e_k) => e_k,
+            None => {
+                error!("{} kepler iteration overflow", sv);
+                return None;
+            },
+        };
 
         // true anomaly
         let (sin_e_k, cos_e_k) = e_k.sin_cos();
@@ -773,10 +1027,18 @@ impl Ephemeris {
     }
     /// Returns [SV] [Orbit]al state at t [Epoch].
     /// t_sv [Epoch] is the satellite free running clock.
+    /// Glonass state vectors are integrated from t_sv (the ToC) up to t.
     /// Self must be correctly selected from navigation record.
     /// See [Bibliography::AsceAppendix3], [Bibliography::JLe19] and [Bibliography::BeiDouICD]
     pub fn kepler2position(&self, sv: SV, t_sv: Epoch, t: Epoch) -> Option<Orbit> {
-        if sv.constellation.is_sbas() || sv.constellation == Constellation::Glonass {
+        if sv.constellation == Constellation::Glonass {
+            let (pos, vel) = self.glonass_position_velocity(t_sv, t)?;
+            Some(Orbit::from_cartesian_pos_vel(
+                Vector6::new(pos.0, pos.1, pos.2, vel.0, vel.1, vel.2),
+                t,
+                IAU_EARTH_FRAME,
+            ))
+        } else if sv.constellation.is_sbas() {
             let (x_km, y_km, z_km) = (
                 self.get_orbit_f64("satPosX")?,
                 self.get_orbit_f64("satPosY")?,
@@ -805,7 +1067,9 @@ impl Ephemeris {
         t_sv: Epoch,
         t: Epoch,
     ) -> Option<((f64, f64, f64), (f64, f64, f64))> {
-        if sv.constellation.is_sbas() || sv.constellation == Constellation::Glonass {
+        if sv.constellation == Constellation::Glonass {
+            self.glonass_position_velocity(t_sv, t)
+        } else if sv.constellation.is_sbas() {
             let (pos_x_km, pos_y_km, pos_z_km) = (
                 self.get_orbit_f64("satPosX")?,
                 self.get_orbit_f64("satPosY")?,
@@ -826,6 +1090,58 @@ impl Ephemeris {
             Some(((pos.x, pos.y, pos.z), (vel.x, vel.y, vel.z)))
         }
     }
+    /// Glonass ECEF position [km] and velocity [km/s] solver at desired instant "t",
+    /// by integrating the broadcast state vector of Self (position, velocity and
+    /// luni-solar acceleration at `toc`) with a 4th order Runge-Kutta scheme.
+    /// Integration step is 60s at most, in either direction.
+    /// Integration takes place in PZ-90, the returned state is converted to WGS84.
+    /// See Glonass ICD (2008) §A.3.1.2.
+    pub fn glonass_position_velocity(
+        &self,
+        toc: Epoch,
+        t: Epoch,
+    ) -> Option<((f64, f64, f64), (f64, f64, f64))> {
+        let mut state = [
+            self.get_orbit_f64("satPosX")?,
+            self.get_orbit_f64("satPosY")?,
+            self.get_orbit_f64("satPosZ")?,
+            self.get_orbit_f64("velX")?,
+            self.get_orbit_f64("velY")?,
+            self.get_orbit_f64("velZ")?,
+        ];
+        let accel = (
+            self.get_orbit_f64("accelX").unwrap_or(0.0),
+            self.get_orbit_f64("accelY").unwrap_or(0.0),
+            self.get_orbit_f64("accelZ").unwrap_or(0.0),
+        );
+
+        let dt = (t - toc).to_seconds();
+        let nb_steps = (dt.abs() / 60.0).ceil().max(1.0);
+        let h = dt / nb_steps;
+
+        let offset = |s: &[f64; 6], k: &[f64; 6], h: f64| -> [f64; 6] {
+            let mut out = *s;
+            for (out, k) in out.iter_mut().zip(k.iter()) {
+                *out += h * k;
+            }
+            out
+        };
+
+        for _ in 0..nb_steps as usize {
+            let k1 = glonass_derivatives(&state, accel);
+            let k2 = glonass_derivatives(&offset(&state, &k1, h / 2.0), accel);
+            let k3 = glonass_derivatives(&offset(&state, &k2, h / 2.0), accel);
+            let k4 = glonass_derivatives(&offset(&state, &k3, h), accel);
+            for (i, state) in state.iter_mut().enumerate() {
+                *state += h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
+            }
+        }
+
+        Some(pz90_to_wgs84(
+            (state[0], state[1], state[2]),
+            (state[3], state[4], state[5]),
+        ))
+    }
     /// [AzElRange] calculation attempt, for following SV as observed at RX,
     /// both coordinates expressed as [km] in fixed body [Frame] centered on Earth.
     pub fn elevation_azimuth_range(
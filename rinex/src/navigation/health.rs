@@ -89,7 +89,7 @@ bitflags! {
     #[derive(Debug, Default, Copy, Clone)]
     #[derive(PartialEq, PartialOrd)]
     #[cfg_attr(feature = "serde", derive(Serialize))]
-    pub struct GalHealth: u8 {
+    pub struct GalHealth: u16 {
         const E1B_DVS = 0x01;
         const E1B_HS0 = 0x02;
         const E1B_HS1 = 0x04;
@@ -98,6 +98,7 @@ bitflags! {
         const E5A_HS1 = 0x20;
         const E5B_HS0 = 0x40;
         const E5B_HS1 = 0x80;
+        const E5B_HS2 = 0x100;
     }
 }
 
@@ -127,5 +128,6 @@ mod test {
     #[test]
     fn test_gal() {
         assert_eq!(GalHealth::default(), GalHealth::empty());
+        assert_eq!(GalHealth::all().bits(), 0x1ff);
     }
 }
@@ -16,6 +16,7 @@ use std::f64::consts::PI;
 
 /// Model parsing error
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     #[error("ng model missing 1st line")]
     NgModelMissing1stLine,
@@ -230,7 +231,7 @@ bitflags! {
 #[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct NgModel {
     /// a_i coefficients
-    /// (sfu, (sfu.semi-circle⁻¹), (sfu.semi-circle⁻²))
+    /// (sfu, (sfu.deg⁻¹), (sfu.deg⁻²))
     pub a: (f64, f64, f64),
     /// Region flags
     pub region: NgRegionFlags,
@@ -272,10 +273,14 @@ impl NgModel {
             },
         ))
     }
-    // /* converts self to meters of delay */
-    // pub(crate) fn meters_delay(&self, freq: f64) -> f64 {
-    //     0.0_f64
-    // }
+    /// Effective ionisation level [sfu] at given MODIP [ddeg]
+    pub fn effective_ionisation_level(&self, modip_ddeg: f64) -> f64 {
+        if self.a.0 == 0.0 && self.a.1 == 0.0 && self.a.2 == 0.0 {
+            return 63.7; // default value, refer to Galileo ICD
+        }
+        let az = self.a.0 + self.a.1 * modip_ddeg + self.a.2 * modip_ddeg.powi(2);
+        az.clamp(0.0, 400.0)
+    }
 }
 
 /// BDGIM Model payload
@@ -330,10 +335,6 @@ impl BdModel {
         );
         Ok((epoch, Self { alpha }))
     }
-    // /* converts self to meters of delay */
-    // pub(crate) fn meters_delay(&self, freq: f64) -> f64 {
-    //     0.0_f64
-    // }
 }
 
 /// IonMessage wraps all known Ionosphere models
@@ -452,27 +453,6 @@ impl IonMessage {
         }
     }
 
-    // /* converts self to meters of delay */
-    // pub(crate) fn meters_delay(
-    //     &self,
-    //     t: Epoch,
-    //     e: f64,
-    //     a: f64,
-    //     h_km: f64,
-    //     user_lat_ddeg: f64,
-    //     user_lon_ddeg: f64,
-    //     carrier: Carrier,
-    // ) -> Option<f64> {
-    //     if let Some(kb) = self.as_klobuchar() {
-    //         Some(kb.meters_delay(t, e, a, h_km, user_lat_ddeg, user_lon_ddeg, carrier))
-    //     } else if let Some(ng) = self.as_nequick_g() {
-    //         None
-    //     } else if let Some(bd) = self.as_bdgim() {
-    //         None
-    //     } else {
-    //         None
-    //     }
-    // }
     /// Returns reference to Klobuchar Model
     pub fn as_klobuchar(&self) -> Option<&KbModel> {
         match self {
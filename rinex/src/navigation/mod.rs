@@ -1,16 +1,29 @@
 //! NAV RINEX module
+mod builder;
 mod eopmessage;
 mod ephemeris;
 mod health;
 mod ionmessage;
 mod stomessage;
 
+#[cfg(feature = "nav")]
+mod dop;
+
 pub mod orbits;
 pub mod record;
 
+pub use builder::NavigationRecordBuilder;
+
+#[cfg(feature = "nav")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nav")))]
+pub use dop::dilution_of_precision;
+
 pub use eopmessage::EopMessage;
 pub use ephemeris::Ephemeris;
-pub use health::{GeoHealth, GloHealth, Health, IrnssHealth};
+
+#[cfg(feature = "nav")]
+pub(crate) use ephemeris::pz90_to_wgs84;
+pub use health::{GalHealth, GeoHealth, GloHealth, Health, IrnssHealth};
 pub use ionmessage::{BdModel, IonMessage, KbModel, KbRegionCode, NgModel, NgRegionFlags};
 pub use orbits::OrbitItem;
 pub use record::{NavFrame, NavMsgType, Record};
@@ -23,6 +36,7 @@ use gnss::prelude::SV;
 
 /// Navigation Record Parsing Error
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("epoch is missing data")]
     MissingData,
@@ -67,6 +67,7 @@ impl From<f64> for OrbitItem {
 
 /// `OrbitItem` related errors
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum OrbitItemError {
     #[error("failed to parse int value")]
     ParseIntError(#[from] std::num::ParseIntError),
@@ -113,9 +114,12 @@ impl OrbitItem {
                 let unsigned = float as u32;
                 match constellation {
                     Constellation::GPS | Constellation::QZSS => {
-                        let flag: health::Health = num::FromPrimitive::from_u32(unsigned)
-                            .unwrap_or(health::Health::default());
-                        Ok(OrbitItem::Health(flag))
+                        let flag: Option<health::Health> = num::FromPrimitive::from_u32(unsigned);
+                        match flag {
+                            Some(flag) => Ok(OrbitItem::Health(flag)),
+                            // preserve words we cannot describe
+                            None => Ok(OrbitItem::U32(unsigned)),
+                        }
                     },
                     Constellation::Glonass => {
                         let flag: health::GloHealth = num::FromPrimitive::from_u32(unsigned)
@@ -123,9 +127,14 @@ impl OrbitItem {
                         Ok(OrbitItem::GloHealth(flag))
                     },
                     Constellation::Galileo => {
-                        let flags = health::GalHealth::from_bits(unsigned as u8)
-                            .unwrap_or(health::GalHealth::empty());
-                        Ok(OrbitItem::GalHealth(flags))
+                        let flags = u16::try_from(unsigned)
+                            .ok()
+                            .and_then(health::GalHealth::from_bits);
+                        match flags {
+                            Some(flags) => Ok(OrbitItem::GalHealth(flags)),
+                            // preserve words we cannot describe
+                            None => Ok(OrbitItem::U32(unsigned)),
+                        }
                     },
                     Constellation::IRNSS => {
                         let flag: health::IrnssHealth = num::FromPrimitive::from_u32(unsigned)
@@ -220,6 +229,28 @@ impl OrbitItem {
             _ => None,
         }
     }
+    /// Interprets Self as a broadcast health indication, returns true
+    /// if the vehicle is usable. Decoded health words are:
+    ///   - GPS and QZSS: null health word (legacy frames),
+    ///     no L1, L2 or L5 flag asserted (CNAV frames)
+    ///   - Glonass: [health::GloHealth::Healthy]
+    ///   - Galileo: no Data Validity or Signal Health flag asserted
+    ///   - BeiDou: null SatH1 flag (D1/D2 frames), null health word (CNAV frames)
+    ///   - IRNSS: [health::IrnssHealth::Healthy].
+    ///
+    /// SBAS health words are not decoded and we return None in this case.
+    pub fn is_healthy(&self) -> Option<bool> {
+        match self {
+            // null health word, whatever the variant name
+            OrbitItem::Health(h) => Some(h.clone() as u32 == 0),
+            OrbitItem::U32(word) => Some(*word == 0),
+            OrbitItem::F64(word) => Some(*word == 0.0),
+            OrbitItem::GloHealth(h) => Some(*h == health::GloHealth::Healthy),
+            OrbitItem::GalHealth(h) => Some(h.is_empty()),
+            OrbitItem::IrnssHealth(h) => Some(*h == health::IrnssHealth::Healthy),
+            _ => None,
+        }
+    }
     /// Unwraps Self as IRNSS orbit Health indication
     pub fn as_irnss_health(&self) -> Option<health::IrnssHealth> {
         match self {
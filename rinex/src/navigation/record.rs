@@ -1,16 +1,19 @@
 //! NAV frames parser
 use super::{Error, FrameClass};
 use regex::{Captures, Regex};
-use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
 #[cfg(docsrs)]
 use crate::Bibliography;
 
 #[cfg(feature = "processing")]
-use qc_traits::processing::{
-    DecimationFilter, DecimationFilterType, FilterItem, MaskFilter, MaskOperand,
-};
+use qc_traits::processing::{DecimationFilter, FilterItem, MaskFilter, MaskOperand};
+
+#[cfg(feature = "processing")]
+use crate::record::decimated_epochs;
 
 /*
  * When formatting floating point number in Navigation RINEX,
@@ -39,19 +42,29 @@ fn double_exponent_digits(content: &str) -> String {
 }
 
 use crate::{
-    epoch, merge, merge::Merge, prelude::*, split, split::Split, types::Type, version::Version,
+    epoch,
+    header::Header,
+    is_rinex_comment, merge,
+    merge::Merge,
+    prelude::*,
+    reader::{BufferedReader, LimitExceeded},
+    split,
+    split::Split,
+    types::Type,
+    version::Version,
 };
+use std::io::BufRead;
 
 use super::{
     orbits::closest_nav_standards, BdModel, EopMessage, Ephemeris, IonMessage, KbModel, NgModel,
-    StoMessage,
+    OrbitItem, StoMessage,
 };
 
 use hifitime::Duration;
 
 /// Navigation Message Types.
 /// Refer to [Bibliography::RINEX4] definitions.
-#[derive(Default, Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum NavMsgType {
     /// Legacy NAV message
@@ -208,6 +221,35 @@ impl NavFrame {
             _ => None,
         }
     }
+    /// Returns a hash of the complete content of this [NavFrame].
+    /// Two frames share the same hash when they are identical,
+    /// whatever the source they were parsed from. Unlike [SV] and ToE
+    /// identification, this will not confuse legitimately different frames,
+    /// but different frames may still collide: confirm equality on match.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        match self {
+            Self::Eph(msg, sv, eph) => {
+                msg.hash(&mut hasher);
+                sv.hash(&mut hasher);
+                eph.clock_bias.to_bits().hash(&mut hasher);
+                eph.clock_drift.to_bits().hash(&mut hasher);
+                eph.clock_drift_rate.to_bits().hash(&mut hasher);
+                // orbits are not sorted: hash them in key order
+                for (key, item) in eph.orbits.iter().collect::<BTreeMap<_, _>>() {
+                    key.hash(&mut hasher);
+                    match item {
+                        OrbitItem::F64(value) => value.to_bits().hash(&mut hasher),
+                        item => format!("{:?}", item).hash(&mut hasher),
+                    }
+                }
+            },
+            // other frames do not contain maps:
+            // their description is exhaustive and deterministic
+            frame => format!("{:?}", frame).hash(&mut hasher),
+        }
+        hasher.finish()
+    }
 }
 
 /// Navigation Record content:
@@ -268,6 +310,52 @@ pub(crate) fn parse_epoch(
     }
 }
 
+/*
+ * Counts the frames contained in the remaining content (header being consumed),
+ * without building the record: each frame is parsed then dropped, so memory usage
+ * is bounded by the size of a single frame. Frames that the record parser
+ * would drop (parsing errors) are not counted.
+ */
+pub(crate) fn count_frames(
+    reader: &mut BufferedReader,
+    header: &Header,
+) -> Result<usize, crate::record::Error> {
+    let options = reader.options();
+    let constellation = header.constellation.unwrap_or(Constellation::Mixed);
+
+    let mut count = 0;
+    let mut nb_epochs = 0;
+    let mut content = String::with_capacity(8 * 80);
+
+    for line in reader.lines() {
+        let line = line.map_err(|e| match LimitExceeded::from_io_error(&e) {
+            Some(limit) => crate::record::Error::from(limit),
+            None => crate::record::Error::FileIoError(e),
+        })?;
+        if is_rinex_comment(&line) {
+            continue;
+        }
+        if is_new_epoch(&line, header.version) {
+            nb_epochs += 1;
+            options.check_epochs(nb_epochs)?;
+            if nb_epochs > 1 {
+                if parse_epoch(header.version, constellation, &content).is_ok() {
+                    count += 1;
+                }
+                content.clear();
+            }
+        }
+        content.push_str(&line);
+        content.push('\n');
+    }
+
+    // final frame
+    if parse_epoch(header.version, constellation, &content).is_ok() {
+        count += 1;
+    }
+    Ok(count)
+}
+
 /// Builds `Record` entry for Modern NAV frames
 fn parse_v4_record_entry(content: &str) -> Result<(Epoch, NavFrame), Error> {
     let mut lines = content.lines();
@@ -554,9 +642,16 @@ impl Merge for Record {
     fn merge_mut(&mut self, rhs: &Self) -> Result<(), merge::Error> {
         for (rhs_epoch, rhs_frames) in rhs {
             if let Some(frames) = self.get_mut(rhs_epoch) {
-                // this epoch already exists
+                // this epoch already exists:
+                // only identical frames are collapsed.
+                // Hashes quickly rule out different frames,
+                // equality is confirmed on collision.
+                let mut hashes = frames
+                    .iter()
+                    .map(|fr| fr.content_hash())
+                    .collect::<HashSet<_>>();
                 for fr in rhs_frames {
-                    if !frames.contains(fr) {
+                    if hashes.insert(fr.content_hash()) || !frames.contains(fr) {
                         frames.push(fr.clone()); // insert new NavFrame
                     }
                 }
@@ -593,9 +688,75 @@ impl Split for Record {
             .collect();
         Ok((r0, r1))
     }
-    fn split_dt(&self, _duration: Duration) -> Result<Vec<Self>, split::Error> {
-        Ok(Vec::new())
+    fn split_dt(&self, duration: Duration) -> Result<Vec<Self>, split::Error> {
+        Ok(split::split_dt(self, duration, |e| *e))
+    }
+}
+
+/*
+ * Returns message type, [SV] and class of any [NavFrame]
+ */
+#[cfg(feature = "processing")]
+fn frame_header(fr: &NavFrame) -> (NavMsgType, SV, FrameClass) {
+    match fr {
+        NavFrame::Eph(msg, sv, _) => (*msg, *sv, FrameClass::Ephemeris),
+        NavFrame::Eop(msg, sv, _) => (*msg, *sv, FrameClass::EarthOrientation),
+        NavFrame::Ion(msg, sv, _) => (*msg, *sv, FrameClass::IonosphericModel),
+        NavFrame::Sto(msg, sv, _) => (*msg, *sv, FrameClass::SystemTimeOffset),
+    }
+}
+
+/*
+ * Returns true if [Constellation] is contained in filter,
+ * which may describe SBAS vehicles in a broad sense
+ */
+#[cfg(feature = "processing")]
+fn constellation_matches(filter: &[Constellation], c: Constellation) -> bool {
+    filter.contains(&c) || (c.is_sbas() && filter.contains(&Constellation::SBAS))
+}
+
+/*
+ * Interprets complex filter items as message types or frame classes,
+ * returns true if this frame matches one of them, None if
+ * none of the items apply to NAV frames.
+ */
+#[cfg(feature = "processing")]
+fn complex_matches(items: &[String], fr: &NavFrame) -> Option<bool> {
+    let msgs = items
+        .iter()
+        .filter_map(|item| NavMsgType::from_str(item).ok())
+        .collect::<Vec<_>>();
+    let classes = items
+        .iter()
+        .filter_map(|item| FrameClass::from_str(item).ok())
+        .collect::<Vec<_>>();
+    if msgs.is_empty() && classes.is_empty() {
+        return None;
     }
+    let (msg, _, class) = frame_header(fr);
+    Some(msgs.contains(&msg) || classes.contains(&class))
+}
+
+/*
+ * Retains frames that pass the test, removes epochs that become empty
+ */
+#[cfg(feature = "processing")]
+fn retain_frames<F: Fn(&NavFrame) -> bool>(rec: &mut Record, f: F) {
+    rec.retain(|_, frames| {
+        frames.retain(|fr| f(fr));
+        !frames.is_empty()
+    });
+}
+
+/*
+ * SV comparison: only applies to items of the same constellation
+ */
+#[cfg(feature = "processing")]
+fn sv_passes<F: Fn(u8, u8) -> bool>(items: &[SV], sv: SV, f: F) -> bool {
+    items
+        .iter()
+        .filter(|item| item.constellation == sv.constellation)
+        .all(|item| f(sv.prn, item.prn))
 }
 
 #[cfg(feature = "processing")]
@@ -603,39 +764,16 @@ fn mask_mut_equal(rec: &mut Record, target: &FilterItem) {
     match target {
         FilterItem::EpochItem(epoch) => rec.retain(|e, _| *e == *epoch),
         FilterItem::SvItem(filter) => {
-            rec.retain(|_, frames| {
-                frames.retain(|fr| {
-                    if let Some((_, sv, _)) = fr.as_eph() {
-                        filter.contains(&sv)
-                    } else {
-                        // Only applies to Ephemeris frames
-                        false
-                    }
-                });
-                !frames.is_empty()
-            });
+            retain_frames(rec, |fr| filter.contains(&frame_header(fr).1));
         },
         FilterItem::ConstellationItem(filter) => {
-            let mut broad_sbas_filter = false;
-            for c in filter {
-                broad_sbas_filter |= *c == Constellation::SBAS;
-            }
-            rec.retain(|_, frames| {
-                frames.retain(|fr| {
-                    if let Some((_, sv, _)) = fr.as_eph() {
-                        if broad_sbas_filter {
-                            sv.constellation.is_sbas() || filter.contains(&sv.constellation)
-                        } else {
-                            filter.contains(&sv.constellation)
-                        }
-                    } else {
-                        // Only applies to Ephemeris frames
-                        false
-                    }
-                });
-                !frames.is_empty()
+            retain_frames(rec, |fr| {
+                constellation_matches(filter, frame_header(fr).1.constellation)
             });
         },
+        FilterItem::ComplexItem(items) => {
+            retain_frames(rec, |fr| complex_matches(items, fr).unwrap_or(true));
+        },
         _ => {}, // Other items: either not supported, or do not apply
     }
 }
@@ -645,43 +783,16 @@ fn mask_mut_ineq(rec: &mut Record, target: &FilterItem) {
     match target {
         FilterItem::EpochItem(epoch) => rec.retain(|e, _| *e != *epoch),
         FilterItem::SvItem(filter) => {
-            rec.retain(|_, frames| {
-                frames.retain(|fr| {
-                    if let Some((_, sv, _)) = fr.as_eph() {
-                        !filter.contains(&sv)
-                    } else if let Some((_, sv, _)) = fr.as_ion() {
-                        !filter.contains(&sv)
-                    } else if let Some((_, sv, _)) = fr.as_eop() {
-                        !filter.contains(&sv)
-                    } else if let Some((_, sv, _)) = fr.as_sto() {
-                        !filter.contains(&sv)
-                    } else {
-                        // non existing
-                        false
-                    }
-                });
-                !frames.is_empty()
-            });
+            retain_frames(rec, |fr| !filter.contains(&frame_header(fr).1));
         },
         FilterItem::ConstellationItem(filter) => {
-            rec.retain(|_, frames| {
-                frames.retain(|fr| {
-                    if let Some((_, sv, _)) = fr.as_eph() {
-                        !filter.contains(&sv.constellation)
-                    } else if let Some((_, sv, _)) = fr.as_ion() {
-                        !filter.contains(&sv.constellation)
-                    } else if let Some((_, sv, _)) = fr.as_eop() {
-                        !filter.contains(&sv.constellation)
-                    } else if let Some((_, sv, _)) = fr.as_sto() {
-                        !filter.contains(&sv.constellation)
-                    } else {
-                        // non existing
-                        false
-                    }
-                });
-                !frames.is_empty()
+            retain_frames(rec, |fr| {
+                !constellation_matches(filter, frame_header(fr).1.constellation)
             });
         },
+        FilterItem::ComplexItem(items) => {
+            retain_frames(rec, |fr| !complex_matches(items, fr).unwrap_or(false));
+        },
         _ => {}, // Other items: either not supported, or do not apply
     }
 }
@@ -691,54 +802,8 @@ fn mask_mut_leq(rec: &mut Record, target: &FilterItem) {
     match target {
         FilterItem::EpochItem(epoch) => rec.retain(|e, _| *e <= *epoch),
         FilterItem::SvItem(items) => {
-            rec.retain(|_, frames| {
-                frames.retain(|fr| {
-                    if let Some((_, sv, _)) = fr.as_eph() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn > item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else if let Some((_, sv, _)) = fr.as_ion() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn > item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else if let Some((_, sv, _)) = fr.as_eop() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn > item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else if let Some((_, sv, _)) = fr.as_sto() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn > item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else {
-                        // non existing
-                        false
-                    }
-                });
-                !frames.is_empty()
+            retain_frames(rec, |fr| {
+                sv_passes(items, frame_header(fr).1, |a, b| a <= b)
             });
         },
         _ => {}, // Other items: either not supported, or do not apply
@@ -750,55 +815,7 @@ fn mask_mut_lt(rec: &mut Record, target: &FilterItem) {
     match target {
         FilterItem::EpochItem(epoch) => rec.retain(|e, _| *e < *epoch),
         FilterItem::SvItem(items) => {
-            rec.retain(|_, frames| {
-                frames.retain(|fr| {
-                    if let Some((_, sv, _)) = fr.as_eph() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn >= item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else if let Some((_, sv, _)) = fr.as_ion() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn >= item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else if let Some((_, sv, _)) = fr.as_eop() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn >= item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else if let Some((_, sv, _)) = fr.as_sto() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn >= item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else {
-                        // non existing
-                        false
-                    }
-                });
-                !frames.is_empty()
-            });
+            retain_frames(rec, |fr| sv_passes(items, frame_header(fr).1, |a, b| a < b));
         },
         _ => {}, // Other items: either not supported, or do not apply
     }
@@ -809,55 +826,7 @@ fn mask_mut_gt(rec: &mut Record, target: &FilterItem) {
     match target {
         FilterItem::EpochItem(epoch) => rec.retain(|e, _| *e > *epoch),
         FilterItem::SvItem(items) => {
-            rec.retain(|_, frames| {
-                frames.retain(|fr| {
-                    if let Some((_, sv, _)) = fr.as_eph() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn <= item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else if let Some((_, sv, _)) = fr.as_ion() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn <= item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else if let Some((_, sv, _)) = fr.as_eop() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn <= item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else if let Some((_, sv, _)) = fr.as_sto() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn <= item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else {
-                        // non existing
-                        false
-                    }
-                });
-                !frames.is_empty()
-            });
+            retain_frames(rec, |fr| sv_passes(items, frame_header(fr).1, |a, b| a > b));
         },
         _ => {}, // Other items: either not supported, or do not apply
     }
@@ -868,54 +837,8 @@ fn mask_mut_geq(rec: &mut Record, target: &FilterItem) {
     match target {
         FilterItem::EpochItem(epoch) => rec.retain(|e, _| *e >= *epoch),
         FilterItem::SvItem(items) => {
-            rec.retain(|_, frames| {
-                frames.retain(|fr| {
-                    if let Some((_, sv, _)) = fr.as_eph() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn < item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else if let Some((_, sv, _)) = fr.as_ion() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn < item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else if let Some((_, sv, _)) = fr.as_eop() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn < item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else if let Some((_, sv, _)) = fr.as_sto() {
-                        let mut pass = true;
-                        for item in items {
-                            if item.constellation == sv.constellation {
-                                if sv.prn < item.prn {
-                                    pass = false;
-                                }
-                            }
-                        }
-                        pass
-                    } else {
-                        // non existing
-                        false
-                    }
-                });
-                !frames.is_empty()
+            retain_frames(rec, |fr| {
+                sv_passes(items, frame_header(fr).1, |a, b| a >= b)
             });
         },
         _ => {}, // Other items: either not supported, or do not apply
@@ -936,33 +859,28 @@ pub(crate) fn navigation_mask_mut(rec: &mut Record, mask: &MaskFilter) {
 
 #[cfg(feature = "processing")]
 pub(crate) fn navigation_decim_mut(rec: &mut Record, f: &DecimationFilter) {
-    if f.item.is_some() {
-        todo!("targetted decimation not supported yet");
-    }
-    match f.filter {
-        DecimationFilterType::Modulo(r) => {
-            let mut i = 0;
-            rec.retain(|_, _| {
-                let retained = (i % r) == 0;
-                i += 1;
-                retained
-            });
-        },
-        DecimationFilterType::Duration(interval) => {
-            let mut last_retained = Option::<Epoch>::None;
-            rec.retain(|e, _| {
-                if let Some(last) = last_retained {
-                    let dt = *e - last;
-                    if dt >= interval {
-                        last_retained = Some(*e);
-                        true
-                    } else {
-                        false
-                    }
-                } else {
-                    last_retained = Some(*e);
-                    true // always retain 1st epoch
+    let retained = decimated_epochs(rec.keys().copied(), &f.filter);
+    match &f.item {
+        None => rec.retain(|e, _| retained.contains(e)),
+        Some(item) => {
+            // only decimate frames of the targetted vehicles,
+            // other frames are preserved at their original rate
+            let decimated = |fr: &NavFrame| {
+                let (_, sv, _) = frame_header(fr);
+                match item {
+                    FilterItem::SvItem(filter) => filter.contains(&sv),
+                    FilterItem::ConstellationItem(filter) => {
+                        constellation_matches(filter, sv.constellation)
+                    },
+                    _ => false, // other items do not apply
                 }
+            };
+            rec.retain(|e, frames| {
+                if retained.contains(e) {
+                    return true;
+                }
+                frames.retain(|fr| !decimated(fr));
+                !frames.is_empty()
             });
         },
     }
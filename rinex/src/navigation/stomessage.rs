@@ -5,6 +5,7 @@ use thiserror::Error;
 
 /// Parsing error
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     #[error("missing data")]
     MissingData,
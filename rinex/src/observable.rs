@@ -1,8 +1,10 @@
 use crate::{carrier, Carrier, Constellation};
+use std::collections::HashMap;
 use thiserror::Error;
 
 #[derive(Error, Debug, Clone, PartialEq)]
 /// Observable Parsing errors
+#[non_exhaustive]
 pub enum ParsingError {
     #[error("unknown observable \"{0}\"")]
     UnknownObservable(String),
@@ -368,6 +370,40 @@ impl std::str::FromStr for Observable {
     }
 }
 
+/*
+ * Renames all observables contained in this list, following the remapping table.
+ * Observables are kept unique.
+ */
+pub(crate) fn remap_observables_vec_mut(
+    observables: &mut Vec<Observable>,
+    map: &HashMap<Observable, Observable>,
+) {
+    let mut remapped = Vec::<Observable>::with_capacity(observables.len());
+    for observable in observables.iter() {
+        let observable = map.get(observable).unwrap_or(observable);
+        if !remapped.contains(observable) {
+            remapped.push(observable.clone());
+        }
+    }
+    *observables = remapped;
+}
+
+/*
+ * Renames all keys of this map, following the remapping table.
+ * A remapped entry overwrites any existing entry sharing the same name.
+ */
+pub(crate) fn remap_observables_map_mut<T>(
+    data: &mut HashMap<Observable, T>,
+    map: &HashMap<Observable, Observable>,
+) {
+    let (remapped, mut retained): (HashMap<_, _>, HashMap<_, _>) =
+        data.drain().partition(|(k, _)| map.contains_key(k));
+    for (k, v) in remapped {
+        retained.insert(map[&k].clone(), v);
+    }
+    *data = retained;
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,108 @@
+//! Observable coverage, per satellite
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    observation::Record,
+    prelude::{Observable, SV},
+};
+
+#[cfg(feature = "qc")]
+use maud::{html, Markup, Render};
+
+/// [ObservableCoverage] describes which [Observable]s each [SV]
+/// is observed with, as the number of epochs each pair appears in.
+/// When the qc feature is activated, it renders as an SV × observable
+/// availability table, to be integrated in QC reports.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObservableCoverage {
+    /// Total number of epochs
+    pub epochs: usize,
+    /// Number of epochs each [Observable] is reported at, per [SV]
+    pub counts: BTreeMap<SV, BTreeMap<Observable, usize>>,
+}
+
+impl ObservableCoverage {
+    /*
+     * Builds coverage of given record
+     */
+    pub(crate) fn new(rec: &Record) -> Self {
+        let mut counts = BTreeMap::<SV, BTreeMap<Observable, usize>>::new();
+        for (_, (_, vehicles)) in rec.iter() {
+            for (sv, observations) in vehicles.iter() {
+                let sv_counts = counts.entry(*sv).or_default();
+                for observable in observations.keys() {
+                    *sv_counts.entry(observable.clone()).or_default() += 1;
+                }
+            }
+        }
+        Self {
+            epochs: rec.len(),
+            counts,
+        }
+    }
+    /// Returns all [Observable]s observed at least once, sorted
+    pub fn observables(&self) -> BTreeSet<&Observable> {
+        self.counts
+            .values()
+            .flat_map(|counts| counts.keys())
+            .collect()
+    }
+    /// Returns the number of epochs this [SV] is observed with this [Observable]
+    pub fn count(&self, sv: &SV, observable: &Observable) -> usize {
+        self.counts
+            .get(sv)
+            .and_then(|counts| counts.get(observable))
+            .copied()
+            .unwrap_or(0)
+    }
+    /// Returns the ratio (0.0 to 1.0) of epochs this [SV] is observed
+    /// with this [Observable]
+    pub fn ratio(&self, sv: &SV, observable: &Observable) -> f64 {
+        if self.epochs == 0 {
+            0.0
+        } else {
+            self.count(sv, observable) as f64 / self.epochs as f64
+        }
+    }
+}
+
+#[cfg(feature = "qc")]
+impl Render for ObservableCoverage {
+    fn render(&self) -> Markup {
+        let observables = self.observables();
+        html! {
+            table class="table is-bordered" {
+                thead {
+                    tr {
+                        th { "SV" }
+                        @for observable in observables.iter() {
+                            th { (observable.to_string()) }
+                        }
+                    }
+                }
+                tbody {
+                    @for sv in self.counts.keys() {
+                        tr {
+                            th { (sv.to_string()) }
+                            @for observable in observables.iter() {
+                                @if self.count(sv, observable) > 0 {
+                                    td title=(format!("{:.1}%", self.ratio(sv, observable) * 100.0)) {
+                                        span class="icon" style="color:green" {
+                                            i class="fa-solid fa-circle-check" {}
+                                        }
+                                    }
+                                } @else {
+                                    td {
+                                        span class="icon" style="color:red" {
+                                            i class="fa-solid fa-circle-xmark" {}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
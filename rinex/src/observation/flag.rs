@@ -5,6 +5,7 @@ use thiserror::Error;
 use serde::{Deserialize, Serialize};
 
 #[derive(Error, Clone, Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     #[error("non recognized epoch flag")]
     UnknownFlag,
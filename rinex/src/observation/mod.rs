@@ -10,10 +10,26 @@ pub use flag::EpochFlag;
 mod snr;
 pub use snr::SNR;
 
+mod sentinel;
+pub use sentinel::Sentinels;
+
+pub(crate) mod repair;
+pub use repair::{CycleSlip, CycleSlipReport};
+
+pub(crate) mod noise;
+
+pub(crate) mod smoothing;
+
+mod coverage;
+pub use coverage::ObservableCoverage;
+
+mod stream;
+pub use stream::ObservationStream;
+
 #[cfg(docsrs)]
 use crate::Bibliography;
 
-pub use record::{LliFlags, ObservationData, Record};
+pub use record::{LliFlags, MergeConflict, ObservationData, Record, RecordEntry, RecordKey};
 
 #[cfg(feature = "processing")]
 use crate::prelude::TimeScale;
@@ -309,15 +325,40 @@ use std::collections::BTreeMap;
 
 #[cfg(feature = "obs")]
 #[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+/// Dual frequency signal combinations, formed between a signal
+/// and a reference signal of the same physics (phase or pseudo range)
+/// observed on a distinct carrier frequency, the reference being observed on L1.
+/// Results are indexed by (signal, reference).
+/// Phase observations are expressed in meters prior combination.
 #[derive(Debug, Copy, Clone)]
 pub enum Combination {
+    /// Geometry Free combination [m], cancels geometric terms
+    /// and only retains the ionospheric delay (and phase ambiguities).
+    /// Phase: L(reference) - L(signal), Pseudo range: C(signal) - C(reference).
     GeometryFree,
+    /// Ionosphere Free combination [m], cancels the first order ionospheric delay.
     IonosphereFree,
+    /// Wide Lane combination [m]: (f_ref * reference - f * signal) / (f_ref - f)
     WideLane,
+    /// Narrow Lane combination [m]: (f_ref * reference + f * signal) / (f_ref + f)
     NarrowLane,
+    /// Melbourne-Wübbena combination [m]: Wide Lane phase minus Narrow Lane code.
     MelbourneWubbena,
 }
 
+/// Pseudo range unit, as identified by [crate::Rinex::detect_pseudorange_units]
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PseudorangeUnit {
+    /// Meters, as specified by RINEX
+    Meters,
+    /// Kilometers, found in some malformed files
+    Kilometers,
+    /// Could not be determined (no data, or unexpected magnitude)
+    Unknown,
+}
+
 /// GNSS signal combination trait.    
 /// This only applies to OBS RINEX records.  
 /// Refer to [Bibliography::ESAGnssCombination] and [Bibliography::ESABookVol1]
@@ -0,0 +1,120 @@
+//! Receiver noise estimation by time differencing
+use std::collections::BTreeMap;
+
+use crate::{
+    observation::{HeaderFields, LliFlags, Record},
+    prelude::{Constellation, Duration, Epoch, Observable, SV},
+};
+
+/// Converts a Median Absolute Deviation to a standard deviation,
+/// for normally distributed samples
+const MAD_TO_SIGMA: f64 = 1.4826;
+
+/// Variance amplification of third order differencing (1² + 3² + 3² + 1²)
+const TRIPLE_DIFFERENCE_VARIANCE: f64 = 20.0;
+
+/// Median of given values, which get sorted
+fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        Some((values[mid - 1] + values[mid]) / 2.0)
+    } else {
+        Some(values[mid])
+    }
+}
+
+/// Robust (MAD based) standard deviation estimate
+fn mad_sigma(values: &[f64]) -> Option<f64> {
+    let mut values = values.to_vec();
+    let med = median(&mut values)?;
+    let mut deviations = values.iter().map(|v| (v - med).abs()).collect::<Vec<_>>();
+    Some(MAD_TO_SIGMA * median(&mut deviations)?)
+}
+
+/// Estimates the noise of each phase and pseudo range [Observable],
+/// from third order time differences, which cancel the geometry and clock terms
+/// on high rate data. Arcs are interrupted by data gaps (more than 1.5 `sampling`),
+/// by epochs that are not flagged OK, and by lock losses. Arcs shorter than `window`
+/// are disregarded. Estimates are obtained per [SV] then aggregated (median) per
+/// [Constellation] and [Observable].
+pub(crate) fn observation_noise(
+    rec: &Record,
+    header: Option<&HeaderFields>,
+    sampling: Duration,
+    window: Duration,
+) -> BTreeMap<(Constellation, Observable), f64> {
+    let max_gap = sampling * 1.5;
+
+    // most recent epoch that was not flagged OK
+    let mut last_bad = Option::<Epoch>::None;
+    let mut arcs = BTreeMap::<(SV, Observable), Vec<Vec<(Epoch, f64)>>>::new();
+
+    for ((t, flag), (_, vehicles)) in rec.iter() {
+        if !flag.is_ok() {
+            last_bad = Some(*t);
+            continue;
+        }
+        for (sv, observations) in vehicles {
+            for (observable, data) in observations {
+                if !observable.is_phase_observable() && !observable.is_pseudorange_observable() {
+                    continue;
+                }
+                let scaling = header
+                    .and_then(|h| h.scaling(sv.constellation, observable.clone()))
+                    .map(|scaling| *scaling as f64)
+                    .unwrap_or(1.0);
+
+                let lock_loss = data
+                    .lli
+                    .map(|lli| lli.intersects(LliFlags::LOCK_LOSS))
+                    .unwrap_or(false);
+
+                let sv_arcs = arcs.entry((*sv, observable.clone())).or_default();
+                let new_arc = match sv_arcs.last().and_then(|arc| arc.last()) {
+                    Some((prev_t, _)) => {
+                        lock_loss
+                            || *t - *prev_t > max_gap
+                            || last_bad.map(|bad| bad > *prev_t).unwrap_or(false)
+                    },
+                    None => true,
+                };
+                if new_arc {
+                    sv_arcs.push(Vec::new());
+                }
+                if let Some(arc) = sv_arcs.last_mut() {
+                    arc.push((*t, data.obs / scaling));
+                }
+            }
+        }
+    }
+
+    let mut sigmas = BTreeMap::<(Constellation, Observable), Vec<f64>>::new();
+    for ((sv, observable), sv_arcs) in arcs {
+        let differences = sv_arcs
+            .iter()
+            .filter(|arc| match (arc.first(), arc.last()) {
+                (Some((t0, _)), Some((t1, _))) => *t1 - *t0 >= window,
+                _ => false,
+            })
+            .flat_map(|arc| {
+                arc.windows(4)
+                    .map(|w| w[3].1 - 3.0 * w[2].1 + 3.0 * w[1].1 - w[0].1)
+            })
+            .collect::<Vec<_>>();
+        if let Some(sigma) = mad_sigma(&differences) {
+            sigmas
+                .entry((sv.constellation, observable))
+                .or_default()
+                .push(sigma / TRIPLE_DIFFERENCE_VARIANCE.sqrt());
+        }
+    }
+
+    sigmas
+        .into_iter()
+        .filter_map(|(key, mut values)| Some((key, median(&mut values)?)))
+        .collect()
+}
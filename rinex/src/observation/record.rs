@@ -4,19 +4,21 @@ use std::str::FromStr;
 use thiserror::Error;
 
 use crate::{
-    epoch, merge, merge::Merge, prelude::Duration, prelude::*, split, split::Split, types::Type,
-    version::Version, Carrier, Observable,
+    epoch, formatting::fmt_fixed, merge, merge::Merge, prelude::Duration, prelude::*, split,
+    split::Split, types::Type, version::Version, Carrier, Observable,
 };
 
 use crate::observation::EpochFlag;
 use crate::observation::SNR;
 
 #[cfg(feature = "processing")]
-use qc_traits::processing::{
-    DecimationFilter, DecimationFilterType, FilterItem, MaskFilter, MaskOperand, Repair,
-};
+use qc_traits::processing::{DecimationFilter, FilterItem, MaskFilter, MaskOperand, Repair};
+
+#[cfg(feature = "processing")]
+use crate::record::decimated_epochs;
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("failed to parse epoch flag")]
     EpochFlag(#[from] crate::observation::flag::Error),
@@ -132,15 +134,19 @@ impl ObservationData {
     }
 }
 
+/// Observation [Record] indexing: [Epoch] and [EpochFlag]
+pub type RecordKey = (Epoch, EpochFlag);
+
+/// Observation [Record] content, for a given [RecordKey]:
+/// receiver clock offset (if any) and observations per [SV] and [Observable]
+pub type RecordEntry = (
+    Option<f64>,
+    BTreeMap<SV, HashMap<Observable, ObservationData>>,
+);
+
 /// Observation Record content, sorted by [`Epoch`], per [`SV`] and per
 /// [`Observable`].
-pub type Record = BTreeMap<
-    (Epoch, EpochFlag),
-    (
-        Option<f64>,
-        BTreeMap<SV, HashMap<Observable, ObservationData>>,
-    ),
->;
+pub type Record = BTreeMap<RecordKey, RecordEntry>;
 
 /// Returns true if given content matches a new OBSERVATION data epoch
 pub(crate) fn is_new_epoch(line: &str, v: Version) -> bool {
@@ -215,6 +221,11 @@ pub(crate) fn parse_epoch(
         line = line.split_at(1).1;
     }
 
+    // date + flag + n_sat
+    if line.len() < offset + 6 {
+        return Err(Error::MissingData);
+    }
+
     let (date, rem) = line.split_at(offset);
     let epoch = epoch::parse_in_timescale(date, ts)?;
     let (flag, rem) = rem.split_at(3);
@@ -223,18 +234,18 @@ pub(crate) fn parse_epoch(
     let n_sat = n_sat.trim().parse::<u16>()?;
 
     // grab possible clock offset
-    let offs: Option<&str> = match header.version.major < 2 {
+    let offs: Option<&str> = match header.version.major < 3 {
         true => {
             // RINEX 2
-            // clock offsets are last 12 characters
-            if line.len() > 60 - 12 {
-                Some(line.split_at(60 - 12).1.trim())
+            // F12.9 clock offset follows the 12 vehicle slots (columns 69-80)
+            if line.len() > 68 {
+                Some(line.split_at(68).1.trim())
             } else {
                 None
             }
         },
         false => {
-            // RINEX 3
+            // RINEX 3 and RINEX 4
             let min_len: usize = 4+1 // y
                 +2+1 // m
                 +2+1 // d
@@ -245,6 +256,7 @@ pub(crate) fn parse_epoch(
                 +3; // n_sat
             if line.len() > min_len {
                 // RINEX3: clock offset precision was increased
+                // RINEX4: 6X + F15.12 field, which might be left blank
                 Some(line.split_at(min_len).1.trim()) // this handles it naturally
             } else {
                 None
@@ -298,6 +310,8 @@ fn parse_normal(
             // This remains empty on RINEX3, because we have such information
             // on following lines, which is much more convenient
             let mut systems = String::with_capacity(24 * 3); //SVNN
+                                                             // 12 vehicle slots at most, possibly followed by the clock offset
+            let rem = if rem.len() > 36 { &rem[..36] } else { rem };
             systems.push_str(rem.trim());
             while systems.len() / 3 < n_sat.into() {
                 if let Some(l) = lines.next() {
@@ -631,7 +645,9 @@ fn parse_v3(
     data
 }
 
-/// Formats one epoch according to standard definitions
+/// Formats one epoch according to standard definitions.
+/// RINEX4 epochs are formatted like RINEX3 epochs,
+/// with the extended (F15.12) receiver clock offset field.
 pub(crate) fn fmt_epoch(
     epoch: Epoch,
     flag: EpochFlag,
@@ -664,7 +680,8 @@ fn fmt_epoch_v3(
     ));
 
     if let Some(data) = clock_offset {
-        lines.push_str(&format!("{:13.4}", data));
+        // 6X + F15.12
+        lines.push_str(&format!("      {}", fmt_fixed(*data, 15, 12)));
     }
 
     lines.push('\n');
@@ -677,7 +694,7 @@ fn fmt_epoch_v3(
         if let Some(observables) = observables {
             for observable in observables {
                 if let Some(observation) = data.get(observable) {
-                    lines.push_str(&format!("{:14.3}", observation.obs));
+                    lines.push_str(&fmt_fixed(observation.obs, 14, 3));
                     if let Some(flag) = observation.lli {
                         lines.push_str(&format!("{}", flag.bits()));
                     } else {
@@ -693,6 +710,8 @@ fn fmt_epoch_v3(
                 }
             }
         }
+        // trailing blank fields are not significant
+        lines.truncate(lines.trim_end().len());
         lines.push('\n');
     }
     lines.truncate(lines.trim_end().len());
@@ -721,10 +740,9 @@ fn fmt_epoch_v2(
         if index == 12 {
             index = 0;
             if sv_index == 12 {
-                // first line
-                if let Some(data) = clock_offset {
-                    // push clock offsets
-                    lines.push_str(&format!(" {:9.1}", data));
+                // first line: F12.9 clock offset follows the 12 vehicle slots
+                if let Some(offset) = clock_offset {
+                    lines.push_str(&fmt_fixed(*offset, 12, 9));
                 }
             }
             lines.push_str("\n                                ");
@@ -732,6 +750,13 @@ fn fmt_epoch_v2(
         lines.push_str(&format!("{:x}", sv));
         index += 1;
     }
+    if data.len() <= 12 {
+        if let Some(offset) = clock_offset {
+            // pad the unused vehicle slots
+            lines.push_str(&" ".repeat(3 * (12 - data.len())));
+            lines.push_str(&fmt_fixed(*offset, 12, 9));
+        }
+    }
     let obs_per_line = 5;
     // for each vehicle per epoch
     for (sv, observations) in data.iter() {
@@ -747,7 +772,7 @@ fn fmt_epoch_v2(
                     lines.push('\n');
                 }
                 if let Some(observation) = observations.get(observable) {
-                    let formatted_obs = format!("{:14.3}", observation.obs);
+                    let formatted_obs = fmt_fixed(observation.obs, 14, 3);
                     let formatted_flags: String = match observation.lli {
                         Some(lli) => match observation.snr {
                             Some(snr) => format!("{}{:x}", lli.bits(), snr),
@@ -782,16 +807,16 @@ impl Merge for Record {
         for (rhs_epoch, (rhs_clk, rhs_vehicles)) in rhs {
             if let Some((clk, vehicles)) = self.get_mut(rhs_epoch) {
                 // exact epoch (both timestamp and flag) did exist
-                //  --> overwrite clock field (as is)
-                *clk = *rhs_clk;
-                // other fields:
-                // either insert (if did not exist), or overwrite
+                //  --> only provide clock offset if missing
+                if clk.is_none() {
+                    *clk = *rhs_clk;
+                }
+                // other fields: insert (if did not exist),
+                // existing values are preserved
                 for (rhs_vehicle, rhs_observations) in rhs_vehicles {
                     if let Some(observations) = vehicles.get_mut(rhs_vehicle) {
                         for (rhs_observable, rhs_data) in rhs_observations {
-                            if let Some(data) = observations.get_mut(rhs_observable) {
-                                *data = *rhs_data; // overwrite
-                            } else {
+                            if !observations.contains_key(rhs_observable) {
                                 // new observation: insert it
                                 observations.insert(rhs_observable.clone(), *rhs_data);
                             }
@@ -810,6 +835,57 @@ impl Merge for Record {
     }
 }
 
+/// Observation that exists in both records being merged, with different values.
+/// [Merge] resolves these deterministically by preserving `lhs`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    /// Shared [RecordKey]
+    pub key: RecordKey,
+    /// [SV] being observed
+    pub sv: SV,
+    /// [Observable] in conflict
+    pub observable: Observable,
+    /// [ObservationData] that was preserved
+    pub lhs: ObservationData,
+    /// [ObservationData] that was dropped
+    pub rhs: ObservationData,
+}
+
+/*
+ * Lists observations that [Merge] will drop, because they already
+ * exist in lhs, with a different value.
+ */
+pub(crate) fn merge_conflicts(lhs: &Record, rhs: &Record) -> Vec<MergeConflict> {
+    let mut conflicts = Vec::new();
+    for (key, (_, rhs_vehicles)) in rhs {
+        let vehicles = match lhs.get(key) {
+            Some((_, vehicles)) => vehicles,
+            None => continue,
+        };
+        for (sv, rhs_observations) in rhs_vehicles {
+            let observations = match vehicles.get(sv) {
+                Some(observations) => observations,
+                None => continue,
+            };
+            for (observable, rhs_data) in rhs_observations {
+                if let Some(data) = observations.get(observable) {
+                    if data != rhs_data {
+                        conflicts.push(MergeConflict {
+                            key: *key,
+                            sv: *sv,
+                            observable: observable.clone(),
+                            lhs: *data,
+                            rhs: *rhs_data,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    conflicts.sort_by(|a, b| (a.key, a.sv, &a.observable).cmp(&(b.key, b.sv, &b.observable)));
+    conflicts
+}
+
 impl Split for Record {
     fn split(&self, epoch: Epoch) -> Result<(Self, Self), split::Error> {
         let r0 = self
@@ -835,23 +911,7 @@ impl Split for Record {
         Ok((r0, r1))
     }
     fn split_dt(&self, duration: Duration) -> Result<Vec<Self>, split::Error> {
-        let mut curr = Self::new();
-        let mut ret: Vec<Self> = Vec::new();
-        let mut prev: Option<Epoch> = None;
-        for ((epoch, flag), data) in self {
-            if let Some(p_epoch) = prev {
-                let dt = *epoch - p_epoch;
-                if dt >= duration {
-                    prev = Some(*epoch);
-                    ret.push(curr);
-                    curr = Self::new();
-                }
-                curr.insert((*epoch, *flag), data.clone());
-            } else {
-                prev = Some(*epoch);
-            }
-        }
-        Ok(ret)
+        Ok(split::split_dt(self, duration, |(e, _)| *e))
     }
 }
 
@@ -1129,35 +1189,39 @@ pub(crate) fn observation_mask_mut(rec: &mut Record, mask: &MaskFilter) {
     }
 }
 
+/*
+ * Returns true if this observation belongs to the decimated subset
+ */
 #[cfg(feature = "processing")]
-pub(crate) fn observation_decim_mut(rec: &mut Record, decim: &DecimationFilter) {
-    if decim.item.is_some() {
-        todo!("targetted decimation not supported yet");
+fn decimated_item(item: &FilterItem, sv: &SV, observable: &Observable) -> bool {
+    match item {
+        FilterItem::SvItem(svs) => svs.contains(sv),
+        FilterItem::ConstellationItem(constells) => constells.contains(&sv.constellation),
+        FilterItem::ComplexItem(filter) => filter
+            .iter()
+            .filter_map(|f| Observable::from_str(f).ok())
+            .any(|ob| ob == *observable),
+        _ => false,
     }
-    match decim.filter {
-        DecimationFilterType::Modulo(r) => {
-            let mut i = 0;
-            rec.retain(|_, _| {
-                let retained = (i % r) == 0;
-                i += 1;
-                retained
-            });
-        },
-        DecimationFilterType::Duration(interval) => {
-            let mut last_retained = Option::<Epoch>::None;
-            rec.retain(|(e, _), _| {
-                if let Some(last) = last_retained {
-                    let dt = *e - last;
-                    if dt >= interval {
-                        last_retained = Some(*e);
-                        true
-                    } else {
-                        false
-                    }
-                } else {
-                    last_retained = Some(*e);
-                    true // always retain 1st epoch
+}
+
+#[cfg(feature = "processing")]
+pub(crate) fn observation_decim_mut(rec: &mut Record, decim: &DecimationFilter) {
+    let retained = decimated_epochs(rec.keys().map(|(e, _)| *e), &decim.filter);
+    match &decim.item {
+        None => rec.retain(|(e, _), _| retained.contains(e)),
+        Some(item) => {
+            // only decimate the targetted subset,
+            // everything else is preserved at its original rate
+            rec.retain(|(e, _), (clk, svs)| {
+                if retained.contains(e) {
+                    return true;
                 }
+                svs.retain(|sv, obs| {
+                    obs.retain(|observable, _| !decimated_item(item, sv, observable));
+                    !obs.is_empty()
+                });
+                clk.is_some() || !svs.is_empty()
             });
         },
     }
@@ -1225,6 +1289,9 @@ fn dual_freq_combination(
                 }
 
                 let (lhs_carrier, ref_carrier) = (lhs_carrier.unwrap(), ref_carrier.unwrap());
+                if lhs_carrier == ref_carrier {
+                    continue; // not a dual frequency combination
+                }
                 let (fj, fi) = (lhs_carrier.frequency(), ref_carrier.frequency());
                 let (lambda_j, lambda_i) = (lhs_carrier.wavelength(), ref_carrier.wavelength());
 
@@ -1301,49 +1368,44 @@ fn mw_combination(
     let code_narrow = dual_freq_combination(rec, Combination::NarrowLane);
     let mut phase_wide = dual_freq_combination(rec, Combination::WideLane);
 
-    phase_wide.retain(|(lhs_obs, rhs_obs), phase_wide| {
-        let lhs_code_obs =
-            Observable::from_str(&format!("C{}", &lhs_obs.to_string()[1..])).unwrap();
-        let rhs_code_obs =
-            Observable::from_str(&format!("C{}", &rhs_obs.to_string()[1..])).unwrap();
-
-        if lhs_obs.is_phase_observable() {
-            if let Some(code_data) = code_narrow.get(&(lhs_code_obs, rhs_code_obs)) {
-                phase_wide.retain(|sv, phase_data| {
-                    if let Some(code_data) = code_data.get(sv) {
-                        phase_data.retain(|epoch, _| code_data.get(epoch).is_some());
-                        !phase_data.is_empty()
-                    } else {
-                        false
-                    }
-                });
-                !phase_wide.is_empty()
-            } else {
-                false
-            }
-        } else {
-            false
-        }
-    });
+    /*
+     * Pseudo range observables that match this phase observable:
+     * V2 files usually describe P codes (P1, P2) rather than C codes
+     */
+    let codes = |phase: &Observable| -> Vec<Observable> {
+        let code = &phase.to_string()[1..];
+        ["C", "P"]
+            .iter()
+            .filter_map(|prefix| Observable::from_str(&format!("{}{}", prefix, code)).ok())
+            .collect()
+    };
+
+    phase_wide.retain(|(lhs_obs, _), _| lhs_obs.is_phase_observable());
 
     for ((lhs_obs, rhs_obs), phase_data) in phase_wide.iter_mut() {
-        let lhs_code_obs =
-            Observable::from_str(&format!("C{}", &lhs_obs.to_string()[1..])).unwrap();
-        let rhs_code_obs =
-            Observable::from_str(&format!("C{}", &rhs_obs.to_string()[1..])).unwrap();
-
-        if let Some(code_data) = code_narrow.get(&(lhs_code_obs, rhs_code_obs)) {
-            for (phase_sv, data) in phase_data {
-                if let Some(code_data) = code_data.get(phase_sv) {
-                    for (epoch, phase_wide) in data {
-                        if let Some(narrow_code) = code_data.get(epoch) {
-                            *phase_wide -= narrow_code;
-                        }
-                    }
+        let (lhs_codes, rhs_codes) = (codes(lhs_obs), codes(rhs_obs));
+        phase_data.retain(|sv, data| {
+            data.retain(|epoch, phase_wide| {
+                let narrow_code = lhs_codes.iter().find_map(|lhs_code| {
+                    rhs_codes.iter().find_map(|rhs_code| {
+                        code_narrow
+                            .get(&(lhs_code.clone(), rhs_code.clone()))?
+                            .get(sv)?
+                            .get(epoch)
+                    })
+                });
+                if let Some(narrow_code) = narrow_code {
+                    *phase_wide -= narrow_code;
+                    true
+                } else {
+                    false
                 }
-            }
-        }
+            });
+            !data.is_empty()
+        });
     }
+
+    phase_wide.retain(|_, phase_data| !phase_data.is_empty());
     phase_wide
 }
 
@@ -1542,6 +1604,9 @@ pub(crate) fn code_multipath(
                 let alpha = (gamma + 1.0) / (gamma - 1.0);
                 let beta = 2.0 / (gamma - 1.0);
                 let value = obsdata.obs - alpha * phase_i.unwrap() + beta * phase_j.unwrap();
+                if !value.is_finite() {
+                    continue; // same frequencies: not a valid combination
+                }
 
                 if let Some(data) = ret.get_mut(observable) {
                     if let Some(data) = data.get_mut(sv) {
@@ -1564,6 +1629,83 @@ pub(crate) fn code_multipath(
     ret
 }
 
+/*
+ * Epoch to epoch code multipath variation (in meters) above which
+ * we consider a new arc started. This is a fallback for cycle slips
+ * that were not flagged by the receiver.
+ */
+#[cfg(feature = "obs")]
+const MULTIPATH_ARC_THRESHOLD: f64 = 5.0;
+
+/*
+ * Returns true if a phase lock loss was flagged for this SV, at this epoch
+ */
+#[cfg(feature = "obs")]
+fn phase_lock_loss(rec: &Record, key: &(Epoch, EpochFlag), sv: &SV) -> bool {
+    rec.get(key)
+        .and_then(|(_, vehicles)| vehicles.get(sv))
+        .map(|observations| {
+            observations.iter().any(|(observable, data)| {
+                observable.is_phase_observable()
+                    && data
+                        .lli
+                        .map(|lli| lli.intersects(LliFlags::LOCK_LOSS))
+                        .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
+
+/*
+ * Code multipath RMS, per SV and code.
+ * Code multipath is only known up to the phase ambiguities,
+ * which are removed by centering each continuous arc on its mean value.
+ * A new arc starts on each phase lock loss (LLI), each epoch that is not flagged OK,
+ * each data gap (more than 1.5 `sampling` interval, when known) and,
+ * as a fallback, each jump larger than MULTIPATH_ARC_THRESHOLD.
+ * Arcs of a single value do not contribute.
+ */
+#[cfg(feature = "obs")]
+pub(crate) fn multipath_rms(
+    rec: &Record,
+    sampling: Option<Duration>,
+) -> BTreeMap<(SV, Observable), f64> {
+    let max_gap = sampling.map(|dt| dt * 1.5);
+    let mut ret = BTreeMap::<(SV, Observable), f64>::new();
+    for (observable, vehicles) in code_multipath(rec) {
+        for (sv, values) in vehicles {
+            let mut arcs = Vec::<Vec<f64>>::new();
+            let mut prev = Option::<(Epoch, f64)>::None;
+            for (key, value) in values {
+                let continuous = match prev {
+                    Some((prev_t, prev_value)) => {
+                        key.1.is_ok()
+                            && !phase_lock_loss(rec, &key, &sv)
+                            && max_gap.map(|max| key.0 - prev_t <= max).unwrap_or(true)
+                            && (value - prev_value).abs() < MULTIPATH_ARC_THRESHOLD
+                    },
+                    None => false,
+                };
+                match arcs.last_mut() {
+                    Some(arc) if continuous => arc.push(value),
+                    _ => arcs.push(vec![value]),
+                }
+                prev = Some((key.0, value));
+            }
+            let (mut sum, mut count) = (0.0_f64, 0_usize);
+            for arc in arcs.iter().filter(|arc| arc.len() > 1) {
+                let mean = arc.iter().sum::<f64>() / arc.len() as f64;
+                sum += arc.iter().map(|value| (value - mean).powi(2)).sum::<f64>();
+                count += arc.len();
+            }
+            if count > 0 {
+                ret.insert((sv, observable.clone()), (sum / count as f64).sqrt());
+            }
+        }
+    }
+    ret
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,211 @@
+//! Doppler aided cycle slip repair
+use std::collections::BTreeSet;
+
+use crate::{
+    observation::{HeaderFields, LliFlags, Record},
+    prelude::{Duration, Epoch, Observable, SV},
+};
+
+/// Phase jump (in cycles) above which we consider a cycle slip,
+/// even though the receiver did not flag it
+const DETECTION_THRESHOLD: f64 = 0.5;
+
+/// Default deviation (in cycles) from the linear phase prediction
+/// above which we consider that the ambiguity was reset.
+/// Natural phase accelerations remain below a few hundred cycles at 30s sampling.
+pub(crate) const PHASE_RESET_THRESHOLD: f64 = 1000.0;
+
+/// Maximal distance to the closest integer number of cycles
+/// for a cycle slip to be repaired
+const REPAIR_THRESHOLD: f64 = 0.3;
+
+/// Cycle slip that was either repaired or left untouched
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleSlip {
+    /// [Epoch] of the first observation affected by this slip
+    pub epoch: Epoch,
+    /// [SV] affected by this slip
+    pub sv: SV,
+    /// Phase [Observable] affected by this slip
+    pub observable: Observable,
+    /// Phase jump estimated from Doppler integration, in cycles.
+    /// None when this could not be estimated (data gap, missing Doppler).
+    pub cycles: Option<f64>,
+}
+
+/// Report returned by [crate::Rinex::repair_cycle_slips_mut]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CycleSlipReport {
+    /// Cycle slips that we repaired: subsequent phase observations
+    /// were corrected by the (integer) estimated number of cycles
+    pub repaired: Vec<CycleSlip>,
+    /// Cycle slips that we could not repair with enough confidence:
+    /// phase observations were not modified
+    pub unrepairable: Vec<CycleSlip>,
+}
+
+/// Returns Doppler [Observable] that matches this phase [Observable]
+fn doppler_observable(phase: &Observable) -> Option<Observable> {
+    match phase {
+        Observable::Phase(code) => Some(Observable::Doppler(code.replacen('L', "D", 1))),
+        _ => None,
+    }
+}
+
+/// Repairs cycle slips by comparing phase jumps to integrated Doppler shifts.
+/// Phase (cycles) evolves opposite to the Doppler shift (Hz), which is positive
+/// for approaching vehicles.
+pub(crate) fn repair_cycle_slips(
+    rec: &mut Record,
+    header: Option<&HeaderFields>,
+    max_gap: Duration,
+) -> CycleSlipReport {
+    let mut report = CycleSlipReport::default();
+
+    let signals = rec
+        .values()
+        .flat_map(|(_, vehicles)| {
+            vehicles.iter().flat_map(|(sv, observations)| {
+                observations
+                    .keys()
+                    .filter(|observable| observable.is_phase_observable())
+                    .map(|observable| (*sv, observable.clone()))
+            })
+        })
+        .collect::<BTreeSet<_>>();
+
+    for (sv, phase) in signals {
+        let doppler = match doppler_observable(&phase) {
+            Some(doppler) => doppler,
+            None => continue,
+        };
+
+        let scaling = |observable: &Observable| -> f64 {
+            header
+                .and_then(|h| h.scaling(sv.constellation, observable.clone()))
+                .map(|scaling| *scaling as f64)
+                .unwrap_or(1.0)
+        };
+        let (phase_scaling, doppler_scaling) = (scaling(&phase), scaling(&doppler));
+
+        // previous (epoch, corrected phase, doppler)
+        let mut prev: Option<(Epoch, f64, Option<f64>)> = None;
+        // correction currently applied, in cycles
+        let mut correction = 0.0_f64;
+
+        for ((t, _), (_, vehicles)) in rec.iter_mut() {
+            let observations = match vehicles.get_mut(&sv) {
+                Some(observations) => observations,
+                None => continue,
+            };
+            let doppler = observations
+                .get(&doppler)
+                .map(|data| data.obs / doppler_scaling);
+            let data = match observations.get_mut(&phase) {
+                Some(data) => data,
+                None => continue,
+            };
+
+            let lock_loss = data
+                .lli
+                .map(|lli| lli.intersects(LliFlags::LOCK_LOSS))
+                .unwrap_or(false);
+
+            let mut corrected = data.obs / phase_scaling - correction;
+
+            if let Some((prev_t, prev_phase, prev_doppler)) = prev {
+                let dt = *t - prev_t;
+                let estimate = match (prev_doppler, doppler) {
+                    (Some(d0), Some(d1)) if dt <= max_gap => {
+                        let predicted = -(d0 + d1) / 2.0 * dt.to_seconds();
+                        Some((corrected - prev_phase) - predicted)
+                    },
+                    _ => None,
+                };
+
+                let slip = |cycles| CycleSlip {
+                    epoch: *t,
+                    sv,
+                    observable: phase.clone(),
+                    cycles,
+                };
+
+                match estimate {
+                    Some(residual) if lock_loss || residual.abs() > DETECTION_THRESHOLD => {
+                        let cycles = residual.round();
+                        if (residual - cycles).abs() < REPAIR_THRESHOLD {
+                            if cycles != 0.0 {
+                                correction += cycles;
+                                corrected -= cycles;
+                                report.repaired.push(slip(Some(cycles)));
+                            }
+                        } else {
+                            // low confidence: this jump is not corrected,
+                            // previous repairs still apply to the new arc
+                            report.unrepairable.push(slip(Some(residual)));
+                        }
+                    },
+                    None if lock_loss => {
+                        report.unrepairable.push(slip(None));
+                    },
+                    _ => {},
+                }
+            }
+
+            if correction != 0.0 {
+                data.obs = corrected * phase_scaling;
+            }
+
+            prev = Some((*t, corrected, doppler));
+        }
+    }
+    report
+}
+
+/// Identifies epochs where the ambiguity of this phase [Observable] was likely reset:
+/// the receiver flagged a lock loss, or the phase deviates from the linear prediction
+/// (based on the two previous samples) by more than `threshold` cycles.
+/// The prediction restarts after each reset, so each reset is reported once.
+pub(crate) fn phase_resets<'a>(
+    rec: &'a Record,
+    header: Option<&HeaderFields>,
+    sv: SV,
+    observable: &Observable,
+    threshold: f64,
+) -> impl Iterator<Item = Epoch> + 'a {
+    let observable = observable.clone();
+    let scaling = header
+        .and_then(|h| h.scaling(sv.constellation, observable.clone()))
+        .map(|scaling| *scaling as f64)
+        .unwrap_or(1.0);
+
+    // two previous (epoch, phase) samples, most recent first
+    let mut prev: (Option<(Epoch, f64)>, Option<(Epoch, f64)>) = (None, None);
+
+    rec.iter().filter_map(move |((t, _), (_, vehicles))| {
+        let data = vehicles.get(&sv)?.get(&observable)?;
+        let phase = data.obs / scaling;
+
+        let lock_loss = data
+            .lli
+            .map(|lli| lli.intersects(LliFlags::LOCK_LOSS))
+            .unwrap_or(false);
+
+        let jump = match prev {
+            (Some((t0, phase0)), Some((t1, phase1))) => {
+                let rate = (phase0 - phase1) / (t0 - t1).to_seconds();
+                let predicted = phase0 + rate * (*t - t0).to_seconds();
+                (phase - predicted).abs() > threshold
+            },
+            _ => false,
+        };
+
+        if lock_loss || jump {
+            prev = (Some((*t, phase)), None);
+            Some(*t)
+        } else {
+            prev = (Some((*t, phase)), prev.0);
+            None
+        }
+    })
+}
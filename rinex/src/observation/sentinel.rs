@@ -0,0 +1,101 @@
+use crate::observable::Observable;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// [Sentinels] describes observation values that receivers emit
+/// to flag invalid data (for example blank pseudo ranges reported as 0.0,
+/// or saturated values that do not fit in the RINEX format),
+/// see [crate::Rinex::drop_sentinels_mut].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sentinels {
+    /// Pseudo range values [m] flagging invalid data
+    pub pseudo_range: Vec<f64>,
+    /// Phase values flagging invalid data
+    pub phase: Vec<f64>,
+    /// Doppler values flagging invalid data
+    pub doppler: Vec<f64>,
+    /// Any observation whose magnitude reaches this value is an overflow
+    pub overflow: Option<f64>,
+}
+
+impl Default for Sentinels {
+    /// Builds default [Sentinels]: null pseudo ranges
+    /// and values that saturate the RINEX format.
+    fn default() -> Self {
+        Self {
+            pseudo_range: vec![0.0],
+            phase: Vec::new(),
+            doppler: Vec::new(),
+            overflow: Some(Self::OVERFLOW),
+        }
+    }
+}
+
+impl Sentinels {
+    /// Largest value a RINEX observation (F14.3) can describe
+    pub const OVERFLOW: f64 = 9_999_999_999.999;
+    /// Adds a pseudo range sentinel value [m]
+    pub fn with_pseudo_range(&self, value: f64) -> Self {
+        let mut s = self.clone();
+        s.pseudo_range.push(value);
+        s
+    }
+    /// Adds a phase sentinel value
+    pub fn with_phase(&self, value: f64) -> Self {
+        let mut s = self.clone();
+        s.phase.push(value);
+        s
+    }
+    /// Adds a doppler sentinel value
+    pub fn with_doppler(&self, value: f64) -> Self {
+        let mut s = self.clone();
+        s.doppler.push(value);
+        s
+    }
+    /// Defines overflow magnitude, None disables overflow detection
+    pub fn with_overflow(&self, overflow: Option<f64>) -> Self {
+        let mut s = self.clone();
+        s.overflow = overflow;
+        s
+    }
+    /// Returns true if this value for given [Observable] is a sentinel
+    pub fn is_sentinel(&self, observable: &Observable, value: f64) -> bool {
+        if let Some(overflow) = self.overflow {
+            if value.abs() >= overflow {
+                return true;
+            }
+        }
+        if observable.is_pseudorange_observable() {
+            self.pseudo_range.contains(&value)
+        } else if observable.is_phase_observable() {
+            self.phase.contains(&value)
+        } else if observable.is_doppler_observable() {
+            self.doppler.contains(&value)
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+    #[test]
+    fn default_sentinels() {
+        let sentinels = Sentinels::default();
+        let c1c = Observable::from_str("C1C").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+        assert!(sentinels.is_sentinel(&c1c, 0.0));
+        assert!(!sentinels.is_sentinel(&c1c, 20_000_000.0));
+        assert!(!sentinels.is_sentinel(&l1c, 0.0));
+        assert!(sentinels.is_sentinel(&l1c, Sentinels::OVERFLOW));
+        assert!(sentinels.is_sentinel(&l1c, -Sentinels::OVERFLOW));
+
+        let sentinels = sentinels.with_phase(0.0).with_overflow(None);
+        assert!(sentinels.is_sentinel(&l1c, 0.0));
+        assert!(!sentinels.is_sentinel(&l1c, Sentinels::OVERFLOW));
+    }
+}
@@ -0,0 +1,140 @@
+//! Carrier smoothing of pseudo ranges (Hatch filter)
+use std::collections::HashMap;
+
+use crate::{
+    observation::{HeaderFields, LliFlags, Record},
+    prelude::{Carrier, Duration, Epoch, Observable, SV},
+};
+
+/// Default Hatch filter window: the filter memory is capped to this many samples,
+/// so it does not integrate code/carrier divergence endlessly
+pub(crate) const HATCH_SMOOTHING_WINDOW: u32 = 100;
+
+/// Returns Phase [Observable] that matches this pseudo range [Observable],
+/// for example L1C for C1C, or L1 for P1
+fn phase_observable(pseudo_range: &Observable) -> Option<Observable> {
+    match pseudo_range {
+        Observable::PseudoRange(code) => {
+            let mut chars = code.chars();
+            chars.next()?;
+            Some(Observable::Phase(format!("L{}", chars.as_str())))
+        },
+        _ => None,
+    }
+}
+
+/// Hatch filter state, for one pseudo range [Observable] of one [SV]
+struct Smoother {
+    /// Number of samples accumulated since last reset
+    n: u32,
+    /// Latest smoothed pseudo range [m]
+    smoothed: f64,
+    /// Latest phase observation [m]
+    phase: f64,
+    /// [Epoch] of latest sample
+    t: Epoch,
+}
+
+/// Smoothes pseudo range observations using the matching carrier phase:
+///   S(k) = P(k) / n + (n-1) / n * (S(k-1) + Φ(k) - Φ(k-1))
+/// where Φ is expressed in meters and n is the number of samples since last reset,
+/// capped to `window`.
+/// The filter is reset whenever the phase observation is missing,
+/// flagged by a lock loss, when an epoch is not flagged OK (for example
+/// [crate::observation::EpochFlag::CycleSlip] or power failure), or when
+/// the gap to the previous sample exceeds the `sampling` interval.
+/// Pseudo ranges that do not come with a phase observation are left untouched,
+/// so are Glonass FDMA signals when the channel of that vehicle is not known.
+pub(crate) fn hatch_smoothing(
+    rec: &mut Record,
+    header: Option<&HeaderFields>,
+    glo_channels: &HashMap<SV, i8>,
+    sampling: Option<Duration>,
+    window: u32,
+) {
+    let window = window.max(1);
+    let mut smoothers = HashMap::<(SV, Observable), Smoother>::new();
+
+    for ((t, flag), (_, vehicles)) in rec.iter_mut() {
+        if !flag.is_ok() {
+            // cycle slip records, power failures and other events
+            // interrupt the smoothing and are left untouched
+            smoothers.clear();
+            continue;
+        }
+        for (sv, observations) in vehicles.iter_mut() {
+            let scaling = |observable: &Observable| -> f64 {
+                header
+                    .and_then(|h| h.scaling(sv.constellation, observable.clone()))
+                    .map(|scaling| *scaling as f64)
+                    .unwrap_or(1.0)
+            };
+            let pseudo_ranges = observations
+                .keys()
+                .filter(|observable| observable.is_pseudorange_observable())
+                .cloned()
+                .collect::<Vec<_>>();
+
+            for pseudo_range in pseudo_ranges {
+                let key = (*sv, pseudo_range.clone());
+
+                let phase = phase_observable(&pseudo_range).and_then(|phase| {
+                    let data = observations.get(&phase)?;
+                    let lock_loss = data
+                        .lli
+                        .map(|lli| lli.intersects(LliFlags::LOCK_LOSS))
+                        .unwrap_or(false);
+                    let carrier = match phase.carrier(sv.constellation).ok()? {
+                        // FDMA: wavelength depends on the channel of this vehicle
+                        carrier @ (Carrier::G1(_) | Carrier::G2(_)) => {
+                            carrier.with_glonass_offset(*glo_channels.get(sv)?)
+                        },
+                        carrier => carrier,
+                    };
+                    Some((data.obs / scaling(&phase) * carrier.wavelength(), lock_loss))
+                });
+
+                let (phase, lock_loss) = match phase {
+                    Some(phase) => phase,
+                    None => {
+                        smoothers.remove(&key);
+                        continue;
+                    },
+                };
+
+                let data = match observations.get_mut(&pseudo_range) {
+                    Some(data) => data,
+                    None => continue,
+                };
+                let pr_scaling = scaling(&pseudo_range);
+                let raw = data.obs / pr_scaling;
+
+                let smoother = smoothers.entry(key).or_insert(Smoother {
+                    n: 0,
+                    smoothed: raw,
+                    phase,
+                    t: *t,
+                });
+                let gap = sampling
+                    .map(|sampling| *t - smoother.t > sampling)
+                    .unwrap_or(false);
+                if lock_loss || gap {
+                    smoother.n = 0;
+                }
+                if smoother.n < window {
+                    smoother.n += 1;
+                }
+
+                let n = smoother.n as f64;
+                smoother.smoothed = if smoother.n == 1 {
+                    raw
+                } else {
+                    raw / n + (n - 1.0) / n * (smoother.smoothed + phase - smoother.phase)
+                };
+                smoother.phase = phase;
+                smoother.t = *t;
+                data.obs = smoother.smoothed * pr_scaling;
+            }
+        }
+    }
+}
@@ -4,6 +4,7 @@ use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 
 #[derive(PartialEq, Debug, Clone)]
+#[non_exhaustive]
 pub enum Error {
     InvalidSNRCode,
 }
@@ -0,0 +1,284 @@
+//! Streamed Observation RINEX parsing
+use std::collections::VecDeque;
+use std::io::{BufRead, Lines};
+
+use crate::{
+    hatanaka::Decompressor,
+    header::{self, Header},
+    is_rinex_comment,
+    observation::record::{is_new_epoch, parse_epoch, RecordEntry, RecordKey},
+    prelude::{ParsingOptions, TimeScale},
+    progress::ProgressHandle,
+    reader::{BufferedReader, LimitExceeded},
+    record::{self, observation_timescale},
+    types::Type,
+    Error,
+};
+
+/// [ObservationStream] parses Observation RINEX (and CRINEX) one epoch at a time,
+/// without building the entire [crate::observation::Record].
+/// The header is parsed when the stream is created, then each
+/// epoch is yielded as soon as it has been parsed. This allows processing
+/// very large files with bounded memory. Comments are not retained.
+/// ```
+/// use rinex::prelude::*;
+/// use std::collections::HashMap;
+///
+/// let stream = Rinex::epochs_iter_from_file("../test_resources/CRNX/V3/ACOR00ESP_R_20213550000_01D_30S_MO.crx")
+///     .unwrap();
+///
+/// assert!(stream.header().is_crinex());
+///
+/// // count observations per SV, with bounded memory
+/// let mut counts = HashMap::<SV, usize>::new();
+/// for entry in stream {
+///     let (_key, (_clock_offset, vehicles)) = entry.unwrap();
+///     for (sv, observations) in vehicles {
+///         *counts.entry(sv).or_default() += observations.len();
+///     }
+/// }
+/// assert!(counts.len() > 0);
+/// ```
+pub struct ObservationStream {
+    /// Parsed [Header]
+    header: Header,
+    /// Remaining file content
+    lines: Lines<BufferedReader<'static>>,
+    /// [ParsingOptions] being enforced
+    options: ParsingOptions,
+    /// CRINEX decompressor
+    decompressor: Decompressor,
+    /// Recovered lines, not processed yet
+    pending: VecDeque<String>,
+    /// Content of the epoch being gathered
+    epoch_content: String,
+    /// Observation [TimeScale]
+    ts: TimeScale,
+    /// Number of epochs encountered so far
+    nb_epochs: usize,
+    /// Number of epochs parsed so far
+    nb_parsed: usize,
+    /// Number of bytes consumed so far
+    nb_bytes: u64,
+    /// [ProgressHandle] to be notified after each parsed epoch
+    progress: Option<ProgressHandle>,
+    /// True once [ProgressHandle] requested cancellation
+    cancelled: bool,
+    /// Error to be returned once the pending epoch was yielded
+    error: Option<Error>,
+    /// True once all content has been consumed
+    done: bool,
+}
+
+impl ObservationStream {
+    /// Creates a new [ObservationStream] from given file fullpath.
+    /// Supports CRINEX and .gz compressed files (with flate2 feature).
+    pub fn from_file(fullpath: &str) -> Result<Self, Error> {
+        Self::from_file_with_options(fullpath, ParsingOptions::default())
+    }
+    /// Creates a new [ObservationStream] from given file fullpath,
+    /// enforcing said [ParsingOptions].
+    pub fn from_file_with_options(fullpath: &str, options: ParsingOptions) -> Result<Self, Error> {
+        let mut reader = BufferedReader::new_with_options(fullpath, options)?;
+
+        let header = Header::new(&mut reader).map_err(|e| match e {
+            header::ParsingError::LimitExceeded { which, limit } => {
+                Error::LimitExceeded { which, limit }
+            },
+            e => Error::HeaderParsingError(e),
+        })?;
+
+        if header.rinex_type != Type::ObservationData {
+            return Err(Error::RecordError(record::Error::TypeError(
+                header.rinex_type.to_string(),
+            )));
+        }
+
+        let ts = observation_timescale(&header)?;
+
+        Ok(Self {
+            header,
+            ts,
+            options,
+            lines: reader.lines(),
+            decompressor: Decompressor::new(),
+            pending: VecDeque::with_capacity(8),
+            epoch_content: String::with_capacity(6 * 64),
+            nb_epochs: 0,
+            nb_parsed: 0,
+            nb_bytes: 0,
+            progress: None,
+            cancelled: false,
+            error: None,
+            done: false,
+        })
+    }
+    /// Notifies given [ProgressHandle] after each parsed epoch.
+    /// When it requests cancellation, the stream yields that epoch,
+    /// then [Error::Cancelled] and terminates.
+    /// ```
+    /// use rinex::prelude::*;
+    /// use std::ops::ControlFlow;
+    ///
+    /// let progress = ProgressHandle::new(|progress: Progress| {
+    ///     if progress.epochs < 2 {
+    ///         ControlFlow::Continue(())
+    ///     } else {
+    ///         ControlFlow::Break(())
+    ///     }
+    /// });
+    ///
+    /// let mut stream = Rinex::epochs_iter_from_file("../test_resources/OBS/V3/ACOR00ESP_R_20213550000_01D_30S_MO.rnx")
+    ///     .unwrap()
+    ///     .with_progress(progress);
+    ///
+    /// // stops once two epochs were parsed
+    /// assert!(stream.next().unwrap().is_ok());
+    /// assert!(stream.next().unwrap().is_ok());
+    /// assert_eq!(stream.next().unwrap().unwrap_err().kind(), ErrorKind::Cancelled);
+    /// assert!(stream.next().is_none());
+    /// ```
+    pub fn with_progress(mut self, progress: ProgressHandle) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+    /// Returns [Header] that was parsed when creating this stream
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+    /*
+     * Reads and possibly decompresses next line(s).
+     * Returns false once all content has been consumed.
+     */
+    fn read_next(&mut self) -> Result<bool, Error> {
+        let line = match self.lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => {
+                return Err(match LimitExceeded::from_io_error(&e) {
+                    Some(limit) => Error::from(record::Error::from(limit)),
+                    None => Error::IoError(e),
+                });
+            },
+            None => return Ok(false),
+        };
+
+        self.nb_bytes += line.len() as u64 + 1;
+
+        if is_rinex_comment(&line) {
+            return Ok(true);
+        }
+
+        let crinex = self
+            .header
+            .obs
+            .as_ref()
+            .and_then(|obs| obs.crinex.as_ref().map(|crx| (crx.version.major, obs)));
+
+        if let Some((crx_major, obs)) = crinex {
+            let constellation = self.header.constellation.as_ref().unwrap();
+            if let Ok(recovered) = self.decompressor.decompress(
+                crx_major,
+                constellation,
+                self.header.version.major,
+                &obs.codes,
+                &(line.to_owned() + "\n"),
+            ) {
+                for line in recovered.lines() {
+                    self.pending.push_back(line.to_string());
+                }
+            }
+        } else {
+            self.pending.push_back(line);
+        }
+        Ok(true)
+    }
+    /*
+     * Stops streaming, on first critical error
+     */
+    fn abort(&mut self) {
+        self.done = true;
+        self.pending.clear();
+        self.epoch_content.clear();
+    }
+    /*
+     * Parses epoch that was gathered so far, then notifies progress
+     */
+    fn parse_epoch(&mut self) -> Result<(RecordKey, RecordEntry), Error> {
+        let parsed = parse_epoch(&self.header, &self.epoch_content, self.ts);
+        self.nb_parsed += 1;
+        if let Some(progress) = self.progress.as_mut() {
+            self.cancelled = progress.cancelled(self.nb_bytes, self.nb_parsed);
+        }
+        let (key, clock_offset, vehicles) = parsed.map_err(record::Error::from)?;
+        Ok((key, (clock_offset, vehicles)))
+    }
+}
+
+impl Iterator for ObservationStream {
+    type Item = Result<(RecordKey, RecordEntry), Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(e) = self.error.take() {
+                // previous epoch was yielded, then we stop
+                return Some(Err(e));
+            }
+            if self.cancelled {
+                // previous epoch was yielded, then we stop
+                self.cancelled = false;
+                self.abort();
+                return Some(Err(Error::Cancelled));
+            }
+            if let Some(line) = self.pending.pop_front() {
+                if is_new_epoch(&line, self.header.version) {
+                    self.nb_epochs += 1;
+                    let parsed = if self.epoch_content.is_empty() {
+                        None
+                    } else {
+                        Some(self.parse_epoch())
+                    };
+                    if let Err(e) = self.options.check_epochs(self.nb_epochs) {
+                        // yield the epoch we just completed, then the error
+                        let e = Error::from(record::Error::from(e));
+                        self.cancelled = false;
+                        self.abort();
+                        return match parsed {
+                            Some(parsed) => {
+                                self.error = Some(e);
+                                Some(parsed)
+                            },
+                            None => Some(Err(e)),
+                        };
+                    }
+                    self.epoch_content.clear();
+                    self.epoch_content.push_str(&line);
+                    self.epoch_content.push('\n');
+                    if parsed.is_some() {
+                        return parsed;
+                    }
+                } else if !self.epoch_content.is_empty() {
+                    self.epoch_content.push_str(&line);
+                    self.epoch_content.push('\n');
+                }
+            } else if self.done {
+                return None;
+            } else {
+                match self.read_next() {
+                    Ok(true) => {},
+                    Ok(false) => {
+                        // final epoch
+                        self.done = true;
+                        if !self.epoch_content.is_empty() {
+                            let parsed = self.parse_epoch();
+                            self.epoch_content.clear();
+                            return Some(parsed);
+                        }
+                    },
+                    Err(e) => {
+                        self.abort();
+                        return Some(Err(e));
+                    },
+                }
+            }
+        }
+    }
+}
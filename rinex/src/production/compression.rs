@@ -0,0 +1,46 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// File compression, as indicated by the filename suffix
+/// of files that follow standard naming conventions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Compression {
+    /// Gzip compression (".gz")
+    Gzip,
+    /// Unix compress (".Z"), typical of older archives
+    Lzw,
+    /// Bzip2 compression (".bz2")
+    Bzip2,
+}
+
+impl std::fmt::Display for Compression {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Gzip => write!(f, ".gz"),
+            Self::Lzw => write!(f, ".Z"),
+            Self::Bzip2 => write!(f, ".bz2"),
+        }
+    }
+}
+
+impl Compression {
+    /// Splits given filename into its stem and its compression suffix, if any.
+    pub(crate) fn strip_suffix(fname: &str) -> (&str, Option<Self>) {
+        for compression in [Self::Gzip, Self::Lzw, Self::Bzip2] {
+            let suffix = compression.to_string();
+            if fname.len() > suffix.len() {
+                let offset = fname.len() - suffix.len();
+                // filename may not be ASCII
+                if !fname.is_char_boundary(offset) {
+                    continue;
+                }
+                let (stem, ext) = fname.split_at(offset);
+                if ext.eq_ignore_ascii_case(&suffix) {
+                    return (stem, Some(compression));
+                }
+            }
+        }
+        (fname, None)
+    }
+}
@@ -29,8 +29,12 @@ pub use ffu::FFU;
 mod source;
 pub use source::DataSource;
 
+mod compression;
+pub use compression::Compression;
+
 #[derive(Error, Debug)]
 /// File Production errors
+#[non_exhaustive]
 pub enum Error {
     #[error("filename does not follow naming conventions")]
     NonStandardFileName,
@@ -63,6 +67,10 @@ pub struct ProductionAttributes {
     /// Optional Regional code present in IONEX file names.
     /// 'G' means Global (World wide) TEC map(s).
     pub region: Option<char>,
+    /// File [Compression], as indicated by the filename suffix.
+    /// This applies to both short and long file names,
+    /// which is why it is not part of the detailed attributes.
+    pub compression: Option<Compression>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -89,6 +97,10 @@ impl ProductionAttributes {
         format!("{}{}{}0.{}I", name, region, ddd, yy,)
     }
     /* filename generator */
+    pub(crate) fn doris_format(name: &str, ddd: &str, yy: &str) -> String {
+        format!("{}rx{}{}", name, yy, ddd)
+    }
+    /* filename generator */
     pub(crate) fn rinex_short_format(name: &str, ddd: &str, yy: &str, ext: char) -> String {
         format!("{}{}0.{}{}", &name, ddd, yy, ext,)
     }
@@ -145,6 +157,9 @@ impl ProductionAttributes {
 impl std::str::FromStr for ProductionAttributes {
     type Err = Error;
     fn from_str(fname: &str) -> Result<Self, Self::Err> {
+        // strip compression suffix, so it does not
+        // interfere with the format identification
+        let (fname, compression) = Compression::strip_suffix(fname);
         let fname = fname.to_uppercase();
         if fname.len() < 13 {
             let offset = fname.find('.').unwrap_or(0);
@@ -177,6 +192,7 @@ impl std::str::FromStr for ProductionAttributes {
                     _ => None,
                 },
                 details: None,
+                compression,
             })
         } else {
             let offset = fname.find('.').unwrap_or(0);
@@ -209,6 +225,7 @@ impl std::str::FromStr for ProductionAttributes {
                         .map_err(|_| Error::NonStandardFileName)?
                 },
                 region: None, // IONEX files only use a short format
+                compression,
                 details: Some(DetailedProductionAttributes {
                     batch,
                     country: fname[6..9].to_string(),
@@ -244,6 +261,7 @@ impl Merge for ProductionAttributes {
     }
     fn merge_mut(&mut self, rhs: &Self) -> Result<(), MergeError> {
         merge_mut_option(&mut self.region, &rhs.region);
+        merge_mut_option(&mut self.compression, &rhs.compression);
         merge_mut_option(&mut self.details, &rhs.details);
         if let Some(lhs) = &mut self.details {
             if let Some(rhs) = &rhs.details {
@@ -0,0 +1,67 @@
+//! Progress reporting and cancellation of long running operations
+use std::ops::ControlFlow;
+
+/// [Progress] of a long running operation
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// Number of bytes processed so far.
+    /// When parsing, this is the (decompressed) content that was consumed,
+    /// when formatting, this is the content that was produced.
+    /// Merge operations do not report bytes.
+    pub bytes: u64,
+    /// Number of epochs processed so far: an epoch is only accounted for
+    /// once it has been entirely parsed, formatted or merged.
+    /// In Navigation RINEX, each frame counts as one epoch,
+    /// in ANTEX, each antenna counts as one epoch.
+    pub epochs: usize,
+}
+
+/// [ProgressHandle] is notified of the [Progress] of long running operations,
+/// once per processed epoch, so the notifications are deterministic for given content.
+/// The operation is cancelled as soon as the callback returns [ControlFlow::Break],
+/// in which case it returns a `Cancelled` error.
+/// ```
+/// use rinex::prelude::*;
+/// use std::ops::ControlFlow;
+///
+/// // stop after 10 epochs
+/// let mut progress = ProgressHandle::new(|progress: Progress| {
+///     if progress.epochs < 10 {
+///         ControlFlow::Continue(())
+///     } else {
+///         ControlFlow::Break(())
+///     }
+/// });
+///
+/// let rinex = Rinex::from_file_with_progress(
+///     "../test_resources/OBS/V3/ACOR00ESP_R_20213550000_01D_30S_MO.rnx",
+///     ParsingOptions::default(),
+///     &mut progress);
+///
+/// assert_eq!(rinex.unwrap_err().kind(), ErrorKind::Cancelled);
+/// ```
+pub struct ProgressHandle {
+    /// Notification callback
+    callback: Box<dyn FnMut(Progress) -> ControlFlow<()> + Send>,
+}
+
+impl std::fmt::Debug for ProgressHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ProgressHandle").finish()
+    }
+}
+
+impl ProgressHandle {
+    /// Builds a new [ProgressHandle] from given callback
+    pub fn new<F: FnMut(Progress) -> ControlFlow<()> + Send + 'static>(callback: F) -> Self {
+        Self {
+            callback: Box::new(callback),
+        }
+    }
+    /*
+     * Notifies progress, returns true if operation should be cancelled
+     */
+    pub(crate) fn cancelled(&mut self, bytes: u64, epochs: usize) -> bool {
+        (self.callback)(Progress { bytes, epochs }).is_break()
+    }
+}
@@ -1,39 +1,235 @@
 //! Buffered Reader wrapper, for efficient data reading
 //! and integrated .gz decompression.
 #[cfg(feature = "flate2")]
-use flate2::read::GzDecoder;
+use flate2::bufread::GzDecoder;
+use log::warn;
 use std::fs::File;
-use std::io::BufReader; // Seek, SeekFrom};
+use std::io::{BufRead, BufReader}; // Seek, SeekFrom};
+use thiserror::Error;
 
-#[derive(Debug)]
-pub enum BufferedReader {
+/// Reading [Limit]s that protect the parser against
+/// maliciously crafted (or simply unexpectedly large) content.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Limit {
+    /// Total number of (decompressed) bytes
+    DecompressedBytes,
+    /// Total number of epochs contained in the record
+    Epochs,
+    /// Length of a single line
+    LineLength,
+}
+
+impl std::fmt::Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::DecompressedBytes => write!(f, "decompressed bytes"),
+            Self::Epochs => write!(f, "epochs"),
+            Self::LineLength => write!(f, "line length"),
+        }
+    }
+}
+
+/// [LimitExceeded] is raised when one of the [ParsingOptions] limits is reached.
+/// When raised by [BufferedReader], it is wrapped as a [std::io::Error].
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+#[error("{which} limit exceeded ({limit})")]
+pub struct LimitExceeded {
+    /// [Limit] that was exceeded
+    pub which: Limit,
+    /// Configured limit value
+    pub limit: u64,
+}
+
+impl LimitExceeded {
+    /// Retrieves [LimitExceeded] from [std::io::Error] raised by [BufferedReader]
+    pub(crate) fn from_io_error(e: &std::io::Error) -> Option<Self> {
+        e.get_ref()?.downcast_ref::<Self>().copied()
+    }
+    fn into_io_error(self) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::Other, self)
+    }
+}
+
+/// [ParsingOptions] define the limits enforced when reading a file.
+/// Default values are generous, but finite.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParsingOptions {
+    /// Maximal number of bytes to be read, after possible decompression
+    pub max_decompressed_bytes: u64,
+    /// Maximal number of epochs to be parsed.
+    /// In Navigation RINEX, each frame counts as one epoch.
+    pub max_epochs: usize,
+    /// Maximal line length, excluding line termination.
+    /// It is enforced while reading, so a single oversized line
+    /// is never entirely loaded in memory.
+    pub max_line_length: usize,
+}
+
+impl Default for ParsingOptions {
+    fn default() -> Self {
+        Self {
+            max_decompressed_bytes: 4 * 1024 * 1024 * 1024,
+            max_epochs: 10_000_000,
+            max_line_length: 16 * 1024,
+        }
+    }
+}
+
+impl ParsingOptions {
+    /// Returns [ParsingOptions] with desired maximal number of (decompressed) bytes
+    pub fn with_max_decompressed_bytes(&self, max: u64) -> Self {
+        let mut s = *self;
+        s.max_decompressed_bytes = max;
+        s
+    }
+    /// Returns [ParsingOptions] with desired maximal number of epochs
+    pub fn with_max_epochs(&self, max: usize) -> Self {
+        let mut s = *self;
+        s.max_epochs = max;
+        s
+    }
+    /// Returns [ParsingOptions] with desired maximal line length
+    pub fn with_max_line_length(&self, max: usize) -> Self {
+        let mut s = *self;
+        s.max_line_length = max;
+        s
+    }
+    /*
+     * Verifies the epoch count
+     */
+    pub(crate) fn check_epochs(&self, epochs: usize) -> Result<(), LimitExceeded> {
+        if epochs > self.max_epochs {
+            Err(LimitExceeded {
+                which: Limit::Epochs,
+                limit: self.max_epochs as u64,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// gzip magic bytes
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// .Z (unix compress) magic bytes
+const COMPRESS_MAGIC: [u8; 2] = [0x1f, 0x9d];
+
+enum ReaderWrapper<'a> {
     /// Readable `RINEX`
-    PlainFile(BufReader<File>),
+    Plain(Box<dyn BufRead + 'a>),
     /// gzip compressed RINEX
     #[cfg(feature = "flate2")]
-    GzFile(BufReader<GzDecoder<File>>),
+    Gz(BufReader<GzDecoder<Box<dyn BufRead + 'a>>>),
 }
 
-impl BufferedReader {
+pub struct BufferedReader<'a> {
+    /// Internal reader
+    reader: ReaderWrapper<'a>,
+    /// [ParsingOptions] being enforced
+    options: ParsingOptions,
+    /// Total number of bytes consumed so far
+    consumed: u64,
+}
+
+impl std::fmt::Debug for BufferedReader<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("BufferedReader")
+            .field("options", &self.options)
+            .field("consumed", &self.consumed)
+            .finish()
+    }
+}
+
+impl<'a> BufferedReader<'a> {
     /// Builds a new BufferedReader for efficient file interation,
-    /// with possible .gz decompression
+    /// with possible .gz decompression, and default [ParsingOptions].
     pub fn new(path: &str) -> std::io::Result<Self> {
+        Self::new_with_options(path, ParsingOptions::default())
+    }
+    /// Builds a new BufferedReader for efficient file interation,
+    /// with possible .gz decompression, enforcing said [ParsingOptions].
+    /// gzip compression is identified from the content itself: the file
+    /// extension is only used to diagnose misleading file names.
+    pub fn new_with_options(path: &str, options: ParsingOptions) -> std::io::Result<Self> {
         let f = File::open(path)?;
-        if path.ends_with(".gz") {
+        let mut reader = BufReader::new(f);
+        let gzip = reader.fill_buf()?.starts_with(&GZIP_MAGIC);
+        if gzip != path.ends_with(".gz") {
+            if gzip {
+                warn!(
+                    "\"{}\": gzip compressed content, despite file extension",
+                    path
+                );
+            } else {
+                warn!("\"{}\": .gz extension, but content is not compressed", path);
+            }
+        }
+        Self::from_reader_with_options(reader, options)
+    }
+    /// Builds a new BufferedReader from any [BufRead] implementation,
+    /// for example a network stream or an in-memory buffer, with default [ParsingOptions].
+    /// gzip compression is identified from the content itself.
+    pub fn from_reader<R: BufRead + 'a>(reader: R) -> std::io::Result<Self> {
+        Self::from_reader_with_options(reader, ParsingOptions::default())
+    }
+    /// Builds a new BufferedReader from any [BufRead] implementation,
+    /// enforcing said [ParsingOptions].
+    /// gzip compression is identified from the content itself.
+    /// Returns [std::io::ErrorKind::Unsupported] for .Z compressed content,
+    /// and for gzip compressed content when the flate2 feature is disabled.
+    pub fn from_reader_with_options<R: BufRead + 'a>(
+        mut reader: R,
+        options: ParsingOptions,
+    ) -> std::io::Result<Self> {
+        let magic = reader.fill_buf()?;
+        if magic.starts_with(&COMPRESS_MAGIC) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                ".Z decompression is not supported: uncompress manually",
+            ));
+        }
+        let gzip = magic.starts_with(&GZIP_MAGIC);
+        let reader: Box<dyn BufRead + 'a> = Box::new(reader);
+        let reader = if gzip {
             // --> gzip encoded
             #[cfg(feature = "flate2")]
             {
-                Ok(Self::GzFile(BufReader::new(GzDecoder::new(f))))
+                ReaderWrapper::Gz(BufReader::new(GzDecoder::new(reader)))
             }
             #[cfg(not(feature = "flate2"))]
             {
-                panic!(".gz data requires --flate2 feature")
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Unsupported,
+                    ".gz data requires the flate2 feature",
+                ));
             }
-        } else if path.ends_with(".Z") {
-            panic!(".z decompresion is not supported: uncompress manually")
         } else {
             // Assumes no extra compression
-            Ok(Self::PlainFile(BufReader::new(f)))
+            ReaderWrapper::Plain(reader)
+        };
+        Ok(Self {
+            reader,
+            options,
+            consumed: 0,
+        })
+    }
+    /// Returns [ParsingOptions] enforced by Self
+    pub fn options(&self) -> ParsingOptions {
+        self.options
+    }
+    /*
+     * Verifies the total number of bytes consumed
+     */
+    fn check_consumed(&self) -> std::io::Result<()> {
+        if self.consumed > self.options.max_decompressed_bytes {
+            Err(LimitExceeded {
+                which: Limit::DecompressedBytes,
+                limit: self.options.max_decompressed_bytes,
+            }
+            .into_io_error())
+        } else {
+            Ok(())
         }
     }
     /*
@@ -81,29 +277,97 @@ impl BufferedReader {
     */
 }
 
-impl std::io::Read for BufferedReader {
+impl std::io::Read for BufferedReader<'_> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, std::io::Error> {
-        match self {
-            Self::PlainFile(ref mut h) => h.read(buf),
+        self.check_consumed()?;
+        let size = match self.reader {
+            ReaderWrapper::Plain(ref mut h) => h.read(buf),
             #[cfg(feature = "flate2")]
-            Self::GzFile(ref mut h) => h.read(buf),
-        }
+            ReaderWrapper::Gz(ref mut h) => h.read(buf),
+        }?;
+        self.consumed += size as u64;
+        self.check_consumed()?;
+        Ok(size)
     }
 }
 
-impl std::io::BufRead for BufferedReader {
+impl std::io::BufRead for BufferedReader<'_> {
     fn fill_buf(&mut self) -> Result<&[u8], std::io::Error> {
-        match self {
-            Self::PlainFile(ref mut bufreader) => bufreader.fill_buf(),
+        self.check_consumed()?;
+        match self.reader {
+            ReaderWrapper::Plain(ref mut bufreader) => bufreader.fill_buf(),
             #[cfg(feature = "flate2")]
-            Self::GzFile(ref mut bufreader) => bufreader.fill_buf(),
+            ReaderWrapper::Gz(ref mut bufreader) => bufreader.fill_buf(),
         }
     }
     fn consume(&mut self, s: usize) {
-        match self {
-            Self::PlainFile(ref mut bufreader) => bufreader.consume(s),
+        self.consumed += s as u64;
+        match self.reader {
+            ReaderWrapper::Plain(ref mut bufreader) => bufreader.consume(s),
             #[cfg(feature = "flate2")]
-            Self::GzFile(ref mut bufreader) => bufreader.consume(s),
+            ReaderWrapper::Gz(ref mut bufreader) => bufreader.consume(s),
         }
     }
+    /// Reads until `delim` is found, like [BufRead::read_until],
+    /// but never buffers more than the maximal line length
+    /// (plus line termination): [Limit::LineLength] is enforced while reading.
+    fn read_until(&mut self, delim: u8, buf: &mut Vec<u8>) -> std::io::Result<usize> {
+        let max_len = self.options.max_line_length;
+        let start = buf.len();
+        loop {
+            // content + possible carriage return + delimiter
+            let allowance = (max_len + 2).saturating_sub(buf.len() - start);
+            let (found, used, exceeded) = {
+                let available = self.fill_buf()?;
+                let n = std::cmp::min(available.len(), allowance);
+                match available[..n].iter().position(|b| *b == delim) {
+                    Some(i) => {
+                        buf.extend_from_slice(&available[..=i]);
+                        (true, i + 1, false)
+                    },
+                    None => {
+                        buf.extend_from_slice(&available[..n]);
+                        (available.is_empty(), n, n < available.len())
+                    },
+                }
+            };
+            self.consume(used);
+            if exceeded {
+                return Err(LimitExceeded {
+                    which: Limit::LineLength,
+                    limit: max_len as u64,
+                }
+                .into_io_error());
+            }
+            if found {
+                break;
+            }
+        }
+        // line length, without termination
+        let mut line = &buf[start..];
+        if let Some(stripped) = line.strip_suffix(&[delim]) {
+            line = stripped;
+        }
+        if let Some(stripped) = line.strip_suffix(b"\r") {
+            line = stripped;
+        }
+        if line.len() > max_len {
+            return Err(LimitExceeded {
+                which: Limit::LineLength,
+                limit: max_len as u64,
+            }
+            .into_io_error());
+        }
+        Ok(buf.len() - start)
+    }
+    /// Reads one line, like [BufRead::read_line], enforcing [Limit::LineLength]
+    /// while reading (see [Self::read_until]).
+    fn read_line(&mut self, buf: &mut String) -> std::io::Result<usize> {
+        let mut bytes = Vec::new();
+        let size = self.read_until(b'\n', &mut bytes)?;
+        let line = String::from_utf8(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.utf8_error()))?;
+        buf.push_str(&line);
+        Ok(size)
+    }
 }
@@ -12,7 +12,8 @@ use super::{
     header, ionex, is_rinex_comment, merge,
     merge::Merge,
     meteo, navigation, observation,
-    reader::BufferedReader,
+    progress::ProgressHandle,
+    reader::{BufferedReader, Limit, LimitExceeded},
     split,
     split::Split,
     types::Type,
@@ -24,6 +25,9 @@ use crate::navigation::record::parse_epoch as parse_nav_epoch;
 
 use hifitime::Duration;
 
+#[cfg(feature = "processing")]
+use qc_traits::processing::DecimationFilterType;
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum Record {
@@ -153,6 +157,28 @@ impl Record {
         header: &header::Header,
         writer: &mut BufferedWriter,
     ) -> Result<(), Error> {
+        self.to_file_with_progress(header, writer, None)
+    }
+    /*
+     * Streams into given file writer,
+     * notifies `progress` after each epoch and stops when requested
+     */
+    pub(crate) fn to_file_with_progress(
+        &self,
+        header: &header::Header,
+        writer: &mut BufferedWriter,
+        mut progress: Option<&mut ProgressHandle>,
+    ) -> Result<(), Error> {
+        let mut nb_epochs = 0;
+        let mut notify = |writer: &BufferedWriter| -> Result<(), Error> {
+            nb_epochs += 1;
+            if let Some(progress) = progress.as_mut() {
+                if progress.cancelled(writer.written(), nb_epochs) {
+                    return Err(Error::Cancelled);
+                }
+            }
+            Ok(())
+        };
         match &header.rinex_type {
             Type::MeteoData => {
                 let record = self.as_meteo().unwrap();
@@ -160,6 +186,7 @@ impl Record {
                     if let Ok(epoch) = meteo::record::fmt_epoch(epoch, data, header) {
                         let _ = write!(writer, "{}", epoch);
                     }
+                    notify(writer)?;
                 }
             },
             Type::ObservationData => {
@@ -185,6 +212,7 @@ impl Record {
                     } else {
                         writeln!(writer, "{}", epoch)?;
                     }
+                    notify(writer)?;
                 }
             },
             Type::NavigationData => {
@@ -193,54 +221,51 @@ impl Record {
                     if let Ok(epoch) = navigation::record::fmt_epoch(epoch, frames, header) {
                         let _ = write!(writer, "{}", epoch);
                     }
+                    notify(writer)?;
+                }
+            },
+            Type::DORIS => {
+                if let Some(rec) = self.as_doris() {
+                    for ((epoch, flag), (clock_offset, stations)) in rec.iter() {
+                        let epoch =
+                            doris::record::fmt_epoch(epoch, flag, clock_offset, stations, header);
+                        write!(writer, "{}", epoch)?;
+                        notify(writer)?;
+                    }
                 }
             },
             Type::ClockData => {
                 if let Some(rec) = self.as_clock() {
                     for (epoch, keys) in rec {
                         for (key, prof) in keys {
-                            let _ =
-                                write!(writer, "{}", clock::record::fmt_epoch(epoch, key, prof));
+                            write!(
+                                writer,
+                                "{}",
+                                clock::record::fmt_epoch(header.version, epoch, key, prof)
+                            )?;
                         }
+                        notify(writer)?;
                     }
                 }
             },
             Type::IonosphereMaps => {
-                if let Some(_r) = self.as_ionex() {
-                    //for (index, (epoch, (_map, _, _))) in r.iter().enumerate() {
-                    //    let _ = write!(writer, "{:6}                                                      START OF TEC MAP", index);
-                    //    let _ = write!(
-                    //        writer,
-                    //        "{}                        EPOCH OF CURRENT MAP",
-                    //        epoch::format(*epoch, None, Type::IonosphereMaps, 1)
-                    //    );
-                    //    let _ = write!(writer, "{:6}                                                      END OF TEC MAP", index);
-                    //}
-                    // /*
-                    //  * not efficient browsing, but matches provided examples and common formatting.
-                    //  * RMS and Height maps are passed after TEC maps.
-                    //  */
-                    //for (index, (epoch, (_, _map, _))) in r.iter().enumerate() {
-                    //    let _ = write!(writer, "{:6}                                                      START OF RMS MAP", index);
-                    //    let _ = write!(
-                    //        writer,
-                    //        "{}                        EPOCH OF CURRENT MAP",
-                    //        epoch::format(*epoch, None, Type::IonosphereMaps, 1)
-                    //    );
-                    //    let _ = write!(writer, "{:6}                                                      END OF RMS MAP", index);
-                    //}
-                    //for (index, (epoch, (_, _, _map))) in r.iter().enumerate() {
-                    //    let _ = write!(writer, "{:6}                                                      START OF HEIGHT MAP", index);
-                    //    let _ = write!(
-                    //        writer,
-                    //        "{}                        EPOCH OF CURRENT MAP",
-                    //        epoch::format(*epoch, None, Type::IonosphereMaps, 1)
-                    //    );
-                    //    let _ = write!(writer, "{:6}                                                      END OF HEIGHT MAP", index);
-                    //}
+                if let Some(rec) = self.as_ionex() {
+                    write!(writer, "{}", ionex::record::fmt_record(rec, header))?;
+                    notify(writer)?;
+                }
+            },
+            Type::AntennaData => {
+                if let Some(rec) = self.as_antex() {
+                    for (antenna, frequencies) in rec.iter() {
+                        write!(
+                            writer,
+                            "{}",
+                            antex::record::fmt_antenna(antenna, frequencies)
+                        )?;
+                        notify(writer)?;
+                    }
                 }
             },
-            _ => panic!("record type not supported yet"),
         }
         Ok(())
     }
@@ -253,6 +278,7 @@ impl Default for Record {
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("record parsing not supported for type \"{0}\"")]
     TypeError(String),
@@ -262,10 +288,41 @@ pub enum Error {
     NavEpochError(#[from] navigation::Error),
     #[error("failed to produce Clock epoch")]
     ClockEpochError(#[from] clock::Error),
+    #[error("failed to produce Observation epoch")]
+    ObsEpochError(#[from] observation::record::Error),
     #[error("missing TIME OF FIRST OBS")]
     BadObservationDataDefinition,
     #[error("failed to identify timescale")]
     ObservationDataTimescaleIdentification,
+    #[error("{which} limit exceeded ({limit})")]
+    LimitExceeded { which: Limit, limit: u64 },
+    #[error("operation cancelled")]
+    Cancelled,
+}
+
+impl From<LimitExceeded> for Error {
+    fn from(e: LimitExceeded) -> Self {
+        Self::LimitExceeded {
+            which: e.which,
+            limit: e.limit,
+        }
+    }
+}
+
+impl Error {
+    /// Returns the [ErrorKind] of this [Error]
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::TypeError(_) => ErrorKind::Unsupported,
+            Self::FileIoError(e) => ErrorKind::from_io_error(e),
+            Self::BadObservationDataDefinition | Self::ObservationDataTimescaleIdentification => {
+                ErrorKind::MalformedHeader
+            },
+            Self::LimitExceeded { .. } => ErrorKind::LimitExceeded,
+            Self::Cancelled => ErrorKind::Cancelled,
+            _ => ErrorKind::MalformedRecord,
+        }
+    }
 }
 
 /// Returns true if given line matches the start   
@@ -278,7 +335,9 @@ pub fn is_new_epoch(line: &str, header: &header::Header) -> bool {
         Type::AntennaData => antex::record::is_new_epoch(line),
         Type::ClockData => clock::record::is_new_epoch(line),
         Type::IonosphereMaps => {
-            ionex::record::is_new_tec_plane(line) || ionex::record::is_new_rms_plane(line)
+            ionex::record::is_new_tec_plane(line)
+                || ionex::record::is_new_rms_plane(line)
+                || ionex::record::is_new_height_plane(line)
         },
         Type::NavigationData => navigation::record::is_new_epoch(line, header.version),
         Type::ObservationData => observation::record::is_new_epoch(line, header.version),
@@ -287,12 +346,104 @@ pub fn is_new_epoch(line: &str, header: &header::Header) -> bool {
     }
 }
 
+/*
+ * OBSERVATION timescale is defined either
+ *   [+] by TIME OF FIRST header field
+ *   [+] fixed system in case of old GPS/GLO Observation Data
+ */
+pub(crate) fn observation_timescale(header: &header::Header) -> Result<TimeScale, Error> {
+    let mut obs_ts = TimeScale::default();
+    if let Some(obs) = &header.obs {
+        match header.constellation {
+            Some(Constellation::Mixed) | None => {
+                let time_of_first_obs = obs
+                    .time_of_first_obs
+                    .ok_or(Error::BadObservationDataDefinition)?;
+                obs_ts = time_of_first_obs.time_scale;
+            },
+            Some(constellation) => {
+                obs_ts = constellation
+                    .timescale()
+                    .ok_or(Error::ObservationDataTimescaleIdentification)?;
+            },
+        }
+    }
+    Ok(obs_ts)
+}
+
+/// [ParseWarning] describes one record entry that could not be parsed
+/// and was skipped, see [crate::Rinex::from_file_lenient].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseWarning {
+    /// Index of the skipped entry (starting at 1), in order of appearance
+    pub index: usize,
+    /// First line of the skipped entry
+    pub line: String,
+    /// Parsing error description
+    pub error: String,
+}
+
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "entry #{} \"{}\": {}", self.index, self.line, self.error)
+    }
+}
+
+/*
+ * Reports an entry that we could not parse.
+ * Residues that are empty (like an empty file body) are not an error.
+ */
+fn warn<E: std::fmt::Display>(
+    warnings: &mut Vec<ParseWarning>,
+    index: usize,
+    content: &str,
+    error: E,
+) {
+    if !content.trim().is_empty() {
+        warnings.push(ParseWarning {
+            index,
+            line: content
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim_end()
+                .to_string(),
+            error: error.to_string(),
+        });
+    }
+}
+
 /// Builds a `Record`, `RINEX` file body content,
-/// which is constellation and `RINEX` file type dependent
+/// which is constellation and `RINEX` file type dependent.
+/// Entries that we cannot parse are silently skipped,
+/// see [parse_record_lenient] to report them.
 pub fn parse_record(
     reader: &mut BufferedReader,
     header: &mut header::Header,
 ) -> Result<(Record, Comments), Error> {
+    let (record, comments, _) = parse_record_lenient(reader, header)?;
+    Ok((record, comments))
+}
+
+/// Builds a `Record` like [parse_record], but also returns
+/// a [ParseWarning] for each entry that we could not parse and skipped.
+pub fn parse_record_lenient(
+    reader: &mut BufferedReader,
+    header: &mut header::Header,
+) -> Result<(Record, Comments, Vec<ParseWarning>), Error> {
+    parse_record_with_progress(reader, header, None)
+}
+
+/*
+ * Builds a `Record` like [parse_record_lenient],
+ * notifies `progress` after each parsed epoch and stops when requested
+ */
+pub(crate) fn parse_record_with_progress(
+    reader: &mut BufferedReader,
+    header: &mut header::Header,
+    mut progress: Option<&mut ProgressHandle>,
+) -> Result<(Record, Comments, Vec<ParseWarning>), Error> {
+    let mut warnings = Vec::<ParseWarning>::new();
     let mut first_epoch = true;
     let mut content = String::default();
     let mut epoch_content = String::with_capacity(6 * 64);
@@ -312,25 +463,7 @@ pub fn parse_record(
     let mut dor_rec = doris::Record::new(); // DORIS
 
     // OBSERVATION case
-    //  timescale is defined either
-    //    [+] by TIME OF FIRST header field
-    //    [+] fixed system in case of old GPS/GLO Observation Data
-    let mut obs_ts = TimeScale::default();
-    if let Some(obs) = &header.obs {
-        match header.constellation {
-            Some(Constellation::Mixed) | None => {
-                let time_of_first_obs = obs
-                    .time_of_first_obs
-                    .ok_or(Error::BadObservationDataDefinition)?;
-                obs_ts = time_of_first_obs.time_scale;
-            },
-            Some(constellation) => {
-                obs_ts = constellation
-                    .timescale()
-                    .ok_or(Error::ObservationDataTimescaleIdentification)?;
-            },
-        }
-    }
+    let obs_ts = observation_timescale(header)?;
     // Clock RINEX TimeScale definition.
     //   Modern revisions define it in header directly.
     //   Old revisions are once again badly defined and most likely not thought out.
@@ -351,16 +484,22 @@ pub fn parse_record(
         }
     }
     // IONEX case
-    //  Default map type is TEC, it will come with identified Epoch
-    //  but others may exist:
-    //    in this case we used the previously identified Epoch
-    //    and attach other kinds of maps
+    //  Default map type is TEC, but RMS and Height maps may exist:
+    //    they are attached to the TEC map sharing the same Epoch and altitude
     let mut ionx_rec = ionex::Record::new();
-    let mut ionex_rms_plane = false;
+
+    // limits to be enforced
+    let options = reader.options();
+    let mut nb_epochs = 0;
+    let mut nb_bytes = 0_u64;
 
     for l in reader.lines() {
         // iterates one line at a time
-        let line = l.unwrap();
+        let line = l.map_err(|e| match LimitExceeded::from_io_error(&e) {
+            Some(limit) => Error::from(limit),
+            None => Error::FileIoError(e),
+        })?;
+        nb_bytes += line.len() as u64 + 1;
         // COMMENTS special case
         // --> store
         // ---> append later with epoch.timestamp attached to it
@@ -435,53 +574,60 @@ pub fn parse_record(
             // in case of CRINEX -> RINEX < 3 being recovered,
             // we have more than 1 ligne to process
             let new_epoch = is_new_epoch(line, header);
-            ionex_rms_plane = ionex::record::is_new_rms_plane(line);
 
             if new_epoch && !first_epoch {
                 match &header.rinex_type {
                     Type::NavigationData => {
                         let constellation = &header.constellation.unwrap();
-                        if let Ok((e, fr)) =
-                            parse_nav_epoch(header.version, *constellation, &epoch_content)
-                        {
-                            nav_rec
-                                .entry(e)
-                                .and_modify(|frames| frames.push(fr.clone()))
-                                .or_insert_with(|| vec![fr.clone()]);
-                            comment_ts = e; // for comments classification & management
+                        match parse_nav_epoch(header.version, *constellation, &epoch_content) {
+                            Ok((e, fr)) => {
+                                nav_rec
+                                    .entry(e)
+                                    .and_modify(|frames| frames.push(fr.clone()))
+                                    .or_insert_with(|| vec![fr.clone()]);
+                                comment_ts = e; // for comments classification & management
+                            },
+                            Err(error) => warn(&mut warnings, nb_epochs, &epoch_content, error),
                         }
                     },
                     Type::ObservationData => {
-                        if let Ok((e, ck_offset, map)) =
-                            observation::record::parse_epoch(header, &epoch_content, obs_ts)
-                        {
-                            obs_rec.insert(e, (ck_offset, map));
-                            comment_ts = e.0; // for comments classification & management
+                        match observation::record::parse_epoch(header, &epoch_content, obs_ts) {
+                            Ok((e, ck_offset, map)) => {
+                                obs_rec.insert(e, (ck_offset, map));
+                                comment_ts = e.0; // for comments classification & management
+                            },
+                            Err(error) => warn(&mut warnings, nb_epochs, &epoch_content, error),
                         }
                     },
-                    Type::DORIS => {
-                        if let Ok((e, map)) = doris::record::parse_epoch(header, &epoch_content) {
-                            dor_rec.insert(e, map);
-                        }
+                    Type::DORIS => match doris::record::parse_epoch(header, &epoch_content) {
+                        Ok((e, ck_offset, map)) => {
+                            dor_rec.insert(e, (ck_offset, map));
+                        },
+                        Err(error) => warn(&mut warnings, nb_epochs, &epoch_content, error),
                     },
                     Type::MeteoData => {
-                        if let Ok((e, map)) = meteo::record::parse_epoch(header, &epoch_content) {
-                            met_rec.insert(e, map);
-                            comment_ts = e; // for comments classification & management
+                        match meteo::record::parse_epoch(header, &epoch_content) {
+                            Ok((e, map)) => {
+                                met_rec.insert(e, map);
+                                comment_ts = e; // for comments classification & management
+                            },
+                            Err(error) => warn(&mut warnings, nb_epochs, &epoch_content, error),
                         }
                     },
                     Type::ClockData => {
-                        if let Ok((epoch, key, profile)) =
-                            clock::record::parse_epoch(header.version, &epoch_content, clk_ts)
-                        {
-                            if let Some(e) = clk_rec.get_mut(&epoch) {
-                                e.insert(key, profile);
-                            } else {
-                                let mut inner: BTreeMap<ClockKey, ClockProfile> = BTreeMap::new();
-                                inner.insert(key, profile);
-                                clk_rec.insert(epoch, inner);
-                            }
-                            comment_ts = epoch; // for comments classification & management
+                        match clock::record::parse_epoch(header.version, &epoch_content, clk_ts) {
+                            Ok((epoch, key, profile)) => {
+                                if let Some(e) = clk_rec.get_mut(&epoch) {
+                                    e.insert(key, profile);
+                                } else {
+                                    let mut inner: BTreeMap<ClockKey, ClockProfile> =
+                                        BTreeMap::new();
+                                    inner.insert(key, profile);
+                                    clk_rec.insert(epoch, inner);
+                                }
+                                comment_ts = epoch; // for comments classification & management
+                            },
+                            Err(error) => warn(&mut warnings, nb_epochs, &epoch_content, error),
                         }
                     },
                     Type::AntennaData => {
@@ -490,32 +636,11 @@ pub fn parse_record(
                         atx_rec.push((antenna, content));
                     },
                     Type::IonosphereMaps => {
-                        if let Ok((epoch, altitude, plane)) =
-                            ionex::record::parse_plane(&epoch_content, header, ionex_rms_plane)
-                        {
-                            if ionex_rms_plane {
-                                if let Some(rec_plane) = ionx_rec.get_mut(&(epoch, altitude)) {
-                                    // provide RMS value for the entire plane
-                                    for ((_, rec_tec), (_, tec)) in
-                                        rec_plane.iter_mut().zip(plane.iter())
-                                    {
-                                        rec_tec.rms = tec.rms;
-                                    }
-                                } else {
-                                    // insert RMS values
-                                    ionx_rec.insert((epoch, altitude), plane);
-                                }
-                            } else if let Some(rec_plane) = ionx_rec.get_mut(&(epoch, altitude)) {
-                                // provide TEC value for the entire plane
-                                for ((_, rec_tec), (_, tec)) in
-                                    rec_plane.iter_mut().zip(plane.iter())
-                                {
-                                    rec_tec.tec = tec.tec;
-                                }
-                            } else {
-                                // insert TEC values
-                                ionx_rec.insert((epoch, altitude), plane);
-                            }
+                        match ionex::record::parse_plane(&epoch_content, header) {
+                            Ok((epoch, altitude, plane)) => {
+                                ionex::record::insert_plane(&mut ionx_rec, epoch, altitude, plane);
+                            },
+                            Err(error) => warn(&mut warnings, nb_epochs, &epoch_content, error),
                         }
                     },
                 }
@@ -525,9 +650,18 @@ pub fn parse_record(
                     comments.insert(comment_ts, comment_content.clone());
                     comment_content.clear() // reset
                 }
+
+                // previous epoch is complete
+                if let Some(progress) = progress.as_mut() {
+                    if progress.cancelled(nb_bytes, nb_epochs) {
+                        return Err(Error::Cancelled);
+                    }
+                }
             } //is_new_epoch() +!first
 
             if new_epoch {
+                nb_epochs += 1;
+                options.check_epochs(nb_epochs)?;
                 if !first_epoch {
                     epoch_content.clear()
                 }
@@ -545,72 +679,62 @@ pub fn parse_record(
     match &header.rinex_type {
         Type::NavigationData => {
             let constellation = &header.constellation.unwrap();
-            if let Ok((e, fr)) = parse_nav_epoch(header.version, *constellation, &epoch_content) {
-                nav_rec
-                    .entry(e)
-                    .and_modify(|current| current.push(fr.clone()))
-                    .or_insert_with(|| vec![fr.clone()]);
-                comment_ts = e; // for comments classification & management
+            match parse_nav_epoch(header.version, *constellation, &epoch_content) {
+                Ok((e, fr)) => {
+                    nav_rec
+                        .entry(e)
+                        .and_modify(|current| current.push(fr.clone()))
+                        .or_insert_with(|| vec![fr.clone()]);
+                    comment_ts = e; // for comments classification & management
+                },
+                Err(error) => warn(&mut warnings, nb_epochs, &epoch_content, error),
             }
         },
         Type::ObservationData => {
-            if let Ok((e, ck_offset, map)) =
-                observation::record::parse_epoch(header, &epoch_content, obs_ts)
-            {
-                obs_rec.insert(e, (ck_offset, map));
-                comment_ts = e.0; // for comments classification + management
+            match observation::record::parse_epoch(header, &epoch_content, obs_ts) {
+                Ok((e, ck_offset, map)) => {
+                    obs_rec.insert(e, (ck_offset, map));
+                    comment_ts = e.0; // for comments classification + management
+                },
+                Err(error) => warn(&mut warnings, nb_epochs, &epoch_content, error),
             }
         },
-        Type::DORIS => {
-            if let Ok((e, map)) = doris::record::parse_epoch(header, &epoch_content) {
-                dor_rec.insert(e, map);
-            }
+        Type::DORIS => match doris::record::parse_epoch(header, &epoch_content) {
+            Ok((e, ck_offset, map)) => {
+                dor_rec.insert(e, (ck_offset, map));
+            },
+            Err(error) => warn(&mut warnings, nb_epochs, &epoch_content, error),
         },
         Type::MeteoData => {
-            if let Ok((e, map)) = meteo::record::parse_epoch(header, &epoch_content) {
-                met_rec.insert(e, map);
-                comment_ts = e; // for comments classification + management
+            match meteo::record::parse_epoch(header, &epoch_content) {
+                Ok((e, map)) => {
+                    met_rec.insert(e, map);
+                    comment_ts = e; // for comments classification + management
+                },
+                Err(error) => warn(&mut warnings, nb_epochs, &epoch_content, error),
             }
         },
         Type::ClockData => {
-            if let Ok((epoch, key, profile)) =
-                clock::record::parse_epoch(header.version, &epoch_content, clk_ts)
-            {
-                if let Some(e) = clk_rec.get_mut(&epoch) {
-                    e.insert(key, profile);
-                } else {
-                    let mut inner: BTreeMap<ClockKey, ClockProfile> = BTreeMap::new();
-                    inner.insert(key, profile);
-                    clk_rec.insert(epoch, inner);
-                }
-                comment_ts = epoch; // for comments classification & management
-            }
-        },
-        Type::IonosphereMaps => {
-            if let Ok((epoch, altitude, plane)) =
-                ionex::record::parse_plane(&epoch_content, header, ionex_rms_plane)
-            {
-                if ionex_rms_plane {
-                    if let Some(rec_plane) = ionx_rec.get_mut(&(epoch, altitude)) {
-                        // provide RMS value for the entire plane
-                        for ((_, rec_tec), (_, tec)) in rec_plane.iter_mut().zip(plane.iter()) {
-                            rec_tec.rms = tec.rms;
-                        }
+            match clock::record::parse_epoch(header.version, &epoch_content, clk_ts) {
+                Ok((epoch, key, profile)) => {
+                    if let Some(e) = clk_rec.get_mut(&epoch) {
+                        e.insert(key, profile);
                     } else {
-                        // insert RMS values
-                        ionx_rec.insert((epoch, altitude), plane);
+                        let mut inner: BTreeMap<ClockKey, ClockProfile> = BTreeMap::new();
+                        inner.insert(key, profile);
+                        clk_rec.insert(epoch, inner);
                     }
-                } else if let Some(rec_plane) = ionx_rec.get_mut(&(epoch, altitude)) {
-                    // provide TEC value for the entire plane
-                    for ((_, rec_tec), (_, tec)) in rec_plane.iter_mut().zip(plane.iter()) {
-                        rec_tec.tec = tec.tec;
-                    }
-                } else {
-                    // insert TEC values
-                    ionx_rec.insert((epoch, altitude), plane);
-                }
+                    comment_ts = epoch; // for comments classification & management
+                },
+                Err(error) => warn(&mut warnings, nb_epochs, &epoch_content, error),
             }
         },
+        Type::IonosphereMaps => match ionex::record::parse_plane(&epoch_content, header) {
+            Ok((epoch, altitude, plane)) => {
+                ionex::record::insert_plane(&mut ionx_rec, epoch, altitude, plane);
+            },
+            Err(error) => warn(&mut warnings, nb_epochs, &epoch_content, error),
+        },
         Type::AntennaData => {
             //if let Ok((antenna, content)) = antex::record::parse_antenna(&epoch_content) {
             //    atx_rec.push((antenna, content));
@@ -623,6 +747,14 @@ pub fn parse_record(
     if !comment_content.is_empty() {
         comments.insert(comment_ts, comment_content.clone());
     }
+    // final epoch is complete
+    if !first_epoch {
+        if let Some(progress) = progress.as_mut() {
+            if progress.cancelled(nb_bytes, nb_epochs) {
+                return Err(Error::Cancelled);
+            }
+        }
+    }
     // wrap record
     let record = match &header.rinex_type {
         Type::AntennaData => Record::AntexRecord(atx_rec),
@@ -633,7 +765,7 @@ pub fn parse_record(
         Type::ObservationData => Record::ObsRecord(obs_rec),
         Type::DORIS => Record::DorisRecord(dor_rec),
     };
-    Ok((record, comments))
+    Ok((record, comments, warnings))
 }
 
 impl Merge for Record {
@@ -645,6 +777,64 @@ impl Merge for Record {
     }
     /// Merges `rhs` into `Self`
     fn merge_mut(&mut self, rhs: &Self) -> Result<(), merge::Error> {
+        self.merge_mut_with_progress(rhs, None)
+    }
+}
+
+/*
+ * Merges `rhs` into `lhs` one epoch at a time,
+ * notifies `progress` after each epoch and stops when requested
+ */
+fn merge_mut_epochs<K: Ord + Clone, V: Clone>(
+    lhs: &mut BTreeMap<K, V>,
+    rhs: &BTreeMap<K, V>,
+    progress: &mut ProgressHandle,
+) -> Result<(), merge::Error>
+where
+    BTreeMap<K, V>: Merge,
+{
+    for (nb_epochs, (k, v)) in rhs.iter().enumerate() {
+        lhs.merge_mut(&BTreeMap::from([(k.clone(), v.clone())]))?;
+        if progress.cancelled(0, nb_epochs + 1) {
+            return Err(merge::Error::Cancelled);
+        }
+    }
+    Ok(())
+}
+
+impl Record {
+    /*
+     * Merges `rhs` into `Self`, with possible progress notifications
+     */
+    pub(crate) fn merge_mut_with_progress(
+        &mut self,
+        rhs: &Self,
+        progress: Option<&mut ProgressHandle>,
+    ) -> Result<(), merge::Error> {
+        if let Some(progress) = progress {
+            match (&mut *self, rhs) {
+                (Self::NavRecord(lhs), Self::NavRecord(rhs)) => {
+                    return merge_mut_epochs(lhs, rhs, progress);
+                },
+                (Self::ObsRecord(lhs), Self::ObsRecord(rhs)) => {
+                    return merge_mut_epochs(lhs, rhs, progress);
+                },
+                (Self::MeteoRecord(lhs), Self::MeteoRecord(rhs)) => {
+                    return merge_mut_epochs(lhs, rhs, progress);
+                },
+                (Self::ClockRecord(lhs), Self::ClockRecord(rhs)) => {
+                    return merge_mut_epochs(lhs, rhs, progress);
+                },
+                _ => {},
+            }
+            // other records are merged at once
+            self.merge_mut(rhs)?;
+            return if progress.cancelled(0, 1) {
+                Err(merge::Error::Cancelled)
+            } else {
+                Ok(())
+            };
+        }
         if let Some(lhs) = self.as_mut_nav() {
             if let Some(rhs) = rhs.as_nav() {
                 lhs.merge_mut(rhs)?;
@@ -695,7 +885,51 @@ impl Split for Record {
             Err(split::Error::NoEpochIteration)
         }
     }
-    fn split_dt(&self, _dt: Duration) -> Result<Vec<Self>, split::Error> {
-        Ok(Vec::new())
+    fn split_dt(&self, dt: Duration) -> Result<Vec<Self>, split::Error> {
+        if let Some(r) = self.as_obs() {
+            Ok(r.split_dt(dt)?.into_iter().map(Self::ObsRecord).collect())
+        } else if let Some(r) = self.as_nav() {
+            Ok(r.split_dt(dt)?.into_iter().map(Self::NavRecord).collect())
+        } else if let Some(r) = self.as_meteo() {
+            Ok(r.split_dt(dt)?.into_iter().map(Self::MeteoRecord).collect())
+        } else if let Some(r) = self.as_ionex() {
+            Ok(r.split_dt(dt)?.into_iter().map(Self::IonexRecord).collect())
+        } else if let Some(r) = self.as_clock() {
+            Ok(r.split_dt(dt)?.into_iter().map(Self::ClockRecord).collect())
+        } else {
+            Err(split::Error::NoEpochIteration)
+        }
     }
 }
+
+/*
+ * Epochs to be retained by given decimation filter.
+ * Epochs are expected in chronological order: repeated epochs
+ * (several entries sharing one sampling instant) are counted once.
+ */
+#[cfg(feature = "processing")]
+pub(crate) fn decimated_epochs<I: Iterator<Item = Epoch>>(
+    epochs: I,
+    filter: &DecimationFilterType,
+) -> std::collections::BTreeSet<Epoch> {
+    use itertools::Itertools;
+    let mut last_retained = Option::<Epoch>::None;
+    let mut i = 0;
+    epochs
+        .dedup()
+        .filter(|e| {
+            let retained = match filter {
+                DecimationFilterType::Modulo(r) => (i % r) == 0,
+                DecimationFilterType::Duration(interval) => match last_retained {
+                    Some(last) => *e - last >= *interval,
+                    None => true, // always retain 1st epoch
+                },
+            };
+            i += 1;
+            if retained {
+                last_retained = Some(*e);
+            }
+            retained
+        })
+        .collect()
+}
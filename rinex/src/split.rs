@@ -1,8 +1,10 @@
 //! RINEX File splitting operation
-use crate::{Duration, Epoch};
+use crate::{Duration, Epoch, ErrorKind};
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 #[derive(Error, Debug, Clone)]
+#[non_exhaustive]
 pub enum Error {
     #[error("this record type is not indexed by epoch")]
     NoEpochIteration,
@@ -10,6 +12,13 @@ pub enum Error {
     NonExistingEpoch,
 }
 
+impl Error {
+    /// Returns the [ErrorKind] of this [Error]
+    pub fn kind(&self) -> ErrorKind {
+        ErrorKind::Unsupported
+    }
+}
+
 pub trait Split {
     /// Splits Self at desired epoch,
     /// retaining |e(k) < epoch| ("before"), as left component,
@@ -35,8 +44,54 @@ pub trait Split {
     where
         Self: Sized;
 
-    /// Splits Self into a serie of epoch of equal durations
+    /// Splits Self into a serie of chunks, each spanning at most `dt`,
+    /// starting from the first epoch. Epochs lying exactly on a boundary
+    /// are part of the following chunk. Returns a single element
+    /// if Self spans less than `dt`.
+    /// ```
+    /// use rinex::Split; // .split_dt()
+    /// use rinex::prelude::*;
+    /// let rnx = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O")
+    ///     .unwrap();
+    /// // epochs: 00:00:00, 00:28:30, 00:57:00
+    /// let chunks = rnx.split_dt(Duration::from_hours(0.5))
+    ///     .unwrap();
+    /// assert_eq!(chunks.len(), 2);
+    /// assert_eq!(chunks[0].epoch().count(), 2);
+    /// assert_eq!(chunks[1].epoch().count(), 1);
+    /// ```
     fn split_dt(&self, dt: Duration) -> Result<Vec<Self>, Error>
     where
         Self: Sized;
 }
+
+/// Splits epoch indexed content into chunks spanning at most `dt`, starting from the
+/// first epoch. Epochs lying exactly on a boundary are part of the following chunk.
+/// Empty chunks (data gaps) are not returned.
+pub(crate) fn split_dt<K: Ord + Clone, V: Clone>(
+    map: &BTreeMap<K, V>,
+    dt: Duration,
+    epoch: impl Fn(&K) -> Epoch,
+) -> Vec<BTreeMap<K, V>> {
+    let mut ret: Vec<BTreeMap<K, V>> = Vec::new();
+    let dt = dt.total_nanoseconds();
+    let mut t0: Option<Epoch> = None;
+    let mut curr_index = 0_i128;
+    for (k, v) in map.iter() {
+        let t = epoch(k);
+        let t0 = *t0.get_or_insert(t);
+        let index = if dt > 0 {
+            (t - t0).total_nanoseconds() / dt
+        } else {
+            0
+        };
+        if ret.is_empty() || index != curr_index {
+            curr_index = index;
+            ret.push(BTreeMap::new());
+        }
+        if let Some(chunk) = ret.last_mut() {
+            chunk.insert(k.clone(), v.clone());
+        }
+    }
+    ret
+}
@@ -1,10 +1,11 @@
 #[cfg(test)]
 mod test {
     use crate::antex::pcv::Pcv;
-    use crate::antex::CalibrationMethod;
+    use crate::antex::{AntennaPhasePattern, CalibrationMethod};
     use crate::carrier::Carrier;
     use crate::linspace::Linspace;
     use crate::prelude::*;
+    use crate::tests::toolkit::random_name;
     use std::str::FromStr;
     #[cfg(feature = "antex")]
     #[test]
@@ -128,4 +129,68 @@ mod test {
             assert_eq!(apc.unwrap(), expected);
         }
     }
+    #[cfg(feature = "antex")]
+    #[test]
+    fn v1_trosar_25r4_leit_2020_09_23_formatting() {
+        let test_resource = env!("CARGO_MANIFEST_DIR").to_owned()
+            + "/../test_resources/ATX/V1/TROSAR25.R4__LEIT_2020_09_23.atx";
+        let rinex = Rinex::from_file(&test_resource).unwrap();
+
+        let tmp_path = format!("test-{}.atx", random_name(5));
+        rinex.to_file(&tmp_path).unwrap();
+
+        let parsed = Rinex::from_file(&tmp_path);
+        let _ = std::fs::remove_file(&tmp_path);
+        let parsed = parsed.unwrap();
+
+        assert!(parsed.is_antex());
+        assert_eq!(parsed.header.antex, rinex.header.antex);
+
+        let (antenna, freq_data) = rinex.record.as_antex().unwrap().first().unwrap();
+        assert_eq!(antenna.azi_inc, 5.0);
+        assert!(matches!(
+            freq_data.get(&Carrier::L1).unwrap().phase_pattern,
+            AntennaPhasePattern::AzimuthDependentPattern { .. }
+        ));
+
+        assert_eq!(parsed.record.as_antex(), rinex.record.as_antex());
+    }
+    #[cfg(feature = "flate2")]
+    #[cfg(feature = "antex")]
+    #[test]
+    fn v1_4_igs_atx_subset_formatting() {
+        let test_resource =
+            env!("CARGO_MANIFEST_DIR").to_owned() + "/../test_resources/ATX/V1/igs14_small.atx.gz";
+
+        let mut rinex = Rinex::from_file(&test_resource).unwrap();
+        rinex.antex_retain(&[
+            AntennaMatcher::IGSCode("jpslegant_e".to_string()),
+            AntennaMatcher::IGSCode("JPSODYSSEY_I".to_string()),
+        ]);
+        assert_eq!(rinex.antennas().count(), 2);
+
+        let tmp_path = format!("test-{}.atx", random_name(5));
+        rinex.to_file(&tmp_path).unwrap();
+
+        let parsed = Rinex::from_file(&tmp_path);
+        let _ = std::fs::remove_file(&tmp_path);
+        let parsed = parsed.unwrap();
+
+        assert_eq!(parsed.antennas().count(), 2);
+
+        let fake_now = Epoch::from_gregorian_utc_at_midnight(2023, 01, 01);
+        for (antenna, expected) in [
+            ("JPSLEGANT_E", (1.36, -0.43, 35.44)),
+            ("JPSODYSSEY_I", (1.06, -2.43, 70.34)),
+        ] {
+            let apc = parsed.rx_antenna_apc_offset(
+                fake_now,
+                AntennaMatcher::IGSCode(antenna.to_string()),
+                Carrier::L1,
+            );
+            assert_eq!(apc, Some(expected), "bad APC for {} antenna", antenna);
+        }
+
+        assert_eq!(parsed.record.as_antex(), rinex.record.as_antex());
+    }
 }
@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod test {
+    use crate::carrier::Carrier;
+    use crate::prelude::*;
+    use crate::tests::toolkit::random_name;
+    use ::binex::prelude::{EphemerisFrame, GPSEphemeris, Message, Meta, Record};
+    use std::{fs::File, io::Read, str::FromStr};
+
+    fn gps_message(sv_prn: u8) -> Vec<u8> {
+        gps_message_with_meta(
+            sv_prn,
+            Meta {
+                reversed: false,
+                enhanced_crc: false,
+                big_endian: true,
+            },
+        )
+    }
+
+    fn gps_message_with_meta(sv_prn: u8, meta: Meta) -> Vec<u8> {
+        let eph = GPSEphemeris {
+            sv_prn,
+            toe: 2138,
+            tow: 345600,
+            toc: 352800,
+            sqrt_a: 5153.6,
+            e: 0.01,
+            ..Default::default()
+        };
+        let msg = Message::new(
+            meta,
+            Record::new_ephemeris_frame(EphemerisFrame::new_gps(eph)),
+        );
+        let mut buf = vec![0; msg.encoding_size()];
+        let size = buf.len();
+        msg.encode(&mut buf, size).unwrap();
+        buf
+    }
+
+    #[test]
+    fn binex_gps_ephemeris() {
+        let mut stream = gps_message(0);
+        stream.extend(gps_message(1));
+
+        let rinex = Rinex::from_binex_stream(stream.as_slice()).unwrap();
+        assert!(rinex.is_navigation_rinex());
+        assert_eq!(rinex.header.constellation, Some(Constellation::GPS));
+
+        let toc = Epoch::from_str("2020-12-31T02:00:00 GPST").unwrap();
+        let ephemerides = rinex.ephemeris().collect::<Vec<_>>();
+        assert_eq!(ephemerides.len(), 2);
+
+        for (t, (_, sv, eph)) in ephemerides {
+            assert_eq!(*t, toc);
+            assert!(sv == SV::new(Constellation::GPS, 1) || sv == SV::new(Constellation::GPS, 2));
+            assert_eq!(eph.get_orbit_f64("sqrta"), Some(5153.6));
+            assert_eq!(eph.get_orbit_f64("toe"), Some(352800.0));
+            assert_eq!(eph.get_week(), Some(2138));
+        }
+    }
+
+    #[test]
+    fn binex_partial_stream() {
+        let mut stream = gps_message(0);
+        let second = gps_message(1);
+        stream.extend(&second[..second.len() / 2]);
+
+        let rinex = Rinex::from_binex_stream(stream.as_slice()).unwrap();
+        assert_eq!(rinex.ephemeris().count(), 1);
+    }
+
+    #[test]
+    fn binex_reversed_messages() {
+        let reversed = Meta {
+            reversed: true,
+            enhanced_crc: false,
+            big_endian: false,
+        };
+
+        // forward and reverse-readable messages may be mixed
+        let mut stream = gps_message_with_meta(0, reversed);
+        stream.extend(gps_message(1));
+        stream.extend(gps_message_with_meta(2, reversed));
+
+        let rinex = Rinex::from_binex_stream(stream.as_slice()).unwrap();
+
+        let vehicles = rinex
+            .ephemeris()
+            .map(|(_, (_, sv, _))| sv)
+            .collect::<Vec<_>>();
+
+        assert_eq!(vehicles.len(), 3);
+        for prn in 1..=3 {
+            assert!(vehicles.contains(&SV::new(Constellation::GPS, prn)));
+        }
+    }
+
+    #[test]
+    fn binex_netrs_observations() {
+        let fd = File::open(format!(
+            "{}/../test_resources/BIN/mfle20190130.bnx",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .unwrap();
+
+        // first minutes of this 1 Hz capture
+        let rinex = Rinex::from_binex_stream(fd.take(64 * 1024)).unwrap();
+        assert!(rinex.is_observation_rinex());
+        assert_eq!(rinex.header.constellation, Some(Constellation::GPS));
+
+        let t0 = Epoch::from_str("2019-01-13T00:00:00 GPST").unwrap();
+        assert_eq!(rinex.first_epoch(), Some(t0));
+
+        let codes = &rinex.header.obs.as_ref().unwrap().codes[&Constellation::GPS];
+        for code in [
+            "C1C", "L1C", "S1C", "C2W", "L2W", "S2W", "C2X", "L2X", "S2X",
+        ] {
+            let observable = Observable::from_str(code).unwrap();
+            assert!(codes.contains(&observable), "missing {} observable", code);
+        }
+
+        let ((t, flag), (clk, vehicles)) = rinex.observation().next().unwrap();
+        assert_eq!(*t, t0);
+        assert!(flag.is_ok());
+        assert!(clk.is_none());
+        assert_eq!(vehicles.len(), 11);
+
+        let lambda_1 = Carrier::L1.wavelength();
+        let lambda_2 = Carrier::L2.wavelength();
+
+        let g30 = &vehicles[&SV::new(Constellation::GPS, 30)];
+        for (code, value) in [
+            ("C1C", 20644587.647),
+            ("L1C", 20644592.84648 / lambda_1),
+            ("S1C", 47.6),
+            ("C2X", 20644595.189),
+            ("L2X", 20644586.9646 / lambda_2),
+            ("S2X", 52.2),
+        ] {
+            let observable = Observable::from_str(code).unwrap();
+            let data = g30
+                .get(&observable)
+                .unwrap_or_else(|| panic!("missing G30 {}", code));
+            let err = (data.obs - value).abs();
+            assert!(err < 1.0E-6, "G30 {}: {} != {}", code, data.obs, value);
+        }
+
+        // G11 (legacy IIR) is tracked as L2 P(Y)
+        let g11 = &vehicles[&SV::new(Constellation::GPS, 11)];
+        assert!(g11.contains_key(&Observable::from_str("C2W").unwrap()));
+        assert!(!g11.contains_key(&Observable::from_str("C2X").unwrap()));
+
+        // formatting
+        let tmp_path = format!("test-{}.rnx", random_name(5));
+        assert!(rinex.to_file(&tmp_path).is_ok());
+
+        let parsed = Rinex::from_file(&tmp_path);
+        let _ = std::fs::remove_file(&tmp_path);
+        let parsed = parsed.unwrap();
+
+        assert_eq!(parsed.first_epoch(), Some(t0));
+        assert_eq!(parsed.observation().count(), rinex.observation().count());
+    }
+
+    #[test]
+    fn binex_nav_stream() {
+        let stream = gps_message(0);
+        let rinex = Rinex::from_binex_nav_stream(stream.as_slice()).unwrap();
+        assert!(rinex.is_navigation_rinex());
+        assert_eq!(rinex.ephemeris().count(), 1);
+    }
+}
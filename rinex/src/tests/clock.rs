@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod test {
     use crate::prelude::*;
+    use crate::tests::toolkit::random_name;
     use std::str::FromStr;
     #[test]
     fn clk_v2_cod20352() {
@@ -149,6 +150,11 @@ mod test {
 
         assert_eq!(clock_header.igs, Some("USN".to_string()));
         assert_eq!(clock_header.site, Some("USNO".to_string()));
+        assert_eq!(clock_header.frame, Some(ReferenceFrame::ITRF(1996)));
+        assert_eq!(
+            rinex.header.reference_frame(),
+            Some(ReferenceFrame::ITRF(1996))
+        );
         assert_eq!(
             clock_header.domes,
             Some(DOMES {
@@ -282,6 +288,28 @@ mod test {
             ]
         );
 
+        assert_eq!(clock_header.frame, Some(ReferenceFrame::ITRF(1996)));
+        assert_eq!(clock_header.solution_stations.len(), 5);
+        assert_eq!(
+            clock_header.solution_stations[0],
+            SolutionStation {
+                name: "GOLD".to_string(),
+                domes: Some(DOMES {
+                    area: 404,
+                    site: 5,
+                    point: DOMESTrackingPoint::Instrument,
+                    sequential: 31,
+                }),
+                ecef_mm: (1234567890, -1234567890, -1234567890),
+            }
+        );
+        assert_eq!(clock_header.solution_satellites.len(), 27);
+        assert_eq!(
+            clock_header.solution_satellites[15],
+            SV::from_str("G18").unwrap(),
+            "16th PRN LIST entry overflows the 60 column content"
+        );
+
         assert_eq!(rinex.epoch().count(), 1);
     }
     #[test]
@@ -292,6 +320,162 @@ mod test {
         assert!(rinex.is_ok());
         let rinex = rinex.unwrap();
 
+        // IGS14 realization
+        assert_eq!(
+            rinex.header.reference_frame(),
+            Some(ReferenceFrame::ITRF(2014))
+        );
         assert_eq!(rinex.epoch().count(), 1);
     }
+    #[test]
+    fn clk_v2_cod20352_signal_clock() {
+        let test_resource =
+            env!("CARGO_MANIFEST_DIR").to_owned() + "/../test_resources/CLK/V2/COD20352.CLK";
+        let rinex = Rinex::from_file(&test_resource).unwrap();
+
+        let g01 = SV::from_str("G01").unwrap();
+        let samples = rinex
+            .precise_sv_clock()
+            .filter_map(|(t, sv, _, profile)| {
+                if sv == g01 {
+                    Some((t, profile.bias))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        assert!(samples.len() > 2);
+
+        // sampling instants
+        let (t0, bias0) = samples[0];
+        let (t1, bias1) = samples[1];
+        assert_eq!(rinex.precise_sv_clock_interpolate(g01, t0), Some(bias0));
+        assert_eq!(rinex.precise_sv_clock_interpolate(g01, t1), Some(bias1));
+
+        // half way
+        let t = t0 + Duration::from_seconds((t1 - t0).to_seconds() / 2.0);
+        let raw = rinex.precise_sv_clock_interpolate(g01, t).unwrap();
+        assert!((raw - (bias0 + bias1) / 2.0).abs() < 1.0E-15);
+
+        // out of range
+        assert!(rinex
+            .precise_sv_clock_interpolate(g01, t0 - Duration::from_seconds(30.0))
+            .is_none());
+
+        // constant synthetic bias, for C1C only
+        let c1c = Observable::from_str("C1C").unwrap();
+        let c2w = Observable::from_str("C2W").unwrap();
+        let bias = |_: SV, observable: &Observable| -> Option<f64> {
+            if observable.to_string() == "C1C" {
+                Some(5.0E-9)
+            } else {
+                None
+            }
+        };
+        let (raw_c1c, corrected) = rinex
+            .precise_sv_clock_for_signal(g01, t, &c1c, Some(&bias))
+            .unwrap();
+        assert_eq!(raw_c1c, raw);
+        assert!((raw - corrected - 5.0E-9).abs() < 1.0E-15);
+
+        // signals without bias are not modified
+        let (raw_c2w, corrected) = rinex
+            .precise_sv_clock_for_signal(g01, t, &c2w, Some(&bias))
+            .unwrap();
+        assert_eq!((raw_c2w, corrected), (raw, raw));
+        assert_eq!(
+            rinex.precise_sv_clock_for_signal(g01, t, &c1c, None),
+            Some((raw, raw))
+        );
+    }
+    /*
+     * Formats given file, parses it back and compares both
+     */
+    fn clock_formatting_round_trip(path: &str) {
+        let rinex = Rinex::from_file(path).unwrap();
+        let tmp_path = format!("test-{}.clk", random_name(8));
+        rinex.to_file(&tmp_path).unwrap();
+        let parsed = Rinex::from_file(&tmp_path);
+        let _ = std::fs::remove_file(&tmp_path);
+        let parsed = parsed.unwrap();
+
+        assert_eq!(parsed.header.version, rinex.header.version);
+        assert_eq!(parsed.header.clock, rinex.header.clock);
+        assert_eq!(
+            parsed.precise_sv_clock().collect::<Vec<_>>(),
+            rinex.precise_sv_clock().collect::<Vec<_>>(),
+        );
+        assert_eq!(parsed.record, rinex.record);
+    }
+    #[test]
+    fn clk_v2_cod20352_formatting() {
+        clock_formatting_round_trip(
+            &(env!("CARGO_MANIFEST_DIR").to_owned() + "/../test_resources/CLK/V2/COD20352.CLK"),
+        );
+    }
+    #[test]
+    fn clk_v3_04_example1_formatting() {
+        // 6 data fields, with continuation lines
+        clock_formatting_round_trip(
+            &(env!("CARGO_MANIFEST_DIR").to_owned() + "/../test_resources/CLK/V3/example1.txt"),
+        );
+    }
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn clk_v3_grg0mgxfin_formatting() {
+        clock_formatting_round_trip(
+            &(env!("CARGO_MANIFEST_DIR").to_owned()
+                + "/../test_resources/CLK/V3/GRG0MGXFIN_20201770000_01D_30S_CLK.CLK.gz"),
+        );
+    }
+    #[test]
+    fn clk_epoch_formatting() {
+        use crate::clock::record::fmt_epoch;
+        use crate::version::Version;
+
+        let t = Epoch::from_str("1994-07-14T20:59:00 GPST").unwrap();
+        let key = ClockKey {
+            clock_type: ClockType::Station("GOLD".to_string()),
+            profile_type: ClockProfileType::AR,
+        };
+        let profile = ClockProfile {
+            bias: -0.123456789012E-01,
+            bias_dev: Some(-0.123456789012E-02),
+            drift: Some(-0.123456789012E-03),
+            drift_dev: Some(-0.123456789012E-04),
+            drift_change: None,
+            drift_change_dev: None,
+        };
+        assert_eq!(
+            fmt_epoch(Version::new(3, 4), &t, &key, &profile),
+            "AR GOLD      1994 07 14 20 59  0.000000  4   -0.123456789012E-01  -0.123456789012E-02
+   -0.123456789012E-03  -0.123456789012E-04\n"
+        );
+
+        let key = ClockKey {
+            clock_type: ClockType::SV(SV::from_str("G01").unwrap()),
+            profile_type: ClockProfileType::AS,
+        };
+        let profile = ClockProfile {
+            bias: -0.141648778557E-03,
+            bias_dev: Some(0.305413520003E-11),
+            ..Default::default()
+        };
+        assert_eq!(
+            fmt_epoch(Version::new(2, 0), &t, &key, &profile),
+            "AS G01  1994 07 14 20 59  0.000000  2   -0.141648778557E-03  0.305413520003E-11\n"
+        );
+
+        // missing sigma is zeroed, to preserve the drift position
+        let profile = ClockProfile {
+            bias: 0.5,
+            drift: Some(1.0E-12),
+            ..Default::default()
+        };
+        assert_eq!(
+            fmt_epoch(Version::new(2, 0), &t, &key, &profile),
+            "AS G01  1994 07 14 20 59  0.000000  3    0.500000000000E+00  0.000000000000E+00
+     0.100000000000E-11\n"
+        );
+    }
 }
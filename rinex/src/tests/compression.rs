@@ -213,4 +213,25 @@ mod test {
             let _ = std::fs::remove_file(&tmp_path);
         }
     }
+    #[test]
+    fn forced_crinex_revision() {
+        let rnx = Rinex::from_file("../test_resources/OBS/V2/AJAC3550.21O").unwrap();
+        for (crinex, major) in [
+            (rnx.rnx2crnx1(), 1),
+            (rnx.rnx2crnx3(), 3),
+            (rnx.rnx2crnx(), 1),
+        ] {
+            let version = crinex
+                .header
+                .obs
+                .as_ref()
+                .and_then(|obs| obs.crinex.as_ref())
+                .map(|crinex| crinex.version.major);
+            assert_eq!(version, Some(major));
+        }
+        let mut rnx = rnx;
+        rnx.rnx2crnx3_mut();
+        let crinex = rnx.header.obs.as_ref().and_then(|obs| obs.crinex.as_ref());
+        assert_eq!(crinex.map(|crinex| crinex.version.major), Some(3));
+    }
 }
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod test {
+    use crate::dd::double_differences;
+    use crate::prelude::*;
+    use crate::tests::toolkit::nav::earth_cef;
+    use std::str::FromStr;
+    #[test]
+    fn esbc00dnk_zero_baseline() {
+        let rover =
+            Rinex::from_file("../test_resources/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz")
+                .unwrap();
+        let nav =
+            Rinex::from_file("../test_resources/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+                .unwrap();
+
+        let almanac = Almanac::default();
+        let dd = double_differences(&rover, &rover, &nav, &almanac, earth_cef(), None);
+        assert!(!dd.is_empty(), "no double differences formed");
+        for ((sv, observable), values) in dd {
+            for (t, value) in values {
+                assert_eq!(value, 0.0, "{}({}) {}: non zero dd", t, sv, observable);
+            }
+        }
+    }
+    #[test]
+    fn esbc00dnk_perturbed() {
+        let base =
+            Rinex::from_file("../test_resources/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz")
+                .unwrap();
+        let nav =
+            Rinex::from_file("../test_resources/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+                .unwrap();
+
+        let gps = base
+            .sv()
+            .filter(|sv| sv.constellation == Constellation::GPS)
+            .collect::<Vec<_>>();
+        let (ref_sv, perturbed) = (gps[0], gps[1]);
+        let l1c = Observable::from_str("L1C").unwrap();
+
+        // rover: 1 cycle offset on L1C
+        let mut rover = base.clone();
+        let record = rover.record.as_mut_obs().unwrap();
+        for (_, (_, vehicles)) in record.iter_mut() {
+            if let Some(observations) = vehicles.get_mut(&perturbed) {
+                if let Some(data) = observations.get_mut(&l1c) {
+                    data.obs += 1.0;
+                }
+            }
+        }
+
+        let lambda = Carrier::L1.wavelength();
+        let almanac = Almanac::default();
+        let dd = double_differences(&rover, &base, &nav, &almanac, earth_cef(), Some(ref_sv));
+        assert!(dd.contains_key(&(perturbed, l1c.clone())));
+        assert!(dd.keys().all(|(sv, _)| *sv != ref_sv));
+        for ((sv, observable), values) in dd {
+            let expected = if sv == perturbed && observable == l1c {
+                lambda
+            } else {
+                0.0
+            };
+            for (t, value) in values {
+                let err = (value - expected).abs();
+                assert!(err < 1.0E-6, "{}({}) {}: dd={}", t, sv, observable, value);
+            }
+        }
+    }
+}
@@ -2,9 +2,11 @@
 mod test {
     use crate::tests::toolkit::doris_check_observables;
     use crate::tests::toolkit::doris_check_stations;
+    use crate::tests::toolkit::{is_null_rinex, random_name};
 
     use crate::prelude::*;
     use std::path::Path;
+    use std::str::FromStr;
 
     #[test]
     #[cfg(feature = "flate2")]
@@ -77,5 +79,83 @@ mod test {
                 "D50  PDOC PONTA DELGADA                 31906S004  4   0",
             ],
         );
+
+        let satellite = rinex.doris_satellite().expect("missing DORIS satellite");
+        assert_eq!(satellite.name, "CRYOSAT-2");
+        assert_eq!(
+            satellite.cospar,
+            Some(COSPAR::from_str("2010-013A").unwrap())
+        );
+        assert_eq!(satellite.ids_code(), "cs2");
+
+        assert_eq!(rinex.standard_filename(true, None, None), "cs2rx18164");
+    }
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn v3_cs2rx18164_formatting() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("DOR")
+            .join("V3")
+            .join("cs2rx18164.gz");
+        let fullpath = path.to_string_lossy();
+        let rinex = Rinex::from_file(fullpath.as_ref()).unwrap();
+
+        let tmp_path = format!("test-{}.rnx", random_name(5));
+        rinex.to_file(&tmp_path).unwrap();
+
+        let parsed = Rinex::from_file(&tmp_path);
+        let _ = std::fs::remove_file(&tmp_path);
+        let parsed = parsed.unwrap();
+
+        assert_eq!(parsed.header.rinex_type, RinexType::DORIS);
+        let (header, parsed_header) = (
+            rinex.header.doris.as_ref().unwrap(),
+            parsed.header.doris.as_ref().unwrap(),
+        );
+        assert_eq!(parsed_header.observables, header.observables);
+        assert_eq!(parsed_header.stations, header.stations);
+        assert_eq!(parsed_header.scaling, header.scaling);
+        assert_eq!(parsed_header.satellite, header.satellite);
+
+        let record = rinex.record.as_doris().unwrap();
+        let reparsed = parsed.record.as_doris().unwrap();
+        assert_eq!(reparsed.len(), record.len(), "epochs lost while formatting");
+
+        for (key, (clock_offset, stations)) in record.iter() {
+            let (parsed_offset, parsed_stations) = reparsed
+                .get(key)
+                .unwrap_or_else(|| panic!("missing epoch {:?}", key));
+            assert_eq!(parsed_offset, clock_offset, "bad clock offset @ {:?}", key);
+            assert_eq!(parsed_stations.len(), stations.len());
+            for (station, observations) in stations.iter() {
+                let parsed_observations = parsed_stations
+                    .get(station)
+                    .unwrap_or_else(|| panic!("missing {} @ {:?}", station.label, key));
+                for (observable, data) in observations.iter() {
+                    assert_eq!(
+                        parsed_observations.get(observable),
+                        Some(data),
+                        "bad {} {} @ {:?}",
+                        station.label,
+                        observable,
+                        key
+                    );
+                }
+                assert_eq!(parsed_observations.len(), observations.len());
+            }
+        }
+    }
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn v3_cs2rx18164_substract() {
+        let rinex = Rinex::from_file("../test_resources/DOR/V3/cs2rx18164.gz").unwrap();
+        let null = rinex.substract(&rinex);
+        assert_eq!(
+            null.record.as_doris().unwrap().len(),
+            rinex.record.as_doris().unwrap().len()
+        );
+        is_null_rinex(&null, 0.0);
     }
 }
@@ -67,3 +67,118 @@ fn long_filename_conventions() {
         assert_eq!(output, expected, "bad filename generated");
     }
 }
+
+// Test that standard names, compressed or not, are parsed
+// and regenerated identically
+#[test]
+fn standard_filename_roundtrip() {
+    use crate::observation::{Crinex, HeaderFields};
+    use crate::prod::{Compression, ProductionAttributes};
+    use crate::record::Record;
+    use std::str::FromStr;
+
+    let obs = Header::basic_obs().with_observation_fields(HeaderFields::default());
+    let crinex = Header::basic_obs().with_observation_fields(HeaderFields {
+        crinex: Some(Crinex::default()),
+        ..Default::default()
+    });
+    let nav = Header::basic_nav();
+    let glo_nav = Header::basic_nav().with_constellation(Constellation::Glonass);
+    let meteo = Header::default().with_type(RinexType::MeteoData);
+
+    for (filename, short, header, compression) in [
+        (
+            "ACOR00ESP_R_20213550000_01D_30S_MO.crx",
+            false,
+            &crinex,
+            None,
+        ),
+        (
+            "ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz",
+            false,
+            &crinex,
+            Some(Compression::Gzip),
+        ),
+        (
+            "KMS300DNK_R_20221591000_01H_30S_MO.crx.gz",
+            false,
+            &crinex,
+            Some(Compression::Gzip),
+        ),
+        (
+            "BRUX00BEL_R_20220010000_01D_30S_MO.crx.gz",
+            false,
+            &crinex,
+            Some(Compression::Gzip),
+        ),
+        (
+            "WTZR00DEU_R_20230010000_15M_01S_MO.crx.gz",
+            false,
+            &crinex,
+            Some(Compression::Gzip),
+        ),
+        (
+            "MOJN00DNK_R_20201770000_01D_30S_MO.rnx.gz",
+            false,
+            &obs,
+            Some(Compression::Gzip),
+        ),
+        ("AMEL00NLD_R_20210010000_01D_MN.rnx", false, &nav, None),
+        (
+            "BRDC00IGS_R_20230010000_01D_MN.rnx.gz",
+            false,
+            &nav,
+            Some(Compression::Gzip),
+        ),
+        (
+            "ESBC00DNK_R_20201770000_01D_MN.rnx.gz",
+            false,
+            &nav,
+            Some(Compression::Gzip),
+        ),
+        ("AJAC3550.21O", true, &obs, None),
+        ("KOSG0010.15O.gz", true, &obs, Some(Compression::Gzip)),
+        ("AJAC3550.21D.Z", true, &crinex, Some(Compression::Lzw)),
+        ("BRDC0010.22N.Z", true, &nav, Some(Compression::Lzw)),
+        ("ESBC1770.20G.bz2", true, &glo_nav, Some(Compression::Bzip2)),
+        ("CLAR0020.00M", true, &meteo, None),
+    ] {
+        let attrs = ProductionAttributes::from_str(filename)
+            .unwrap_or_else(|e| panic!("failed to parse \"{}\": {}", filename, e));
+        assert_eq!(attrs.compression, compression, "\"{}\"", filename);
+        assert_eq!(attrs.details.is_some(), !short, "\"{}\"", filename);
+
+        let record = match header.rinex_type {
+            RinexType::ObservationData => Record::ObsRecord(Default::default()),
+            RinexType::NavigationData => Record::NavRecord(Default::default()),
+            _ => Record::MeteoRecord(Default::default()),
+        };
+        let mut rinex = Rinex::new(header.clone(), record);
+        rinex.prod_attr = Some(attrs.clone());
+
+        assert_eq!(
+            rinex.standard_filename(short, None, Some(attrs.clone())),
+            filename,
+            "bad filename regenerated"
+        );
+
+        // compression suffix can be overridden
+        let stem = filename
+            .trim_end_matches(".gz")
+            .trim_end_matches(".Z")
+            .trim_end_matches(".bz2");
+        assert_eq!(rinex.standard_filename(short, Some(""), Some(attrs)), stem);
+    }
+}
+#[test]
+fn non_ascii_filename() {
+    use crate::prod::ProductionAttributes;
+    use std::str::FromStr;
+    for filename in ["ESBC1770.20€", "ESBC1770.20O€", "ESBC1770.20O.€z"] {
+        assert!(
+            ProductionAttributes::from_str(filename).is_err(),
+            "\"{}\" should not be parsed",
+            filename
+        );
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod test {
+    use crate::fileops::{patch_header, HeaderPatch};
+    use crate::hardware::Antenna;
+    use crate::header::Header;
+    use crate::prelude::*;
+    use crate::reader::BufferedReader;
+    use crate::tests::toolkit::random_name;
+    /*
+     * Splits file content after END OF HEADER
+     */
+    fn split_header(content: &[u8]) -> (&[u8], &[u8]) {
+        let marker = b"END OF HEADER";
+        let pos = content
+            .windows(marker.len())
+            .position(|w| w == marker)
+            .unwrap();
+        let end = pos + content[pos..].iter().position(|b| *b == b'\n').unwrap() + 1;
+        content.split_at(end)
+    }
+    #[test]
+    #[cfg(feature = "obs")]
+    fn patch_obs_header() {
+        let original = std::fs::read("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        let path = format!("test-{}.22O", random_name(8));
+        std::fs::write(&path, &original).unwrap();
+
+        let position = GroundPosition::from_ecef_wgs84((4362690.1234, 2026648.5678, 4174234.9012));
+        let patch = HeaderPatch::default()
+            .with_marker("DUTH2")
+            .with_agency("AUTH")
+            .with_position(position);
+        patch_header(&path, patch).unwrap();
+
+        let patched = std::fs::read(&path).unwrap();
+        let rinex = Rinex::from_file(&path);
+        let _ = std::fs::remove_file(&path);
+
+        let (original_header, original_record) = split_header(&original);
+        let (header, record) = split_header(&patched);
+        assert_eq!(record, original_record, "record should be preserved");
+        assert_eq!(
+            header.split(|b| *b == b'\n').count(),
+            original_header.split(|b| *b == b'\n').count(),
+            "header lines should be patched in place"
+        );
+
+        let rinex = rinex.unwrap();
+        let marker = rinex.header.geodetic_marker.as_ref().unwrap();
+        assert_eq!(marker.name, "DUTH2");
+        assert_eq!(marker.number(), Some("12621M001".to_string()));
+        assert_eq!(rinex.header.agency, "AUTH");
+        assert_eq!(rinex.header.observer, "Dr. Christos Pikrida");
+        assert_eq!(rinex.header.ground_position, Some(position));
+
+        let original = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        assert_eq!(rinex.record, original.record);
+    }
+    #[test]
+    #[cfg(all(feature = "obs", feature = "flate2"))]
+    fn patch_gzip_crinex_header() {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+
+        let resource = "../test_resources/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz";
+        let path = format!("test-{}.crx.gz", random_name(8));
+        std::fs::copy(resource, &path).unwrap();
+
+        let antenna = Antenna::default()
+            .with_model("CR5200327016")
+            .with_serial_number("ASH701945E_M    NONE")
+            .with_height(0.25);
+        let patch = HeaderPatch::default()
+            .with_observer("ESBC OPERATOR")
+            .with_antenna(antenna);
+        patch_header(&path, patch).unwrap();
+
+        let mut original = Vec::new();
+        let mut decoder = GzDecoder::new(std::fs::File::open(resource).unwrap());
+        decoder.read_to_end(&mut original).unwrap();
+
+        let mut patched = Vec::new();
+        let mut decoder = GzDecoder::new(std::fs::File::open(&path).unwrap());
+        let read = decoder.read_to_end(&mut patched);
+        let _ = std::fs::remove_file(&path);
+        read.unwrap();
+
+        let (_, original_record) = split_header(&original);
+        let (header, record) = split_header(&patched);
+        assert_eq!(record, original_record, "record should be preserved");
+
+        let header = String::from_utf8_lossy(header);
+        assert!(header.contains(
+            "ESBC OPERATOR       SDFE                                    OBSERVER / AGENCY"
+        ));
+        assert!(header
+            .contains("CR5200327016        ASH701945E_M    NONE                    ANT # / TYPE"));
+        assert!(header.contains(
+            "        0.2500        0.0000        0.0000                  ANTENNA: DELTA H/E/N"
+        ));
+    }
+    #[test]
+    #[cfg(feature = "obs")]
+    fn header_only_skeleton() {
+        let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        let path = format!("test-{}.22O", random_name(8));
+        rinex.write_header_only(&path).unwrap();
+
+        let patch = HeaderPatch::default()
+            .with_observer("OBSERVER")
+            .with_agency("AGENCY");
+        patch_header(&path, patch).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let header = BufferedReader::new(&path)
+            .map(|mut reader| Header::new(&mut reader))
+            .unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(content.trim_end().ends_with("END OF HEADER"));
+        let header = header.unwrap();
+        assert_eq!(header.observer, "OBSERVER");
+        assert_eq!(header.agency, "AGENCY");
+        assert_eq!(header.geodetic_marker, rinex.header.geodetic_marker);
+        assert_eq!(
+            header.obs.map(|obs| obs.codes),
+            rinex.header.obs.map(|obs| obs.codes)
+        );
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod test {
+    use crate::formatting::{fmt_fixed, fmt_scientific};
+    use proptest::prelude::*;
+
+    /*
+     * (width, precision) of every F field produced by this crate
+     */
+    const FIELDS: [(usize, usize); 15] = [
+        (6, 1),
+        (7, 1),
+        (8, 1),
+        (8, 2),
+        (10, 2),
+        (10, 3),
+        (10, 6),
+        (12, 9),
+        (13, 3),
+        (13, 7),
+        (14, 3),
+        (14, 4),
+        (15, 12),
+        (16, 3),
+        (19, 9),
+    ];
+
+    /*
+     * Formats the value described by given number of decimal units,
+     * verifies the field width and that it is parsed back bit-exactly.
+     */
+    fn round_trip(units: i64, width: usize, precision: usize) {
+        let value = units as f64 / 10.0_f64.powi(precision as i32);
+        let formatted = fmt_fixed(value, width, precision);
+        assert_eq!(
+            formatted.len(),
+            width,
+            "F{}.{}: \"{}\"",
+            width,
+            precision,
+            formatted
+        );
+        let parsed = formatted.trim().parse::<f64>().unwrap();
+        assert_eq!(
+            parsed.to_bits(),
+            value.to_bits(),
+            "F{}.{}: {} formatted as \"{}\"",
+            width,
+            precision,
+            value,
+            formatted
+        );
+    }
+
+    /*
+     * Largest number of decimal units that fits in given field,
+     * with a possible sign, and remains well within f64 integer precision
+     */
+    fn max_units(width: usize) -> i64 {
+        let max = 10_i64.pow(width as u32 - 2) - 1;
+        max.min(1 << 49)
+    }
+
+    proptest! {
+        #[test]
+        fn fixed_width_round_trip(field in 0..FIELDS.len(), ratio in -1.0_f64..1.0) {
+            let (width, precision) = FIELDS[field];
+            let units = (ratio * max_units(width) as f64) as i64;
+            round_trip(units, width, precision);
+        }
+        #[test]
+        fn small_values_round_trip(field in 0..FIELDS.len(), units in -100_000_i64..100_000) {
+            let (width, precision) = FIELDS[field];
+            let max = max_units(width);
+            round_trip(units.clamp(-max, max), width, precision);
+        }
+    }
+
+    #[test]
+    fn fixed_width_rounding() {
+        for (value, width, precision, expected) in [
+            (0.0005, 6, 3, " 0.001"),
+            (-0.0005, 7, 3, " -0.001"),
+            (0.25, 5, 1, "  0.3"),
+            (-0.25, 5, 1, " -0.3"),
+            (2.5, 3, 0, "  3"),
+            (0.0004, 6, 3, " 0.000"),
+            (-0.0004, 6, 3, " 0.000"),
+            (-0.0, 6, 3, " 0.000"),
+            (23619095.450, 14, 3, "  23619095.450"),
+            (-0.123456789012, 15, 12, "-0.123456789012"),
+            (12345.6, 6, 1, "12345.6"),
+        ] {
+            assert_eq!(
+                fmt_fixed(value, width, precision),
+                expected,
+                "F{}.{} formatting of {}",
+                width,
+                precision,
+                value
+            );
+        }
+    }
+    #[test]
+    fn scientific_formatting() {
+        for (value, width, precision, expected) in [
+            (0.0, 20, 12, "  0.000000000000E+00"),
+            (-0.434274916279E-03, 20, 12, " -0.434274916279E-03"),
+            (0.162031620104E-10, 20, 12, "  0.162031620104E-10"),
+            (-0.123456789012E+00, 21, 12, "  -0.123456789012E+00"),
+            (0.123456789012E+05, 21, 12, "   0.123456789012E+05"),
+            (1.0, 20, 12, "  0.100000000000E+01"),
+            (-123.45, 12, 5, "-0.12345E+03"),
+            (1.0E-100, 20, 12, "  0.100000000000E-99"),
+        ] {
+            let formatted = fmt_scientific(value, width, precision);
+            assert_eq!(formatted, expected, "E{}.{} formatting", width, precision);
+            assert_eq!(formatted.trim().parse::<f64>().unwrap(), value);
+        }
+    }
+}
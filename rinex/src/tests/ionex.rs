@@ -202,4 +202,248 @@ mod test {
         //     }
         // }
     }
+    /*
+     * Synthetic 2D IONEX combining TEC, RMS and height maps:
+     * 2 epochs (2h apart), 2 latitudes, 5 longitudes.
+     * Values increase by one step per longitude, and by one unit per epoch.
+     */
+    fn combined_ionex() -> String {
+        let mut lines = Vec::<String>::new();
+        for (content, marker) in [
+            (
+                "     1.0            IONOSPHERE MAPS     GNSS",
+                "IONEX VERSION / TYPE",
+            ),
+            (
+                "RINEX-TEST          RINEX               01-JAN-24 12:00",
+                "PGM / RUN BY / DATE",
+            ),
+            ("  2024     1     1     0     0     0", "EPOCH OF FIRST MAP"),
+            ("  2024     1     1     2     0     0", "EPOCH OF LAST MAP"),
+            ("  7200", "INTERVAL"),
+            ("     2", "# OF MAPS IN FILE"),
+            ("  NONE", "MAPPING FUNCTION"),
+            ("     0.0", "ELEVATION CUTOFF"),
+            ("", "OBSERVABLES USED"),
+            ("  6371.0", "BASE RADIUS"),
+            ("     2", "MAP DIMENSION"),
+            ("   350.0 350.0   0.0", "HGT1 / HGT2 / DHGT"),
+            ("    87.5  85.0  -2.5", "LAT1 / LAT2 / DLAT"),
+            ("  -180.0 180.0  90.0", "LON1 / LON2 / DLON"),
+            ("    -1", "EXPONENT"),
+            ("", "END OF HEADER"),
+        ] {
+            lines.push(format!("{:<60}{}", content, marker));
+        }
+        // map kind, value of first longitude for each latitude, longitude step
+        for (kind, first, step) in [
+            ("TEC", [92, 90], 1),
+            ("RMS", [11, 10], 1),
+            ("HEIGHT", [3500, 3490], 10),
+        ] {
+            for (index, hour) in [(1, 0), (2, 2)] {
+                lines.push(format!(
+                    "{:<60}START OF {} MAP",
+                    format!("{:6}", index),
+                    kind
+                ));
+                lines.push(format!(
+                    "{:<60}EPOCH OF CURRENT MAP",
+                    format!("  2024     1     1{:6}     0     0", hour)
+                ));
+                for (row, lat) in [87.5_f64, 85.0_f64].iter().enumerate() {
+                    lines.push(format!(
+                        "{:<60}LAT/LON1/LON2/DLON/H",
+                        format!(
+                            "{:8.1}{:6.1}{:6.1}{:6.1}{:6.1}",
+                            lat, -180.0, 180.0, 90.0, 350.0
+                        )
+                    ));
+                    lines.push(
+                        (0..5)
+                            .map(|col| format!("{:5}", first[row] + step * col + index - 1))
+                            .collect::<String>(),
+                    );
+                }
+                lines.push(format!("{:<60}END OF {} MAP", format!("{:6}", index), kind));
+            }
+        }
+        lines.push(format!("{:<60}END OF FILE", ""));
+        lines.join("\n") + "\n"
+    }
+    #[test]
+    fn combined_tec_rms_height_maps() {
+        let rinex = Rinex::from_reader(combined_ionex().as_bytes());
+        assert!(rinex.is_ok(), "failed to parse combined IONEX");
+
+        let rinex = rinex.unwrap();
+        assert!(rinex.is_ionex_2d());
+        assert_eq!(
+            rinex.epoch().count(),
+            2,
+            "wrong amount of epochs identified"
+        );
+
+        // 2 epochs * 2 latitudes * 5 longitudes, for each stream
+        assert_eq!(rinex.tec().count(), 20, "failed to parse TEC maps");
+        assert_eq!(rinex.tec_rms().count(), 20, "failed to parse RMS maps");
+        assert_eq!(
+            rinex.tec_height().count(),
+            20,
+            "failed to parse height maps"
+        );
+
+        let t0 = Epoch::from_gregorian_utc(2024, 1, 1, 0, 0, 0, 0);
+        let t1 = Epoch::from_gregorian_utc(2024, 1, 1, 2, 0, 0, 0);
+        for (t, lat, lon, alt, tec) in rinex.tec() {
+            assert_eq!(alt, 350.0);
+            let rms = rinex
+                .tec_rms()
+                .find(|(t_i, lat_i, lon_i, _, _)| *t_i == t && *lat_i == lat && *lon_i == lon)
+                .map(|(_, _, _, _, rms)| rms)
+                .expect("missing RMS value");
+            let height = rinex
+                .tec_height()
+                .find(|(t_i, lat_i, lon_i, _, _)| *t_i == t && *lat_i == lat && *lon_i == lon)
+                .map(|(_, _, _, _, height)| height)
+                .expect("missing height value");
+            if t == t0 && lon == -180.0 && lat > 86.0 {
+                assert!((tec - 9.2).abs() < 1E-6);
+                assert!((rms - 1.1).abs() < 1E-6);
+                assert!((height - 350.0).abs() < 1E-6);
+            }
+            if t == t1 && lon == 180.0 && lat < 86.0 {
+                assert!((tec - 9.5).abs() < 1E-6);
+                assert!((rms - 1.5).abs() < 1E-6);
+                assert!((height - 353.1).abs() < 1E-6);
+            }
+        }
+    }
+    #[test]
+    fn combined_tec_planes() {
+        let rinex = Rinex::from_reader(combined_ionex().as_bytes()).unwrap();
+
+        let grid = &rinex.header.ionex.as_ref().unwrap().grid;
+        let shape = (grid.latitude.length(), grid.longitude.length());
+        assert_eq!(shape, (2, 5));
+
+        assert_eq!(rinex.tec_planes().count(), 2);
+        for (t, alt, plane) in rinex.tec_planes() {
+            assert_eq!(alt, 350.0);
+            assert_eq!(plane.shape(), shape, "plane shape should match header grid");
+            assert_eq!(plane.len(), 10);
+
+            let matrix = plane.as_matrix();
+            assert_eq!(matrix.len(), shape.0);
+            for (row, values) in matrix.iter().enumerate() {
+                assert_eq!(values.len(), shape.1);
+                for (col, value) in values.iter().enumerate() {
+                    let (lat, lon) = (plane.latitude(row), plane.longitude(col));
+                    let tec = rinex
+                        .tec()
+                        .find(|(t_i, lat_i, lon_i, _, _)| {
+                            *t_i == t && *lat_i == lat && *lon_i == lon
+                        })
+                        .map(|(_, _, _, _, tec)| tec);
+                    assert_eq!(
+                        *value, tec,
+                        "matrix and tec() disagree at ({}, {})",
+                        lat, lon
+                    );
+                    assert_eq!(plane.get(lat, lon).map(|tec| tec.tec), tec);
+                }
+            }
+            assert_eq!(plane.latitude(0), 87.5);
+            assert_eq!(plane.latitude(1), 85.0);
+            assert_eq!(plane.longitude(0), -180.0);
+            assert_eq!(plane.longitude(4), 180.0);
+            if t == Epoch::from_gregorian_utc(2024, 1, 1, 0, 0, 0, 0) {
+                assert!((matrix[0][0].unwrap() - 9.2).abs() < 1E-6);
+                assert!((matrix[1][4].unwrap() - 9.4).abs() < 1E-6);
+            }
+        }
+    }
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn v1_header_fields() {
+        use crate::header::Header;
+        use crate::ionex::BiasSource;
+
+        struct IonexHeader<'a>(&'a Header);
+        impl std::fmt::Display for IonexHeader<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                self.0.fmt_ionex(f)
+            }
+        }
+
+        let rinex = Rinex::from_file("../test_resources/IONEX/V1/jplg0010.17i.gz").unwrap();
+        let header = rinex.header.ionex.as_ref().unwrap();
+        assert_eq!(header.mapping, None);
+        assert_eq!(rinex.tec_mapping_function(), None);
+        assert_eq!(rinex.tec_elevation_cutoff(), Some(10.0));
+        assert_eq!(
+            rinex.tec_observables(),
+            Some("One-way carrier phase leveled to code")
+        );
+        assert!(!header.is_theoretical_model());
+        assert_eq!(rinex.tec_nb_stations(), Some(170));
+        assert_eq!(rinex.tec_nb_satellites(), Some(31));
+        assert_eq!(rinex.tec_base_radius(), Some(6371.0));
+        assert!(header
+            .description
+            .as_ref()
+            .unwrap()
+            .starts_with("Global Ionospheric Maps (GIM) are generated"));
+
+        let svs = rinex
+            .tec_dcbs()
+            .filter(|(src, _)| matches!(src, BiasSource::SpaceVehicle(_)))
+            .count();
+        assert_eq!(svs, 32);
+        assert!(rinex.tec_dcbs().count() > svs, "missing station DCBs");
+        assert_eq!(
+            header
+                .dcbs
+                .get(&BiasSource::SpaceVehicle(SV::new(Constellation::GPS, 1))),
+            Some(&(-7.516, 0.007))
+        );
+        assert_eq!(
+            header.dcbs.get(&BiasSource::Station("AJAC".to_string())),
+            Some(&(25.095, 0.011))
+        );
+
+        let formatted = IonexHeader(&rinex.header).to_string();
+        for expected in [
+            "  2017     1     1     0     0     0                        EPOCH OF FIRST MAP",
+            "  NONE                                                      MAPPING FUNCTION",
+            "    10.0                                                    ELEVATION CUTOFF",
+            "One-way carrier phase leveled to code                       OBSERVABLES USED",
+            "   170                                                      # OF STATIONS",
+            "    31                                                      # OF SATELLITES",
+            "  6371.0                                                    BASE RADIUS",
+            "   G01    -7.516     0.007                                  PRN / BIAS / RMS",
+            "      AJAC                    25.095     0.011              STATION / BIAS / RMS",
+        ] {
+            assert!(
+                formatted.lines().any(|line| line == expected),
+                "missing \"{}\"",
+                expected
+            );
+        }
+
+        let rinex = Rinex::from_file("../test_resources/IONEX/V1/CKMG0020.22I.gz").unwrap();
+        let header = rinex.header.ionex.as_ref().unwrap();
+        assert_eq!(rinex.tec_mapping_function(), None);
+        assert_eq!(rinex.tec_elevation_cutoff(), Some(0.0));
+        assert_eq!(rinex.tec_observables(), None);
+        assert_eq!(rinex.tec_nb_stations(), None);
+        assert_eq!(rinex.tec_nb_satellites(), None);
+        assert_eq!(rinex.tec_base_radius(), Some(6371.0));
+        assert_eq!(rinex.tec_dcbs().count(), 0);
+        assert!(header.description.is_none());
+
+        let formatted = IonexHeader(&rinex.header).to_string();
+        assert!(!formatted.contains("# OF STATIONS"));
+        assert!(!formatted.contains("START OF AUX DATA"));
+    }
 }
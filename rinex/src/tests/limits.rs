@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod test {
+    use crate::observation::ObservationStream;
+    use crate::prelude::*;
+    use crate::reader::Limit;
+    use crate::tests::toolkit::random_name;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::{BufReader, Read, Write};
+    use std::path::Path;
+
+    /*
+     * Generates a gzip bomb: valid header followed by
+     * the same epoch repeated over and over.
+     */
+    fn gzip_bomb(nb_epochs: usize) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("OBS")
+            .join("V2")
+            .join("aopr0010.17o");
+        let content = std::fs::read_to_string(path).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        let (header, record) = lines.split_at(19);
+        let epoch = record[..11].join("\n") + "\n";
+
+        let tmp_path = format!("test-{}.rnx.gz", random_name(5));
+        let fd = std::fs::File::create(&tmp_path).unwrap();
+        let mut encoder = GzEncoder::new(fd, Compression::best());
+        writeln!(encoder, "{}", header.join("\n")).unwrap();
+        for _ in 0..nb_epochs {
+            encoder.write_all(epoch.as_bytes()).unwrap();
+        }
+        encoder.finish().unwrap();
+        tmp_path
+    }
+
+    #[test]
+    fn decompressed_bytes_limit() {
+        // about 17 MB once decompressed
+        let path = gzip_bomb(20_000);
+        let compressed_size = std::fs::metadata(&path).unwrap().len();
+        assert!(compressed_size < 100 * 1024, "bomb is not small enough");
+
+        let options = ParsingOptions::default().with_max_decompressed_bytes(1024 * 1024);
+        let rinex = Rinex::from_file_with_options(&path, options);
+        let _ = std::fs::remove_file(&path);
+
+        match rinex {
+            Err(Error::LimitExceeded { which, limit }) => {
+                assert_eq!(which, Limit::DecompressedBytes);
+                assert_eq!(limit, 1024 * 1024);
+            },
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("decompressed bytes limit should have been exceeded"),
+        }
+    }
+
+    #[test]
+    fn epochs_limit() {
+        let path = gzip_bomb(1000);
+        let options = ParsingOptions::default().with_max_epochs(100);
+        let rinex = Rinex::from_file_with_options(&path, options);
+
+        match rinex {
+            Err(Error::LimitExceeded { which, limit }) => {
+                assert_eq!(which, Limit::Epochs);
+                assert_eq!(limit, 100);
+            },
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("epochs limit should have been exceeded"),
+        }
+
+        // default options are generous
+        let rinex = Rinex::from_file(&path);
+        let _ = std::fs::remove_file(&path);
+        assert!(rinex.is_ok());
+    }
+
+    #[test]
+    fn streamed_epochs_limit() {
+        let path = gzip_bomb(1000);
+        let options = ParsingOptions::default().with_max_epochs(100);
+        let stream = ObservationStream::from_file_with_options(&path, options).unwrap();
+        let entries = stream.collect::<Vec<_>>();
+        let _ = std::fs::remove_file(&path);
+
+        // epochs that fit within the limit are all yielded, then the error
+        assert_eq!(entries.len(), 101);
+        assert!(entries[..100].iter().all(|entry| entry.is_ok()));
+        match &entries[100] {
+            Err(Error::LimitExceeded { which, limit }) => {
+                assert_eq!(*which, Limit::Epochs);
+                assert_eq!(*limit, 100);
+            },
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("epochs limit should have been exceeded"),
+        }
+    }
+
+    #[test]
+    fn line_length_limit() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("OBS")
+            .join("V2")
+            .join("aopr0010.17o");
+        let content = std::fs::read_to_string(path).unwrap();
+
+        let tmp_path = format!("test-{}.rnx.gz", random_name(5));
+        let fd = std::fs::File::create(&tmp_path).unwrap();
+        let mut encoder = GzEncoder::new(fd, Compression::best());
+        encoder.write_all(content.as_bytes()).unwrap();
+        encoder.write_all(&vec![b'A'; 1024 * 1024]).unwrap();
+        encoder.finish().unwrap();
+
+        let rinex = Rinex::from_file_with_options(&tmp_path, ParsingOptions::default());
+        let _ = std::fs::remove_file(&tmp_path);
+
+        match rinex {
+            Err(Error::LimitExceeded { which, .. }) => {
+                assert_eq!(which, Limit::LineLength);
+            },
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("line length limit should have been exceeded"),
+        }
+    }
+    #[test]
+    fn line_length_limit_bounds_memory() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("OBS")
+            .join("V2")
+            .join("aopr0010.17o");
+        let content = std::fs::read(path).unwrap();
+
+        // valid content, followed by a line that never terminates:
+        // the limit must be enforced while reading, or this never returns
+        let reader = BufReader::new(content.as_slice().chain(std::io::repeat(b'A')));
+        let options = ParsingOptions::default().with_max_line_length(1024);
+
+        match Rinex::from_reader_with_options(reader, options) {
+            Err(Error::LimitExceeded { which, limit }) => {
+                assert_eq!(which, Limit::LineLength);
+                assert_eq!(limit, 1024);
+            },
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("line length limit should have been exceeded"),
+        }
+
+        // limit applies to the content, not the line termination
+        let reader = BufReader::new(content.as_slice());
+        let options = ParsingOptions::default().with_max_line_length(80);
+        assert!(Rinex::from_reader_with_options(reader, options).is_ok());
+    }
+}
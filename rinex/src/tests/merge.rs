@@ -98,6 +98,113 @@ mod test {
         let _ = std::fs::remove_file("merge.txt");
     }
     #[test]
+    #[cfg(feature = "nav")]
+    fn merge_nav_identical_frames() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("AMEL00NLD_R_20210010000_01D_MN.rnx");
+        let rinex = Rinex::from_file(&path.to_string_lossy()).unwrap();
+        let record = rinex.record.as_nav().unwrap();
+
+        let (t, frames) = record
+            .iter()
+            .find(|(_, frames)| frames.iter().any(|fr| fr.as_eph().is_some()))
+            .unwrap();
+        let identical = frames.iter().find(|fr| fr.as_eph().is_some()).unwrap();
+
+        // same SV, same ToE, different content
+        let mut different = identical.clone();
+        let (_, _, eph) = different.as_mut_eph().unwrap();
+        eph.clock_bias += 1.0E-9;
+        assert_eq!(identical.content_hash(), identical.clone().content_hash());
+        assert_ne!(identical.content_hash(), different.content_hash());
+
+        let mut rhs = rinex.clone();
+        let rhs_record = rhs.record.as_mut_nav().unwrap();
+        rhs_record.clear();
+        rhs_record.insert(*t, vec![identical.clone(), different.clone()]);
+
+        let merged = rinex.merge(&rhs).unwrap();
+        let merged_record = merged.record.as_nav().unwrap();
+        assert_eq!(merged_record.len(), record.len());
+
+        let merged_frames = merged_record.get(t).unwrap();
+        assert_eq!(merged_frames.len(), frames.len() + 1);
+        assert_eq!(
+            merged_frames.iter().filter(|fr| *fr == identical).count(),
+            1,
+            "identical frames should collapse"
+        );
+        assert!(merged_frames.contains(&different));
+    }
+    #[test]
+    fn merge_boundaries() {
+        let test_resources = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources");
+
+        // teqc / gfzrnx style comments
+        let path = test_resources
+            .clone()
+            .join("OBS")
+            .join("V2")
+            .join("barq071q_merged.19o");
+        let rinex = Rinex::from_file(&path.to_string_lossy()).unwrap();
+        assert!(rinex.is_merged());
+        assert_eq!(rinex.merged_file_count(), 3);
+        assert_eq!(
+            rinex.merge_boundaries(),
+            vec![
+                Epoch::from_str("2019-03-12T12:00:00 GPST").unwrap(),
+                Epoch::from_str("2019-03-12T18:00:00 UTC").unwrap(),
+            ]
+        );
+
+        // unmerged file
+        let path = test_resources
+            .clone()
+            .join("OBS")
+            .join("V2")
+            .join("barq071q.19o");
+        let rinex = Rinex::from_file(&path.to_string_lossy()).unwrap();
+        assert!(!rinex.is_merged());
+        assert_eq!(rinex.merged_file_count(), 1);
+        assert!(rinex.merge_boundaries().is_empty());
+
+        // round trip through our own merge
+        let path_a = test_resources
+            .clone()
+            .join("NAV")
+            .join("V3")
+            .join("AMEL00NLD_R_20210010000_01D_MN.rnx");
+        let path_b = test_resources
+            .clone()
+            .join("NAV")
+            .join("V3")
+            .join("CBW100NLD_R_20210010000_01D_MN.rnx");
+        let rnx_a = Rinex::from_file(&path_a.to_string_lossy()).unwrap();
+        let rnx_b = Rinex::from_file(&path_b.to_string_lossy()).unwrap();
+
+        let merged = rnx_a.merge(&rnx_b).unwrap();
+        let boundary = rnx_b.first_epoch().unwrap();
+        assert_eq!(merged.merged_file_count(), 2);
+        assert_eq!(merged.merge_boundaries(), vec![boundary]);
+
+        let tmp_path = "merge-boundaries.txt";
+        assert!(merged.to_file(tmp_path).is_ok());
+        let parsed = Rinex::from_file(tmp_path);
+        let _ = std::fs::remove_file(tmp_path);
+
+        let parsed = parsed.unwrap();
+        assert_eq!(parsed.merged_file_count(), 2);
+        assert_eq!(parsed.merge_boundaries(), vec![boundary]);
+    }
+    #[test]
     #[ignore]
     fn merge_obs() {
         let test_resources = PathBuf::new()
@@ -209,4 +316,178 @@ mod test {
             assert_eq!(apc.unwrap(), expected_apc);
         }
     }
+    #[test]
+    fn merge_obs_overlapping_epochs() {
+        use crate::observation::{ObservationData, Record};
+        use std::collections::{BTreeMap, HashMap};
+
+        let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+        let t1 = Epoch::from_str("2021-01-01T00:00:30 GPST").unwrap();
+        let g01 = SV::from_str("G01").unwrap();
+        let g02 = SV::from_str("G02").unwrap();
+        let c1c = Observable::from_str("C1C").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+
+        let entry = |observations: &[(SV, &Observable, f64)]| {
+            let mut vehicles = BTreeMap::<SV, HashMap<Observable, ObservationData>>::new();
+            for (sv, observable, value) in observations {
+                vehicles.entry(*sv).or_default().insert(
+                    (*observable).clone(),
+                    ObservationData::new(*value, None, None),
+                );
+            }
+            vehicles
+        };
+
+        let mut lhs = Record::new();
+        lhs.insert(
+            (t0, EpochFlag::Ok),
+            (None, entry(&[(g01, &c1c, 1.0), (g01, &l1c, 2.0)])),
+        );
+
+        let mut rhs = Record::new();
+        rhs.insert(
+            (t0, EpochFlag::Ok),
+            (
+                Some(1.0E-6),
+                entry(&[(g01, &c1c, 10.0), (g02, &c1c, 3.0), (g02, &l1c, 4.0)]),
+            ),
+        );
+        rhs.insert((t1, EpochFlag::Ok), (None, entry(&[(g01, &c1c, 5.0)])));
+
+        lhs.merge_mut(&rhs).unwrap();
+
+        let expected = BTreeMap::from([
+            (
+                (t0, EpochFlag::Ok),
+                (
+                    Some(1.0E-6),
+                    entry(&[
+                        (g01, &c1c, 1.0), // existing value is preserved
+                        (g01, &l1c, 2.0),
+                        (g02, &c1c, 3.0),
+                        (g02, &l1c, 4.0),
+                    ]),
+                ),
+            ),
+            ((t1, EpochFlag::Ok), (None, entry(&[(g01, &c1c, 5.0)]))),
+        ]);
+        assert_eq!(lhs, expected);
+    }
+    #[test]
+    #[cfg(feature = "obs")]
+    fn merge_obs_overlapping_files() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("OBS")
+            .join("V3")
+            .join("DUTH0630.22O");
+        let rinex = Rinex::from_file(&path.to_string_lossy()).unwrap();
+        let boundary = rinex.epoch().nth(3).unwrap();
+        let c1c = Observable::from_str("C1C").unwrap();
+
+        // lhs: up to boundary, where only C1C is kept
+        let mut lhs = rinex.clone();
+        let record = lhs.record.as_mut_obs().unwrap();
+        record.retain(|(t, _), _| *t <= boundary);
+        for ((t, _), (_, vehicles)) in record.iter_mut() {
+            if *t == boundary {
+                for (_, observations) in vehicles.iter_mut() {
+                    observations.retain(|observable, _| *observable == c1c);
+                }
+            }
+        }
+
+        // rhs: from boundary, where C1C is missing
+        let mut rhs = rinex.clone();
+        let record = rhs.record.as_mut_obs().unwrap();
+        record.retain(|(t, _), _| *t >= boundary);
+        for ((t, _), (_, vehicles)) in record.iter_mut() {
+            if *t == boundary {
+                for (_, observations) in vehicles.iter_mut() {
+                    observations.retain(|observable, _| *observable != c1c);
+                }
+            }
+        }
+
+        assert!(lhs.merge_conflicts(&rhs).is_empty());
+
+        // union of all observations
+        let merged = lhs.merge(&rhs).unwrap();
+        assert_eq!(merged.record, rinex.record);
+
+        // conflicting values: lhs is preserved, conflicts are reported
+        let g01 = SV::from_str("G01").unwrap();
+        let mut rhs = rinex.clone();
+        let record = rhs.record.as_mut_obs().unwrap();
+        record.retain(|(t, _), _| *t >= boundary);
+        let (key, (_, vehicles)) = record.iter_mut().next().unwrap();
+        let key = *key;
+        vehicles.get_mut(&g01).unwrap().get_mut(&c1c).unwrap().obs += 1.0;
+
+        let conflicts = lhs.merge_conflicts(&rhs);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].key, key);
+        assert_eq!(conflicts[0].sv, g01);
+        assert_eq!(conflicts[0].observable, c1c);
+        assert_eq!(conflicts[0].rhs.obs, conflicts[0].lhs.obs + 1.0);
+
+        let merged = lhs.merge(&rhs).unwrap();
+        assert_eq!(merged.record, rinex.record);
+    }
+    #[test]
+    #[cfg(feature = "obs")]
+    fn merge_obs_overlapping_vehicles() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("OBS")
+            .join("V3")
+            .join("DUTH0630.22O");
+        let rinex = Rinex::from_file(&path.to_string_lossy()).unwrap();
+        let boundary = rinex.epoch().nth(1).unwrap();
+
+        // lhs: up to boundary, where only GPS is kept
+        let mut lhs = rinex.clone();
+        let record = lhs.record.as_mut_obs().unwrap();
+        record.retain(|(t, _), _| *t <= boundary);
+        for ((t, _), (_, vehicles)) in record.iter_mut() {
+            if *t == boundary {
+                vehicles.retain(|sv, _| sv.constellation == Constellation::GPS);
+            }
+        }
+
+        // rhs: from boundary, where GPS is missing
+        let mut rhs = rinex.clone();
+        let record = rhs.record.as_mut_obs().unwrap();
+        record.retain(|(t, _), _| *t >= boundary);
+        for ((t, _), (_, vehicles)) in record.iter_mut() {
+            if *t == boundary {
+                vehicles.retain(|sv, _| sv.constellation != Constellation::GPS);
+            }
+        }
+
+        // both sides do contribute to the shared epoch
+        let shared = |rinex: &Rinex| {
+            rinex
+                .record
+                .as_obs()
+                .unwrap()
+                .iter()
+                .find(|((t, _), _)| *t == boundary)
+                .map(|(_, (_, vehicles))| vehicles.len())
+                .unwrap()
+        };
+        assert!(shared(&lhs) > 0);
+        assert!(shared(&rhs) > 0);
+        assert_eq!(shared(&lhs) + shared(&rhs), shared(&rinex));
+
+        assert!(lhs.merge_conflicts(&rhs).is_empty());
+
+        let merged = lhs.merge(&rhs).unwrap();
+        assert_eq!(merged.record, rinex.record);
+    }
 }
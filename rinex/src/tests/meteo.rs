@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod test {
     use crate::prelude::*;
-    use crate::tests::toolkit::test_meteo_rinex;
+    use crate::tests::toolkit::{is_null_rinex, test_meteo_rinex};
     use crate::{erratic_time_frame, evenly_spaced_time_frame, tests::toolkit::TestTimeFrame};
     use itertools::Itertools;
     use std::str::FromStr;
@@ -285,4 +285,112 @@ mod test {
             }
         }
     }
+    #[test]
+    fn meteo_substract() {
+        let rinex = Rinex::from_file("../test_resources/MET/V2/abvi0010.15m").unwrap();
+        let null = rinex.substract(&rinex);
+        assert_eq!(null.epoch().count(), rinex.epoch().count());
+        is_null_rinex(&null, 0.0);
+
+        // data that the reference does not describe is dropped
+        let t0 = rinex.first_epoch().unwrap();
+        let mut reference = rinex.clone();
+        let record = reference.record.as_mut_meteo().unwrap();
+        record.remove(&t0);
+        for (_, observations) in record.iter_mut() {
+            observations.retain(|observable, _| *observable != Observable::Temperature);
+        }
+        let diff = rinex.substract(&reference);
+        assert_eq!(diff.epoch().count(), rinex.epoch().count() - 1);
+        assert_ne!(diff.first_epoch(), Some(t0));
+        for (_, observations) in diff.meteo() {
+            assert!(!observations.contains_key(&Observable::Temperature));
+            assert!(observations.values().all(|value| *value == 0.0));
+        }
+    }
+    /*
+     * Meteo RINEX V2 example of the RINEX 2.11 specifications:
+     * pressure and temperature sensors are located, humidity sensor is not.
+     */
+    fn located_sensors_meteo() -> String {
+        let mut lines = Vec::<String>::new();
+        for (content, marker) in [
+            (
+                "     2.11           METEOROLOGICAL DATA",
+                "RINEX VERSION / TYPE",
+            ),
+            (
+                "XXRINEXM V9.9       AIUB                 3-APR-96 00:10",
+                "PGM / RUN BY / DATE",
+            ),
+            ("ZIMM", "MARKER NAME"),
+            ("     3    PR    TD    HR", "# / TYPES OF OBSERV"),
+            (
+                "PAROSCIENTIFIC      740-16B                       0.2    PR",
+                "SENSOR MOD/TYPE/ACC",
+            ),
+            (
+                "HAENNI                                            0.1    TD",
+                "SENSOR MOD/TYPE/ACC",
+            ),
+            (
+                "ROTRONIC            I-240W                        5.0    HR",
+                "SENSOR MOD/TYPE/ACC",
+            ),
+            (
+                "  4331297.3480   567555.6390  4633133.7280      200.3000 PR",
+                "SENSOR POS XYZ/H",
+            ),
+            (
+                "  4331297.3480   567555.6390  4633133.7280      201.1000 TD",
+                "SENSOR POS XYZ/H",
+            ),
+            ("", "END OF HEADER"),
+        ] {
+            lines.push(format!("{:<60}{}", content, marker));
+        }
+        lines.push(" 96  4  1  0  0 15  987.1   10.6   89.5".to_string());
+        lines.push(" 96  4  1  0  0 30  987.2   10.9   90.0".to_string());
+        lines.push(" 96  4  1  0  0 45  987.1   11.6   89.0".to_string());
+        lines.join("\n")
+    }
+    #[test]
+    fn v2_located_sensors() {
+        let rinex = Rinex::from_reader(located_sensors_meteo().as_bytes()).unwrap();
+        let sensors = rinex.meteo_sensors().collect::<Vec<_>>();
+        assert_eq!(sensors.len(), 3);
+
+        let pressure = sensors[0];
+        assert_eq!(pressure.observable, Observable::Pressure);
+        assert_eq!(pressure.model, Some("PAROSCIENTIFIC".to_string()));
+        assert_eq!(pressure.sensor_type, Some("740-16B".to_string()));
+        assert_eq!(pressure.accuracy, Some(0.2));
+        assert_eq!(pressure.height, Some(200.3));
+        let position = pressure
+            .position
+            .expect("pressure sensor should be located");
+        assert_eq!(
+            position.to_ecef_wgs84(),
+            (4331297.348, 567555.639, 4633133.728)
+        );
+        let (lat, lon, _) = position.to_geodetic();
+        assert!((lat - 46.877).abs() < 1.0E-3, "bad latitude {}", lat);
+        assert!((lon - 7.465).abs() < 1.0E-3, "bad longitude {}", lon);
+
+        let temperature = sensors[1];
+        assert_eq!(temperature.observable, Observable::Temperature);
+        assert_eq!(temperature.model, Some("HAENNI".to_string()));
+        assert_eq!(temperature.sensor_type, None);
+        assert_eq!(temperature.accuracy, Some(0.1));
+        assert_eq!(temperature.height, Some(201.1));
+        assert_eq!(temperature.position, pressure.position);
+
+        let humidity = sensors[2];
+        assert_eq!(humidity.observable, Observable::HumidityRate);
+        assert_eq!(humidity.model, Some("ROTRONIC".to_string()));
+        assert_eq!(humidity.sensor_type, Some("I-240W".to_string()));
+        assert_eq!(humidity.accuracy, Some(5.0));
+        assert_eq!(humidity.position, None);
+        assert_eq!(humidity.height, None);
+    }
 }
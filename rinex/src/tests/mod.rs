@@ -5,9 +5,16 @@ mod antex;
 mod compression;
 mod decompression;
 mod filename;
+mod fileops;
+mod formatting;
 mod merge;
 mod parsing;
 mod production;
+mod progress;
+mod split;
+
+#[cfg(all(feature = "binex", feature = "nav"))]
+mod binex;
 
 #[cfg(feature = "clock")]
 mod clock;
@@ -15,9 +22,15 @@ mod clock;
 #[cfg(feature = "processing")]
 mod processing;
 
+#[cfg(all(feature = "obs", feature = "nav", feature = "flate2"))]
+mod dd;
+
 #[cfg(feature = "doris")]
 mod doris;
 
+#[cfg(feature = "flate2")]
+mod limits;
+
 #[cfg(feature = "ionex")]
 mod ionex;
 
@@ -5,6 +5,9 @@ mod test {
     use crate::prelude::*;
     use crate::tests::toolkit::nav::check_klobuchar_models;
     use crate::tests::toolkit::nav::check_nequick_g_models;
+    use crate::tests::toolkit::nav::{earth_cef, elevation_deg};
+    use crate::tests::toolkit::random_name;
+    use crate::version::Version;
     use gnss_rs::prelude::SV;
     use gnss_rs::sv;
     use hifitime::Unit;
@@ -1475,6 +1478,39 @@ mod test {
         }
     }
     #[test]
+    #[cfg(feature = "flate2")]
+    fn v4_nequick_g_bdgim_corr() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V4")
+            .join("BRD400DLR_S_20230710000_01D_MN.rnx.gz");
+        let rinex = Rinex::from_file(&path.to_string_lossy()).unwrap();
+
+        let (lat_ddeg, lon_ddeg) = (50.0, 10.0);
+        let azim = 60.0_f64.to_radians();
+        let low = 10.0_f64.to_radians();
+
+        let (_, ng) = rinex.nequick_g_models().next().unwrap();
+        assert!((ng.effective_ionisation_level(0.0) - ng.a.0).abs() < 1.0E-9);
+        assert_eq!(NgModel::default().effective_ionisation_level(45.0), 63.7);
+        assert!(rinex.bdgim_models().count() > 0);
+
+        // NeQuick-G and BDGIM models are not supported:
+        // the nearest Klobuchar model is used
+        let (t, _, _) = rinex.klobuchar_models().next().unwrap();
+        let t = t + 1.0 * Unit::Hour;
+        let corr = rinex.ionod_correction(t, low, azim, lat_ddeg, lon_ddeg, Carrier::L1);
+        assert!(corr.is_some());
+        assert!(corr.unwrap() > 0.0);
+
+        let t = Epoch::from_gregorian_utc_at_midnight(2023, 3, 10);
+        let corr = rinex.ionod_correction(t, low, azim, lat_ddeg, lon_ddeg, Carrier::L1);
+        assert!(corr.is_none(), "no model published prior that date");
+    }
+    #[test]
     #[cfg(feature = "nav")]
     fn v2_iono_alphabeta_and_toe() {
         let path = PathBuf::new()
@@ -1679,6 +1715,155 @@ mod test {
             }
         }
     }
+    #[test]
+    #[cfg(feature = "nav")]
+    fn nav_record_builder() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("CBW100NLD_R_20210010000_01D_MN.rnx");
+        let rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        let (toc, (msg, sv, eph)) = rinex
+            .ephemeris()
+            .find(|(_, (_, sv, _))| sv.constellation == Constellation::GPS)
+            .expect("missing GPS ephemeris");
+
+        let built = NavigationRecordBuilder::new(Version::new(3, 4))
+            .with_ephemeris(*toc, msg, sv, eph.clone())
+            .build();
+
+        assert!(built.is_navigation_rinex());
+        assert_eq!(built.header.constellation, Some(Constellation::GPS));
+        assert_eq!(built.ephemeris().count(), 1);
+
+        let tmp_path = format!("test-{}.rnx", random_name(5));
+        assert!(built.to_file(&tmp_path).is_ok());
+
+        let parsed = Rinex::from_file(&tmp_path);
+        let _ = std::fs::remove_file(&tmp_path);
+        let parsed = parsed.unwrap();
+
+        let (parsed_toc, (parsed_msg, parsed_sv, parsed_eph)) = parsed
+            .ephemeris()
+            .next()
+            .expect("failed to parse built NAV record");
+
+        assert_eq!(parsed.ephemeris().count(), 1);
+        assert_eq!(parsed_toc, toc);
+        assert_eq!(parsed_msg, msg);
+        assert_eq!(parsed_sv, sv);
+        assert!((parsed_eph.clock_bias - eph.clock_bias).abs() < 1.0E-12);
+        for key in ["sqrta", "e", "i0", "omega", "omega0", "m0", "toe"] {
+            let (value, expected) = (
+                parsed_eph.get_orbit_f64(key).unwrap(),
+                eph.get_orbit_f64(key).unwrap(),
+            );
+            assert!(
+                (value - expected).abs() < 1.0E-9 * expected.abs().max(1.0),
+                "reparsed \"{}\" differs: {} vs {}",
+                key,
+                value,
+                expected
+            );
+        }
+    }
+    #[test]
+    #[cfg(all(feature = "nav", feature = "flate2"))]
+    fn v3_hert00gbr_sv_speed() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("HERT00GBR_R_20240920000_01D_GN.rnx.gz");
+        let rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        let g05 = sv!("G05");
+
+        let positions = rinex
+            .sv_position()
+            .filter_map(|(t, sv, _)| if sv == g05 { Some(t) } else { None })
+            .unique()
+            .collect::<Vec<_>>();
+        assert!(
+            positions.len() > 1,
+            "test file should contain several G05 ephemerides"
+        );
+
+        // one velocity per pair of positions, assigned to the later epoch
+        let speed = rinex
+            .sv_speed()
+            .filter_map(|(t, sv, _)| if sv == g05 { Some(t) } else { None })
+            .collect::<Vec<_>>();
+        assert_eq!(speed, positions[1..]);
+
+        for (t, sv, (vx, vy, vz)) in rinex.sv_speed() {
+            let norm = (vx.powi(2) + vy.powi(2) + vz.powi(2)).sqrt();
+            assert!(
+                norm < 6.0,
+                "{}({}): unrealistic velocity {} km/s",
+                t,
+                sv,
+                norm
+            );
+        }
+        for (t, sv, (vx, vy, vz)) in rinex.sv_speed_kepler() {
+            let norm = (vx.powi(2) + vy.powi(2) + vz.powi(2)).sqrt();
+            assert!(
+                norm > 1.0 && norm < 6.0,
+                "{}({}): unrealistic velocity {} km/s",
+                t,
+                sv,
+                norm
+            );
+        }
+    }
+    #[test]
+    #[cfg(all(feature = "nav", feature = "flate2"))]
+    fn v3_hert00gbr_orbital_planes() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("HERT00GBR_R_20240920000_01D_GN.rnx.gz");
+        let rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        // one plane description per SV
+        let mut raans = Vec::<f64>::new();
+        for sv in rinex.sv() {
+            let (_, (_, _, eph)) = rinex
+                .ephemeris()
+                .find(|(_, (_, sv_i, _))| *sv_i == sv)
+                .unwrap();
+            let (inclination, raan) = eph.orbital_plane().unwrap();
+            assert!(
+                (inclination - 55.0).abs() < 5.0,
+                "{}: unexpected inclination {}°",
+                sv,
+                inclination
+            );
+            assert!((0.0..360.0).contains(&raan));
+            raans.push(raan);
+        }
+        raans.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        // GPS planes are 60° apart: count the gaps between clusters
+        let mut planes = raans.windows(2).filter(|w| w[1] - w[0] > 20.0).count();
+        if raans[0] + 360.0 - raans[raans.len() - 1] > 20.0 {
+            planes += 1;
+        }
+        assert_eq!(
+            planes, 6,
+            "GPS vehicles should be grouped in 6 orbital planes"
+        );
+    }
     // Computes TOE in said timescale
     fn toe_helper(week: f64, week_s: f64, ts: TimeScale) -> Epoch {
         if ts == TimeScale::GST {
@@ -1687,4 +1872,826 @@ mod test {
             Epoch::from_duration(week * Unit::Week + week_s * Unit::Second, ts)
         }
     }
+    #[test]
+    fn nav_frame_count() {
+        let mut files = vec![
+            "NAV/V2/amel0010.21g",
+            "NAV/V2/dlf10010.21g",
+            "NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx",
+            "NAV/V3/CBW100NLD_R_20210010000_01D_MN.rnx",
+        ];
+        if cfg!(feature = "flate2") {
+            files.push("NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz");
+            files.push("NAV/V4/KMS300DNK_R_20221591000_01H_MN.rnx.gz");
+        }
+        for file in files {
+            let path = format!("{}/../test_resources/{}", env!("CARGO_MANIFEST_DIR"), file);
+            let rinex = Rinex::from_file(&path).unwrap();
+            let expected = rinex
+                .navigation()
+                .map(|(_, frames)| frames.len())
+                .sum::<usize>();
+            assert!(expected > 0);
+            let count = Rinex::nav_frame_count(&path).unwrap();
+            assert_eq!(count, expected, "{}: bad frame count", file);
+
+            // limit: each frame counts as one epoch
+            let options = ParsingOptions::default().with_max_epochs(expected - 1);
+            assert!(Rinex::nav_frame_count_with_options(&path, options).is_err());
+        }
+
+        // not a NAV file
+        let path = format!(
+            "{}/../test_resources/OBS/V3/DUTH0630.22O",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        assert!(Rinex::nav_frame_count(&path).is_err());
+    }
+    #[test]
+    #[cfg(all(feature = "nav", feature = "flate2"))]
+    fn v3_esbc00dnk_sv_speed() {
+        use std::collections::HashMap;
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("ESBC00DNK_R_20201770000_01D_MN.rnx.gz");
+        let rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        let mut positions = HashMap::<SV, Vec<Epoch>>::new();
+        for (t, sv, _) in rinex.sv_position() {
+            if sv.constellation == Constellation::GPS {
+                positions.entry(sv).or_default().push(t);
+            }
+        }
+
+        let mut tested = 0;
+        for (t, sv, (vx, vy, vz)) in rinex.sv_speed() {
+            if sv.constellation != Constellation::GPS {
+                continue;
+            }
+            let epochs = &positions[&sv];
+            let previous = epochs
+                .iter()
+                .filter(|t_i| **t_i < t)
+                .max()
+                .expect("velocity should be assigned to the later epoch");
+            // finite differences are only meaningful over short intervals.
+            // GPS vehicles move at about 3.9 km/s, Earth rotation
+            // reduces that to roughly 2.7 to 3.2 km/s in ECEF
+            if t - *previous > Duration::from_hours(2.0) {
+                continue;
+            }
+            let norm = (vx.powi(2) + vy.powi(2) + vz.powi(2)).sqrt();
+            assert!(
+                norm > 2.0 && norm < 4.0,
+                "{}({}): unrealistic MEO velocity {} km/s",
+                t,
+                sv,
+                norm
+            );
+            tested += 1;
+        }
+        assert!(tested > 0);
+    }
+    #[test]
+    #[cfg(all(feature = "nav", feature = "flate2"))]
+    fn v4_brd400dlr_ecef_to_eci() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V4")
+            .join("BRD400DLR_S_20230710000_01D_MN.rnx.gz");
+        let rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        // closest EOP message: J04 2023-03-12T12:00:00
+        let t = Epoch::from_str("2023-03-12T12:20:00 GPST").unwrap();
+        let rotation = rinex.ecef_to_eci(t).unwrap();
+
+        // reference computed offline: GMST (IAU-82) 354.8178821747502°,
+        // xp=-0.0406754 arcsec, yp=0.3511989 arcsec, UT1-UTC=-0.0193862s
+        let expected = [
+            [0.995912636796, 0.090321757452, 0.000000350181],
+            [-0.090321757453, 0.995912636795, 0.000001677890],
+            [-0.000000197200, -0.000001702660, 0.999999999999],
+        ];
+        for (i, row) in expected.iter().enumerate() {
+            for (j, expected) in row.iter().enumerate() {
+                assert!(
+                    (rotation[(i, j)] - expected).abs() < 1.0E-9,
+                    "({}, {}): {} != {}",
+                    i,
+                    j,
+                    rotation[(i, j)],
+                    expected
+                );
+            }
+        }
+
+        // inverse
+        let inverse = rinex.eci_to_ecef(t).unwrap();
+        let identity = rotation * inverse;
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((identity[(i, j)] - expected).abs() < 1.0E-12);
+            }
+        }
+
+        let (x, y, z) = rinex
+            .rotate_position(t, (15_000.0, -20_000.0, 5_000.0))
+            .unwrap();
+        assert!((x - 13132.256153807417).abs() < 1.0E-5);
+        assert!((y - -21273.070708240797).abs() < 1.0E-5);
+        assert!((z - 5000.031095200763).abs() < 1.0E-5);
+
+        // no EOP messages
+        let rinex = Rinex::from_file("../test_resources/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx")
+            .unwrap();
+        assert!(rinex.ecef_to_eci(t).is_none());
+    }
+    #[test]
+    #[cfg(feature = "nav")]
+    fn v3_cbw100nld_sv_clock_interpolate() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("CBW100NLD_R_20210010000_01D_MN.rnx");
+        let rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        let g20 = sv!("G20");
+        let toc = Epoch::from_str("2021-01-01T16:00:00 GPST").unwrap();
+
+        let (_, _, (a0, a1, a2)) = rinex
+            .sv_clock()
+            .find(|(t, sv, _)| *t == toc && *sv == g20)
+            .unwrap();
+        assert_eq!(a0, 5.253581330180e-04);
+
+        // at toc: the ephemeris is not considered valid yet,
+        // but its clock is still evaluated
+        let (_, (_, _, eph)) = rinex
+            .ephemeris()
+            .find(|(t, (_, sv, _))| **t == toc && *sv == g20)
+            .unwrap();
+        assert!(!eph.is_valid(g20, toc));
+        assert!(eph.is_valid(g20, toc + Duration::from_seconds(1.0)));
+        assert_eq!(rinex.sv_clock_interpolate(toc, g20), Some(a0));
+
+        // within validity period
+        let dt = Duration::from_hours(1.0);
+        let offset = rinex.sv_clock_interpolate(toc + dt, g20).unwrap();
+        let expected = a0 + a1 * 3600.0 + a2 * 3600.0_f64.powi(2);
+        assert!((offset - expected).abs() < 1.0E-15);
+
+        // prior toc, or past validity period
+        assert!(rinex.sv_clock_interpolate(toc - dt, g20).is_none());
+        assert!(rinex
+            .sv_clock_interpolate(toc + Duration::from_hours(3.0), g20)
+            .is_none());
+
+        // not described
+        assert!(rinex.sv_clock_interpolate(toc, sv!("G01")).is_none());
+    }
+    #[test]
+    #[cfg(feature = "nav")]
+    #[cfg(feature = "flate2")]
+    fn v3_nya100nor_ephemeris_coverage() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("NYA100NOR_S_20241240000_01D_GN.rnx.gz");
+        let rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        let coverage = rinex.ephemeris_coverage();
+        for spans in coverage.values() {
+            assert!(!spans.is_empty());
+            for (start, end) in spans {
+                assert!(end > start);
+            }
+            for pair in spans.windows(2) {
+                assert!(pair[1].0 > pair[0].1, "spans should have been merged");
+            }
+        }
+
+        // G20: coverage holes from 04:00 to 08:00 and from 14:00 to 20:00
+        let g20 = coverage.get(&sv!("G20")).unwrap();
+        let expected = [
+            ("2024-05-03T02:00:00 GPST", "2024-05-03T04:00:00 GPST"),
+            ("2024-05-03T08:00:00 GPST", "2024-05-03T14:00:00 GPST"),
+            ("2024-05-03T20:00:00 GPST", "2024-05-04T02:00:00 GPST"),
+        ]
+        .iter()
+        .map(|(start, end)| {
+            (
+                Epoch::from_str(start).unwrap(),
+                Epoch::from_str(end).unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+        assert_eq!(g20, &expected);
+
+        let t = Epoch::from_str("2024-05-03T06:00:00 GPST").unwrap();
+        assert!(rinex.sv_ephemeris(sv!("G20"), t).is_none());
+    }
+    #[test]
+    #[cfg(feature = "nav")]
+    fn v3_cbw100nld_sv_group_delay() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("CBW100NLD_R_20210010000_01D_MN.rnx");
+        let rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        let toc = Epoch::from_str("2021-01-01T16:00:00 GPST").unwrap();
+        let find = |carrier: Carrier, sv: SV, toc: Epoch| {
+            rinex
+                .sv_group_delay(carrier)
+                .find(|(t, sv_i, _)| *t == toc && *sv_i == sv)
+                .map(|(_, _, delay)| delay)
+        };
+
+        // GPS: TGD on L1, scaled on L2
+        let tgd = find(Carrier::L1, sv!("G20"), toc).unwrap();
+        assert_eq!(tgd, -8.381903171539e-09);
+        let l2 = find(Carrier::L2, sv!("G20"), toc).unwrap();
+        assert!((l2 - tgd * (77.0_f64 / 60.0).powi(2)).abs() < 1.0E-15);
+        assert!(find(Carrier::L5, sv!("G20"), toc).is_none());
+
+        // Galileo: null E5b/E1 BGD is not broadcast
+        let t = Epoch::from_str("2021-01-01T00:00:00 GST").unwrap();
+        assert_eq!(find(Carrier::E1, sv!("E01"), t), Some(2.328306436539e-10));
+        let e5a = find(Carrier::E5a, sv!("E01"), t).unwrap();
+        let gamma = (Carrier::E1.frequency() / Carrier::E5a.frequency()).powi(2);
+        assert!((e5a - 2.328306436539e-10 * gamma).abs() < 1.0E-15);
+        assert!(find(Carrier::E5b, sv!("E01"), t).is_none());
+
+        // BeiDou: TGD1 and TGD2
+        let t = Epoch::from_str("2021-01-01T00:00:00 BDT").unwrap();
+        assert_eq!(find(Carrier::B1I, sv!("C05"), t), Some(-6.0E-10));
+        assert_eq!(find(Carrier::B2I, sv!("C05"), t), Some(-9.0E-09));
+
+        // only GPS and QZSS describe a delay on L1
+        for (_, sv, _) in rinex.sv_group_delay(Carrier::L1) {
+            assert!(
+                sv.constellation == Constellation::GPS || sv.constellation == Constellation::QZSS
+            );
+        }
+    }
+    #[test]
+    #[cfg(feature = "nav")]
+    fn v3_cbw100nld_sv_ephemeris_healthy() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("CBW100NLD_R_20210010000_01D_MN.rnx");
+        let mut rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        let g20 = sv!("G20");
+        let t = Epoch::from_str("2021-01-01T16:30:00 GPST").unwrap();
+        let toc = Epoch::from_str("2021-01-01T16:00:00 GPST").unwrap();
+
+        for (_, sv, healthy) in rinex.ephemeris_health() {
+            if sv == g20 || sv == sv!("E01") {
+                assert!(healthy, "{} should be healthy", sv);
+            }
+        }
+        let (toc_i, _, _) = rinex.sv_ephemeris_healthy(g20, t).unwrap();
+        assert_eq!(toc_i, toc);
+
+        // G20 declared unusable at 16:00
+        let record = rinex.record.as_mut_nav().unwrap();
+        for frame in record.get_mut(&toc).unwrap().iter_mut() {
+            if let Some((_, sv, eph)) = frame.as_mut_eph() {
+                if sv == g20 {
+                    eph.orbits.insert("health".to_string(), OrbitItem::U32(63));
+                }
+            }
+        }
+        assert!(rinex
+            .ephemeris_health()
+            .any(|(t_i, sv, healthy)| t_i == toc && sv == g20 && !healthy));
+
+        let (toc_i, _, _) = rinex.sv_ephemeris(g20, t).unwrap();
+        assert_eq!(toc_i, toc, "sv_ephemeris does not consider health");
+
+        // this was the only G20 frame
+        assert!(rinex.sv_ephemeris_healthy(g20, t).is_none());
+    }
+    #[test]
+    #[cfg(all(feature = "nav", feature = "flate2"))]
+    fn v4_brd400dlr_health_words() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V4")
+            .join("BRD400DLR_S_20230710000_01D_MN.rnx.gz");
+        let mut rinex = Rinex::from_file(&path.to_string_lossy()).unwrap();
+
+        // BeiDou D1/D2 describe SatH1, GPS/QZSS CNAV describe svHealth
+        let (mut d1d2, mut cnav) = (0, 0);
+        for (_, (msg, sv, eph)) in rinex.ephemeris() {
+            match (sv.constellation, msg) {
+                (Constellation::BeiDou, NavMsgType::D1 | NavMsgType::D2) => {
+                    assert!(eph.orbits.contains_key("satH1"));
+                    assert!(eph.is_healthy(sv, msg).is_some(), "{}({:?})", sv, msg);
+                    d1d2 += 1;
+                },
+                (Constellation::GPS | Constellation::QZSS, NavMsgType::CNAV | NavMsgType::CNV2) => {
+                    assert!(eph.orbits.contains_key("svHealth"));
+                    assert!(eph.is_healthy(sv, msg).is_some(), "{}({:?})", sv, msg);
+                    cnav += 1;
+                },
+                _ => {},
+            }
+        }
+        assert!(d1d2 > 0 && cnav > 0);
+        assert!(rinex.ephemeris_health().any(|(_, _, healthy)| healthy));
+
+        // declare one D1 and one CNAV frame unusable
+        let mut flagged = Vec::new();
+        for (t, frames) in rinex.record.as_mut_nav().unwrap().iter_mut() {
+            for frame in frames.iter_mut() {
+                if let Some((msg, sv, eph)) = frame.as_mut_eph() {
+                    let field = match msg {
+                        NavMsgType::D1 => "satH1",
+                        NavMsgType::CNAV => "svHealth",
+                        _ => continue,
+                    };
+                    if flagged.iter().all(|(_, _, m)| *m != msg) {
+                        eph.orbits.insert(field.to_string(), OrbitItem::F64(1.0));
+                        flagged.push((*t, sv, msg));
+                    }
+                }
+            }
+        }
+        assert_eq!(flagged.len(), 2);
+        for (t, sv, _) in flagged {
+            assert!(rinex
+                .ephemeris_health()
+                .any(|(t_i, sv_i, healthy)| t_i == t && sv_i == sv && !healthy));
+        }
+
+        // legacy GPS health word: any flagged signal is unusable
+        let item = OrbitItem::new("health", "0.0", Constellation::GPS).unwrap();
+        assert_eq!(item.as_gps_health(), Some(Health::Unhealthy));
+        assert_eq!(item.is_healthy(), Some(true));
+        let item = OrbitItem::new("health", "1.0", Constellation::GPS).unwrap();
+        assert_eq!(item.as_gps_health(), Some(Health::L1Healthy));
+        assert_eq!(item.is_healthy(), Some(false));
+
+        // Galileo health word spans 9 bits: E5b flags are not truncated
+        let item = OrbitItem::new("health", "2.560000000000E+02", Constellation::Galileo).unwrap();
+        assert_eq!(item.as_gal_health(), Some(GalHealth::E5B_HS2));
+        assert_eq!(item.is_healthy(), Some(false));
+        assert_eq!(item.to_string(), "2.56000000000E2");
+        let item = OrbitItem::new("health", "5.110000000000E+02", Constellation::Galileo).unwrap();
+        assert_eq!(item.as_gal_health(), Some(GalHealth::all()));
+        // words we cannot describe are preserved
+        let item = OrbitItem::new("health", "5.120000000000E+02", Constellation::Galileo).unwrap();
+        assert_eq!(item.as_gal_health(), None);
+        assert_eq!(item.is_healthy(), Some(false));
+    }
+    #[test]
+    #[cfg(feature = "nav")]
+    fn v3_cbw100nld_sv_clock_corrected() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("CBW100NLD_R_20210010000_01D_MN.rnx");
+        let rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        let g20 = sv!("G20");
+        let t = Epoch::from_str("2021-01-01T16:30:00 GPST").unwrap();
+
+        let polynomial = rinex.sv_clock_interpolate(t, g20).unwrap();
+        let (epoch, sv, offset) = rinex.sv_clock_corrected(t, g20, false).unwrap();
+        assert_eq!(epoch, t);
+        assert_eq!(sv, g20);
+
+        // relativistic effect remains within a few tens of ns for GPS orbits
+        let dtr = offset - polynomial;
+        assert!(dtr != 0.0);
+        assert!(
+            dtr.abs() < 50.0E-9,
+            "relativistic correction {} too large",
+            dtr
+        );
+
+        // group delay
+        let (_, _, eph) = rinex.sv_ephemeris(g20, t).unwrap();
+        let tgd = eph.group_delay(g20, Carrier::L1).unwrap();
+        let (_, _, with_tgd) = rinex.sv_clock_corrected(t, g20, true).unwrap();
+        assert!((offset - tgd - with_tgd).abs() < 1.0E-15);
+
+        // not described
+        assert!(rinex.sv_clock_corrected(t, sv!("G01"), false).is_none());
+    }
+    #[test]
+    #[cfg(feature = "nav")]
+    fn v3_cbw100nld_sv_position_interpolate() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("CBW100NLD_R_20210010000_01D_MN.rnx");
+        let rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        let g20 = sv!("G20");
+        let toc = Epoch::from_str("2021-01-01T16:00:00 GPST").unwrap();
+
+        let (_, _, broadcast) = rinex
+            .sv_position()
+            .find(|(t, sv, _)| *t == toc && *sv == g20)
+            .unwrap();
+
+        // at toc
+        assert_eq!(rinex.sv_position_interpolate(g20, toc), Some(broadcast));
+
+        // in between publications
+        let t = toc + Duration::from_seconds(15.0 * 60.0);
+        let (x, y, z) = rinex.sv_position_interpolate(g20, t).unwrap();
+        let radius = (x.powi(2) + y.powi(2) + z.powi(2)).sqrt();
+        assert!(
+            (radius - 26_560.0).abs() < 300.0,
+            "bad orbit radius {}",
+            radius
+        );
+        let travelled =
+            ((x - broadcast.0).powi(2) + (y - broadcast.1).powi(2) + (z - broadcast.2).powi(2))
+                .sqrt();
+        assert!(
+            travelled > 2_500.0 && travelled < 4_500.0,
+            "bad distance travelled {}",
+            travelled
+        );
+
+        // prior toc: no valid ephemeris
+        assert!(rinex
+            .sv_position_interpolate(g20, toc - Duration::from_hours(1.0))
+            .is_none());
+    }
+    #[test]
+    #[cfg(all(feature = "nav", feature = "flate2"))]
+    fn v3_esbc00dnk_glonass_position_interpolate() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("ESBC00DNK_R_20201770000_01D_MN.rnx.gz");
+        let rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        let r01 = sv!("R01");
+        let toc = Epoch::from_str("2020-06-25T00:15:00 UTC").unwrap();
+
+        // at toc: broadcast state vector, expressed in WGS84
+        let ((x_e, y_e, z_e), _) = pz90_to_wgs84(
+            (1.682726318359e+04, 5.647285644531e+03, 1.833408203125e+04),
+            (0.0, 0.0, 0.0),
+        );
+        let (x, y, z) = rinex.sv_position_interpolate(r01, toc).unwrap();
+        assert!((x - x_e).abs() < 1.0E-6);
+        assert!((y - y_e).abs() < 1.0E-6);
+        assert!((z - z_e).abs() < 1.0E-6);
+
+        // integrated forward from 00:15:00 (closest ephemeris)
+        let t = Epoch::from_str("2020-06-25T00:25:00 UTC").unwrap();
+        let (x, y, z) = rinex.sv_position_interpolate(r01, t).unwrap();
+        let radius = (x.powi(2) + y.powi(2) + z.powi(2)).sqrt();
+        assert!(
+            (radius - 25_518.7).abs() < 1.0,
+            "bad orbit radius {}",
+            radius
+        );
+
+        // integrated backward from next publication: consistent within 10m
+        let next_toc = Epoch::from_str("2020-06-25T00:45:00 UTC").unwrap();
+        let (_, (_, _, next)) = rinex
+            .ephemeris()
+            .find(|(t, (_, sv, _))| **t == next_toc && *sv == r01)
+            .unwrap();
+        let ((x_b, y_b, z_b), _) = next.glonass_position_velocity(next_toc, t).unwrap();
+        let err = ((x - x_b).powi(2) + (y - y_b).powi(2) + (z - z_b).powi(2)).sqrt();
+        assert!(err < 1.0E-2, "integration error {} km", err);
+
+        // too far from any publication
+        let t = Epoch::from_str("2020-06-26T12:00:00 UTC").unwrap();
+        assert!(rinex.sv_position_interpolate(r01, t).is_none());
+    }
+    #[test]
+    #[cfg(all(feature = "nav", feature = "flate2"))]
+    fn v3_esbc00dnk_glonass_sv_position() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("NAV")
+            .join("V3")
+            .join("ESBC00DNK_R_20201770000_01D_MN.rnx.gz");
+        let rinex = Rinex::from_file(path.to_string_lossy().as_ref()).unwrap();
+
+        let glonass = rinex
+            .ephemeris()
+            .filter_map(|(_, (_, sv, _))| {
+                if sv.constellation == Constellation::Glonass {
+                    Some(sv)
+                } else {
+                    None
+                }
+            })
+            .unique()
+            .sorted()
+            .collect::<Vec<_>>();
+        assert!(
+            !glonass.is_empty(),
+            "test file should contain Glonass frames"
+        );
+
+        let positions = rinex
+            .sv_position()
+            .filter(|(_, sv, _)| sv.constellation == Constellation::Glonass)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions
+                .iter()
+                .map(|(_, sv, _)| *sv)
+                .unique()
+                .sorted()
+                .collect::<Vec<_>>(),
+            glonass,
+            "all Glonass vehicles should be resolved"
+        );
+
+        for (t, sv, (x, y, z)) in positions {
+            let radius = (x.powi(2) + y.powi(2) + z.powi(2)).sqrt();
+            assert!(
+                (radius - 25_510.0).abs() < 100.0,
+                "{}({}): unrealistic orbit radius {} km",
+                t,
+                sv,
+                radius
+            );
+        }
+
+        // PZ-90.11 to WGS84: a few millimeters at most
+        let r01 = sv!("R01");
+        let toc = Epoch::from_str("2020-06-25T00:15:00 UTC").unwrap();
+        let (_, _, (x, y, z)) = rinex
+            .sv_position()
+            .find(|(t, sv, _)| *t == toc && *sv == r01)
+            .unwrap();
+        let err = ((x - 1.682726318359e+04).powi(2)
+            + (y - 5.647285644531e+03).powi(2)
+            + (z - 1.833408203125e+04).powi(2))
+        .sqrt();
+        assert!(err > 0.0 && err < 2.0E-5, "bad frame conversion {} km", err);
+    }
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn v3_esbc00dnk_sv_ephemeris_iode() {
+        let rinex =
+            Rinex::from_file("../test_resources/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+                .unwrap();
+        let g06 = sv!("G06");
+        let t = Epoch::from_str("2020-06-25T06:30:00 GPST").unwrap();
+        let toc_0 = Epoch::from_str("2020-06-25T05:59:44 GPST").unwrap();
+        let toc_37 = Epoch::from_str("2020-06-25T06:00:00 GPST").unwrap();
+
+        let iodes = rinex.ephemeris_iode().collect::<Vec<_>>();
+        assert!(iodes.contains(&(toc_0, g06, 0)));
+        assert!(iodes.contains(&(toc_37, g06, 37)));
+        assert!(iodes
+            .iter()
+            .all(|(_, sv, _)| sv.constellation != Constellation::Glonass));
+
+        // two orbit sets are valid: nearest one is selected by default
+        let (toc, _, eph) = rinex.sv_ephemeris(g06, t).unwrap();
+        assert_eq!(toc, toc_37);
+        assert_eq!(eph.iode(), Some(37));
+        assert_eq!(eph.iodc(), Some(37));
+        assert!(eph.is_iod_consistent());
+
+        let (toc, toe, eph) = rinex.sv_ephemeris_iode(g06, t, 0).unwrap();
+        assert_eq!(toc, toc_0);
+        assert_eq!(toe, toc_0);
+        assert_eq!(eph.iode(), Some(0));
+        assert!(eph.is_iod_consistent());
+
+        let (toc, _, _) = rinex.sv_ephemeris_iode(g06, t, 37).unwrap();
+        assert_eq!(toc, toc_37);
+
+        // issued later on
+        assert!(rinex.sv_ephemeris_iode(g06, t, 38).is_none());
+
+        // stale clock record
+        let eph = eph.with_orbit("iodc", OrbitItem::from(37.0));
+        assert!(!eph.is_iod_consistent());
+    }
+    #[test]
+    #[cfg(all(feature = "nav", feature = "flate2"))]
+    fn v3_esbc00dnk_predict_visibility() {
+        let nav =
+            Rinex::from_file("../test_resources/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+                .unwrap();
+        let obs =
+            Rinex::from_file("../test_resources/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz")
+                .unwrap();
+        let site = obs.header.ground_position.unwrap();
+        let record = obs.record.as_obs().unwrap();
+
+        let step = Duration::from_seconds(30.0);
+        let (start, end) = (
+            Epoch::from_str("2020-06-25T06:00:00 GPST").unwrap(),
+            Epoch::from_str("2020-06-25T12:00:00 GPST").unwrap(),
+        );
+        let (almanac, earth_cef) = (Almanac::default(), earth_cef());
+        let visibility =
+            nav.predict_visibility(&almanac, earth_cef, site, (start, end), step, 10.0);
+        let site = site.to_ecef_wgs84();
+
+        let (mut passes, mut observed) = (0, 0);
+        for (sv, intervals) in visibility.iter() {
+            for (rise, set) in intervals.iter() {
+                assert!(*rise >= start && *set <= end, "{}: pass exceeds window", sv);
+                assert!(rise <= set, "{}: invalid pass", sv);
+
+                // independently computed elevation
+                let elev = nav
+                    .sv_position_interpolate(*sv, *rise)
+                    .map(|sv_km| elevation_deg(site, sv_km))
+                    .unwrap();
+                assert!(elev >= 10.0 - 1.0E-3, "{}: rises @ {} below mask", sv, rise);
+
+                if *rise > start {
+                    let before = nav
+                        .sv_position_interpolate(*sv, *rise - step)
+                        .map(|sv_km| elevation_deg(site, sv_km));
+                    if let Some(before) = before {
+                        assert!(
+                            before < 10.0 + 1.0E-3,
+                            "{}: rise @ {} is not a rise",
+                            sv,
+                            rise
+                        );
+                    }
+                }
+                // the receiver should have tracked this pass
+                if sv.constellation == Constellation::GPS {
+                    let mid = (*rise + (*set - *rise) * 0.5).round(step);
+                    passes += 1;
+                    if let Some((_, vehicles)) = record.get(&(mid, EpochFlag::Ok)) {
+                        if vehicles.contains_key(sv) {
+                            observed += 1;
+                        }
+                    }
+                }
+            }
+        }
+        assert!(passes > 0, "no GPS pass predicted");
+        assert!(
+            observed as f64 >= 0.9 * passes as f64,
+            "only {}/{} predicted GPS passes were actually observed",
+            observed,
+            passes
+        );
+        // invalid sampling
+        let site = obs.header.ground_position.unwrap();
+        assert!(nav
+            .predict_visibility(
+                &almanac,
+                earth_cef,
+                site,
+                (start, end),
+                Duration::ZERO,
+                10.0
+            )
+            .is_empty());
+    }
+    #[test]
+    #[cfg(all(feature = "nav", feature = "flate2"))]
+    fn v3_esbc00dnk_sv_ground_track() {
+        let nav =
+            Rinex::from_file("../test_resources/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+                .unwrap();
+        // GPS ground tracks repeat every sidereal day,
+        // which is a multiple of this sampling period
+        let sidereal_day = Duration::from_seconds(86164.0);
+        let dt = Duration::from_seconds(52.0);
+
+        for sv in [sv!("G02"), sv!("G05"), sv!("G09")] {
+            let track = nav.sv_ground_track(sv, dt);
+            assert!(!track.is_empty(), "{}: empty ground track", sv);
+
+            let mut compared = 0;
+            for (t, lat, long) in track.iter() {
+                assert!(lat.abs() <= 90.0 && long.abs() <= 180.0);
+                // GPS orbits are inclined by 55°
+                assert!(
+                    lat.abs() < 56.0,
+                    "{}: unexpected latitude {} @ {}",
+                    sv,
+                    lat,
+                    t
+                );
+
+                let repeated = track.iter().find(|(t_i, _, _)| *t_i == *t + sidereal_day);
+                if let Some((_, lat_i, long_i)) = repeated {
+                    let mut dlong = (long_i - long).abs();
+                    if dlong > 180.0 {
+                        dlong = 360.0 - dlong;
+                    }
+                    assert!(
+                        (lat_i - lat).abs() < 1.0 && dlong < 1.0,
+                        "{}: ground track does not repeat @ {}",
+                        sv,
+                        t
+                    );
+                    compared += 1;
+                }
+            }
+            assert!(compared > 0, "{}: file does not span a sidereal day", sv);
+        }
+    }
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn v4_brd400dlr_cnav_toe() {
+        let rinex =
+            Rinex::from_file("../test_resources/NAV/V4/BRD400DLR_S_20230710000_01D_MN.rnx.gz")
+                .unwrap();
+
+        // GPS CNAV: ToE is ToC
+        let g01 = sv!("G01");
+        let toc = Epoch::from_str("2023-03-12T01:30:00 GPST").unwrap();
+        let (_, (_, _, cnav)) = rinex
+            .ephemeris()
+            .find(|(t, (msg, sv, _))| **t == toc && *msg == NavMsgType::CNAV && *sv == g01)
+            .expect("missing G01 CNAV frame");
+        assert_eq!(cnav.toe(TimeScale::GPST), Some(toc));
+
+        let t = toc + 15.0 * Unit::Minute;
+        assert!(cnav.is_valid(g01, t));
+
+        // CNAV and LNAV orbits should agree
+        let lnav_toc = Epoch::from_str("2023-03-12T00:00:00 GPST").unwrap();
+        let (_, (_, _, lnav)) = rinex
+            .ephemeris()
+            .find(|(t, (msg, sv, _))| **t == lnav_toc && *msg == NavMsgType::LNAV && *sv == g01)
+            .expect("missing G01 LNAV frame");
+        assert_eq!(lnav.toe(TimeScale::GPST), Some(lnav_toc));
+
+        let cnav = cnav
+            .kepler2position(g01, t, t)
+            .expect("CNAV kepler solver failed")
+            .to_cartesian_pos_vel();
+        let lnav = lnav
+            .kepler2position(g01, t, t)
+            .expect("LNAV kepler solver failed")
+            .to_cartesian_pos_vel();
+        let err_km = ((cnav[0] - lnav[0]).powi(2)
+            + (cnav[1] - lnav[1]).powi(2)
+            + (cnav[2] - lnav[2]).powi(2))
+        .sqrt();
+        assert!(err_km < 1.0E-2, "CNAV/LNAV orbits differ by {} km", err_km);
+
+        let (toc_i, toe_i, _) = rinex.sv_ephemeris(g01, t).unwrap();
+        assert_eq!(toc_i, toc);
+        assert_eq!(toe_i, toc);
+
+        // BeiDou CNV1: week counter deduced from ToC
+        let c19 = sv!("C19");
+        let toc = Epoch::from_str("2023-03-12T00:00:00 BDT").unwrap();
+        let (_, (_, _, cnv1)) = rinex
+            .ephemeris()
+            .find(|(t, (msg, sv, _))| **t == toc && *msg == NavMsgType::CNV1 && *sv == c19)
+            .expect("missing C19 CNV1 frame");
+        assert_eq!(cnv1.toe(TimeScale::BDT), Some(toc));
+        assert!(cnv1.kepler2position(c19, toc, toc).is_some());
+
+        assert!(rinex.sv_position().any(|(t, sv, _)| sv == c19 && t == toc));
+    }
 }
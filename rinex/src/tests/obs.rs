@@ -3,13 +3,19 @@ mod test {
     use crate::marker::MarkerType;
     use crate::observable;
     use crate::observation::SNR;
+    #[cfg(feature = "nav")]
+    use crate::tests::toolkit::nav::{earth_cef, elevation_deg};
     use crate::tests::toolkit::obsrinex_check_observables;
+    use crate::tests::toolkit::random_name;
+    use crate::tests::toolkit::synthetic_1hz_testbench;
     use crate::tests::toolkit::test_observation_rinex;
+    use crate::tests::toolkit::vlns0010_testbench;
     use crate::{erratic_time_frame, evenly_spaced_time_frame, tests::toolkit::TestTimeFrame};
     use crate::{observation::*, prelude::*};
     use gnss_rs::prelude::SV;
     use gnss_rs::sv;
     use itertools::Itertools;
+    use std::collections::{BTreeMap, HashMap};
     use std::path::Path;
     use std::str::FromStr;
     #[test]
@@ -1277,6 +1283,2028 @@ mod test {
             "IRNSS sv badly identified"
         );
     }
+    #[test]
+    fn v3_vlns0630_remap_observables() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("OBS")
+            .join("V3")
+            .join("VLNS0630.22O");
+        let fullpath = path.to_string_lossy();
+        let rinex = Rinex::from_file(fullpath.as_ref()).unwrap();
+
+        let c2x = Observable::from_str("C2X").unwrap();
+        let c2l = Observable::from_str("C2L").unwrap();
+
+        let mut map = HashMap::<Observable, Observable>::new();
+        map.insert(c2x.clone(), c2l.clone());
+
+        let remapped = rinex.remap_observables(&map);
+
+        // header
+        let gps_codes = remapped
+            .header
+            .obs
+            .as_ref()
+            .unwrap()
+            .codes
+            .get(&Constellation::GPS)
+            .unwrap();
+        assert!(gps_codes.contains(&c2l));
+        assert!(!gps_codes.contains(&c2x));
+        assert_eq!(
+            gps_codes.iter().filter(|code| **code == c2l).count(),
+            1,
+            "remapped observables should remain unique"
+        );
+
+        // record
+        assert!(remapped.observable().all(|obs| *obs != c2x));
+        for ((t, flag), (_, vehicles)) in rinex.observation() {
+            for (sv, observations) in vehicles.iter() {
+                if let Some(data) = observations.get(&c2x) {
+                    let remapped_data = remapped
+                        .record
+                        .as_obs()
+                        .unwrap()
+                        .get(&(*t, *flag))
+                        .and_then(|(_, vehicles)| vehicles.get(sv))
+                        .and_then(|observations| observations.get(&c2l))
+                        .expect("remapped observation is missing");
+                    assert_eq!(remapped_data, data);
+                }
+            }
+        }
+
+        // still formats correctly
+        let tmp_path = format!("test-{}.rnx", random_name(5));
+        assert!(remapped.to_file(&tmp_path).is_ok());
+        let parsed = Rinex::from_file(&tmp_path);
+        let _ = std::fs::remove_file(&tmp_path);
+        let parsed = parsed.unwrap();
+        assert!(parsed.observable().all(|obs| *obs != c2x));
+        assert!(parsed.observable().any(|obs| *obs == c2l));
+        assert_eq!(parsed.epoch().count(), rinex.epoch().count());
+    }
+    #[test]
+    fn v3_duth0630_geometry_free() {
+        let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        let t0 = Epoch::from_str("2022-03-04T00:00:00 GPST").unwrap();
+        let g01 = sv!("G01");
+
+        let (_, _, gf) = rinex
+            .geometry_free_pseudo_range()
+            .find(|(t, sv, _)| *t == t0 && *sv == g01)
+            .unwrap();
+        // C2W - C1C
+        assert!((gf - (20243518.680 - 20243517.560)).abs() < 1.0E-6);
+
+        let (_, _, gf) = rinex
+            .geometry_free_phase()
+            .find(|(t, sv, _)| *t == t0 && *sv == g01)
+            .unwrap();
+        // λ1 * L1C - λ2 * L2W
+        let (l1, l2) = (
+            299_792_458.0 / 1575.42E6 * 106380411.418,
+            299_792_458.0 / 1227.60E6 * 82893846.800,
+        );
+        assert!((gf - (l1 - l2)).abs() < 1.0E-6);
+
+        for (t, sv, gf) in rinex.geometry_free_pseudo_range() {
+            assert!(
+                gf.abs() < 100.0,
+                "{}({}): unrealistic GF value {}",
+                t,
+                sv,
+                gf
+            );
+        }
+    }
+    #[test]
+    fn v2_ajac3550_ssi() {
+        let rinex = Rinex::from_file("../test_resources/OBS/V2/AJAC3550.21O").unwrap();
+
+        assert!(rinex
+            .ssi()
+            .all(|(_, _, observable, _)| observable.is_ssi_observable()));
+
+        let ssi = rinex
+            .ssi()
+            .map(|(_, _, observable, _)| observable)
+            .unique()
+            .count();
+        let expected = rinex
+            .observable()
+            .filter(|observable| observable.is_ssi_observable())
+            .count();
+        assert!(ssi > 0);
+        assert_eq!(ssi, expected);
+
+        // high precision RINEX: scaling is applied
+        let s1 = Observable::from_str("S1").unwrap();
+        let mut scaled = rinex.clone();
+        scaled
+            .header
+            .obs
+            .as_mut()
+            .unwrap()
+            .with_scaling(Constellation::GPS, s1.clone(), 10);
+
+        for ((raw_k, raw_sv, raw_obs, raw), (k, sv, obs, value)) in rinex.ssi().zip(scaled.ssi()) {
+            assert_eq!((raw_k, raw_sv, raw_obs), (k, sv, obs));
+            if sv.constellation == Constellation::GPS && *obs == s1 {
+                assert_eq!(value, raw / 10.0);
+            } else {
+                assert_eq!(value, raw);
+            }
+        }
+    }
+    #[test]
+    fn v3_acor00esp_snr_interpolation() {
+        let mut rinex =
+            Rinex::from_file("../test_resources/OBS/V3/ACOR00ESP_R_20213550000_01D_30S_MO.rnx")
+                .unwrap();
+
+        let g01 = sv!("G01");
+        let s1c = Observable::from_str("S1C").unwrap();
+        let t0 = Epoch::from_str("2021-12-21T00:00:00 GPST").unwrap();
+        let dt = Duration::from_seconds(30.0);
+
+        let value = |rinex: &Rinex, t: Epoch| -> f64 {
+            rinex
+                .ssi()
+                .find(|((t_i, _), sv, obs, _)| *t_i == t && *sv == g01 && **obs == s1c)
+                .map(|(_, _, _, value)| value)
+                .unwrap()
+        };
+
+        let (before, after) = (value(&rinex, t0 + dt), value(&rinex, t0 + dt * 3.0));
+
+        // create a one epoch gap, and a large gap
+        let record = rinex.record.as_mut_obs().unwrap();
+        for ((t, _), (_, vehicles)) in record.iter_mut() {
+            let gap = *t == t0 + dt * 2.0 || (*t >= t0 + dt * 8.0 && *t <= t0 + dt * 19.0);
+            if gap {
+                vehicles.get_mut(&g01).unwrap().remove(&s1c);
+            }
+        }
+
+        let grid = rinex.timeseries().unwrap();
+        let interpolated = rinex
+            .snr_interpolated(g01, &s1c, grid)
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(interpolated[&(t0 + dt)], Some(before));
+        assert_eq!(interpolated[&(t0 + dt * 3.0)], Some(after));
+
+        let filled = interpolated[&(t0 + dt * 2.0)].expect("one epoch gap should be filled");
+        assert!((filled - (before + after) / 2.0).abs() < 1.0E-6);
+
+        for i in 8..20 {
+            assert!(
+                interpolated[&(t0 + dt * i as f64)].is_none(),
+                "large gap should not be interpolated"
+            );
+        }
+
+        // high precision: same data, scaled by 10
+        let mut scaled = rinex.clone();
+        scaled
+            .header
+            .obs
+            .as_mut()
+            .unwrap()
+            .scaling
+            .insert((Constellation::GPS, s1c.clone()), 10);
+        let record = scaled.record.as_mut_obs().unwrap();
+        for (_, (_, vehicles)) in record.iter_mut() {
+            if let Some(data) = vehicles.get_mut(&g01).and_then(|obs| obs.get_mut(&s1c)) {
+                data.obs *= 10.0;
+            }
+        }
+        let grid = scaled.timeseries().unwrap();
+        for (t, value) in scaled.snr_interpolated(g01, &s1c, grid) {
+            match (value, interpolated[&t]) {
+                (Some(value), Some(expected)) => assert!((value - expected).abs() < 1.0E-6),
+                (value, expected) => assert_eq!(value, expected, "@{}", t),
+            }
+        }
+    }
+    #[test]
+    fn v2_ajac3550_doppler() {
+        let rinex = Rinex::from_file("../test_resources/OBS/V2/AJAC3550.21O").unwrap();
+        let t0 = Epoch::from_str("2021-12-21T00:00:00 GPST").unwrap();
+        let d1 = Observable::from_str("D1").unwrap();
+
+        assert!(rinex
+            .doppler()
+            .all(|(_, _, observable, _)| observable.is_doppler_observable()));
+
+        let (_, _, _, value) = rinex
+            .doppler()
+            .find(|((t, _), sv, observable, _)| *t == t0 && *sv == sv!("G07") && **observable == d1)
+            .expect("missing G07 D1 observation");
+
+        // negative: G07 is moving away
+        assert_eq!(value, -411.138);
+
+        // not an observation rinex
+        let rinex = Rinex::from_file("../test_resources/NAV/V2/amel0010.21g").unwrap();
+        assert_eq!(rinex.doppler().count(), 0);
+    }
+    #[test]
+    fn v3_duth0630_derived_observable() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("OBS")
+            .join("V3")
+            .join("DUTH0630.22O");
+        let fullpath = path.to_string_lossy();
+        let mut rinex = Rinex::from_file(fullpath.as_ref()).unwrap();
+
+        let c1c = Observable::from_str("C1C").unwrap();
+        let c1s = Observable::from_str("C1S").unwrap();
+
+        // "smoothed" C1C
+        let smoothed = rinex
+            .observation()
+            .flat_map(|((t, _), (_, vehicles))| {
+                vehicles.iter().filter_map(|(sv, observations)| {
+                    let data = observations.get(&c1c)?;
+                    Some((*t, *sv, data.obs + 1.0))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        rinex
+            .insert_derived_observable_mut(Constellation::GPS, c1s.clone(), smoothed.into_iter())
+            .unwrap();
+
+        let codes = &rinex.header.obs.as_ref().unwrap().codes;
+        assert!(codes[&Constellation::GPS].contains(&c1s));
+        assert!(!codes[&Constellation::Glonass].contains(&c1s));
+        assert!(rinex
+            .header
+            .comments
+            .iter()
+            .any(|c| c.contains("DERIVED OBSERVABLE G C1S")));
+
+        assert!(
+            rinex
+                .insert_derived_observable_mut(Constellation::GPS, c1c.clone(), std::iter::empty())
+                .is_err(),
+            "observable collision should not be permitted"
+        );
+
+        // round trip
+        let tmp_path = format!("test-{}.rnx", random_name(5));
+        assert!(rinex.to_file(&tmp_path).is_ok());
+        let parsed = Rinex::from_file(&tmp_path);
+        let _ = std::fs::remove_file(&tmp_path);
+        let parsed = parsed.unwrap();
+
+        let mut tested = 0;
+        for ((_, _), (_, vehicles)) in parsed.observation() {
+            for (sv, observations) in vehicles.iter() {
+                if let Some(derived) = observations.get(&c1s) {
+                    assert_eq!(sv.constellation, Constellation::GPS);
+                    let reference = observations.get(&c1c).unwrap();
+                    assert!((derived.obs - reference.obs - 1.0).abs() < 1.0E-3);
+                    tested += 1;
+                }
+            }
+        }
+        assert!(tested > 0, "derived observable was not formatted");
+
+        // V2 can only describe 2 character codes
+        let mut rinex = Rinex::from_file("../test_resources/OBS/V2/aopr0010.17o").unwrap();
+        rinex
+            .insert_derived_observable_mut(Constellation::GPS, c1s, std::iter::empty())
+            .unwrap();
+        let tmp_path = format!("test-{}.rnx", random_name(5));
+        assert!(matches!(
+            rinex.to_file(&tmp_path),
+            Err(Error::NonRepresentableObservable(_))
+        ));
+    }
+    /*
+     * DUTH0630 upgraded to RINEX V4: V4 version line,
+     * V4 identification fields and receiver clock offsets
+     */
+    fn duth0630_v4() -> String {
+        let content = std::fs::read_to_string("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        let mut lines = Vec::<String>::new();
+        for line in content.lines() {
+            if line.ends_with("RINEX VERSION / TYPE") {
+                lines.push(format!(
+                    "{:<60}RINEX VERSION / TYPE",
+                    "     4.00           OBSERVATION DATA    M"
+                ));
+                continue;
+            }
+            let offset = match line {
+                "> 2022 03 04 00 00  0.0000000  0 18" => Some("       0.000012345678"),
+                "> 2022 03 04 00 28 30.0000000  0 17" => Some("      -0.000001234567"),
+                "> 2022 03 04 00 57  0.0000000  0 17" => Some("       0.000000987654"),
+                _ => None,
+            };
+            match offset {
+                Some(offset) => lines.push(format!("{}{}", line, offset)),
+                None => lines.push(line.to_string()),
+            }
+            if line.ends_with("ANT # / TYPE") {
+                for (content, marker) in [
+                    ("10.5281/zenodo.0000000", "DOI"),
+                    ("CC BY 4.0", "LICENSE OF USE"),
+                    (
+                        "https://epncb.oma.be/_networkdata/siteinfo4onestation.php",
+                        "STATION INFORMATION",
+                    ),
+                ] {
+                    lines.push(format!("{:<60}{}", content, marker));
+                }
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+    #[test]
+    fn v4_duth0630() {
+        let content = duth0630_v4();
+        let rinex = Rinex::from_reader(content.as_bytes()).unwrap();
+
+        assert_eq!(rinex.header.version, crate::version::Version::new(4, 0));
+        assert_eq!(rinex.header.doi.as_deref(), Some("10.5281/zenodo.0000000"));
+        assert_eq!(rinex.header.license.as_deref(), Some("CC BY 4.0"));
+        assert_eq!(
+            rinex.header.station_url,
+            "https://epncb.oma.be/_networkdata/siteinfo4onestation.php"
+        );
+
+        let clocks = rinex
+            .recvr_clock()
+            .map(|(_, offset)| offset)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            clocks,
+            vec![0.000012345678, -0.000001234567, 0.000000987654]
+        );
+
+        // epoch lines are reproduced byte for byte
+        let expected = content
+            .lines()
+            .filter(|line| line.starts_with('>'))
+            .collect::<Vec<_>>();
+
+        let record = rinex.record.as_obs().unwrap();
+        assert_eq!(record.len(), expected.len());
+
+        for (((epoch, flag), (clock_offset, data)), expected) in record.iter().zip(expected) {
+            let formatted = record::fmt_epoch(*epoch, *flag, clock_offset, data, &rinex.header);
+            assert_eq!(formatted.lines().next(), Some(expected));
+        }
+
+        // round trip
+        let tmp_path = format!("test-{}.rnx", random_name(5));
+        assert!(rinex.to_file(&tmp_path).is_ok());
+        let parsed = Rinex::from_file(&tmp_path);
+        let _ = std::fs::remove_file(&tmp_path);
+        let parsed = parsed.unwrap();
+
+        assert_eq!(parsed.header.doi, rinex.header.doi);
+        assert_eq!(parsed.header.license, rinex.header.license);
+        assert_eq!(parsed.header.station_url, rinex.header.station_url);
+        assert_eq!(parsed.record, rinex.record);
+    }
+    #[test]
+    fn receiver_clock_offset_fields() {
+        for (path, width) in [
+            // F12.9 in columns 69-80, after the 12 vehicle slots
+            ("../test_resources/OBS/V2/npaz3550.21o", 80),
+            // 6X + F15.12
+            ("../test_resources/OBS/V3/DUTH0630.22O", 56),
+        ] {
+            let mut rinex = Rinex::from_file(path).unwrap();
+            let record = rinex.record.as_mut_obs().unwrap();
+
+            for (i, (_, (clock_offset, _))) in record.iter_mut().enumerate() {
+                *clock_offset = Some(-0.000123456 + i as f64 * 1.0E-9);
+            }
+
+            let record = rinex.record.as_obs().unwrap();
+            for ((epoch, flag), (clock_offset, data)) in record.iter() {
+                let formatted = record::fmt_epoch(*epoch, *flag, clock_offset, data, &rinex.header);
+                let first = formatted.lines().next().unwrap();
+                assert_eq!(first.len(), width, "{}: \"{}\"", path, first);
+                let offset = first[width - 12..].trim().parse::<f64>().unwrap();
+                assert!((offset - clock_offset.unwrap()).abs() < 1.0E-12);
+            }
+
+            let tmp_path = format!("test-{}.rnx", random_name(5));
+            assert!(rinex.to_file(&tmp_path).is_ok());
+            let parsed = Rinex::from_file(&tmp_path);
+            let _ = std::fs::remove_file(&tmp_path);
+            let parsed = parsed.unwrap();
+
+            let expected = rinex.recvr_clock().collect::<Vec<_>>();
+            let clocks = parsed.recvr_clock().collect::<Vec<_>>();
+            assert_eq!(clocks.len(), expected.len(), "{}", path);
+            for ((t, offset), (expected_t, expected_offset)) in clocks.iter().zip(expected.iter()) {
+                assert_eq!(t, expected_t);
+                assert!((offset - expected_offset).abs() < 1.0E-12, "{}", path);
+            }
+        }
+    }
+    /// Synthetic G01 L1C/D1C tracking, with linear Doppler shift
+    /// and integer phase jumps injected at given epochs
+    fn cycle_slip_testbench(slips: &[(usize, f64, bool)], noise: &[(usize, f64)]) -> Rinex {
+        let l1c = Observable::from_str("L1C").unwrap();
+        let d1c = Observable::from_str("D1C").unwrap();
+        let t0 = Epoch::from_str("2022-01-01T00:00:00 GPST").unwrap();
+
+        let mut record = Record::new();
+        for i in 0..40 {
+            let dt = 30.0 * i as f64;
+            let doppler = -500.0 + 0.1 * dt;
+            let mut phase = 1.0E6 + 500.0 * dt - 0.05 * dt * dt;
+            let mut lli = None;
+            for (index, cycles, flagged) in slips {
+                if i >= *index {
+                    phase += cycles;
+                }
+                if i == *index && *flagged {
+                    lli = Some(LliFlags::LOCK_LOSS);
+                }
+            }
+            for (index, value) in noise {
+                if i == *index {
+                    phase += value;
+                }
+            }
+            let mut observations = HashMap::new();
+            observations.insert(l1c.clone(), ObservationData::new(phase, lli, None));
+            observations.insert(d1c.clone(), ObservationData::new(doppler, None, None));
+            let mut vehicles = BTreeMap::new();
+            vehicles.insert(sv!("G01"), observations);
+            record.insert(
+                (t0 + Duration::from_seconds(dt), EpochFlag::Ok),
+                (None, vehicles),
+            );
+        }
+
+        let mut codes = HashMap::new();
+        codes.insert(Constellation::GPS, vec![l1c, d1c]);
+        let header = Header::basic_obs().with_observation_fields(HeaderFields {
+            codes,
+            ..Default::default()
+        });
+        Rinex::new(header, crate::record::Record::ObsRecord(record))
+    }
+    #[test]
+    fn phase_reset_epochs() {
+        let t0 = Epoch::from_str("2022-01-01T00:00:00 GPST").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+
+        let clean = cycle_slip_testbench(&[], &[]);
+        assert_eq!(clean.phase_reset_epochs(sv!("G01"), &l1c).count(), 0);
+
+        // LLI flagged lock loss, followed by a jump the receiver did not report
+        let rinex = cycle_slip_testbench(&[(10, 7.0, true), (25, 5000.0, false)], &[]);
+        let resets = rinex
+            .phase_reset_epochs(sv!("G01"), &l1c)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            resets,
+            vec![
+                t0 + Duration::from_seconds(300.0),
+                t0 + Duration::from_seconds(750.0),
+            ]
+        );
+
+        // larger threshold: only the lock loss remains
+        let resets = rinex
+            .phase_reset_epochs_with_threshold(sv!("G01"), &l1c, 10_000.0)
+            .collect::<Vec<_>>();
+        assert_eq!(resets, vec![t0 + Duration::from_seconds(300.0)]);
+
+        // other signals are not affected
+        assert_eq!(rinex.phase_reset_epochs(sv!("G02"), &l1c).count(), 0);
+    }
+    #[test]
+    fn cycle_slip_repair() {
+        let max_gap = Duration::from_seconds(30.0);
+        let clean = cycle_slip_testbench(&[], &[]);
+        let l1c = Observable::from_str("L1C").unwrap();
+
+        // LLI flagged slip, followed by a slip the receiver did not report
+        let mut rinex = cycle_slip_testbench(&[(10, 7.0, true), (25, -3.0, false)], &[]);
+        let report = rinex.repair_cycle_slips_mut(max_gap);
+
+        assert!(report.unrepairable.is_empty());
+        assert_eq!(report.repaired.len(), 2);
+        assert_eq!(report.repaired[0].cycles, Some(7.0));
+        assert_eq!(report.repaired[1].cycles, Some(-3.0));
+        assert!(report.repaired.iter().all(|slip| slip.observable == l1c));
+
+        for ((_, (_, repaired)), (_, (_, reference))) in
+            rinex.observation().zip(clean.observation())
+        {
+            let repaired = repaired[&sv!("G01")][&l1c].obs;
+            let reference = reference[&sv!("G01")][&l1c].obs;
+            assert!((repaired - reference).abs() < 1.0E-6);
+        }
+        assert!(rinex
+            .header
+            .comments
+            .iter()
+            .any(|c| c.starts_with("rustrnx CS REPAIR G01 L1C")));
+
+        // clean data is never modified
+        let (repaired, report) = clean.carrier_phase_cycle_slips_repaired(max_gap);
+        assert_eq!(report, Default::default());
+        assert_eq!(repaired.record, clean.record);
+    }
+    #[test]
+    fn cycle_slip_repair_low_confidence() {
+        let max_gap = Duration::from_seconds(30.0);
+
+        // noisy slip: residual is too far from an integer number of cycles
+        let rinex = cycle_slip_testbench(&[(10, 5.0, true)], &[(10, 0.45)]);
+        let (repaired, report) = rinex.carrier_phase_cycle_slips_repaired(max_gap);
+
+        assert!(report.repaired.is_empty());
+        assert_eq!(report.unrepairable.len(), 1);
+        assert_eq!(report.unrepairable[0].sv, sv!("G01"));
+        assert_eq!(
+            report.unrepairable[0].epoch,
+            Epoch::from_str("2022-01-01T00:05:00 GPST").unwrap()
+        );
+        assert_eq!(repaired.record, rinex.record);
+
+        // gap too large to integrate the Doppler shift
+        let report = cycle_slip_testbench(&[(10, 5.0, true)], &[])
+            .repair_cycle_slips_mut(Duration::from_seconds(10.0));
+        assert!(report.repaired.is_empty());
+        assert_eq!(report.unrepairable.len(), 1);
+        assert_eq!(report.unrepairable[0].cycles, None);
+
+        // repaired slip, followed by a noisy slip:
+        // the first repair still applies to the following arc
+        let l1c = Observable::from_str("L1C").unwrap();
+        let rinex = cycle_slip_testbench(&[(10, 7.0, true), (25, 5.0, true)], &[(25, 0.45)]);
+        let (repaired, report) = rinex.carrier_phase_cycle_slips_repaired(max_gap);
+
+        assert_eq!(report.repaired.len(), 1);
+        assert_eq!(report.repaired[0].cycles, Some(7.0));
+        assert_eq!(report.unrepairable.len(), 1);
+        assert_eq!(
+            report.unrepairable[0].epoch,
+            Epoch::from_str("2022-01-01T00:12:30 GPST").unwrap()
+        );
+
+        for (i, ((_, (_, repaired)), (_, (_, original)))) in
+            repaired.observation().zip(rinex.observation()).enumerate()
+        {
+            let repaired = repaired[&sv!("G01")][&l1c].obs;
+            let original = original[&sv!("G01")][&l1c].obs;
+            let expected = if i >= 10 { original - 7.0 } else { original };
+            assert!((repaired - expected).abs() < 1.0E-6, "epoch #{}", i);
+        }
+    }
+    #[test]
+    fn v3_duth0630_code_multipath() {
+        let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        let mp = rinex.code_multipath();
+
+        // R10 and R23 never come with two phase observations
+        for (code, expected) in [("C1C", 18), ("C2W", 12), ("C2P", 6)] {
+            let observable = Observable::from_str(code).unwrap();
+            let series = mp
+                .get(&observable)
+                .unwrap_or_else(|| panic!("missing {} multipath", code));
+            assert_eq!(
+                series.values().filter(|values| !values.is_empty()).count(),
+                expected,
+                "wrong number of SV for {} multipath",
+                code
+            );
+            for values in series.values() {
+                assert!(values.values().all(|value| value.is_finite()));
+            }
+        }
+        assert_eq!(
+            mp.len(),
+            3,
+            "only pseudo range observables should be combined"
+        );
+    }
+    #[test]
+    fn v3_acor00esp_multipath_rms() {
+        let rinex =
+            Rinex::from_file("../test_resources/OBS/V3/ACOR00ESP_R_20213550000_01D_30S_MO.rnx")
+                .unwrap();
+        let rms = rinex.multipath_rms();
+
+        // only combined pseudo ranges are reported
+        let mp = rinex.code_multipath();
+        for (sv, observable) in rms.keys() {
+            assert!(mp.get(observable).and_then(|mp| mp.get(sv)).is_some());
+        }
+
+        // clean dataset: GPS multipath is decimeter level
+        let c1c = Observable::from_str("C1C").unwrap();
+        let gps_c1c = rms
+            .iter()
+            .filter_map(|((sv, observable), rms)| {
+                if sv.constellation == Constellation::GPS && *observable == c1c {
+                    Some(*rms)
+                } else {
+                    None
+                }
+            })
+            .sorted_by(|a, b| a.partial_cmp(b).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(gps_c1c.len(), 10);
+        for rms in gps_c1c.iter() {
+            assert!(*rms > 0.0 && *rms < 1.0, "unexpected MP RMS {}", rms);
+        }
+        let median = gps_c1c[gps_c1c.len() / 2];
+        assert!(
+            median > 0.01 && median < 0.3,
+            "unexpected MP RMS {}",
+            median
+        );
+
+        let g08_c1c = rms.get(&(sv!("G08"), c1c)).unwrap();
+        assert!(*g08_c1c < 0.05, "unexpected G08 MP RMS {}", g08_c1c);
+    }
+    #[test]
+    fn multipath_rms_arcs() {
+        let g08 = sv!("G08");
+        let c1c = Observable::from_str("C1C").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+
+        // constant observations: G08 L1C jumps by 2 cycles @ 4th epoch,
+        // which is below the arc threshold
+        let mut rinex = Rinex::from_reader(vlns0010_testbench(&[], &[]).as_bytes()).unwrap();
+        for (_, (_, vehicles)) in rinex.record.as_mut_obs().unwrap().iter_mut().skip(4) {
+            vehicles.get_mut(&g08).unwrap().get_mut(&l1c).unwrap().obs += 2.0;
+        }
+        let rms = rinex.multipath_rms();
+        assert!(
+            *rms.get(&(g08, c1c.clone())).unwrap() > 0.1,
+            "undetected slip should bias MP RMS"
+        );
+
+        // flagged lock loss starts a new arc
+        let mut flagged = rinex.clone();
+        let (_, (_, vehicles)) = flagged
+            .record
+            .as_mut_obs()
+            .unwrap()
+            .iter_mut()
+            .nth(4)
+            .unwrap();
+        vehicles.get_mut(&g08).unwrap().get_mut(&l1c).unwrap().lli = Some(LliFlags::LOCK_LOSS);
+        let rms = flagged.multipath_rms();
+        assert!(*rms.get(&(g08, c1c.clone())).unwrap() < 1.0E-6);
+
+        // data gap starts a new arc
+        let mut gapped = rinex.clone();
+        let record = gapped.record.as_mut_obs().unwrap();
+        let key = *record.keys().nth(3).unwrap();
+        record.remove(&key);
+        assert_eq!(
+            gapped.dominant_sample_rate(),
+            Some(Duration::from_seconds(30.0))
+        );
+        let rms = gapped.multipath_rms();
+        assert!(*rms.get(&(g08, c1c)).unwrap() < 1.0E-6);
+    }
+    #[test]
+    fn v2_ajac3550_code_multipath() {
+        let rinex = Rinex::from_file("../test_resources/OBS/V2/AJAC3550.21O").unwrap();
+        let mp = rinex.code_multipath();
+
+        // C1 is combined with L1 and L2
+        let c1 = mp.get(&observable!("C1")).expect("missing C1 multipath");
+        assert!(!c1.is_empty());
+        assert!(c1
+            .get(&sv!("G07"))
+            .map(|values| !values.is_empty())
+            .unwrap_or(false));
+
+        // Galileo does not provide L2: skipped
+        for (sv, values) in c1.iter() {
+            assert!(
+                sv.constellation == Constellation::GPS
+                    || sv.constellation == Constellation::Glonass,
+                "{} has no L2 phase",
+                sv
+            );
+            assert!(values.values().all(|value| value.is_finite()));
+        }
+    }
+    #[test]
+    fn v3_alac00esp_snr_lli() {
+        let rinex =
+            Rinex::from_file("../test_resources/OBS/V3/ALAC00ESP_R_20220090000_01D_30S_MO.rnx")
+                .unwrap();
+
+        let t0 = Epoch::from_str("2022-01-09T00:00:00 GPST").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+        let c1c = Observable::from_str("C1C").unwrap();
+
+        assert!(rinex.snr().count() > 0);
+        assert!(rinex.lli().count() > 0);
+
+        // G01 L1C "117424213.48008"
+        let (_, _, _, snr) = rinex
+            .snr()
+            .find(|((t, _), sv, observable, _)| {
+                *t == t0 && *sv == sv!("G01") && **observable == l1c
+            })
+            .expect("missing G01 L1C SNR");
+        assert_eq!(snr, SNR::DbHz48_53);
+        assert!(snr > 29.0.into());
+        assert!(snr.strong());
+
+        let (_, _, _, lli) = rinex
+            .lli()
+            .find(|((t, _), sv, observable, _)| {
+                *t == t0 && *sv == sv!("G01") && **observable == l1c
+            })
+            .expect("missing G01 L1C LLI");
+        assert_eq!(lli, LliFlags::OK_OR_UNKNOWN);
+
+        // only observations that came with flags are yielded
+        assert!(rinex.snr().all(|((t, _), sv, observable, _)| !(t == t0
+            && sv == sv!("G01")
+            && *observable == c1c)));
+        assert!(rinex.lli().all(|((t, _), sv, observable, _)| !(t == t0
+            && sv == sv!("G01")
+            && *observable == c1c)));
+    }
+    #[test]
+    fn melbourne_wubbena() {
+        let l1c = Observable::from_str("L1C").unwrap();
+        let c1c = Observable::from_str("C1C").unwrap();
+        let l2w = Observable::from_str("L2W").unwrap();
+        let c2w = Observable::from_str("C2W").unwrap();
+        let (lambda_1, lambda_2) = (Carrier::L1.wavelength(), Carrier::L2.wavelength());
+        let t0 = Epoch::from_str("2022-01-01T00:00:00 GPST").unwrap();
+
+        // G01: N1=5, N2=-3, varying range and ionosphere
+        // G02: missing C2W
+        let mut record = Record::new();
+        for i in 0..10 {
+            let rho = 2.2E7 + 150.0 * i as f64;
+            let iono_1 = 5.0 + 0.1 * i as f64;
+            let iono_2 = iono_1 * (lambda_2 / lambda_1).powi(2);
+
+            let mut g01 = HashMap::new();
+            g01.insert(
+                l1c.clone(),
+                ObservationData::new((rho - iono_1) / lambda_1 + 5.0, None, None),
+            );
+            g01.insert(
+                l2w.clone(),
+                ObservationData::new((rho - iono_2) / lambda_2 - 3.0, None, None),
+            );
+            g01.insert(c1c.clone(), ObservationData::new(rho + iono_1, None, None));
+            g01.insert(c2w.clone(), ObservationData::new(rho + iono_2, None, None));
+
+            let mut g02 = g01.clone();
+            g02.remove(&c2w);
+
+            let mut vehicles = BTreeMap::new();
+            vehicles.insert(sv!("G01"), g01);
+            vehicles.insert(sv!("G02"), g02);
+            record.insert(
+                (t0 + Duration::from_seconds(30.0 * i as f64), EpochFlag::Ok),
+                (None, vehicles),
+            );
+        }
+
+        let mut codes = HashMap::new();
+        codes.insert(Constellation::GPS, vec![l1c, c1c, l2w, c2w]);
+        let header = Header::basic_obs().with_observation_fields(HeaderFields {
+            codes,
+            ..Default::default()
+        });
+        let rinex = Rinex::new(header, crate::record::Record::ObsRecord(record));
+
+        let mw = rinex.melbourne_wubbena().collect::<Vec<_>>();
+        assert_eq!(mw.len(), 10);
+        for (i, (t, sv, value)) in mw.iter().enumerate() {
+            assert_eq!(*t, t0 + Duration::from_seconds(30.0 * i as f64));
+            assert_eq!(*sv, sv!("G01"));
+            assert!((value - 8.0).abs() < 1E-6, "bad MW value {}", value);
+        }
+    }
+    #[test]
+    fn melbourne_wubbena_v2_scaled() {
+        let l1 = Observable::from_str("L1").unwrap();
+        let l2 = Observable::from_str("L2").unwrap();
+        let c1 = Observable::from_str("C1").unwrap();
+        let p1 = Observable::from_str("P1").unwrap();
+        let p2 = Observable::from_str("P2").unwrap();
+        let (lambda_1, lambda_2) = (Carrier::L1.wavelength(), Carrier::L2.wavelength());
+        let t0 = Epoch::from_str("2017-01-01T00:00:00 GPST").unwrap();
+
+        // G01: N1=5, N2=-3, phase is expressed with a x100 scaling
+        let mut record = Record::new();
+        for i in 0..10 {
+            let rho = 2.2E7 + 150.0 * i as f64;
+            let iono_1 = 5.0 + 0.1 * i as f64;
+            let iono_2 = iono_1 * (lambda_2 / lambda_1).powi(2);
+
+            let mut g01 = HashMap::new();
+            g01.insert(
+                l1.clone(),
+                ObservationData::new(((rho - iono_1) / lambda_1 + 5.0) * 100.0, None, None),
+            );
+            g01.insert(
+                l2.clone(),
+                ObservationData::new(((rho - iono_2) / lambda_2 - 3.0) * 100.0, None, None),
+            );
+            g01.insert(c1.clone(), ObservationData::new(rho + iono_1, None, None));
+            g01.insert(p1.clone(), ObservationData::new(rho + iono_1, None, None));
+            g01.insert(p2.clone(), ObservationData::new(rho + iono_2, None, None));
+
+            let mut vehicles = BTreeMap::new();
+            vehicles.insert(sv!("G01"), g01);
+            record.insert(
+                (t0 + Duration::from_seconds(30.0 * i as f64), EpochFlag::Ok),
+                (None, vehicles),
+            );
+        }
+
+        let mut codes = HashMap::new();
+        codes.insert(Constellation::GPS, vec![l1.clone(), l2.clone(), c1, p1, p2]);
+        let mut fields = HeaderFields {
+            codes,
+            ..Default::default()
+        };
+        fields.with_scaling(Constellation::GPS, l1, 100);
+        fields.with_scaling(Constellation::GPS, l2, 100);
+        let header = Header::basic_obs()
+            .with_version(crate::version::Version::new(2, 11))
+            .with_observation_fields(fields);
+        let rinex = Rinex::new(header, crate::record::Record::ObsRecord(record));
+
+        let mw = rinex.melbourne_wubbena().collect::<Vec<_>>();
+        assert_eq!(mw.len(), 10);
+        for (i, (t, sv, value)) in mw.iter().enumerate() {
+            assert_eq!(*t, t0 + Duration::from_seconds(30.0 * i as f64));
+            assert_eq!(*sv, sv!("G01"));
+            assert!((value - 8.0).abs() < 1E-6, "bad MW value {}", value);
+        }
+    }
+    #[test]
+    fn complete_epoch() {
+        let t0 = Epoch::from_str("2022-01-01T00:00:00 GPST").unwrap();
+        let (strong, weak) = (Some(SNR::from(45.0)), Some(SNR::from(20.0)));
+
+        let signals = |codes: &[&str], snr: Option<SNR>| {
+            codes
+                .iter()
+                .map(|code| {
+                    (
+                        Observable::from_str(code).unwrap(),
+                        ObservationData::new(1.0, None, snr),
+                    )
+                })
+                .collect::<HashMap<_, _>>()
+        };
+
+        let mut vehicles = BTreeMap::new();
+        // complete on L2
+        vehicles.insert(sv!("G01"), signals(&["C1C", "L1C", "C2W", "L2W"], strong));
+        // missing L2 phase
+        vehicles.insert(sv!("G02"), signals(&["C1C", "L1C", "C2W"], strong));
+        // complete on L5 and L2
+        vehicles.insert(
+            sv!("G03"),
+            signals(&["C5Q", "L5Q", "C1C", "L1C", "C2W", "L2W"], strong),
+        );
+        // complete on L2, but weak L2 phase
+        let mut g04 = signals(&["C1C", "L1C", "C2W"], strong);
+        g04.extend(signals(&["L2W"], weak));
+        vehicles.insert(sv!("G04"), g04);
+        // complete on L2, but no SNR indication
+        vehicles.insert(sv!("G05"), signals(&["C1C", "L1C", "C2W", "L2W"], None));
+
+        let mut record = Record::new();
+        record.insert((t0, EpochFlag::Ok), (None, vehicles.clone()));
+        record.insert(
+            (t0 + Duration::from_seconds(30.0), EpochFlag::PowerFailure),
+            (None, vehicles.clone()),
+        );
+        // L1 only
+        let mut l1_only = BTreeMap::new();
+        l1_only.insert(sv!("G01"), signals(&["C1C", "L1C"], strong));
+        record.insert(
+            (t0 + Duration::from_seconds(60.0), EpochFlag::Ok),
+            (None, l1_only),
+        );
+
+        let header = Header::basic_obs().with_observation_fields(HeaderFields::default());
+        let rinex = Rinex::new(header, crate::record::Record::ObsRecord(record));
+
+        let complete = rinex.complete_epoch(None).collect::<Vec<_>>();
+        assert_eq!(
+            complete,
+            vec![(
+                t0,
+                vec![
+                    (sv!("G01"), Carrier::L2),
+                    (sv!("G03"), Carrier::L2),
+                    (sv!("G03"), Carrier::L5),
+                    (sv!("G04"), Carrier::L2),
+                    (sv!("G05"), Carrier::L2),
+                ]
+            )],
+            "epochs with event flags or single frequency should not be listed"
+        );
+
+        let complete = rinex
+            .complete_epoch(Some(SNR::from(30.0)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            complete,
+            vec![(
+                t0,
+                vec![
+                    (sv!("G01"), Carrier::L2),
+                    (sv!("G03"), Carrier::L2),
+                    (sv!("G03"), Carrier::L5),
+                ]
+            )],
+            "weak or unknown SNR should not be listed"
+        );
+    }
+    #[test]
+    fn v3_acor00esp_complete_epoch() {
+        let rinex =
+            Rinex::from_file("../test_resources/OBS/V3/ACOR00ESP_R_20213550000_01D_30S_MO.rnx")
+                .unwrap();
+        let record = rinex.record.as_obs().unwrap();
+
+        let has_signal = |t: Epoch, sv: SV, carrier: Carrier| -> (bool, bool) {
+            let (_, vehicles) = record.get(&(t, EpochFlag::Ok)).unwrap();
+            let observations = vehicles.get(&sv).unwrap();
+            let mut pr_ph = (false, false);
+            for observable in observations.keys() {
+                if Carrier::from_observable(sv.constellation, observable) == Ok(carrier) {
+                    pr_ph.0 |= observable.is_pseudorange_observable();
+                    pr_ph.1 |= observable.is_phase_observable();
+                }
+            }
+            pr_ph
+        };
+
+        let mut count = 0;
+        for (t, signals) in rinex.complete_epoch(None) {
+            assert!(!signals.is_empty());
+            for (sv, carrier) in signals {
+                assert_ne!(carrier, Carrier::L1, "L1 is the primary signal");
+                assert_eq!(has_signal(t, sv, Carrier::L1), (true, true), "{} {}", t, sv);
+                assert_eq!(has_signal(t, sv, carrier), (true, true), "{} {}", t, sv);
+                count += 1;
+            }
+        }
+        assert!(count > 0, "dual frequency data expected");
+
+        let gated = rinex
+            .complete_epoch(Some(SNR::from(40.0)))
+            .map(|(_, signals)| signals.len())
+            .sum::<usize>();
+        assert!(gated <= count);
+    }
+    #[test]
+    fn pseudorange_units() {
+        let prefix = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("test_resources")
+            .join("OBS")
+            .join("V3");
+
+        let meters =
+            Rinex::from_file(prefix.join("DUTH0630.22O").to_string_lossy().as_ref()).unwrap();
+        assert_eq!(meters.detect_pseudorange_units(), PseudorangeUnit::Meters);
+
+        // same content, with pseudo ranges expressed in kilometers
+        let mut km = meters.clone();
+        for (_, (_, vehicles)) in km.record.as_mut_obs().unwrap().iter_mut() {
+            for (_, observations) in vehicles.iter_mut() {
+                for (observable, data) in observations.iter_mut() {
+                    if observable.is_pseudorange_observable() {
+                        data.obs /= 1.0E3;
+                    }
+                }
+            }
+        }
+        assert_eq!(km.detect_pseudorange_units(), PseudorangeUnit::Kilometers);
+
+        assert_eq!(
+            km.normalize_pseudorange_units_mut(),
+            PseudorangeUnit::Kilometers
+        );
+        assert_eq!(km.detect_pseudorange_units(), PseudorangeUnit::Meters);
+
+        // normalizing twice has no effect
+        assert_eq!(
+            km.normalize_pseudorange_units_mut(),
+            PseudorangeUnit::Meters
+        );
+
+        let expected = meters
+            .pseudo_range()
+            .map(|(k, sv, obs, value)| ((k, sv, obs.clone()), value))
+            .collect::<HashMap<_, _>>();
+        let mut count = 0;
+        for (k, sv, obs, value) in km.pseudo_range() {
+            let expected = expected
+                .get(&(k, sv, obs.clone()))
+                .unwrap_or_else(|| panic!("missing {:?} {} {}", k, sv, obs));
+            assert!(
+                (value - expected).abs() < 1.0E-3,
+                "{:?} {} {}: {} != {}",
+                k,
+                sv,
+                obs,
+                value,
+                expected
+            );
+            count += 1;
+        }
+        assert_eq!(count, expected.len());
+        // other observables are preserved
+        assert_eq!(km.carrier_phase().count(), meters.carrier_phase().count());
+    }
+    #[test]
+    fn recvr_clock() {
+        let mut rinex = Rinex::from_reader(vlns0010_testbench(&[], &[]).as_bytes()).unwrap();
+        let expected = vec![
+            0.000450, 0.000462, 0.000474, 0.000486, -0.000502, -0.000490, -0.000478, -0.000466,
+        ];
+
+        let clocks = rinex.recvr_clock().collect::<Vec<_>>();
+        assert_eq!(clocks.len(), expected.len());
+        assert!(
+            clocks.windows(2).all(|w| w[0].0 .0 < w[1].0 .0),
+            "not chronological"
+        );
+        for ((_, clk), expected) in clocks.iter().zip(expected.iter()) {
+            assert!((clk - expected).abs() < 1.0E-12);
+        }
+
+        let t0 = Epoch::from_str("2022-01-01T00:00:00 GPST").unwrap();
+        assert_eq!(clocks[0].0, (t0, EpochFlag::Ok));
+
+        // epochs without clock offset are not exposed
+        let record = rinex.record.as_mut_obs().unwrap();
+        let (_, (clk, _)) = record.iter_mut().next().unwrap();
+        *clk = None;
+        let clocks = rinex.recvr_clock().collect::<Vec<_>>();
+        assert_eq!(clocks.len(), expected.len() - 1);
+        assert!(clocks.iter().all(|((t, _), _)| *t != t0));
+    }
+    #[test]
+    fn clock_steering_events() {
+        let rinex = Rinex::from_reader(vlns0010_testbench(&[], &[]).as_bytes()).unwrap();
+        assert_eq!(rinex.recvr_clock().count(), 8);
+
+        let events = rinex.clock_steering_events();
+        assert_eq!(events.len(), 1, "one millisecond reset expected");
+
+        let (t, step) = events[0];
+        assert_eq!(t, Epoch::from_str("2022-01-01T00:02:00 GPST").unwrap());
+        assert!((step - -1.0E-3).abs() < 1.0E-9, "bad step size {}", step);
+
+        // null offsets: no event
+        let rinex = Rinex::from_file("../test_resources/OBS/V3/VLNS0010.22O").unwrap();
+        assert!(rinex.recvr_clock().count() > 0);
+        assert!(rinex.clock_steering_events().is_empty());
+
+        // no offsets
+        let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        assert!(rinex.clock_steering_events().is_empty());
+    }
+    #[test]
+    fn complete_epoch_filter() {
+        let mut rinex = Rinex::from_reader(
+            vlns0010_testbench(&[(2, "G08"), (4, "G08"), (4, "G10")], &[(6, 1)]).as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(rinex.epoch().count(), 8);
+
+        rinex.complete_epoch_filter_mut(None);
+
+        // 00:02:00 lacks L2 phase, 00:03:00 is flagged
+        let epochs = rinex.epoch().collect::<Vec<_>>();
+        let t0 = Epoch::from_str("2022-01-01T00:00:00 GPST").unwrap();
+        let expected = [0.0, 30.0, 60.0, 90.0, 150.0, 210.0]
+            .iter()
+            .map(|dt| t0 + Duration::from_seconds(*dt))
+            .collect::<Vec<_>>();
+        assert_eq!(epochs, expected);
+
+        let t = t0 + Duration::from_seconds(60.0);
+        for ((e, flag), (_, vehicles)) in rinex.observation() {
+            assert!(flag.is_ok());
+            if *e == t {
+                assert_eq!(
+                    vehicles.keys().copied().collect::<Vec<_>>(),
+                    vec![sv!("G10")],
+                    "G08 lacks L2 phase"
+                );
+            } else {
+                assert_eq!(vehicles.len(), 2);
+            }
+            for observables in vehicles.values() {
+                // C1C, L1C, S1C, C2W, L2W, S2W
+                assert_eq!(observables.len(), 6);
+            }
+        }
+    }
+    #[test]
+    fn phase_align_origin() {
+        let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        let aligned = rinex.observation_phase_align_origin();
+
+        let mut origins = HashMap::<(SV, Observable), f64>::new();
+        for ((e, flag), (_, vehicles)) in rinex.observation() {
+            for (sv, observations) in vehicles {
+                for (observable, data) in observations {
+                    let value =
+                        aligned.record.as_obs().unwrap()[&(*e, *flag)].1[sv][observable].obs;
+                    if observable.is_phase_observable() {
+                        match origins.get(&(*sv, observable.clone())) {
+                            Some(origin) => {
+                                assert_eq!(value, data.obs - origin);
+                            },
+                            None => {
+                                assert_eq!(
+                                    value, 0.0,
+                                    "{}({}) should start at zero",
+                                    sv, observable
+                                );
+                                origins.insert((*sv, observable.clone()), data.obs);
+                            },
+                        }
+                    } else {
+                        assert_eq!(value, data.obs, "only phase should be modified");
+                    }
+                }
+            }
+        }
+        assert!(!origins.is_empty());
+    }
+    #[test]
+    fn phase_align_origin_meters() {
+        let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        // normalized phase, in meters: both operations commute
+        let aligned = rinex
+            .observation_phase_to_meters()
+            .observation_phase_align_origin();
+        let reversed = rinex
+            .observation_phase_align_origin()
+            .observation_phase_to_meters();
+
+        let mut started = Vec::<(SV, Observable)>::new();
+        for ((k, (_, vehicles)), (_, (_, vehicles_r))) in
+            aligned.observation().zip(reversed.observation())
+        {
+            for (sv, observations) in vehicles {
+                for (observable, data) in observations {
+                    if !observable.is_phase_observable() {
+                        continue;
+                    }
+                    let key = (*sv, observable.clone());
+                    if !started.contains(&key) {
+                        assert_eq!(data.obs, 0.0, "{}({}) should start at zero", sv, observable);
+                        started.push(key);
+                    }
+                    let other = vehicles_r[sv][observable].obs;
+                    assert!(
+                        (data.obs - other).abs() < 1.0E-3,
+                        "{}({}) {}: {} != {}",
+                        k.0,
+                        sv,
+                        observable,
+                        data.obs,
+                        other
+                    );
+                }
+            }
+        }
+        assert!(!started.is_empty());
+    }
+    #[test]
+    fn epoch_anomalies() {
+        let rinex = Rinex::from_reader(
+            vlns0010_testbench(&[(2, "G08"), (4, "G08"), (4, "G10")], &[(6, 1)]).as_bytes(),
+        )
+        .unwrap();
+        let t0 = Epoch::from_str("2022-01-01T00:00:00 GPST").unwrap();
+        let t = t0 + Duration::from_seconds(180.0);
+
+        let anomalies = rinex.epoch_anomalies().collect::<Vec<_>>();
+        assert_eq!(anomalies, vec![(t, EpochFlag::PowerFailure)]);
+
+        let ok = rinex.epoch_ok().collect::<Vec<_>>();
+        assert_eq!(ok.len(), 7);
+        assert!(!ok.contains(&t));
+        assert_eq!(ok.len() + anomalies.len(), rinex.epoch().count());
+
+        // nominal file
+        let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        assert_eq!(rinex.epoch_anomalies().count(), 0);
+        assert_eq!(rinex.epoch_ok().count(), rinex.epoch().count());
+    }
+    #[test]
+    fn synthetic_epoch_anomalies() {
+        let mut rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        let epochs = rinex.epoch().collect::<Vec<_>>();
+        let (t_cs, t_pwr) = (epochs[0], epochs[1]);
+
+        let record = rinex.record.as_mut_obs().unwrap();
+        let entry = record.remove(&(t_cs, EpochFlag::Ok)).unwrap();
+        record.insert((t_cs, EpochFlag::CycleSlip), entry);
+        let entry = record.remove(&(t_pwr, EpochFlag::Ok)).unwrap();
+        record.insert((t_pwr, EpochFlag::PowerFailure), entry);
+
+        assert_eq!(
+            rinex.epoch_anomalies().collect::<Vec<_>>(),
+            vec![
+                (t_cs, EpochFlag::CycleSlip),
+                (t_pwr, EpochFlag::PowerFailure)
+            ]
+        );
+        assert_eq!(rinex.epoch_cs().collect::<Vec<_>>(), vec![t_cs]);
+        assert_eq!(rinex.epoch_ok().count(), epochs.len() - 2);
+    }
+    #[test]
+    fn observation_noise() {
+        let rinex = synthetic_1hz_testbench();
+        let window = Duration::from_seconds(30.0);
+
+        let noise = rinex.observation_noise(window);
+        let keys = noise.keys().cloned().collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec![
+                (Constellation::GPS, observable!("C1C")),
+                (Constellation::GPS, observable!("L1C")),
+            ],
+            "only phase and pseudo range should be estimated"
+        );
+
+        // simulated: 0.5 m code and 0.01 cycle phase noise
+        let code = noise[&(Constellation::GPS, observable!("C1C"))];
+        let phase = noise[&(Constellation::GPS, observable!("L1C"))];
+        assert!(code > 0.4 && code < 0.65, "bad code noise {}", code);
+        assert!(phase > 0.007 && phase < 0.013, "bad phase noise {}", phase);
+        assert!(code > 10.0 * phase);
+
+        // deterministic
+        assert_eq!(rinex.observation_noise(window), noise);
+
+        // arcs shorter than window
+        assert!(rinex
+            .observation_noise(Duration::from_hours(1.0))
+            .is_empty());
+    }
+    #[test]
+    fn obs_stream() {
+        for (dir, filename) in [
+            ("OBS/V2", "aopr0010.17o"),
+            ("OBS/V3", "DUTH0630.22O"),
+            ("CRNX/V3", "ACOR00ESP_R_20213550000_01D_30S_MO.crx"),
+        ] {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("..")
+                .join("test_resources")
+                .join(dir)
+                .join(filename);
+            let fullpath = path.to_string_lossy();
+
+            let rinex = Rinex::from_file(fullpath.as_ref()).unwrap();
+            let record = rinex.record.as_obs().unwrap();
+
+            let stream = Rinex::epochs_iter_from_file(fullpath.as_ref()).unwrap();
+            assert_eq!(stream.header().obs, rinex.header.obs);
+
+            let streamed = stream.filter_map(|entry| entry.ok()).collect::<Record>();
+            assert_eq!(&streamed, record, "streamed {} differs", filename);
+        }
+    }
+    #[test]
+    fn v3_reference_frame() {
+        // frame is not declared
+        let rinex = Rinex::from_file("../test_resources/OBS/V3/VLNS0010.22O").unwrap();
+        assert_eq!(rinex.header.reference_frame(), None);
+
+        let position = rinex.header.ground_position.unwrap();
+        assert_eq!(position.reference_frame(), ReferenceFrame::WGS84);
+
+        // free text is not interpreted
+        let content = std::fs::read_to_string("../test_resources/OBS/V3/VLNS0010.22O").unwrap();
+        let content = content.replacen(
+            "TPP 3.5.8",
+            &format!(
+                "{:<60}COMMENT\nTPP 3.5.8",
+                "APPROX POSITION XYZ REFERRED TO ITRF2014"
+            ),
+            1,
+        );
+        let rinex = Rinex::from_reader(content.as_bytes()).unwrap();
+        assert_eq!(rinex.header.reference_frame(), None);
+
+        let position = rinex.header.ground_position.unwrap();
+        assert_eq!(position.reference_frame(), ReferenceFrame::WGS84);
+        assert_eq!(
+            position.to_ecef_wgs84(),
+            (3343600.9781, 1580417.5602, 5179337.1310),
+        );
+
+        for (frame, expected) in [
+            ("WGS-84", ReferenceFrame::WGS84),
+            ("ITRF96", ReferenceFrame::ITRF(1996)),
+            ("ITRF2020", ReferenceFrame::ITRF(2020)),
+            ("IGS14", ReferenceFrame::ITRF(2014)),
+            ("IGb08", ReferenceFrame::ITRF(2008)),
+        ] {
+            assert_eq!(ReferenceFrame::from_str(frame).unwrap(), expected);
+        }
+        assert!(ReferenceFrame::from_str("ETRS89").is_err());
+    }
+    #[test]
+    fn lock_loss_filter() {
+        let rinex = Rinex::from_file("../test_resources/OBS/V3/LARM0010.22O").unwrap();
+        let filtered = rinex.lock_loss_filter();
+
+        let t0 = Epoch::from_str("2022-01-01T00:00:00 GPST").unwrap();
+        let t1 = Epoch::from_str("2022-01-01T00:01:30 GPST").unwrap();
+        let expected = vec![
+            (t0, sv!("R02"), observable!("L2P")),
+            (t1, sv!("R22"), observable!("L2C")),
+        ];
+
+        let mut retained = Vec::new();
+        for ((t, _), (_, vehicles)) in filtered.observation() {
+            for (sv, observations) in vehicles {
+                for (observable, data) in observations {
+                    assert!(data.lli.unwrap().intersects(LliFlags::LOCK_LOSS));
+                    retained.push((*t, *sv, observable.clone()));
+                }
+            }
+        }
+        assert_eq!(
+            retained, expected,
+            "only flagged observations should remain"
+        );
+
+        // mutable and masking implementations are consistent
+        let mut rinex = rinex;
+        let masked = rinex.lli_and_mask(LliFlags::LOCK_LOSS);
+        rinex.lock_loss_filter_mut();
+        assert_eq!(rinex, masked);
+        assert_eq!(rinex, filtered);
+    }
+    #[test]
+    fn lli_mask_preserves_events_and_clocks() {
+        // no lock loss in this file, power failure @ 6th epoch
+        let rinex = Rinex::from_reader(vlns0010_testbench(&[], &[(6, 1)]).as_bytes()).unwrap();
+        let masked = rinex.lock_loss_filter();
+
+        // all epochs come with a receiver clock offset
+        assert_eq!(masked.epoch().count(), 8);
+        assert_eq!(masked.recvr_clock().count(), 8);
+        for (k, (_, vehicles)) in masked.observation() {
+            if k.1 == EpochFlag::PowerFailure {
+                let (_, original) = rinex.record.as_obs().unwrap().get(k).unwrap();
+                assert_eq!(vehicles, original, "event epoch should be preserved");
+            } else {
+                assert!(vehicles.is_empty(), "{:?}: should be masked", k);
+            }
+        }
+    }
+    #[test]
+    fn drop_sentinel_values() {
+        let original = Rinex::from_file("../test_resources/OBS/V3/VLNS0010.22O").unwrap();
+        // first epoch: null G08 pseudo range and saturated R22 phase
+        let content = std::fs::read_to_string("../test_resources/OBS/V3/VLNS0010.22O").unwrap();
+        let content = content
+            .replacen("G08  20982937.082", "G08         0.000", 1)
+            .replacen(
+                "R22  24004891.660   128139740.043",
+                "R22  24004891.660  9999999999.999",
+                1,
+            );
+        let rinex = Rinex::from_reader(content.as_bytes()).unwrap();
+
+        let t0 = Epoch::from_str("2022-01-01T00:00:00 GPST").unwrap();
+        let (c1c, l1c) = (observable!("C1C"), observable!("L1C"));
+        let count = |rinex: &Rinex| {
+            rinex
+                .observation()
+                .map(|(_, (_, vehicles))| vehicles.values().map(|obs| obs.len()).sum::<usize>())
+                .sum::<usize>()
+        };
+
+        let blanked = rinex.drop_sentinel_values();
+        assert_eq!(count(&blanked), count(&rinex) - 2);
+
+        for ((k, (_, vehicles)), (_, (_, original_vehicles))) in
+            blanked.observation().zip(original.observation())
+        {
+            for (sv, observations) in vehicles {
+                if k.0 == t0 && *sv == sv!("G08") {
+                    assert!(observations.get(&c1c).is_none(), "null pseudo range");
+                    assert!(observations.get(&l1c).is_some());
+                }
+                if k.0 == t0 && *sv == sv!("R22") {
+                    assert!(observations.get(&l1c).is_none(), "saturated phase");
+                    assert!(observations.get(&c1c).is_some());
+                }
+                // valid data is preserved
+                for (observable, data) in observations {
+                    assert_eq!(data, &original_vehicles[sv][observable]);
+                }
+            }
+        }
+
+        // custom sentinels
+        let mut rinex = rinex;
+        rinex.drop_sentinels_mut(&Sentinels::default().with_overflow(None));
+        assert_eq!(count(&rinex), count(&blanked) + 1);
+    }
+    #[test]
+    fn retain_min_sv_epochs() {
+        let mut rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+
+        // only 3 vehicles remain in the first epoch
+        let record = rinex.record.as_mut_obs().unwrap();
+        let (t0, (_, vehicles)) = record.iter_mut().next().unwrap();
+        let t0 = *t0;
+        let kept = vehicles.keys().take(3).copied().collect::<Vec<_>>();
+        vehicles.retain(|sv, _| kept.contains(sv));
+
+        let epochs = rinex.epoch().count();
+        assert!(rinex.observation().skip(1).all(|(_, (_, v))| v.len() >= 4));
+
+        let dut = rinex.retain_min_sv_epochs(3);
+        assert_eq!(dut.epoch().count(), epochs);
+
+        let dut = rinex.retain_min_sv_epochs(4);
+        assert_eq!(dut.epoch().count(), epochs - 1);
+        assert!(dut.observation().all(|(k, _)| *k != t0));
+
+        // per constellation
+        let gps = rinex
+            .observation()
+            .filter(|(_, (_, vehicles))| {
+                vehicles
+                    .keys()
+                    .filter(|sv| sv.constellation == Constellation::GPS)
+                    .count()
+                    >= 4
+            })
+            .count();
+        let dut = rinex.retain_min_constellation_sv_epochs(Constellation::GPS, 4);
+        assert_eq!(dut.epoch().count(), gps);
+        assert!(
+            dut.constellation().count() > 1,
+            "other constellations should be preserved"
+        );
+    }
+    #[test]
+    fn v2_ajac3550_phase_to_meters() {
+        let rinex = Rinex::from_file("../test_resources/OBS/V2/AJAC3550.21O").unwrap();
+        let meters = rinex.observation_phase_to_meters();
+        assert_ne!(meters, rinex);
+
+        let l1 = observable!("L1");
+        let lambda_l1 = Carrier::L1.wavelength();
+        for ((k, (_, vehicles)), (_, (_, vehicles_m))) in
+            rinex.observation().zip(meters.observation())
+        {
+            for (sv, observations) in vehicles {
+                if sv.constellation != Constellation::GPS {
+                    continue;
+                }
+                if let Some(data) = observations.get(&l1) {
+                    let converted = vehicles_m[sv][&l1].obs;
+                    assert!(
+                        (converted - data.obs * lambda_l1).abs() < 1.0E-6,
+                        "{}({}): bad L1 conversion",
+                        k.0,
+                        sv
+                    );
+                }
+            }
+        }
+
+        // round trip, relative precision
+        let cycles = meters.observation_phase_to_cycles();
+        for ((k, (_, vehicles)), (_, (_, vehicles_c))) in
+            rinex.observation().zip(cycles.observation())
+        {
+            for (sv, observations) in vehicles {
+                for (observable, data) in observations {
+                    let restored = vehicles_c[sv][observable].obs;
+                    let err = (restored - data.obs).abs() / data.obs.abs().max(1.0);
+                    assert!(
+                        err < 1.0E-9,
+                        "{}({}) {}: round trip error {}",
+                        k.0,
+                        sv,
+                        observable,
+                        err
+                    );
+                }
+            }
+        }
+    }
+    #[test]
+    fn v3_acor00esp_combinations() {
+        let mut rinex =
+            Rinex::from_file("../test_resources/OBS/V3/ACOR00ESP_R_20213550000_01D_30S_MO.rnx")
+                .unwrap();
+
+        let l1c = Observable::from_str("L1C").unwrap();
+        let l2w = Observable::from_str("L2W").unwrap();
+        let c1c = Observable::from_str("C1C").unwrap();
+        let c2w = Observable::from_str("C2W").unwrap();
+        let dt = Duration::from_seconds(30.0);
+
+        // (Epoch, SV) where L1C or L2W tracking was lost
+        let mut lock_loss = Vec::<(Epoch, SV)>::new();
+        for ((t, _), (_, vehicles)) in rinex.observation() {
+            for (sv, observations) in vehicles {
+                for (observable, data) in observations {
+                    if *observable != l1c && *observable != l2w {
+                        continue;
+                    }
+                    if let Some(lli) = data.lli {
+                        if lli.intersects(LliFlags::LOCK_LOSS) {
+                            lock_loss.push((*t, *sv));
+                        }
+                    }
+                }
+            }
+        }
+        assert!(!lock_loss.is_empty(), "this file contains cycle slips");
+
+        let gf = rinex.combine(Combination::GeometryFree);
+        let gf_l2w = gf
+            .get(&(l2w.clone(), l1c.clone()))
+            .expect("missing L2W/L1C geometry free combination");
+        assert!(gf
+            .keys()
+            .all(|(lhs, rhs)| lhs.is_phase_observable() == rhs.is_phase_observable()));
+
+        let mut nb_steps = 0;
+        for (sv, values) in gf_l2w {
+            assert_eq!(sv.constellation, Constellation::GPS);
+            for (((t0, _), gf0), ((t1, _), gf1)) in values.iter().tuple_windows() {
+                if *t1 - *t0 != dt || lock_loss.contains(&(*t1, *sv)) {
+                    continue;
+                }
+                nb_steps += 1;
+                assert!(
+                    (gf1 - gf0).abs() < 0.1,
+                    "{}({}) gf discontinuity: {} m",
+                    t1,
+                    sv,
+                    gf1 - gf0
+                );
+            }
+        }
+        assert!(nb_steps > 100);
+
+        // wide lane phase & narrow lane code
+        let wl = rinex.combine(Combination::WideLane);
+        assert!(wl.get(&(l2w.clone(), l1c.clone())).is_some());
+        let nl = rinex.combine(Combination::NarrowLane);
+        let nl_c2w = nl
+            .get(&(c2w.clone(), c1c.clone()))
+            .expect("missing C2W/C1C narrow lane combination");
+        for (_, values) in nl_c2w {
+            for (_, value) in values {
+                assert!(
+                    *value > 1.9E7 && *value < 2.7E7,
+                    "bad narrow lane: {}",
+                    value
+                );
+            }
+        }
+
+        // introduce a single L1C cycle slip on G01
+        let g01 = sv!("G01");
+        let t_slip = *gf
+            .get(&(l2w.clone(), l1c.clone()))
+            .and_then(|values| values.get(&g01))
+            .and_then(|values| values.keys().nth(10))
+            .map(|(t, _)| t)
+            .unwrap();
+        let record = rinex.record.as_mut_obs().unwrap();
+        for ((t, _), (_, vehicles)) in record.iter_mut() {
+            if *t >= t_slip {
+                if let Some(data) = vehicles.get_mut(&g01).and_then(|obs| obs.get_mut(&l1c)) {
+                    data.obs += 1.0;
+                }
+            }
+        }
+        let lambda_l1 = Carrier::L1.wavelength();
+        let slipped = rinex.combine(Combination::GeometryFree);
+        let slipped = slipped
+            .get(&(l2w.clone(), l1c.clone()))
+            .and_then(|values| values.get(&g01))
+            .unwrap();
+        let original = gf_l2w.get(&g01).unwrap();
+        for ((t, flag), value) in slipped {
+            let err = value - original.get(&(*t, *flag)).unwrap();
+            if *t >= t_slip {
+                assert!((err - lambda_l1).abs() < 1.0E-6);
+            } else {
+                assert!(err.abs() < 1.0E-6);
+            }
+        }
+    }
+    #[test]
+    fn hatch_smoothing() {
+        let mut rinex = synthetic_1hz_testbench();
+        let g01 = sv!("G01");
+        let g02 = sv!("G02");
+        let c1c = Observable::from_str("C1C").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+
+        // G02: phase is missing @ 100th epoch
+        // all vehicles: cycle slip epoch @ 200th epoch
+        let record = rinex.record.as_mut_obs().unwrap();
+        let t_missing = record.keys().nth(100).unwrap().0;
+        let t_cs = record.keys().nth(200).unwrap().0;
+        if let Some((_, vehicles)) = record.get_mut(&(t_missing, EpochFlag::Ok)) {
+            vehicles.get_mut(&g02).unwrap().remove(&l1c);
+        }
+        let entry = record.remove(&(t_cs, EpochFlag::Ok)).unwrap();
+        record.insert((t_cs, EpochFlag::CycleSlip), entry);
+
+        let smoothed = rinex.hatch_smoothing();
+        let record = rinex.record.as_obs().unwrap();
+        let smoothed_record = smoothed.record.as_obs().unwrap();
+
+        let mut resets = Vec::<(Epoch, SV)>::new();
+        for (index, (k, (_, vehicles))) in record.iter().enumerate() {
+            let (_, smoothed_vehicles) = smoothed_record.get(k).unwrap();
+            for (sv, observations) in vehicles {
+                let raw = observations.get(&c1c).unwrap().obs;
+                let smoothed = smoothed_vehicles
+                    .get(sv)
+                    .and_then(|obs| obs.get(&c1c))
+                    .unwrap()
+                    .obs;
+                let lock_loss = observations
+                    .get(&l1c)
+                    .and_then(|data| data.lli)
+                    .map(|lli| lli.intersects(LliFlags::LOCK_LOSS))
+                    .unwrap_or(false);
+                let has_phase = observations.contains_key(&l1c);
+
+                if index == 0 || !k.1.is_ok() || !has_phase || lock_loss {
+                    // first sample, invalid phase or reset: raw value
+                    assert_eq!(smoothed, raw, "{}({}) @ {}", sv, c1c, k.0);
+                    if has_phase && k.1.is_ok() {
+                        resets.push((k.0, *sv));
+                    }
+                } else if (index == 101 && *sv == g02) || index == 201 {
+                    // first sample following a reset
+                    assert_eq!(smoothed, raw, "{}({}) @ {}", sv, c1c, k.0);
+                } else {
+                    assert_ne!(smoothed, raw, "{}({}) @ {} not smoothed", sv, c1c, k.0);
+                    // smoothed values remain close to raw values (sigma=0.5m)
+                    assert!((smoothed - raw).abs() < 5.0, "{}({}) @ {}", sv, c1c, k.0);
+                }
+                // phase is preserved
+                assert_eq!(
+                    observations.get(&l1c),
+                    smoothed_vehicles.get(sv).and_then(|obs| obs.get(&l1c))
+                );
+            }
+        }
+        // initial samples and G01 lock loss
+        assert_eq!(resets.len(), 3, "{:?}", resets);
+        assert!(resets.iter().filter(|(_, sv)| *sv == g01).count() == 2);
+    }
+    #[test]
+    fn hatch_smoothing_window() {
+        let rinex = synthetic_1hz_testbench();
+        let g02 = sv!("G02");
+        let c1c = Observable::from_str("C1C").unwrap();
+
+        // single sample window: nothing to smooth
+        assert_eq!(rinex.hatch_smoothing_window(1), rinex);
+
+        let pseudo_range = |rinex: &Rinex| {
+            rinex
+                .observation()
+                .map(|(_, (_, vehicles))| vehicles.get(&g02).unwrap().get(&c1c).unwrap().obs)
+                .collect::<Vec<_>>()
+        };
+        let capped = pseudo_range(&rinex.hatch_smoothing());
+        let unbounded = pseudo_range(&rinex.hatch_smoothing_window(u32::MAX));
+
+        // G02 is never reset: both filters match until the default window (100) is reached
+        for (index, (capped, unbounded)) in capped.iter().zip(unbounded.iter()).enumerate() {
+            if index < 100 {
+                assert_eq!(capped, unbounded, "@{}", index);
+            } else {
+                assert_ne!(capped, unbounded, "@{}", index);
+            }
+        }
+    }
+    #[test]
+    fn hatch_smoothing_data_gap() {
+        let mut rinex = synthetic_1hz_testbench();
+        let g01 = sv!("G01");
+        let g02 = sv!("G02");
+        let c1c = Observable::from_str("C1C").unwrap();
+
+        // G02 is not tracked from 50th to 54th epoch
+        let record = rinex.record.as_mut_obs().unwrap();
+        for (_, (_, vehicles)) in record.iter_mut().skip(50).take(5) {
+            vehicles.remove(&g02);
+        }
+        let pseudo_range = |rinex: &Rinex, sv: SV, index: usize| {
+            let (_, (_, vehicles)) = rinex.observation().nth(index).unwrap();
+            vehicles.get(&sv).unwrap().get(&c1c).unwrap().obs
+        };
+
+        // dominant 1s sampling: G02 filter is reset when tracking resumes
+        let smoothed = rinex.hatch_smoothing();
+        assert_eq!(
+            pseudo_range(&smoothed, g02, 55),
+            pseudo_range(&rinex, g02, 55)
+        );
+        assert_ne!(
+            pseudo_range(&smoothed, g02, 56),
+            pseudo_range(&rinex, g02, 56)
+        );
+        assert_ne!(
+            pseudo_range(&smoothed, g01, 55),
+            pseudo_range(&rinex, g01, 55)
+        );
+
+        // declared sampling interval prevails: this gap is tolerated
+        rinex.header.sampling_interval = Some(Duration::from_seconds(10.0));
+        let smoothed = rinex.hatch_smoothing();
+        assert_ne!(
+            pseudo_range(&smoothed, g02, 55),
+            pseudo_range(&rinex, g02, 55)
+        );
+    }
+    #[test]
+    fn hatch_smoothing_glonass() {
+        let mut rinex = synthetic_1hz_testbench();
+        let g02 = sv!("G02");
+        let r02 = sv!("R02");
+        let c1c = Observable::from_str("C1C").unwrap();
+
+        // G02 becomes a Glonass vehicle
+        for (_, (_, vehicles)) in rinex.record.as_mut_obs().unwrap().iter_mut() {
+            let observations = vehicles.remove(&g02).unwrap();
+            vehicles.insert(r02, observations);
+        }
+        let pseudo_range = |rinex: &Rinex| {
+            rinex
+                .observation()
+                .map(|(_, (_, vehicles))| vehicles.get(&r02).unwrap().get(&c1c).unwrap().obs)
+                .collect::<Vec<_>>()
+        };
+        let raw = pseudo_range(&rinex);
+
+        // FDMA channel is unknown: left untouched
+        let smoothed = pseudo_range(&rinex.hatch_smoothing());
+        assert_eq!(smoothed, raw);
+
+        // FDMA channel is known: smoothed
+        rinex.header.glo_channels.insert(r02, -4);
+        let smoothed = pseudo_range(&rinex.hatch_smoothing());
+        assert_eq!(smoothed[0], raw[0]);
+        assert!(smoothed
+            .iter()
+            .zip(raw.iter())
+            .skip(1)
+            .all(|(smoothed, raw)| smoothed != raw));
+    }
+    #[test]
+    #[cfg(all(feature = "nav", feature = "flate2"))]
+    fn elevation_mask() {
+        let rinex =
+            Rinex::from_file("../test_resources/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz")
+                .unwrap();
+        let nav =
+            Rinex::from_file("../test_resources/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+                .unwrap();
+
+        let position = rinex.header.ground_position.unwrap().to_ecef_wgs84();
+
+        let almanac = Almanac::default();
+        let masked = rinex.elevation_mask(&nav, &almanac, earth_cef(), 10.0, None, None, false);
+        let record = rinex.record.as_obs().unwrap();
+        let masked_record = masked.record.as_obs().unwrap();
+
+        let (mut dropped, mut masked_count) = (0, 0);
+        for (k, (_, vehicles)) in record.iter() {
+            let masked_vehicles = masked_record.get(k).map(|(_, vehicles)| vehicles);
+            for (sv, observations) in vehicles {
+                // independently computed elevation
+                let elev = nav
+                    .sv_position_interpolate(*sv, k.0)
+                    .map(|sv_km| elevation_deg(position, sv_km));
+                let retained = masked_vehicles.and_then(|vehicles| vehicles.get(sv));
+                if retained.is_some() {
+                    masked_count += 1;
+                }
+                match elev {
+                    Some(elev) if (elev - 10.0).abs() < 1.0E-3 => {
+                        // too close to the mask to be conclusive
+                        if retained.is_none() {
+                            dropped += 1;
+                        }
+                    },
+                    Some(elev) if elev < 10.0 => {
+                        assert!(retained.is_none(), "{}({}) @ {} deg", k.0, sv, elev);
+                        dropped += 1;
+                    },
+                    _ => {
+                        // above mask or unknown elevation: preserved
+                        assert_eq!(retained, Some(observations), "{}({})", k.0, sv);
+                    },
+                }
+            }
+        }
+        assert!(dropped > 0, "elevation mask did not apply");
+        let count = record
+            .values()
+            .map(|(_, vehicles)| vehicles.len())
+            .sum::<usize>();
+        assert_eq!(masked_count, count - dropped);
+    }
+    #[test]
+    #[cfg(all(feature = "nav", feature = "flate2"))]
+    fn elevation_mask_unknown_ephemeris() {
+        let rinex =
+            Rinex::from_file("../test_resources/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz")
+                .unwrap();
+        let nav =
+            Rinex::from_file("../test_resources/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+                .unwrap();
+
+        // ESBC site, described manually
+        let position = GroundPosition::from_ecef_wgs84((3582105.2910, 532589.7313, 5232754.8054));
+        let (almanac, earth_cef) = (Almanac::default(), earth_cef());
+
+        let masked =
+            rinex.elevation_mask(&nav, &almanac, earth_cef, 10.0, None, Some(position), true);
+        let record = rinex.record.as_obs().unwrap();
+        let masked_record = masked.record.as_obs().unwrap();
+
+        let (mut dropped_low, mut dropped_unknown, mut retained) = (0, 0, 0);
+        for (k, (_, vehicles)) in record.iter() {
+            let masked_vehicles = masked_record.get(k).map(|(_, vehicles)| vehicles);
+            for (sv, observations) in vehicles {
+                // independently computed elevation
+                let elev = nav
+                    .sv_position_interpolate(*sv, k.0)
+                    .map(|sv_km| elevation_deg(position.to_ecef_wgs84(), sv_km));
+                let masked = masked_vehicles.and_then(|vehicles| vehicles.get(sv));
+                match elev {
+                    Some(elev) if (elev - 10.0).abs() < 1.0E-3 => {
+                        // too close to the mask to be conclusive
+                        if masked.is_some() {
+                            retained += 1;
+                        }
+                    },
+                    Some(elev) if elev >= 10.0 => {
+                        assert_eq!(masked, Some(observations), "{}({}) @ {} deg", k.0, sv, elev);
+                        retained += 1;
+                    },
+                    Some(elev) => {
+                        assert!(masked.is_none(), "{}({}) @ {} deg", k.0, sv, elev);
+                        dropped_low += 1;
+                    },
+                    None => {
+                        assert!(masked.is_none(), "{}({}) has no ephemeris", k.0, sv);
+                        dropped_unknown += 1;
+                    },
+                }
+            }
+        }
+        assert!(retained > 0, "high elevation vehicles should be preserved");
+        assert!(dropped_low > 0, "low elevation vehicles should be dropped");
+
+        // unknown vehicles are preserved by default
+        let preserved =
+            rinex.elevation_mask(&nav, &almanac, earth_cef, 10.0, None, Some(position), false);
+        let count = preserved
+            .record
+            .as_obs()
+            .unwrap()
+            .values()
+            .map(|(_, vehicles)| vehicles.len())
+            .sum::<usize>();
+        assert_eq!(count, retained + dropped_unknown);
+    }
+    #[test]
+    #[cfg(feature = "nav")]
+    fn elevation_mask_preserves_events_and_clocks() {
+        // power failure @ 6th epoch
+        let rinex = Rinex::from_reader(vlns0010_testbench(&[], &[(6, 1)]).as_bytes()).unwrap();
+        let nav = Rinex::from_file("../test_resources/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx")
+            .unwrap();
+
+        // drops every single vehicle
+        let almanac = Almanac::default();
+        let masked = rinex.elevation_mask(&nav, &almanac, earth_cef(), 91.0, None, None, true);
+
+        // all epochs come with a receiver clock offset
+        assert_eq!(masked.epoch().count(), 8);
+        assert_eq!(masked.recvr_clock().count(), 8);
+        for (k, (_, vehicles)) in masked.observation() {
+            if k.1 == EpochFlag::PowerFailure {
+                let (_, original) = rinex.record.as_obs().unwrap().get(k).unwrap();
+                assert_eq!(vehicles, original, "event epoch should be preserved");
+            } else {
+                assert!(vehicles.is_empty(), "{:?}: should be masked", k);
+            }
+        }
+    }
+    #[test]
+    fn shift_epochs() {
+        let rinex = synthetic_1hz_testbench();
+        let dt = Duration::from_seconds(18.0);
+        let shifted = rinex.shift_epochs(dt);
+
+        assert_eq!(shifted.epoch().count(), rinex.epoch().count());
+        for (t, t_shifted) in rinex.epoch().zip(shifted.epoch()) {
+            assert_eq!(t_shifted, t + dt);
+        }
+        // content is preserved
+        for ((_, entry), (_, shifted_entry)) in rinex.observation().zip(shifted.observation()) {
+            assert_eq!(entry, shifted_entry);
+        }
+        // header bounds
+        let header = rinex.header.obs.as_ref().unwrap();
+        let shifted_header = shifted.header.obs.as_ref().unwrap();
+        assert_eq!(
+            shifted_header.time_of_first_obs,
+            header.time_of_first_obs.map(|t| t + dt)
+        );
+        assert_eq!(
+            shifted_header.time_of_last_obs,
+            header.time_of_last_obs.map(|t| t + dt)
+        );
+        assert!(shifted_header.time_of_first_obs.is_some());
+    }
+    #[test]
+    fn observable_coverage() {
+        let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        let coverage = rinex.observable_coverage();
+        assert_eq!(coverage.epochs, 3);
+        assert_eq!(coverage.counts.len(), 20);
+        assert_eq!(coverage.observables().len(), 12);
+
+        let c1c = Observable::from_str("C1C").unwrap();
+        let c2p = Observable::from_str("C2P").unwrap();
+        assert_eq!(coverage.count(&sv!("G01"), &c1c), 3);
+        assert_eq!(coverage.count(&sv!("G22"), &c1c), 2);
+        assert_eq!(coverage.ratio(&sv!("G06"), &c1c), 1.0 / 3.0);
+        assert_eq!(coverage.count(&sv!("R10"), &c2p), 0);
+        assert_eq!(coverage.count(&sv!("R01"), &c2p), 3);
+        assert_eq!(coverage.count(&sv!("G01"), &c2p), 0);
+
+        #[cfg(feature = "qc")]
+        {
+            use maud::Render;
+            let html = coverage.render().into_string();
+            for sv in coverage.counts.keys() {
+                assert!(
+                    html.contains(&format!("<th>{}</th>", sv)),
+                    "missing {} row",
+                    sv
+                );
+            }
+            // 12 GPS with 8 observables, 6 GLO with 8 and 2 GLO with 4
+            assert_eq!(html.matches("fa-circle-check").count(), 152);
+            assert_eq!(html.matches("fa-circle-xmark").count(), 20 * 12 - 152);
+        }
+    }
     /*
         #[test]
         fn obs_v3_duth0630_processing() {
@@ -166,4 +166,99 @@ mod test {
             }
         }
     }
+    #[test]
+    fn unsupported_compression() {
+        // .Z (unix compress) content is reported, not a panic
+        let content = [0x1f, 0x9d, 0x90, 0x00, 0x00];
+        match Rinex::from_reader(&content[..]) {
+            Err(Error::IoError(e)) => {
+                assert_eq!(e.kind(), std::io::ErrorKind::Unsupported);
+            },
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!(".Z content should not be supported"),
+        }
+    }
+    #[test]
+    fn error_kind() {
+        use crate::tests::toolkit::random_name;
+        use crate::{record, ErrorKind};
+
+        // missing file: retrying will not help
+        let err = Rinex::from_file("../test_resources/OBS/V3/not_a_file.22O").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unavailable);
+        assert!(!err.kind().is_retryable());
+
+        // io::Error are categorized by kind
+        for (kind, expected) in [
+            (std::io::ErrorKind::Interrupted, ErrorKind::Io),
+            (std::io::ErrorKind::TimedOut, ErrorKind::Io),
+            (std::io::ErrorKind::ConnectionReset, ErrorKind::Io),
+            (std::io::ErrorKind::InvalidData, ErrorKind::MalformedRecord),
+            (
+                std::io::ErrorKind::UnexpectedEof,
+                ErrorKind::MalformedRecord,
+            ),
+            (std::io::ErrorKind::PermissionDenied, ErrorKind::Unavailable),
+        ] {
+            let err = Error::IoError(std::io::Error::from(kind));
+            assert_eq!(err.kind(), expected, "wrong kind for {:?}", kind);
+            assert_eq!(err.kind().is_retryable(), expected == ErrorKind::Io);
+            let err = Error::RecordError(record::Error::FileIoError(std::io::Error::from(kind)));
+            assert_eq!(err.kind(), expected, "wrong kind for {:?}", kind);
+        }
+
+        let content = std::fs::read_to_string("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
+        for (version, kind) in [
+            ("     9.02", ErrorKind::Unsupported),
+            ("     X.02", ErrorKind::MalformedHeader),
+        ] {
+            let path = format!("test-{}.22O", random_name(8));
+            std::fs::write(&path, content.replacen("     3.02", version, 1)).unwrap();
+            let rinex = Rinex::from_file(&path);
+            let _ = std::fs::remove_file(&path);
+            let err = rinex.unwrap_err();
+            assert_eq!(err.kind(), kind, "wrong kind for {}", err);
+            assert!(!err.kind().is_retryable());
+        }
+
+        let err = Error::RecordError(record::Error::BadObservationDataDefinition);
+        assert_eq!(err.kind(), ErrorKind::MalformedHeader);
+        let err = Error::RecordError(record::Error::TypeError("X".to_string()));
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = "X".parse::<u32>().unwrap_err();
+        let err = Error::RecordError(record::Error::ObsEpochError(err.into()));
+        assert_eq!(err.kind(), ErrorKind::MalformedRecord);
+        let err = Error::NonRepresentableObservable(Observable::default());
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+    #[test]
+    #[cfg(feature = "obs")]
+    fn lenient_parsing() {
+        use crate::tests::toolkit::random_name;
+        use std::str::FromStr;
+        let original = Rinex::from_file("../test_resources/OBS/V3/VLNS0010.22O").unwrap();
+        let (rinex, warnings) =
+            Rinex::from_file_lenient("../test_resources/OBS/V3/VLNS0010.22O").unwrap();
+        assert!(warnings.is_empty(), "unexpected warnings: {:?}", warnings);
+        assert_eq!(rinex, original);
+
+        // one corrupt epoch
+        let content = std::fs::read_to_string("../test_resources/OBS/V3/VLNS0010.22O").unwrap();
+        let corrupt = "> 2022 01 01  0 XX 30.0000000";
+        let content = content.replacen("> 2022 01 01  0  0 30.0000000", corrupt, 1);
+        let path = format!("test-{}.22O", random_name(8));
+        std::fs::write(&path, content).unwrap();
+        let lenient = Rinex::from_file_lenient(&path);
+        let strict = Rinex::from_file(&path);
+        let _ = std::fs::remove_file(&path);
+
+        let (rinex, warnings) = lenient.unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].index, 2);
+        assert!(warnings[0].line.starts_with(corrupt));
+        assert_eq!(rinex.epoch().count(), original.epoch().count() - 1);
+        let t = Epoch::from_str("2022-01-01T00:00:30 GPST").unwrap();
+        assert!(rinex.epoch().all(|e| e != t));
+        assert_eq!(strict.unwrap(), rinex);
+    }
 }
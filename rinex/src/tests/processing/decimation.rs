@@ -2,8 +2,11 @@
 #[cfg(test)]
 mod decimation {
     use crate::prelude::*;
-    use qc_traits::processing::{Decimate, DecimationFilter};
+    use crate::tests::toolkit::synthetic_1hz_testbench;
+    use itertools::Itertools;
+    use qc_traits::processing::{Decimate, DecimationFilter, Filter, FilterItem, Preprocessing};
     use std::path::Path;
+    use std::str::FromStr;
     #[test]
     #[cfg(feature = "flate2")]
     fn obs_dt_decimation() {
@@ -97,4 +100,190 @@ mod decimation {
         let count = rinex.epoch().count();
         assert_eq!(count, 1013, "decimate(1'+1s): error",);
     }
+    #[test]
+    #[cfg(all(feature = "ionex", feature = "flate2"))]
+    fn ionex_modulo_decimation() {
+        let mut rinex = Rinex::from_file("../test_resources/IONEX/V1/CKMG0020.22I.gz").unwrap();
+        let len = rinex.epoch().count();
+
+        rinex.decimate_mut(&DecimationFilter::modulo(2));
+        assert_eq!(rinex.epoch().count(), (len + 1) / 2, "decimate(/2): error");
+
+        let dut = rinex.decimate(&DecimationFilter::duration(Duration::from_hours(4.0)));
+        assert_eq!(dut.epoch().count(), (len + 3) / 4, "decimate(4h): error");
+    }
+    #[test]
+    #[cfg(all(feature = "clock", feature = "flate2"))]
+    fn clock_modulo_decimation() {
+        let mut rinex =
+            Rinex::from_file("../test_resources/CLK/V3/GRG0MGXFIN_20201770000_01D_30S_CLK.CLK.gz")
+                .unwrap();
+        let len = rinex.epoch().count();
+
+        rinex.decimate_mut(&DecimationFilter::modulo(10));
+        assert_eq!(
+            rinex.epoch().count(),
+            (len + 9) / 10,
+            "decimate(/10): error"
+        );
+    }
+    #[test]
+    #[cfg(all(feature = "doris", feature = "flate2"))]
+    fn doris_modulo_decimation() {
+        let mut rinex = Rinex::from_file("../test_resources/DOR/V3/cs2rx18164.gz").unwrap();
+        let len = rinex.epoch().count();
+
+        rinex.decimate_mut(&DecimationFilter::modulo(4));
+        assert_eq!(rinex.epoch().count(), (len + 3) / 4, "decimate(/4): error");
+    }
+    #[test]
+    fn meteo_modulo_decimation() {
+        let mut rinex = Rinex::from_file("../test_resources/MET/V2/cari0010.07m").unwrap();
+        let len = rinex.epoch().count();
+
+        rinex.decimate_mut(&DecimationFilter::modulo(3));
+        assert_eq!(rinex.epoch().count(), (len + 2) / 3, "decimate(/3): error");
+    }
+    #[test]
+    fn meteo_targetted_decimation() {
+        let rinex = Rinex::from_file("../test_resources/MET/V2/abvi0010.15m").unwrap();
+        let len = rinex.epoch().count();
+
+        // only wind speed is decimated
+        let filter =
+            DecimationFilter::modulo(2).with_item(FilterItem::ComplexItem(vec!["WS".to_string()]));
+        let dut = rinex.decimate(&filter);
+        assert_eq!(dut.epoch().count(), len);
+        assert_eq!(dut.wind_speed().count(), (len + 1) / 2);
+        assert_eq!(dut.pressure().count(), len);
+
+        // items that do not apply do not decimate anything
+        let filter = DecimationFilter::modulo(2)
+            .with_item(FilterItem::ConstellationItem(vec![Constellation::GPS]));
+        let dut = rinex.decimate(&filter);
+        assert_eq!(dut, rinex);
+    }
+    #[test]
+    #[cfg(all(feature = "clock", feature = "flate2"))]
+    fn clock_targetted_decimation() {
+        let rinex =
+            Rinex::from_file("../test_resources/CLK/V3/GRG0MGXFIN_20201770000_01D_30S_CLK.CLK.gz")
+                .unwrap();
+        let len = rinex.epoch().count();
+        let gps = |rinex: &Rinex| {
+            rinex
+                .precise_sv_clock()
+                .filter(|(_, sv, _, _)| sv.constellation == Constellation::GPS)
+                .count()
+        };
+        let others = |rinex: &Rinex| {
+            rinex
+                .precise_sv_clock()
+                .filter(|(_, sv, _, _)| sv.constellation != Constellation::GPS)
+                .count()
+        };
+
+        // only GPS clocks are decimated
+        let filter = DecimationFilter::modulo(10)
+            .with_item(FilterItem::ConstellationItem(vec![Constellation::GPS]));
+        let dut = rinex.decimate(&filter);
+        assert_eq!(dut.epoch().count(), len);
+        assert!(gps(&dut) < gps(&rinex));
+        assert_eq!(others(&dut), others(&rinex));
+        assert!(
+            dut.precise_sv_clock()
+                .filter(|(_, sv, _, _)| sv.constellation == Constellation::GPS)
+                .map(|(t, _, _, _)| t)
+                .unique()
+                .count()
+                <= (len + 9) / 10
+        );
+    }
+    #[test]
+    #[cfg(feature = "obs")]
+    fn obs_1hz_dt_decimation() {
+        let rinex = synthetic_1hz_testbench();
+        assert_eq!(rinex.epoch().count(), 300);
+        assert_eq!(
+            rinex.dominant_sample_rate(),
+            Some(Duration::from_seconds(1.0))
+        );
+
+        let dt_30s = DecimationFilter::duration(Duration::from_seconds(30.0));
+        let dut = rinex.decimate(&dt_30s);
+        assert_eq!(dut.epoch().count(), 10);
+        assert_eq!(
+            dut.dominant_sample_rate(),
+            Some(Duration::from_seconds(30.0))
+        );
+
+        for desc in ["d:30 s", "d:dt=30s", "decim:30 s", "decim:dt=30 s"] {
+            let filter = Filter::from_str(desc).unwrap();
+            let dut = rinex.filter(&filter);
+            assert_eq!(dut.epoch().count(), 10, "\"{}\" decimation error", desc);
+            assert_eq!(
+                dut.dominant_sample_rate(),
+                Some(Duration::from_seconds(30.0)),
+                "\"{}\" decimation error",
+                desc
+            );
+        }
+    }
+    #[test]
+    #[cfg(feature = "obs")]
+    fn obs_1hz_targetted_dt_decimation() {
+        let rinex = synthetic_1hz_testbench();
+        let g01 = SV::from_str("G01").unwrap();
+        let g02 = SV::from_str("G02").unwrap();
+
+        let filter = Filter::from_str("d:30 s:G01").unwrap();
+        let dut = rinex.filter(&filter);
+
+        // G02 is preserved at 1 Hz
+        assert_eq!(dut.epoch().count(), 300);
+        assert_eq!(
+            dut.dominant_sample_rate(),
+            Some(Duration::from_seconds(1.0))
+        );
+
+        let record = dut.record.as_obs().unwrap();
+        let g01_epochs = record
+            .iter()
+            .filter_map(|((t, _), (_, svs))| {
+                if svs.contains_key(&g01) {
+                    Some(*t)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        let g02_epochs = record
+            .iter()
+            .filter(|(_, (_, svs))| svs.contains_key(&g02))
+            .count();
+
+        assert_eq!(g01_epochs.len(), 10);
+        assert_eq!(g02_epochs, 300);
+        for pair in g01_epochs.windows(2) {
+            assert_eq!(pair[1] - pair[0], Duration::from_seconds(30.0));
+        }
+
+        // targetted observable: remaining observables are preserved
+        let l1c = Observable::from_str("L1C").unwrap();
+        let c1c = Observable::from_str("C1C").unwrap();
+        let filter = Filter::from_str("d:dt=30s:L1C").unwrap();
+        let dut = rinex.filter(&filter);
+        assert_eq!(dut.epoch().count(), 300);
+        let record = dut.record.as_obs().unwrap();
+        let (l1c_count, c1c_count) =
+            record
+                .values()
+                .fold((0, 0), |(l1c_count, c1c_count), (_, svs)| {
+                    let l1c_n = svs.values().filter(|obs| obs.contains_key(&l1c)).count();
+                    let c1c_n = svs.values().filter(|obs| obs.contains_key(&c1c)).count();
+                    (l1c_count + l1c_n, c1c_count + c1c_n)
+                });
+        assert_eq!(l1c_count, 20);
+        assert_eq!(c1c_count, 600);
+    }
 }
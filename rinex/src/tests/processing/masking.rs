@@ -84,6 +84,44 @@ mod test {
         assert_eq!(dut.observable().count(), 0);
     }
     #[test]
+    fn meteo_v2_abvi0010() {
+        let rnx = Rinex::from_file("../test_resources/MET/V2/abvi0010.15m").unwrap();
+        assert_eq!(rnx.epoch().count(), 74);
+        assert_eq!(rnx.observable().count(), 7);
+
+        // epoch cropping
+        let mask = Filter::greater_equals("2015-01-01T12:00:00 UTC").unwrap();
+        let dut = rnx.filter(&mask);
+        assert_eq!(dut.epoch().count(), 59);
+        assert_eq!(dut.observable().count(), 7);
+
+        let mask = Filter::mask(
+            MaskOperand::LowerThan,
+            FilterItem::EpochItem(Epoch::from_str("2015-01-01T09:00:00 UTC").unwrap()),
+        );
+        let dut = rnx.filter(&mask);
+        assert_eq!(dut.epoch().count(), 10);
+
+        // observable masking
+        let mask = Filter::mask(
+            MaskOperand::NotEquals,
+            FilterItem::ComplexItem(vec!["WS".to_string(), "WD".to_string()]),
+        );
+        let dut = rnx.filter(&mask);
+        assert_eq!(dut.epoch().count(), 74);
+        assert_eq!(dut.observable().count(), 5);
+        assert_eq!(dut.wind_speed().count(), 0);
+        assert_eq!(dut.pressure().count(), 74);
+
+        let mask = Filter::mask(
+            MaskOperand::Equals,
+            FilterItem::ComplexItem(vec!["WS".to_string()]),
+        );
+        let dut = rnx.filter(&mask);
+        assert_eq!(dut.observable().count(), 1);
+        assert_eq!(dut.wind_speed().count(), 74);
+    }
+    #[test]
     fn obs_observ_v3_duth0630() {
         let rinex = Rinex::from_file("../test_resources/OBS/V3/DUTH0630.22O").unwrap();
 
@@ -225,4 +263,189 @@ mod test {
         assert_eq!(dut.constellation().count(), 1);
         assert_eq!(dut.carrier().collect::<Vec<_>>(), vec![Carrier::G2(None)]);
     }
+    #[test]
+    #[cfg(feature = "nav")]
+    fn nav_v3_cbw100nld() {
+        let rnx = Rinex::from_file("../test_resources/NAV/V3/CBW100NLD_R_20210010000_01D_MN.rnx")
+            .unwrap();
+
+        let mask = Filter::mask(
+            MaskOperand::Equals,
+            FilterItem::ConstellationItem(vec![Constellation::GPS]),
+        );
+        let dut = rnx.filter(&mask);
+        assert_eq!(
+            dut.sv().sorted().collect::<Vec<_>>(),
+            vec![
+                SV::new(Constellation::GPS, 19),
+                SV::new(Constellation::GPS, 20),
+            ]
+        );
+        assert_eq!(
+            dut.constellation().collect::<Vec<_>>(),
+            vec![Constellation::GPS]
+        );
+
+        let mask = Filter::mask(
+            MaskOperand::NotEquals,
+            FilterItem::ConstellationItem(vec![Constellation::GPS]),
+        );
+        let dut = rnx.filter(&mask);
+        assert!(dut.sv().all(|sv| sv.constellation != Constellation::GPS));
+        assert_eq!(dut.sv().count(), 4);
+
+        let mask = Filter::mask(
+            MaskOperand::Equals,
+            FilterItem::SvItem(vec![SV::new(Constellation::GPS, 20)]),
+        );
+        let dut = rnx.filter(&mask);
+        assert_eq!(
+            dut.sv().collect::<Vec<_>>(),
+            vec![SV::new(Constellation::GPS, 20)]
+        );
+
+        let mask = Filter::mask(
+            MaskOperand::GreaterThan,
+            FilterItem::SvItem(vec![SV::new(Constellation::GPS, 19)]),
+        );
+        let dut = rnx.filter(&mask);
+        assert!(dut.sv().contains(&SV::new(Constellation::GPS, 20)));
+        assert!(!dut.sv().contains(&SV::new(Constellation::GPS, 19)));
+        assert!(dut.sv().contains(&SV::new(Constellation::Galileo, 1)));
+
+        let t = Epoch::from_str("2021-01-01T12:00:00 GPST").unwrap();
+        let mask = Filter::mask(MaskOperand::GreaterEquals, FilterItem::EpochItem(t));
+        let dut = rnx.filter(&mask);
+        assert!(dut.first_epoch().unwrap() >= t);
+        for (_, frames) in dut.navigation() {
+            assert!(!frames.is_empty(), "empty epochs should be removed");
+        }
+    }
+    #[test]
+    #[cfg(all(feature = "nav", feature = "flate2"))]
+    fn nav_v4_kms300dnk_msgtype() {
+        use crate::navigation::NavMsgType;
+        let rnx =
+            Rinex::from_file("../test_resources/NAV/V4/KMS300DNK_R_20221591000_01H_MN.rnx.gz")
+                .unwrap();
+
+        let mask = Filter::mask(
+            MaskOperand::Equals,
+            FilterItem::ComplexItem(vec!["LNAV".to_string()]),
+        );
+        let dut = rnx.filter(&mask);
+        assert!(dut.navigation().count() > 0);
+        for (_, frames) in dut.navigation() {
+            for fr in frames {
+                let msg = fr
+                    .as_eph()
+                    .map(|(msg, _, _)| msg)
+                    .or(fr.as_ion().map(|(msg, _, _)| msg))
+                    .or(fr.as_eop().map(|(msg, _, _)| msg))
+                    .or(fr.as_sto().map(|(msg, _, _)| msg))
+                    .unwrap();
+                assert_eq!(msg, NavMsgType::LNAV);
+            }
+        }
+
+        // ION frames are not ephemeris dependent
+        assert!(rnx.ionod_correction_models().count() > 0);
+        let mask = Filter::mask(
+            MaskOperand::NotEquals,
+            FilterItem::ComplexItem(vec!["ION".to_string()]),
+        );
+        let dut = rnx.filter(&mask);
+        assert!(dut.ephemeris().count() > 0);
+        for (_, frames) in dut.navigation() {
+            assert!(frames.iter().all(|fr| fr.as_ion().is_none()));
+        }
+
+        // SV mask applies to all frame types
+        let mask = Filter::mask(
+            MaskOperand::Equals,
+            FilterItem::SvItem(vec![SV::new(Constellation::GPS, 29)]),
+        );
+        let dut = rnx.filter(&mask);
+        assert!(dut
+            .navigation()
+            .any(|(_, frames)| frames.iter().any(|fr| fr.as_ion().is_some())));
+    }
+    #[test]
+    #[cfg(all(feature = "ionex", feature = "flate2"))]
+    fn ionex_v1_ckmg0020() {
+        let rnx = Rinex::from_file("../test_resources/IONEX/V1/CKMG0020.22I.gz").unwrap();
+        let t0 = rnx.first_epoch().unwrap();
+        let len = rnx.epoch().unique().count();
+
+        // epoch cropping
+        let mask = Filter::mask(
+            MaskOperand::GreaterThan,
+            FilterItem::EpochItem(t0 + Duration::from_hours(12.0)),
+        );
+        let dut = rnx.filter(&mask);
+        assert_eq!(dut.epoch().unique().count(), len - 13);
+        assert!(dut.epoch().all(|t| t > t0 + Duration::from_hours(12.0)));
+
+        // spatial cropping
+        let mask = Filter::mask(MaskOperand::GreaterEquals, FilterItem::LatitudeItem(0.0));
+        let dut = rnx.filter(&mask);
+        assert_eq!(dut.epoch().unique().count(), len);
+        assert!(dut.tec().count() > 0);
+        assert!(dut.tec().count() < rnx.tec().count());
+        assert!(dut.tec().all(|(_, lat, _, _, _)| lat >= 0.0));
+
+        let mask = Filter::mask(MaskOperand::LowerThan, FilterItem::LongitudeItem(-180.0));
+        let dut = rnx.filter(&mask);
+        assert_eq!(dut.epoch().count(), 0, "empty maps should be dropped");
+    }
+    #[test]
+    #[cfg(all(feature = "clock", feature = "flate2"))]
+    fn clock_v3_grg0mgxfin() {
+        let rnx =
+            Rinex::from_file("../test_resources/CLK/V3/GRG0MGXFIN_20201770000_01D_30S_CLK.CLK.gz")
+                .unwrap();
+        let t0 = rnx.first_epoch().unwrap();
+        let len = rnx.epoch().count();
+
+        let mask = Filter::mask(
+            MaskOperand::LowerEquals,
+            FilterItem::EpochItem(t0 + Duration::from_seconds(90.0)),
+        );
+        let dut = rnx.filter(&mask);
+        assert_eq!(dut.epoch().count(), 4);
+
+        let mask = Filter::mask(
+            MaskOperand::GreaterEquals,
+            FilterItem::EpochItem(t0 + Duration::from_seconds(90.0)),
+        );
+        let dut = rnx.filter(&mask);
+        assert_eq!(dut.epoch().count(), len - 3);
+
+        let mask = Filter::mask(
+            MaskOperand::NotEquals,
+            FilterItem::ConstellationItem(vec![Constellation::GPS]),
+        );
+        let dut = rnx.filter(&mask);
+        assert!(dut.precise_sv_clock().count() > 0);
+        assert!(dut
+            .precise_sv_clock()
+            .all(|(_, sv, _, _)| sv.constellation != Constellation::GPS));
+    }
+    #[test]
+    #[cfg(all(feature = "doris", feature = "flate2"))]
+    fn doris_v3_cs2rx18164() {
+        let rnx = Rinex::from_file("../test_resources/DOR/V3/cs2rx18164.gz").unwrap();
+        let t0 = rnx.first_epoch().unwrap();
+        let t1 = rnx.last_epoch().unwrap();
+        let len = rnx.epoch().count();
+
+        let mask = Filter::mask(MaskOperand::GreaterThan, FilterItem::EpochItem(t0));
+        let dut = rnx.filter(&mask);
+        assert_eq!(dut.epoch().count(), len - 1);
+
+        let mask = Filter::mask(MaskOperand::LowerThan, FilterItem::EpochItem(t1));
+        let dut = rnx.filter(&mask);
+        assert_eq!(dut.epoch().count(), len - 1);
+        assert_eq!(dut.first_epoch(), Some(t0));
+    }
 }
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod test {
-    use crate::tests::toolkit::{random_name, test_against_model};
+    use crate::tests::toolkit::{random_name, test_against_model, vlns0010_testbench};
     use crate::*;
     use std::path::Path;
     fn testbench(path: &str) {
@@ -55,6 +55,28 @@ mod test {
         }
     }
     #[test]
+    fn obs_v4() {
+        // V4 observations are only provided as CRINEX:
+        // we decompress them, to test the V4 epoch formatting
+        let path = env!("CARGO_MANIFEST_DIR").to_owned()
+            + "/../test_resources/CRNX/V3/KMS300DNK_R_20221591000_01H_30S_MO.crx";
+
+        let rnx = Rinex::from_file(&path).unwrap().crnx2rnx();
+        assert_eq!(rnx.header.version.major, 4);
+
+        let tmp_path = format!("test-{}.rnx", random_name(5));
+        assert!(rnx.to_file(&tmp_path).is_ok());
+
+        let copy = Rinex::from_file(&tmp_path);
+        let _ = std::fs::remove_file(&tmp_path);
+        let copy = copy.unwrap();
+
+        assert_eq!(copy.header.version, rnx.header.version);
+        if copy != rnx {
+            test_against_model(&copy, &rnx, &path, 1.0E-6);
+        }
+    }
+    #[test]
     #[cfg(feature = "flate2")]
     fn meteo_v2() {
         let folder = env!("CARGO_MANIFEST_DIR").to_owned() + "/../test_resources/MET/V2/";
@@ -128,4 +150,177 @@ mod test {
             testbench(fp.to_str().unwrap());
         }
     }
+    #[test]
+    fn in_memory_production() {
+        for path in [
+            "../test_resources/OBS/V3/DUTH0630.22O",
+            "../test_resources/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx",
+        ] {
+            let rinex = Rinex::from_file(path).unwrap();
+
+            let content = std::fs::read(path).unwrap();
+            let parsed = Rinex::from_reader(std::io::Cursor::new(content)).unwrap();
+            assert_eq!(parsed.header, rinex.header);
+            assert_eq!(parsed.record, rinex.record);
+            assert!(parsed.prod_attr.is_none());
+
+            let mut buffer = Vec::<u8>::new();
+            parsed.to_writer(&mut buffer).unwrap();
+
+            let tmp_path = format!("test-{}.rnx", random_name(5));
+            rinex.to_file(&tmp_path).unwrap();
+            let written = std::fs::read(&tmp_path).unwrap();
+            let _ = std::fs::remove_file(tmp_path);
+
+            assert_eq!(buffer, written, "in memory and file production differ");
+        }
+    }
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn in_memory_gzip_parsing() {
+        // gzip is identified from the content itself
+        let path = "../test_resources/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz";
+        let rinex = Rinex::from_file(path).unwrap();
+
+        let content = std::fs::read(path).unwrap();
+        let parsed = Rinex::from_reader(content.as_slice()).unwrap();
+        assert_eq!(parsed.header, rinex.header);
+        assert_eq!(parsed.record, rinex.record);
+    }
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn gzip_content_detection() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+        // gzip is identified from the content, whatever the file extension
+        let path = "../test_resources/MET/V2/cari0010.07m";
+        let rinex = Rinex::from_file(path).unwrap();
+
+        let plain = std::fs::read(path).unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&plain).unwrap();
+        let gzip = encoder.finish().unwrap();
+
+        for (extension, content) in [
+            ("07m", &plain),
+            ("07m.gz", &gzip),
+            ("07m.gz", &plain),
+            ("07m", &gzip),
+        ] {
+            let tmp_path = format!("test-{}.{}", random_name(5), extension);
+            std::fs::write(&tmp_path, content).unwrap();
+            let copy = Rinex::from_file(&tmp_path);
+            let _ = std::fs::remove_file(&tmp_path);
+            let copy = copy.unwrap_or_else(|e| {
+                panic!(
+                    "failed to parse \".{}\" file (gzip: {}): {}",
+                    extension,
+                    content.starts_with(&[0x1f, 0x8b]),
+                    e
+                )
+            });
+            assert_eq!(copy.header, rinex.header);
+            assert_eq!(copy.record, rinex.record);
+        }
+    }
+    #[test]
+    #[cfg(feature = "obs")]
+    fn filtered_obs_production() {
+        let mut rinex = Rinex::from_reader(
+            vlns0010_testbench(&[(2, "G08"), (4, "G08"), (4, "G10")], &[(6, 1)]).as_bytes(),
+        )
+        .unwrap();
+        rinex.complete_epoch_filter_mut(None);
+
+        let tmp_path = format!("test-{}.22O", random_name(5));
+        rinex.to_file(&tmp_path).unwrap();
+        let copy = Rinex::from_file(&tmp_path);
+        let _ = std::fs::remove_file(&tmp_path);
+        let copy = copy.unwrap();
+
+        // filtered content is preserved
+        assert_eq!(copy.epoch().count(), 6);
+        assert_eq!(
+            copy.epoch().collect::<Vec<_>>(),
+            rinex.epoch().collect::<Vec<_>>()
+        );
+        for ((k, (_, vehicles)), (k_c, (_, vehicles_c))) in
+            rinex.observation().zip(copy.observation())
+        {
+            assert_eq!(k, k_c);
+            assert_eq!(
+                vehicles.keys().collect::<Vec<_>>(),
+                vehicles_c.keys().collect::<Vec<_>>(),
+                "filtered vehicles differ @ {}",
+                k.0
+            );
+        }
+        test_against_model(&copy, &rinex, "VLNS0010_gaps.22O", 1.0E-6);
+    }
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn gzip_production() {
+        for (path, extension) in [
+            ("../test_resources/OBS/V3/DUTH0630.22O", "22O"),
+            (
+                "../test_resources/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz",
+                "crx",
+            ),
+        ] {
+            let rinex = Rinex::from_file(path).unwrap();
+
+            // compression deduced from extension, or explicit
+            let tmp_path = format!("test-{}.{}.gz", random_name(5), extension);
+            let explicit_path = format!("test-{}.{}", random_name(5), extension);
+            rinex.to_file(&tmp_path).unwrap();
+            rinex.to_gzip_file(&explicit_path, 9).unwrap();
+
+            for tmp_path in [tmp_path, explicit_path] {
+                let content = std::fs::read(&tmp_path).unwrap();
+                let copy = Rinex::from_file(&tmp_path);
+                let _ = std::fs::remove_file(&tmp_path);
+
+                assert_eq!(
+                    &content[..2],
+                    &[0x1f, 0x8b],
+                    "{}: not gzip compressed",
+                    tmp_path
+                );
+                let copy = copy.unwrap();
+                assert_eq!(
+                    copy.header.obs.as_ref().map(|obs| obs.crinex.is_some()),
+                    rinex.header.obs.as_ref().map(|obs| obs.crinex.is_some()),
+                    "{}: CRINEX should be preserved",
+                    tmp_path
+                );
+                if copy != rinex {
+                    test_against_model(&copy, &rinex, path, 1.0E-6);
+                }
+            }
+        }
+    }
+    #[test]
+    #[cfg(all(feature = "flate2", feature = "ionex"))]
+    fn ionex_gzip_production() {
+        let path = "../test_resources/IONEX/V1/CKMG0020.22I.gz";
+        let rinex = Rinex::from_file(path).unwrap();
+
+        let tmp_path = format!("test-{}.22I.gz", random_name(5));
+        rinex.to_file(&tmp_path).unwrap();
+
+        let content = std::fs::read(&tmp_path).unwrap();
+        let copy = Rinex::from_file(&tmp_path);
+        let _ = std::fs::remove_file(&tmp_path);
+
+        assert_eq!(&content[..2], &[0x1f, 0x8b], "not gzip compressed");
+
+        let copy = copy.unwrap();
+        let (ionex, copy_ionex) = (
+            rinex.header.ionex.as_ref().unwrap(),
+            copy.header.ionex.as_ref().unwrap(),
+        );
+        assert_eq!(copy_ionex.grid, ionex.grid);
+        assert_eq!(copy_ionex.exponent, ionex.exponent);
+        assert_eq!(copy.record, rinex.record, "IONEX record not preserved");
+    }
 }
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod test {
+    use crate::merge;
+    use crate::prelude::*;
+    use crate::tests::toolkit::random_name;
+    use std::ops::ControlFlow;
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+
+    const PATH: &str = "../test_resources/OBS/V3/ACOR00ESP_R_20213550000_01D_30S_MO.rnx";
+
+    /*
+     * Builds a handle that cancels after `nb_epochs`,
+     * and keeps track of all notifications
+     */
+    fn cancel_after(nb_epochs: usize) -> (ProgressHandle, Arc<Mutex<Vec<Progress>>>) {
+        let notifications = Arc::new(Mutex::new(Vec::<Progress>::new()));
+        let shared = notifications.clone();
+        let handle = ProgressHandle::new(move |progress: Progress| {
+            shared.lock().unwrap().push(progress);
+            if progress.epochs < nb_epochs {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+        (handle, notifications)
+    }
+
+    #[test]
+    fn parsing_progress() {
+        let (mut progress, notifications) = cancel_after(usize::MAX);
+        let rinex =
+            Rinex::from_file_with_progress(PATH, ParsingOptions::default(), &mut progress).unwrap();
+        assert_eq!(rinex, Rinex::from_file(PATH).unwrap());
+
+        let notifications = notifications.lock().unwrap();
+        assert_eq!(notifications.len(), 25);
+        for (i, progress) in notifications.iter().enumerate() {
+            assert_eq!(progress.epochs, i + 1);
+            if i > 0 {
+                assert!(progress.bytes > notifications[i - 1].bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn parsing_cancellation() {
+        let (mut progress, notifications) = cancel_after(10);
+        let rinex = Rinex::from_file_with_progress(PATH, ParsingOptions::default(), &mut progress);
+        match rinex {
+            Err(Error::Cancelled) => {},
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("parsing should have been cancelled"),
+        }
+        // terminated as soon as requested
+        assert_eq!(notifications.lock().unwrap().len(), 10);
+    }
+
+    #[test]
+    fn stream_cancellation() {
+        let (progress, notifications) = cancel_after(5);
+        let stream = Rinex::epochs_iter_from_file(PATH)
+            .unwrap()
+            .with_progress(progress);
+        let entries = stream.collect::<Vec<_>>();
+        // all parsed epochs are yielded, then the stream stops
+        assert_eq!(entries.len(), 6);
+        for entry in &entries[..5] {
+            assert!(entry.is_ok());
+        }
+        assert_eq!(
+            entries[5].as_ref().unwrap_err().kind(),
+            ErrorKind::Cancelled
+        );
+
+        let notifications = notifications.lock().unwrap();
+        assert_eq!(notifications.len(), 5);
+        for (i, progress) in notifications.iter().enumerate() {
+            assert_eq!(progress.epochs, i + 1);
+        }
+    }
+
+    #[test]
+    fn formatting_cancellation() {
+        let rinex = Rinex::from_file(PATH).unwrap();
+        let path = format!("test-{}.rnx", random_name(8));
+        let staging = format!("{}.part", path);
+
+        let (mut progress, notifications) = cancel_after(10);
+        let err = rinex
+            .to_file_with_progress(&path, &mut progress)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Cancelled);
+        assert_eq!(notifications.lock().unwrap().len(), 10);
+        // no partial output
+        assert!(!Path::new(&path).exists());
+        assert!(!Path::new(&staging).exists());
+
+        let (mut progress, notifications) = cancel_after(usize::MAX);
+        rinex.to_file_with_progress(&path, &mut progress).unwrap();
+        assert!(!Path::new(&staging).exists());
+
+        let notifications = notifications.lock().unwrap();
+        assert_eq!(notifications.len(), 25);
+        let size = std::fs::metadata(&path).unwrap().len();
+        assert_eq!(notifications[24].bytes, size);
+
+        let parsed = Rinex::from_file(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(parsed.epoch().count(), rinex.epoch().count());
+    }
+
+    #[test]
+    fn merge_cancellation() {
+        let rinex = Rinex::from_file(PATH).unwrap();
+        let (mut progress, notifications) = cancel_after(10);
+        match rinex.merge_with_progress(&rinex, &mut progress) {
+            Err(merge::Error::Cancelled) => {},
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("merge should have been cancelled"),
+        }
+        assert_eq!(notifications.lock().unwrap().len(), 10);
+    }
+}
@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::prod::PPU;
+    use crate::Split;
+    use std::str::FromStr;
+    #[test]
+    fn obs_split_dt() {
+        let rinex =
+            Rinex::from_file("../test_resources/OBS/V3/ACOR00ESP_R_20213550000_01D_30S_MO.rnx")
+                .unwrap();
+
+        // 25 epochs, from 00:00:00 to 00:12:00
+        let chunks = rinex.split_dt(Duration::from_seconds(300.0)).unwrap();
+        assert_eq!(chunks.len(), 3);
+
+        let sizes = chunks
+            .iter()
+            .map(|chunk| chunk.epoch().count())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, vec![10, 10, 5]);
+
+        // nothing is lost
+        assert_eq!(sizes.iter().sum::<usize>(), rinex.epoch().count());
+
+        for (index, chunk) in chunks.iter().enumerate() {
+            let t0 = Epoch::from_str("2021-12-21T00:00:00 GPST").unwrap()
+                + Duration::from_seconds(300.0 * index as f64);
+            // boundaries are part of the following chunk
+            assert_eq!(chunk.first_epoch(), Some(t0));
+
+            let obs = chunk.header.obs.as_ref().unwrap();
+            assert_eq!(obs.time_of_first_obs, chunk.first_epoch());
+            assert_eq!(obs.time_of_last_obs, chunk.last_epoch());
+        }
+
+        // shorter than the duration
+        let chunks = rinex.split_dt(Duration::from_hours(1.0)).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].record, rinex.record);
+    }
+    #[test]
+    fn nav_split_dt() {
+        let rinex = Rinex::from_file("../test_resources/NAV/V3/CBW100NLD_R_20210010000_01D_MN.rnx")
+            .unwrap();
+
+        // epochs: E01 00:00, C05 00:00, E33 02:20, G19 13:59, C19 15:00, G20 16:00
+        let chunks = rinex.split_dt(Duration::from_hours(1.0)).unwrap();
+        assert_eq!(chunks.len(), 5);
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.epoch().count())
+                .sum::<usize>(),
+            rinex.epoch().count()
+        );
+
+        assert_eq!(
+            chunks[3].first_epoch(),
+            Some(Epoch::from_str("2021-01-01T15:00:00 BDT").unwrap())
+        );
+
+        // production attributes describe the nominal chunk
+        let details = chunks[1]
+            .prod_attr
+            .as_ref()
+            .unwrap()
+            .details
+            .as_ref()
+            .unwrap();
+        assert_eq!((details.hh, details.mm), (2, 0));
+        assert_eq!(details.ppu, PPU::Hourly);
+
+        assert_eq!(
+            chunks[0].standard_filename(false, None, None),
+            "CBW100NLD_R_20210010000_01H_MN.rnx"
+        );
+    }
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn obs_split_dt_6h() {
+        let rinex =
+            Rinex::from_file("../test_resources/CRNX/V3/ESBC00DNK_R_20201770000_01D_30S_MO.crx.gz")
+                .unwrap();
+
+        // 24h at 30s, from 00:00:00 to 23:59:30
+        let t0 = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+        let dt = Duration::from_hours(6.0);
+        assert_eq!(rinex.first_epoch(), Some(t0));
+
+        let chunks = rinex.split_dt(dt).unwrap();
+        assert_eq!(chunks.len(), 4);
+
+        for (index, chunk) in chunks.iter().enumerate() {
+            let start = t0 + dt * index as f64;
+            let (first, last) = (chunk.first_epoch().unwrap(), chunk.last_epoch().unwrap());
+            // boundaries are part of the following chunk
+            assert_eq!(first, start, "chunk #{} lost its first epoch", index);
+            assert!(
+                last < start + dt,
+                "chunk #{} ends too late: {}",
+                index,
+                last
+            );
+            if let Some(next) = chunks.get(index + 1) {
+                assert!(last < next.first_epoch().unwrap(), "overlapping chunks");
+            }
+
+            // header and comments are preserved
+            assert_eq!(chunk.header.version, rinex.header.version);
+            assert_eq!(chunk.header.station, rinex.header.station);
+            assert_eq!(chunk.comments, rinex.comments);
+
+            let obs = chunk.header.obs.as_ref().unwrap();
+            assert_eq!(obs.time_of_first_obs, Some(first));
+            assert_eq!(obs.time_of_last_obs, Some(last));
+        }
+
+        // nothing is lost
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.epoch().count())
+                .sum::<usize>(),
+            rinex.epoch().count()
+        );
+        assert_eq!(
+            chunks[3].last_epoch(),
+            Some(Epoch::from_str("2020-06-25T23:59:30 GPST").unwrap())
+        );
+    }
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn nav_split_dt_6h() {
+        let mut rinex =
+            Rinex::from_file("../test_resources/NAV/V3/ESBC00DNK_R_20201770000_01D_MN.rnx.gz")
+                .unwrap();
+
+        // daily files spill over adjacent days: only retain 2020-06-25
+        let t0 = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+        let dt = Duration::from_hours(6.0);
+        rinex
+            .record
+            .as_mut_nav()
+            .unwrap()
+            .retain(|t, _| *t >= t0 && *t < t0 + Duration::from_days(1.0));
+
+        assert_eq!(rinex.first_epoch(), Some(t0));
+
+        let chunks = rinex.split_dt(dt).unwrap();
+        assert_eq!(chunks.len(), 4);
+
+        for (index, chunk) in chunks.iter().enumerate() {
+            let start = t0 + dt * index as f64;
+            let (first, last) = (chunk.first_epoch().unwrap(), chunk.last_epoch().unwrap());
+            assert!(
+                first >= start,
+                "chunk #{} starts too early: {}",
+                index,
+                first
+            );
+            assert!(
+                last < start + dt,
+                "chunk #{} ends too late: {}",
+                index,
+                last
+            );
+            if let Some(next) = chunks.get(index + 1) {
+                assert!(last < next.first_epoch().unwrap(), "overlapping chunks");
+            }
+        }
+
+        // nothing is lost
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.epoch().count())
+                .sum::<usize>(),
+            rinex.epoch().count()
+        );
+    }
+}
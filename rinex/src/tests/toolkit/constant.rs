@@ -1,3 +1,4 @@
+use crate::doris::Record as DorisRecord;
 use crate::meteo::Record as MetRecord;
 use crate::observation::Record as ObsRecord;
 use crate::Rinex;
@@ -10,6 +11,8 @@ pub fn is_constant_rinex(rnx: &Rinex, constant: f64, tolerance: f64) {
         is_constant_obs_record(record, constant, tolerance)
     } else if let Some(record) = rnx.record.as_meteo() {
         is_constant_meteo_record(record, constant, tolerance)
+    } else if let Some(record) = rnx.record.as_doris() {
+        is_constant_doris_record(record, constant, tolerance)
     } else {
         unimplemented!("is_constant_rinex({})", rnx.header.rinex_type);
     }
@@ -51,3 +54,25 @@ fn is_constant_meteo_record(record: &MetRecord, constant: f64, tolerance: f64) {
         }
     }
 }
+
+fn is_constant_doris_record(record: &DorisRecord, constant: f64, tolerance: f64) {
+    for (_, (clk, stations)) in record {
+        if let Some(clk) = clk {
+            let err = (clk.offset - constant).abs();
+            if err > tolerance {
+                panic!("rcvr clock {} != {}", clk.offset, constant);
+            }
+        }
+        for (_, observables) in stations {
+            for (observable, observation) in observables {
+                let err = (observation.value - constant).abs();
+                if err > tolerance {
+                    panic!(
+                        "{} observation {} != {}",
+                        observable, observation.value, constant
+                    );
+                }
+            }
+        }
+    }
+}
@@ -7,7 +7,9 @@ use hifitime::TimeSeries;
 /* OBS RINEX dedicated tools */
 mod observation;
 pub use observation::check_observables as obsrinex_check_observables;
+pub use observation::synthetic_1hz_testbench;
 pub use observation::test_observation_rinex;
+pub use observation::vlns0010_testbench;
 
 /* NAV RINEX dedicated tools */
 pub mod nav;
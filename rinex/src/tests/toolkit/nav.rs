@@ -2,6 +2,38 @@ use crate::navigation::KbModel;
 use crate::navigation::NgModel;
 use crate::prelude::{Constellation, Rinex};
 
+#[cfg(feature = "nav")]
+use crate::prelude::Frame;
+
+#[cfg(feature = "nav")]
+use anise::{constants::frames::IAU_EARTH_FRAME, structure::planetocentric::ellipsoid::Ellipsoid};
+use map_3d::{ecef2geodetic, Ellipsoid as MapEllipsoid};
+
+/// Earth centered, body fixed [Frame] with WGS84 shape,
+/// to be used in elevation calculations.
+#[cfg(feature = "nav")]
+pub fn earth_cef() -> Frame {
+    Frame {
+        shape: Some(Ellipsoid::from_spheroid(6378.137, 6356.752314245)),
+        ..IAU_EARTH_FRAME
+    }
+}
+
+/// Elevation angle (in degrees) of a vehicle whose ECEF position is expressed in km,
+/// as seen from a receiver whose ECEF position is expressed in meters.
+/// Computed by local ENU projection, independently of the library.
+pub fn elevation_deg(rx_m: (f64, f64, f64), sv_km: (f64, f64, f64)) -> f64 {
+    let (lat, lon, _) = ecef2geodetic(rx_m.0, rx_m.1, rx_m.2, MapEllipsoid::WGS84);
+    let (dx, dy, dz) = (
+        sv_km.0 * 1.0E3 - rx_m.0,
+        sv_km.1 * 1.0E3 - rx_m.1,
+        sv_km.2 * 1.0E3 - rx_m.2,
+    );
+    let norm = (dx.powi(2) + dy.powi(2) + dz.powi(2)).sqrt();
+    let up = lat.cos() * lon.cos() * dx + lat.cos() * lon.sin() * dy + lat.sin() * dz;
+    (up / norm).asin().to_degrees()
+}
+
 pub fn check_klobuchar_models(rinex: &Rinex, tuple: &[(Constellation, KbModel)]) {
     let header = &rinex.header;
     for (constell, model) in tuple {
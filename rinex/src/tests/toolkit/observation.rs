@@ -1,8 +1,12 @@
 // use crate::observation::Record as ObsRecord;
-use crate::prelude::{Constellation, Epoch, Observable, Rinex};
+use crate::observation::{EpochFlag, HeaderFields, LliFlags, ObservationData, Record, SNR};
+use crate::prelude::{Constellation, Duration, Epoch, Header, Observable, Rinex, SV};
 use crate::tests::toolkit::{
     test_gnss_csv, test_observables_csv, test_rinex, test_sv_csv, test_time_frame, TestTimeFrame,
 };
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::{BTreeMap, HashMap};
+use std::f64::consts::PI;
 use std::str::FromStr;
 
 /*
@@ -132,3 +136,198 @@ pub fn test_observation_rinex(
         );
     }
 }
+
+/*
+ * Synthetic excerpt of VLNS0010 (V3): G08 and G10 sampled every 30s over 8 epochs,
+ * with constant observations and a receiver clock that drifts by 12 µs per epoch
+ * and undergoes a 1 ms reset at 00:02:00.
+ * L2W is blanked for each (epoch index, SV) of `l2w_gaps`,
+ * each (epoch index, flag) of `flags` overrides the epoch flag.
+ */
+pub fn vlns0010_testbench(l2w_gaps: &[(usize, &str)], flags: &[(usize, u8)]) -> String {
+    const CLOCK_OFFSETS: [f64; 8] = [
+        0.000450, 0.000462, 0.000474, 0.000486, -0.000502, -0.000490, -0.000478, -0.000466,
+    ];
+    const GPS_CODES: [&str; 18] = [
+        "C1C", "L1C", "S1C", "C2P", "C2W", "C2S", "C2L", "C2X", "L2P", "L2W", "L2S", "L2L", "L2X",
+        "S2P", "S2W", "S2S", "S2L", "S2X",
+    ];
+    // (code, value, SSI)
+    const OBSERVATIONS: [(&str, [(&str, f64, &str); 6]); 2] = [
+        (
+            "G08",
+            [
+                ("C1C", 20982937.082, " "),
+                ("L1C", 110266080.971, "8"),
+                ("S1C", 49.500, " "),
+                ("C2W", 20982932.182, " "),
+                ("L2W", 85921597.759, "8"),
+                ("S2W", 49.250, " "),
+            ],
+        ),
+        (
+            "G10",
+            [
+                ("C1C", 20653556.564, " "),
+                ("L1C", 108535187.798, "8"),
+                ("S1C", 51.250, " "),
+                ("C2W", 20653551.404, " "),
+                ("L2W", 84572863.286, "8"),
+                ("S2W", 51.250, " "),
+            ],
+        ),
+    ];
+
+    let mut lines = Vec::<String>::new();
+    for (content, marker) in [
+        (
+            "     3.02           OBSERVATION DATA    M (MIXED)",
+            "RINEX VERSION / TYPE",
+        ),
+        (
+            "TPP 3.5.8           VGTU GI             20211231 235930 UTC",
+            "PGM / RUN BY / DATE",
+        ),
+        ("VLNS", "MARKER NAME"),
+        ("10801M001", "MARKER NUMBER"),
+        ("VGTU GI             VGTU GI", "OBSERVER / AGENCY"),
+        (
+            "496804              LEICA GRX1200+GNSS  V 9.20",
+            "REC # / TYPE / VERS",
+        ),
+        ("725555              LEIAR25.R4      NONE", "ANT # / TYPE"),
+        (
+            "  3343600.9781  1580417.5602  5179337.1310",
+            "APPROX POSITION XYZ",
+        ),
+        (
+            "        0.0730        0.0000        0.0000",
+            "ANTENNA: DELTA H/E/N",
+        ),
+        (
+            "G   18 C1C L1C S1C C2P C2W C2S C2L C2X L2P L2W L2S L2L L2X",
+            "SYS / # / OBS TYPES",
+        ),
+        ("       S2P S2W S2S S2L S2X", "SYS / # / OBS TYPES"),
+        (
+            "R    9 C1C L1C S1C C2C C2P L2C L2P S2C S2P",
+            "SYS / # / OBS TYPES",
+        ),
+        ("    30.000", "INTERVAL"),
+        ("     0", "RCV CLOCK OFFS APPL"),
+        ("    18", "LEAP SECONDS"),
+        ("     2", "# OF SATELLITES"),
+        (
+            "  2022    01    01    00    00   00.0000000     GPS",
+            "TIME OF FIRST OBS",
+        ),
+        ("", "END OF HEADER"),
+    ] {
+        lines.push(format!("{:<60}{}", content, marker));
+    }
+    for (index, clock_offset) in CLOCK_OFFSETS.iter().enumerate() {
+        let flag = flags
+            .iter()
+            .find_map(|(i, flag)| if *i == index { Some(*flag) } else { None })
+            .unwrap_or(0);
+        let seconds = index * 30;
+        lines.push(format!(
+            "> 2022 01 01  0 {:2}{:11.7}  {}{:3}{:21.12}",
+            seconds / 60,
+            (seconds % 60) as f64,
+            flag,
+            OBSERVATIONS.len(),
+            clock_offset
+        ));
+        for (sv, observations) in OBSERVATIONS {
+            let mut line = sv.to_string();
+            for code in GPS_CODES {
+                let blanked = code == "L2W" && l2w_gaps.contains(&(index, sv));
+                match observations.iter().find(|(c, _, _)| *c == code) {
+                    Some((_, value, ssi)) if !blanked => {
+                        line.push_str(&format!("{:14.3} {}", value, ssi));
+                    },
+                    _ => line.push_str(&" ".repeat(16)),
+                }
+            }
+            lines.push(line.trim_end().to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+/*
+ * Synthetic 1 Hz GPS observations over 5 minutes, starting 2024-01-01T00:00:00 GPST:
+ * G01 and G02 tracked on L1 (C1C, L1C, D1C, S1C), following a constant acceleration range model.
+ * White noise is added to pseudo range (0.5 m sigma) and phase (0.01 cycle sigma),
+ * using a seeded generator so the content is reproducible.
+ * G01 L1C undergoes a +10 cycle slip at 00:02:30, declared by the LLI.
+ */
+pub fn synthetic_1hz_testbench() -> Rinex {
+    const LAMBDA_L1: f64 = 299_792_458.0 / 1575.42E6;
+    // (SV, initial range [m], range rate [m/s], acceleration [m/s^2])
+    const VEHICLES: [(&str, f64, f64, f64); 2] = [
+        ("G01", 21_000_000.0, 512.3, 0.11),
+        ("G02", 23_500_000.0, -321.7, -0.08),
+    ];
+    let (c1c, l1c, d1c, s1c) = (
+        Observable::from_str("C1C").unwrap(),
+        Observable::from_str("L1C").unwrap(),
+        Observable::from_str("D1C").unwrap(),
+        Observable::from_str("S1C").unwrap(),
+    );
+    let t0 = Epoch::from_str("2024-01-01T00:00:00 GPST").unwrap();
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut gaussian = |sigma: f64| {
+        // Box-Muller transform
+        let u1 = rng.gen_range(f64::EPSILON..1.0);
+        let u2: f64 = rng.gen();
+        sigma * (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+    };
+
+    let mut record = Record::new();
+    for i in 0..300 {
+        let dt = i as f64;
+        let mut vehicles = BTreeMap::new();
+        for (sv, range, rate, acceleration) in VEHICLES {
+            let range = range + rate * dt + 0.5 * acceleration * dt * dt;
+            let mut phase = range / LAMBDA_L1;
+            let mut lli = None;
+            if sv == "G01" && i >= 150 {
+                phase += 10.0;
+                if i == 150 {
+                    lli = Some(LliFlags::LOCK_LOSS);
+                }
+            }
+            let doppler = -(rate + acceleration * dt) / LAMBDA_L1;
+
+            let mut observations = HashMap::new();
+            observations.insert(
+                c1c.clone(),
+                ObservationData::new(range + gaussian(0.5), None, None),
+            );
+            observations.insert(
+                l1c.clone(),
+                ObservationData::new(phase + gaussian(0.01), lli, Some(SNR::DbHz48_53)),
+            );
+            observations.insert(d1c.clone(), ObservationData::new(doppler, None, None));
+            observations.insert(s1c.clone(), ObservationData::new(45.0, None, None));
+            vehicles.insert(SV::from_str(sv).unwrap(), observations);
+        }
+        record.insert(
+            (t0 + Duration::from_seconds(dt), EpochFlag::Ok),
+            (None, vehicles),
+        );
+    }
+
+    let mut codes = HashMap::new();
+    codes.insert(Constellation::GPS, vec![c1c, l1c, d1c, s1c]);
+    let header = Header::basic_obs()
+        .with_constellation(Constellation::GPS)
+        .with_observation_fields(HeaderFields {
+            codes,
+            time_of_first_obs: Some(t0),
+            ..Default::default()
+        });
+    Rinex::new(header, crate::record::Record::ObsRecord(record))
+}
@@ -15,6 +15,7 @@ pub struct Version {
 }
 
 #[derive(Clone, Debug, Error)]
+#[non_exhaustive]
 pub enum ParsingError {
     #[error("non supported version \"{0}\"")]
     NotSupported(String),
@@ -2,40 +2,45 @@
 //! with integrated optionnal .gz compression
 #[cfg(feature = "flate2")]
 use flate2::{write::GzEncoder, Compression};
-use std::fs::File;
-use std::io::BufWriter; // Seek, SeekFrom};
+use std::io::{BufWriter, Write}; // Seek, SeekFrom};
 
-#[derive(Debug)]
-pub enum WriterWrapper {
+pub enum WriterWrapper<'a> {
     /// Readable `RINEX`
-    PlainFile(BufWriter<File>),
+    Plain(BufWriter<Box<dyn Write + 'a>>),
     /// gzip compressed RINEX
     #[cfg(feature = "flate2")]
-    GzFile(BufWriter<GzEncoder<File>>),
+    Gz(BufWriter<GzEncoder<Box<dyn Write + 'a>>>),
 }
 
-pub struct BufferedWriter {
+pub struct BufferedWriter<'a> {
     /// internal writer,
-    writer: WriterWrapper,
+    writer: WriterWrapper<'a>,
+    /// number of (uncompressed) bytes written so far
+    written: u64,
 }
 
-impl BufferedWriter {
+impl<'a> BufferedWriter<'a> {
+    /// Default gzip compression level
+    #[cfg(feature = "flate2")]
+    const GZIP_LEVEL: u32 = 6;
     /// Opens given file for efficient buffered write operation
     /// with possible .gz compression
     pub fn new(path: &str) -> std::io::Result<Self> {
-        let f = std::fs::File::create(path)?;
+        Self::new_staged(path, path)
+    }
+    /*
+     * Opens the `staging` file for buffered write operation,
+     * compression being selected from the final `path`
+     */
+    pub(crate) fn new_staged(staging: &str, path: &str) -> std::io::Result<Self> {
+        let f = std::fs::File::create(staging)?;
         if path.ends_with(".gz") {
             // --> .gz compression
             #[cfg(feature = "flate2")]
             {
                 // .gz
                 // example : i.gz, .n.gz, .crx.gz
-                Ok(Self {
-                    writer: WriterWrapper::GzFile(
-                        // compression lvl 6 seems to be the optimal standard
-                        BufWriter::new(GzEncoder::new(f, Compression::new(6))),
-                    ),
-                })
+                Ok(Self::from_writer_gz(f))
             }
             #[cfg(not(feature = "flate2"))]
             {
@@ -45,26 +50,86 @@ impl BufferedWriter {
             panic!(".z compression is not supported yet, compress manually")
         } else {
             // Assumes no extra compression
-            Ok(Self {
-                writer: WriterWrapper::PlainFile(BufWriter::new(f)),
-            })
+            Ok(Self::from_writer(f))
+        }
+    }
+    /// Wraps any [Write] implementation, for example a network stream
+    /// or an in-memory buffer, for efficient buffered write operation.
+    pub fn from_writer<W: Write + 'a>(w: W) -> Self {
+        let w: Box<dyn Write + 'a> = Box::new(w);
+        Self {
+            writer: WriterWrapper::Plain(BufWriter::new(w)),
+            written: 0,
+        }
+    }
+    /// Opens given file for efficient buffered write operation,
+    /// with gzip compression at desired level (0: none, 9: best),
+    /// whatever the file extension.
+    #[cfg(feature = "flate2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "flate2")))]
+    pub fn new_gzip(path: &str, level: u32) -> std::io::Result<Self> {
+        let f = std::fs::File::create(path)?;
+        Ok(Self::from_writer_gz_level(f, level))
+    }
+    /// Wraps any [Write] implementation, like [Self::from_writer],
+    /// with gzip compression.
+    #[cfg(feature = "flate2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "flate2")))]
+    pub fn from_writer_gz<W: Write + 'a>(w: W) -> Self {
+        // compression lvl 6 seems to be the optimal standard
+        Self::from_writer_gz_level(w, Self::GZIP_LEVEL)
+    }
+    /// Wraps any [Write] implementation, like [Self::from_writer_gz],
+    /// with desired compression level (0: none, 9: best).
+    #[cfg(feature = "flate2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "flate2")))]
+    pub fn from_writer_gz_level<W: Write + 'a>(w: W, level: u32) -> Self {
+        let w: Box<dyn Write + 'a> = Box::new(w);
+        Self {
+            writer: WriterWrapper::Gz(BufWriter::new(GzEncoder::new(
+                w,
+                Compression::new(level.min(9)),
+            ))),
+            written: 0,
+        }
+    }
+    /*
+     * Returns number of (uncompressed) bytes written so far
+     */
+    pub(crate) fn written(&self) -> u64 {
+        self.written
+    }
+    /// Flushes all pending content and terminates the stream.
+    /// This is required to report errors that would otherwise happen
+    /// silently on drop, in particular when writing the gzip trailer.
+    pub fn finish(self) -> std::io::Result<()> {
+        match self.writer {
+            WriterWrapper::Plain(mut writer) => writer.flush(),
+            #[cfg(feature = "flate2")]
+            WriterWrapper::Gz(writer) => {
+                let mut encoder = writer.into_inner().map_err(|e| e.into_error())?;
+                encoder.try_finish()?;
+                encoder.flush()
+            },
         }
     }
 }
 
-impl std::io::Write for BufferedWriter {
+impl Write for BufferedWriter<'_> {
     fn write(&mut self, buf: &[u8]) -> Result<usize, std::io::Error> {
-        match self.writer {
-            WriterWrapper::PlainFile(ref mut writer) => writer.write(buf),
+        let size = match self.writer {
+            WriterWrapper::Plain(ref mut writer) => writer.write(buf),
             #[cfg(feature = "flate2")]
-            WriterWrapper::GzFile(ref mut writer) => writer.write(buf),
-        }
+            WriterWrapper::Gz(ref mut writer) => writer.write(buf),
+        }?;
+        self.written += size as u64;
+        Ok(size)
     }
     fn flush(&mut self) -> Result<(), std::io::Error> {
         match self.writer {
-            WriterWrapper::PlainFile(ref mut writer) => writer.flush(),
+            WriterWrapper::Plain(ref mut writer) => writer.flush(),
             #[cfg(feature = "flate2")]
-            WriterWrapper::GzFile(ref mut writer) => writer.flush(),
+            WriterWrapper::Gz(ref mut writer) => writer.flush(),
         }
     }
 }
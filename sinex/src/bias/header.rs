@@ -14,6 +14,7 @@ pub enum BiasMode {
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum BiasModeError {
     #[error("unknown bias mode")]
     UnknownBiasMode,
@@ -43,6 +44,7 @@ impl std::str::FromStr for BiasMode {
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Header line should start with %=
     #[error("missing header delimiter")]
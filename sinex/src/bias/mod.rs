@@ -18,6 +18,7 @@ pub enum TimeSystem {
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum TimeSystemError {
     #[error("unknown time system \"{0}\"")]
     UnknownSystem(String),
@@ -45,6 +46,7 @@ impl Default for TimeSystem {
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum DeterminationMethodError {
     #[error("unknown determination method \"{0}\"")]
     UnknownMethod(String),
@@ -101,6 +103,7 @@ pub enum BiasType {
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum SolutionParsingError {
     #[error("failed to parse BiasType")]
     ParseBiasTypeError(#[from] strum::ParseError),
@@ -187,6 +190,11 @@ impl Solution {
     pub fn duration(&self) -> chrono::TimeDelta {
         self.end_time - self.start_time
     }
+    /// Returns true if this bias solution applies at given instant,
+    /// expressed in the [TimeSystem] of the file
+    pub fn is_valid(&self, t: chrono::NaiveDateTime) -> bool {
+        self.start_time <= t && t < self.end_time
+    }
 }
 
 #[cfg(test)]
@@ -304,6 +312,36 @@ mod tests {
         assert_eq!(solutions.len(), 50);
     }
     #[test]
+    fn test_bia_v1_satellite_osb() {
+        let file = env!("CARGO_MANIFEST_DIR").to_owned() + "/data/BIA/V1/example-1a.bia";
+        let sinex = Sinex::from_file(&file).unwrap();
+
+        let solutions = sinex.satellite_osb("G01", "C1C").collect::<Vec<_>>();
+        assert_eq!(solutions.len(), 1);
+        let solution = solutions[0];
+        assert_eq!(solution.svn, "G063");
+        assert_eq!(solution.unit, "ns");
+        assert!((solution.estimate - 10.2472).abs() < 1E-6);
+
+        let t = chrono::NaiveDate::from_yo_opt(2016, 300)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        assert!(solution.is_valid(t));
+        let t = chrono::NaiveDate::from_yo_opt(2016, 333)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        assert!(!solution.is_valid(t));
+
+        let solutions = sinex.satellite_osb("G01", "C2W").collect::<Vec<_>>();
+        assert_eq!(solutions.len(), 1);
+        assert!((solutions[0].estimate - 19.2442).abs() < 1E-6);
+
+        assert_eq!(sinex.satellite_osb("G01", "L1C").count(), 0);
+        assert_eq!(sinex.satellite_osb("G99", "C1C").count(), 0);
+    }
+    #[test]
     fn test_bia_v1_example1b() {
         let file = env!("CARGO_MANIFEST_DIR").to_owned() + "/data/BIA/V1/example-1b.bia";
         let sinex = Sinex::from_file(&file);
@@ -2,6 +2,7 @@ use std::str::FromStr;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ParseDateTimeError {
     #[error("failed to parse YYYY:DDD")]
     DatetimeError(#[from] chrono::format::ParseError),
@@ -21,6 +21,7 @@ impl Default for DocumentType {
 }
 
 #[derive(Debug, Error, Clone)]
+#[non_exhaustive]
 pub enum DocumentTypeError {
     /// Sinex file type not recognized
     #[error("unknown file type \"{0}\"")]
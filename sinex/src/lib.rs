@@ -45,6 +45,7 @@ fn is_dotdotdot(line: &str) -> bool {
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// SINEX file should start with proper header
     #[error("missing header delimiter")]
@@ -75,6 +76,20 @@ pub enum Error {
     ParseTimeSystemError(#[from] bias::TimeSystemError),
 }
 
+pub use rinex_qc_traits::ErrorKind;
+
+impl Error {
+    /// Returns the [ErrorKind] of this [Error]
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::FileError(e) => ErrorKind::from_io_error(e),
+            Self::MissingHeader | Self::InvalidHeader => ErrorKind::MalformedHeader,
+            Self::UnknownSection(_) => ErrorKind::Unsupported,
+            _ => ErrorKind::MalformedRecord,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Record {
     /// Bias (BIA) record case
@@ -92,6 +107,12 @@ impl Record {
             Self::BiasSolutions(r) => Some(r),
         }
     }
+    /// Unwraps Bias Solutions mutably, if feasible
+    pub fn bias_solutions_mut(&mut self) -> Option<&mut Vec<bias::Solution>> {
+        match self {
+            Self::BiasSolutions(r) => Some(r),
+        }
+    }
     /*
         /// Unwraps Troposphere Record, if feasible,
         /// is [troposphere::Record] definition for more detail
@@ -289,4 +310,24 @@ impl Sinex {
             record: Record::BiasSolutions(bias_solutions),
         })
     }
+    /// Returns the satellite Observable Specific Bias (OSB) solutions
+    /// of given `prn` (like "G01") and `observable` (like "C1C"),
+    /// in order of appearance. Station specific solutions are disregarded.
+    /// Use [bias::Solution::is_valid] to select the one that applies.
+    pub fn satellite_osb<'a>(
+        &'a self,
+        prn: &'a str,
+        observable: &'a str,
+    ) -> Box<dyn Iterator<Item = &'a bias::Solution> + 'a> {
+        match self.record.bias_solutions() {
+            Some(solutions) => Box::new(solutions.iter().filter(move |sol| {
+                sol.btype == bias::BiasType::OSB
+                    && sol.station.is_none()
+                    && sol.prn == prn
+                    && sol.obs.0 == observable
+                    && sol.obs.1.is_none()
+            })),
+            None => Box::new(std::iter::empty()),
+        }
+    }
 }
@@ -22,6 +22,7 @@ pub struct Receiver {
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ReceiverError {
     #[error("failed to parse datetime field")]
     ParseDateError(#[from] chrono::format::ParseError),
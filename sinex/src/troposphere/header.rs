@@ -29,6 +29,7 @@ pub enum Technique {
 
 /// Technique Parsing Error
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum TechniqueParsingError {
     /// Unknown Technique descriptor
     UnknownTechnique(String),
@@ -62,6 +63,7 @@ impl std::str::FromStr for Technique {
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Header line should start with %=BIA
     #[error("missing header delimiter")]
@@ -83,6 +83,7 @@ pub enum Solution {
 }
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ParseCoordinatesError {
     #[error("failed to parse sol#n")]
     ParseSOLnError(#[from] std::num::ParseIntError),
@@ -34,4 +34,16 @@ mod test {
             }
         }
     }
+    #[test]
+    fn error_kind() {
+        let test_resources = env!("CARGO_MANIFEST_DIR").to_owned() + "/../test_resources/";
+        let err =
+            Sinex::from_file(&(test_resources.clone() + "BIA/V1/not_a_file.BIA")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unavailable);
+        assert!(!err.kind().is_retryable());
+
+        let err = Sinex::from_file(&(test_resources + "OBS/V3/DUTH0630.22O")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MalformedHeader);
+        assert!(!err.kind().is_retryable());
+    }
 }
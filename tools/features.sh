@@ -0,0 +1,43 @@
+#! /bin/sh
+# Compiles (library and tests) each crate against the meaningful
+# feature combinations, to make sure no cfg boundary leaks.
+set -e
+
+check() {
+    folder=$1
+    features=$2
+    echo "[$folder] features: \"$features\""
+    (cd $folder && cargo check --all-targets --no-default-features --features "$features")
+}
+
+for features in \
+    "" \
+    "obs" \
+    "nav" \
+    "meteo" \
+    "clock" \
+    "ionex" \
+    "antex" \
+    "doris" \
+    "flate2" \
+    "serde" \
+    "qc" \
+    "processing" \
+    "binex,nav" \
+    "obs,nav" \
+    "obs,flate2" \
+    "obs,processing" \
+    "nav,qc" \
+    "nav,processing" \
+    "obs,nav,processing" \
+    "full"; do
+    check rinex "$features"
+done
+
+for features in "" "qc" "processing"; do
+    check sp3 "$features"
+done
+
+for features in "" "sp3"; do
+    check rinex-qc "$features"
+done
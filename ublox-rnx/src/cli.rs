@@ -50,6 +50,13 @@ impl Cli {
                             .long("nav")
                             .help("Generate RINEX Navigation, disabled by default"),
                     )
+                    .arg(
+                        Arg::new("jamming")
+                            .short('j')
+                            .long("jamming")
+                            .value_name("THRESHOLD")
+                            .help("Jamming indicator (0-255) above which a COMMENT is emitted, default: \"64\""),
+                    )
                     .get_matches()
             },
         }
@@ -70,6 +77,14 @@ impl Cli {
             Ok(9600)
         }
     }
+    /* returns jamming indicator threshold */
+    pub fn jamming_threshold(&self) -> Result<u8, std::num::ParseIntError> {
+        if let Some(t) = self.matches.get_one::<String>("jamming") {
+            t.parse::<u8>()
+        } else {
+            Ok(64)
+        }
+    }
     /* returns true if Observation Data to be generated */
     pub fn observation(&self) -> bool {
         self.matches.get_flag("observation")
@@ -0,0 +1,443 @@
+//! UBX packet handling, decoupled from the serial device
+//! so it can be fed synthetic packet sequences.
+use rinex::epoch;
+use rinex::navigation::{IonMessage, KbModel, KbRegionCode};
+use rinex::observation::{LliFlags, ObservationData};
+use rinex::prelude::*;
+
+use gnss::sv;
+
+use ublox::NavTimeUtcFlags;
+use ublox::PacketRef;
+use ublox::{GpsFix, RecStatFlags};
+
+use log::{debug, error, info, trace, warn};
+
+use crate::identify_constellation;
+
+/// Default jamming indicator threshold (MonHw jam_ind, 0 to 255)
+pub const DEFAULT_JAMMING_THRESHOLD: u8 = 64;
+
+/// Receiver events that should be described in the RINEX output
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// Comment to insert in the RINEX stream
+    Comment(String),
+    /// Special event epoch (flag > 1), followed by its description
+    Special {
+        epoch: Epoch,
+        flag: EpochFlag,
+        description: Vec<String>,
+    },
+}
+
+fn fmt_comment(comment: &str) -> String {
+    let comment = comment.chars().take(60).collect::<String>();
+    format!("{:<60}COMMENT", comment)
+}
+
+impl std::fmt::Display for Event {
+    /// Formats [Event] as RINEX (V3) content
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Comment(comment) => write!(f, "{}", fmt_comment(comment)),
+            Self::Special {
+                epoch,
+                flag,
+                description,
+            } => {
+                write!(
+                    f,
+                    "> {}  {} {:2}",
+                    epoch::format(*epoch, RinexType::ObservationData, 3),
+                    flag,
+                    description.len()
+                )?;
+                for line in description {
+                    write!(f, "\n{}", fmt_comment(line))?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// [Handler] tracks the receiver state, from one packet to another
+pub struct Handler {
+    /// Jamming indicator threshold, above which we emit a comment
+    pub jamming_threshold: u8,
+    /// Latest [Epoch]
+    pub epoch: Epoch,
+    /// Current [EpochFlag]
+    pub epoch_flag: EpochFlag,
+    /// Current LLI
+    pub lli: Option<LliFlags>,
+    /// Current observation
+    pub obs_data: ObservationData,
+    /// Receiver uptime
+    pub uptime: Duration,
+    /// Current fix status
+    pub fix_type: GpsFix,
+    /// True while jamming indicator exceeds the threshold
+    jammed: bool,
+}
+
+impl Default for Handler {
+    fn default() -> Self {
+        Self {
+            jamming_threshold: DEFAULT_JAMMING_THRESHOLD,
+            epoch: Epoch::default(),
+            epoch_flag: EpochFlag::default(),
+            lli: None,
+            obs_data: ObservationData::default(),
+            uptime: Duration::default(),
+            fix_type: GpsFix::NoFix,
+            jammed: false,
+        }
+    }
+}
+
+impl Handler {
+    /// Builds a new [Handler] with desired jamming indicator threshold
+    pub fn new(jamming_threshold: u8) -> Self {
+        Self {
+            jamming_threshold,
+            ..Default::default()
+        }
+    }
+    /// Consumes a new packet, returns the [Event]s it gave rise to
+    pub fn handle(&mut self, packet: PacketRef) -> Vec<Event> {
+        let mut events = Vec::new();
+        match packet {
+            /*
+             * Configuration frames:
+             * should be depiceted by HEADER section
+             */
+            //PacketRef::CfgRate(pkt) => {
+            //    //TODO EPOCH INTERVAL
+            //    let gps_rate = pkt.measure_rate_ms();
+            //    //TODO EPOCH INTERVAL
+            //    let nav_rate = pkt.nav_rate();
+            //    //TODO reference time
+            //    let time = pkt.time_ref();
+            //},
+            PacketRef::CfgNav5(pkt) => {
+                // Dynamic model
+                let _dyn_model = pkt.dyn_model();
+            },
+            PacketRef::RxmRawx(pkt) => {
+                let _leap_s = pkt.leap_s();
+                if pkt.rec_stat().intersects(RecStatFlags::CLK_RESET) {
+                    // notify reset event
+                    if let Some(ref mut lli) = self.lli {
+                        *lli |= LliFlags::LOCK_LOSS;
+                    } else {
+                        self.lli = Some(LliFlags::LOCK_LOSS);
+                    }
+                    if self.epoch_flag != EpochFlag::CycleSlip {
+                        events.push(Event::Comment(format!(
+                            "RECEIVER CLOCK RESET AT TOW {:.3}",
+                            pkt.rcv_tow()
+                        )));
+                    }
+                    self.epoch_flag = EpochFlag::CycleSlip;
+                }
+                self.obs_data.lli = self.lli;
+            },
+            PacketRef::MonHw(pkt) => {
+                let jamming = pkt.jam_ind();
+                if jamming > self.jamming_threshold {
+                    if !self.jammed {
+                        events.push(Event::Comment(format!(
+                            "JAMMING INDICATOR {} (THRESHOLD {})",
+                            jamming, self.jamming_threshold
+                        )));
+                    }
+                    self.jammed = true;
+                } else {
+                    self.jammed = false;
+                }
+                //antenna problem:
+                // pkt.a_status();
+                // pkt.a_power();
+            },
+            PacketRef::MonGnss(_pkt) => {
+                //pkt.supported(); // GNSS
+                //pkt.default(); // GNSS
+                //pkt.enabled(); //GNSS
+            },
+            PacketRef::MonVer(pkt) => {
+                //UBX revision
+                pkt.software_version();
+                pkt.hardware_version();
+            },
+            /*
+             * NAVIGATION
+             */
+            PacketRef::NavSat(pkt) => {
+                for sv in pkt.svs() {
+                    let gnss = identify_constellation(sv.gnss_id());
+                    if gnss.is_ok() {
+                        let _elev = sv.elev();
+                        let _azim = sv.azim();
+                        let _pr_res = sv.pr_res();
+                        let _flags = sv.flags();
+
+                        let _sv = SV {
+                            constellation: gnss.unwrap(),
+                            prn: sv.sv_id(),
+                        };
+
+                        // flags.sv_used()
+                        //flags.health();
+                        //flags.quality_ind();
+                        //flags.differential_correction_available();
+                        //flags.ephemeris_available();
+                    }
+                }
+            },
+            PacketRef::NavTimeUTC(pkt) => {
+                if pkt.valid().intersects(NavTimeUtcFlags::VALID_UTC) {
+                    // leap seconds already known
+                    let e = Epoch::maybe_from_gregorian(
+                        pkt.year().into(),
+                        pkt.month(),
+                        pkt.day(),
+                        pkt.hour(),
+                        pkt.min(),
+                        pkt.sec(),
+                        pkt.nanos() as u32,
+                        TimeScale::UTC,
+                    );
+                    if let Ok(e) = e {
+                        self.epoch = e;
+                    }
+                }
+            },
+            PacketRef::NavStatus(pkt) => {
+                let fix_type = pkt.fix_type();
+                if fix_type != self.fix_type {
+                    // fix transition: described as special event
+                    events.push(Event::Special {
+                        epoch: self.epoch,
+                        flag: EpochFlag::HeaderInformationFollows,
+                        description: vec![format!(
+                            "FIX TYPE {:?} -> {:?}",
+                            self.fix_type, fix_type
+                        )],
+                    });
+                }
+                self.fix_type = fix_type;
+                self.uptime = Duration::from_milliseconds(pkt.uptime_ms() as f64);
+                trace!(
+                    "itow: {} uptime: {} flags: {:?} status: {:?}",
+                    pkt.itow(),
+                    self.uptime,
+                    pkt.flags(),
+                    pkt.flags2()
+                );
+            },
+            PacketRef::NavEoe(_pkt) => {
+                // reset Epoch
+                self.lli = None;
+                self.epoch_flag = EpochFlag::default();
+            },
+            /*
+             * NAVIGATION : EPHEMERIS
+             */
+            PacketRef::MgaGpsEph(pkt) => {
+                let _sv = sv!(&format!("G{}", pkt.sv_id()));
+                //nav_record.insert(epoch, sv);
+            },
+            PacketRef::MgaGloEph(pkt) => {
+                let _sv = sv!(&format!("R{}", pkt.sv_id()));
+                //nav_record.insert(epoch, sv);
+            },
+            /*
+             * NAVIGATION: IONOSPHERIC MODELS
+             */
+            PacketRef::MgaGpsIono(pkt) => {
+                let kbmodel = KbModel {
+                    alpha: (pkt.alpha0(), pkt.alpha1(), pkt.alpha2(), pkt.alpha3()),
+                    beta: (pkt.beta0(), pkt.beta1(), pkt.beta2(), pkt.beta3()),
+                    region: KbRegionCode::default(), // TODO,
+                };
+                let _iono = IonMessage::KlobucharModel(kbmodel);
+            },
+            /*
+             * OBSERVATION: Receiver Clock
+             */
+            PacketRef::NavClock(pkt) => {
+                let _bias = pkt.clk_b();
+                let _drift = pkt.clk_d();
+                // pkt.t_acc(); // phase accuracy
+                // pkt.f_acc(); // frequency accuracy
+            },
+            /*
+             * Errors, Warnings
+             */
+            PacketRef::InfTest(pkt) => {
+                if let Some(msg) = pkt.message() {
+                    trace!("{}", msg);
+                }
+            },
+            PacketRef::InfDebug(pkt) => {
+                if let Some(msg) = pkt.message() {
+                    debug!("{}", msg);
+                }
+            },
+            PacketRef::InfNotice(pkt) => {
+                if let Some(msg) = pkt.message() {
+                    info!("{}", msg);
+                }
+            },
+            PacketRef::InfError(pkt) => {
+                if let Some(msg) = pkt.message() {
+                    error!("{}", msg);
+                }
+            },
+            PacketRef::InfWarning(pkt) => {
+                if let Some(msg) = pkt.message() {
+                    warn!("{}", msg);
+                }
+            },
+            _ => {},
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+    use ublox::Parser;
+
+    /// Frames given payload as UBX packet
+    fn ubx_frame(class: u8, id: u8, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0xb5, 0x62, class, id];
+        frame.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+        frame.extend_from_slice(payload);
+        let (mut ck_a, mut ck_b) = (0_u8, 0_u8);
+        for byte in &frame[2..] {
+            ck_a = ck_a.wrapping_add(*byte);
+            ck_b = ck_b.wrapping_add(ck_a);
+        }
+        frame.push(ck_a);
+        frame.push(ck_b);
+        frame
+    }
+
+    fn nav_status(fix: u8) -> Vec<u8> {
+        let mut payload = [0_u8; 16];
+        payload[4] = fix;
+        ubx_frame(0x01, 0x03, &payload)
+    }
+
+    fn rxm_rawx(rec_stat: u8) -> Vec<u8> {
+        let mut payload = [0_u8; 16];
+        payload[0..8].copy_from_slice(&1000.0_f64.to_le_bytes());
+        payload[12] = rec_stat;
+        ubx_frame(0x02, 0x15, &payload)
+    }
+
+    fn mon_hw(jam_ind: u8) -> Vec<u8> {
+        let mut payload = [0_u8; 60];
+        payload[45] = jam_ind;
+        ubx_frame(0x0a, 0x09, &payload)
+    }
+
+    fn nav_eoe() -> Vec<u8> {
+        ubx_frame(0x01, 0x61, &[0_u8; 4])
+    }
+
+    /// Feeds all frames to the [Handler], returns all events
+    fn run(handler: &mut Handler, frames: Vec<Vec<u8>>) -> Vec<Event> {
+        let mut parser = Parser::default();
+        let mut events = Vec::new();
+        for frame in frames {
+            let mut it = parser.consume(&frame);
+            while let Some(packet) = it.next() {
+                events.extend(handler.handle(packet.unwrap()));
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn clock_reset() {
+        let mut handler = Handler::default();
+        let events = run(&mut handler, vec![rxm_rawx(0x02), rxm_rawx(0x02)]);
+        assert_eq!(handler.epoch_flag, EpochFlag::CycleSlip);
+        assert_eq!(handler.obs_data.lli, Some(LliFlags::LOCK_LOSS));
+        // described once per epoch
+        assert_eq!(
+            events,
+            vec![Event::Comment(
+                "RECEIVER CLOCK RESET AT TOW 1000.000".to_string()
+            )]
+        );
+
+        let events = run(&mut handler, vec![nav_eoe(), rxm_rawx(0x01)]);
+        assert!(events.is_empty());
+        assert_eq!(handler.epoch_flag, EpochFlag::Ok);
+        assert_eq!(handler.obs_data.lli, None);
+    }
+
+    #[test]
+    fn fix_transitions() {
+        let mut handler = Handler::default();
+        handler.epoch = Epoch::from_str("2024-01-01T12:00:00 UTC").unwrap();
+
+        let events = run(
+            &mut handler,
+            vec![nav_status(0x03), nav_status(0x03), nav_status(0x00)],
+        );
+        assert_eq!(events.len(), 2);
+        assert_eq!(handler.fix_type, GpsFix::NoFix);
+
+        match &events[0] {
+            Event::Special {
+                flag, description, ..
+            } => {
+                assert_eq!(*flag, EpochFlag::HeaderInformationFollows);
+                assert_eq!(description, &vec!["FIX TYPE NoFix -> Fix3D".to_string()]);
+            },
+            _ => panic!("fix transition should be a special event"),
+        }
+
+        assert_eq!(
+            events[1].to_string(),
+            format!(
+                "> 2024 01 01 12 00  0.0000000  4  1\n{:<60}COMMENT",
+                "FIX TYPE Fix3D -> NoFix"
+            )
+        );
+    }
+
+    #[test]
+    fn jamming() {
+        let mut handler = Handler::new(100);
+        let events = run(
+            &mut handler,
+            vec![
+                mon_hw(10),
+                mon_hw(150),
+                mon_hw(200),
+                mon_hw(50),
+                mon_hw(101),
+            ],
+        );
+        assert_eq!(
+            events,
+            vec![
+                Event::Comment("JAMMING INDICATOR 150 (THRESHOLD 100)".to_string()),
+                Event::Comment("JAMMING INDICATOR 101 (THRESHOLD 100)".to_string()),
+            ]
+        );
+        assert_eq!(
+            events[0].to_string(),
+            format!("{:<60}COMMENT", "JAMMING INDICATOR 150 (THRESHOLD 100)")
+        );
+    }
+}
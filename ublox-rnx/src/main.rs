@@ -1,35 +1,27 @@
 //! Application to generate RINEX data in standard format
 //! using a Ublox receiver.   
 //! Homepage: <https://github.com/georust/rinex>
-use std::str::FromStr;
-
 use thiserror::Error;
 
-use rinex::navigation::{IonMessage, KbModel, KbRegionCode};
-use rinex::observation::{LliFlags, ObservationData};
-use rinex::prelude::EpochFlag;
 use rinex::prelude::*;
 
 extern crate gnss_rs as gnss;
 
-use gnss::prelude::SV;
-use gnss::sv;
-
 extern crate ublox;
+use ublox::NavSat;
 use ublox::{
     CfgMsgAllPorts, CfgMsgAllPortsBuilder, CfgPrtUart, CfgPrtUartBuilder, DataBits, InProtoMask,
-    OutProtoMask, PacketRef, Parity, StopBits, UartMode, UartPortId,
+    OutProtoMask, Parity, StopBits, UartMode, UartPortId,
 };
-use ublox::{GpsFix, RecStatFlags};
-use ublox::{NavSat, NavTimeUtcFlags};
-use ublox::{NavStatusFlags, NavStatusFlags2};
 
-use log::{debug, error, info, trace, warn};
+use log::{error, info};
 
 mod cli;
 mod device;
+mod handler;
 
 use cli::Cli;
+use handler::{Event, Handler};
 
 #[derive(Debug, Clone, Error)]
 pub enum Error {
@@ -164,193 +156,41 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Create header section
     let mut _nav_header = Header::basic_nav();
-    let mut _obs_header = Header::basic_obs();
+    let obs_header = Header::basic_obs();
     // let mut clk_header = Header::basic_clk();
 
     //TODO header CLI customization
 
-    // current work structures
-    let mut itow = 0_u32;
-    let mut epoch = Epoch::default();
-    let mut epoch_flag = EpochFlag::default();
-
-    // observation
-    let mut _observable = Observable::default();
-    let mut lli: Option<LliFlags> = None;
-    let mut obs_data = ObservationData::default();
-
-    let mut uptime = Duration::default();
+    // packet handler
+    let jamming_threshold = match cli.jamming_threshold() {
+        Ok(threshold) => threshold,
+        Err(e) => {
+            error!("failed to parse jamming threshold: {}", e);
+            handler::DEFAULT_JAMMING_THRESHOLD
+        },
+    };
+    let mut handler = Handler::new(jamming_threshold);
 
-    let mut fix_type = GpsFix::NoFix; // current fix status
-    let mut fix_flags = NavStatusFlags::empty(); // current fix flag
-    let mut nav_status = NavStatusFlags2::Inactive;
+    // header section is written once and for all:
+    // receiver events are then described inline
+    print!("{}", obs_header);
 
     loop {
         // main loop
         let _ = device.update(|packet| {
-            match packet {
-                /*
-                 * Configuration frames:
-                 * should be depiceted by HEADER section
-                 */
-                //PacketRef::CfgRate(pkt) => {
-                //    //TODO EPOCH INTERVAL
-                //    let gps_rate = pkt.measure_rate_ms();
-                //    //TODO EPOCH INTERVAL
-                //    let nav_rate = pkt.nav_rate();
-                //    //TODO reference time
-                //    let time = pkt.time_ref();
-                //},
-                PacketRef::CfgNav5(pkt) => {
-                    // Dynamic model
-                    let _dyn_model = pkt.dyn_model();
-                },
-                PacketRef::RxmRawx(pkt) => {
-                    let _leap_s = pkt.leap_s();
-                    if pkt.rec_stat().intersects(RecStatFlags::CLK_RESET) {
-                        // notify reset event
-                        if let Some(ref mut lli) = lli {
-                            *lli |= LliFlags::LOCK_LOSS;
-                        } else {
-                            lli = Some(LliFlags::LOCK_LOSS);
-                        }
-                        epoch_flag = EpochFlag::CycleSlip;
-                    }
-                    obs_data.lli = lli;
-                },
-                PacketRef::MonHw(_pkt) => {
-                    //let jamming = pkt.jam_ind(); //TODO
-                    //antenna problem:
-                    // pkt.a_status();
-                    // pkt.a_power();
-                },
-                PacketRef::MonGnss(_pkt) => {
-                    //pkt.supported(); // GNSS
-                    //pkt.default(); // GNSS
-                    //pkt.enabled(); //GNSS
-                },
-                PacketRef::MonVer(pkt) => {
-                    //UBX revision
-                    pkt.software_version();
-                    pkt.hardware_version();
-                },
-                /*
-                 * NAVIGATION
-                 */
-                PacketRef::NavSat(pkt) => {
-                    for sv in pkt.svs() {
-                        let gnss = identify_constellation(sv.gnss_id());
-                        if gnss.is_ok() {
-                            let _elev = sv.elev();
-                            let _azim = sv.azim();
-                            let _pr_res = sv.pr_res();
-                            let _flags = sv.flags();
-
-                            let _sv = SV {
-                                constellation: gnss.unwrap(),
-                                prn: sv.sv_id(),
-                            };
-
-                            // flags.sv_used()
-                            //flags.health();
-                            //flags.quality_ind();
-                            //flags.differential_correction_available();
-                            //flags.ephemeris_available();
-                        }
-                    }
-                },
-                PacketRef::NavTimeUTC(pkt) => {
-                    if pkt.valid().intersects(NavTimeUtcFlags::VALID_UTC) {
-                        // leap seconds already known
-                        let e = Epoch::maybe_from_gregorian(
-                            pkt.year().into(),
-                            pkt.month(),
-                            pkt.day(),
-                            pkt.hour(),
-                            pkt.min(),
-                            pkt.sec(),
-                            pkt.nanos() as u32,
-                            TimeScale::UTC,
-                        );
-                        if e.is_ok() {
-                            epoch = e.unwrap();
-                        }
-                    }
-                },
-                PacketRef::NavStatus(pkt) => {
-                    itow = pkt.itow();
-                    fix_type = pkt.fix_type();
-                    fix_flags = pkt.flags();
-                    nav_status = pkt.flags2();
-                    uptime = Duration::from_milliseconds(pkt.uptime_ms() as f64);
-                    trace!("uptime: {}", uptime);
-                },
-                PacketRef::NavEoe(pkt) => {
-                    itow = pkt.itow();
-                    // reset Epoch
-                    lli = None;
-                    epoch_flag = EpochFlag::default();
-                },
-                /*
-                 * NAVIGATION : EPHEMERIS
-                 */
-                PacketRef::MgaGpsEph(pkt) => {
-                    let _sv = sv!(&format!("G{}", pkt.sv_id()));
-                    //nav_record.insert(epoch, sv);
-                },
-                PacketRef::MgaGloEph(pkt) => {
-                    let _sv = sv!(&format!("R{}", pkt.sv_id()));
-                    //nav_record.insert(epoch, sv);
-                },
-                /*
-                 * NAVIGATION: IONOSPHERIC MODELS
-                 */
-                PacketRef::MgaGpsIono(pkt) => {
-                    let kbmodel = KbModel {
-                        alpha: (pkt.alpha0(), pkt.alpha1(), pkt.alpha2(), pkt.alpha3()),
-                        beta: (pkt.beta0(), pkt.beta1(), pkt.beta2(), pkt.beta3()),
-                        region: KbRegionCode::default(), // TODO,
-                    };
-                    let _iono = IonMessage::KlobucharModel(kbmodel);
-                },
-                /*
-                 * OBSERVATION: Receiver Clock
-                 */
-                PacketRef::NavClock(pkt) => {
-                    let _bias = pkt.clk_b();
-                    let _drift = pkt.clk_d();
-                    // pkt.t_acc(); // phase accuracy
-                    // pkt.f_acc(); // frequency accuracy
-                },
-                /*
-                 * Errors, Warnings
-                 */
-                PacketRef::InfTest(pkt) => {
-                    if let Some(msg) = pkt.message() {
-                        trace!("{}", msg);
-                    }
-                },
-                PacketRef::InfDebug(pkt) => {
-                    if let Some(msg) = pkt.message() {
-                        debug!("{}", msg);
-                    }
-                },
-                PacketRef::InfNotice(pkt) => {
-                    if let Some(msg) = pkt.message() {
-                        info!("{}", msg);
-                    }
-                },
-                PacketRef::InfError(pkt) => {
-                    if let Some(msg) = pkt.message() {
-                        error!("{}", msg);
-                    }
-                },
-                PacketRef::InfWarning(pkt) => {
-                    if let Some(msg) = pkt.message() {
-                        warn!("{}", msg);
-                    }
-                },
-                _ => {},
+            for event in handler.handle(packet) {
+                info!("receiver event: {:?}", event);
+                // comments within the record are only permitted
+                // as part of a special event
+                let event = match event {
+                    Event::Comment(comment) => Event::Special {
+                        epoch: handler.epoch,
+                        flag: EpochFlag::HeaderInformationFollows,
+                        description: vec![comment],
+                    },
+                    event => event,
+                };
+                println!("{}", event);
             }
         });
     }